@@ -0,0 +1,274 @@
+//! Incident alerts for policy violations.
+//!
+//! When a policy marked `alert_on_violation` denies a request or fails, a
+//! [`ViolationAlerter`] raises an incident in Incident Manager. Violations
+//! are coalesced by fingerprint (policy, rule and outcome): only the first
+//! occurrence within the dedup window creates an incident, so a recurring
+//! violation does not flood Incident Manager.
+
+use super::{EvaluationContext, PolicyDecision};
+use crate::integration::{
+    in_current_request, CreateIncidentRequest, DecisionOutcome, IncidentManagerClient,
+    IncidentSeverity, TraceContext,
+};
+use crate::policy::{DecisionType, ReasonCode};
+use crate::{Error, Result};
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default window within which repeated violations raise one incident.
+pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(300);
+
+/// Context metadata key holding a W3C `traceparent` for incidents.
+const TRACEPARENT_KEY: &str = "traceparent";
+
+/// Raises incidents for violations of alerting policies.
+pub struct ViolationAlerter {
+    client: Arc<IncidentManagerClient>,
+    dedup_window: Duration,
+    /// When each fingerprint last raised an incident
+    recent: Mutex<HashMap<String, Instant>>,
+}
+
+impl ViolationAlerter {
+    /// Create an alerter with the default dedup window.
+    pub fn new(client: Arc<IncidentManagerClient>) -> Self {
+        Self {
+            client,
+            dedup_window: DEFAULT_DEDUP_WINDOW,
+            recent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the window within which repeated violations raise one incident.
+    pub fn with_dedup_window(mut self, window: Duration) -> Self {
+        self.dedup_window = window;
+        self
+    }
+
+    /// Raise an incident for a violation unless one was raised for the same
+    /// fingerprint within the dedup window.
+    ///
+    /// With `fail_on_error` the incident is created before returning and a
+    /// failure is returned; otherwise it is created in the background and
+    /// failures are only logged.
+    pub(crate) async fn alert(&self, violation: Violation, fail_on_error: bool) -> Result<()> {
+        let fingerprint = violation.fingerprint();
+        if !self.first_in_window(&fingerprint) {
+            tracing::debug!(%fingerprint, "Suppressing duplicate violation incident");
+            return Ok(());
+        }
+
+        let request = violation.into_request();
+        if fail_on_error {
+            self.client
+                .create_incident(&request)
+                .await
+                .map_err(|e| Error::integration("incident_manager", e.to_string()))?;
+        } else {
+            let client = self.client.clone();
+            tokio::spawn(in_current_request(async move {
+                if let Err(e) = client.create_incident(&request).await {
+                    tracing::warn!(
+                        integration = "incident_manager",
+                        error = %e,
+                        "Failed to create violation incident; failing open"
+                    );
+                }
+            }));
+        }
+        Ok(())
+    }
+
+    /// Record an occurrence of `fingerprint`, returning whether it is the
+    /// first within the dedup window.
+    fn first_in_window(&self, fingerprint: &str) -> bool {
+        let now = Instant::now();
+        let mut recent = self.recent.lock();
+        if recent
+            .get(fingerprint)
+            .is_some_and(|raised| now.duration_since(*raised) < self.dedup_window)
+        {
+            return false;
+        }
+
+        // Forget expired fingerprints so the map stays bounded.
+        recent.retain(|_, raised| now.duration_since(*raised) < self.dedup_window);
+        recent.insert(fingerprint.to_string(), now);
+        true
+    }
+}
+
+impl std::fmt::Debug for ViolationAlerter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ViolationAlerter")
+            .field("dedup_window", &self.dedup_window)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A deny or error attributed to a policy.
+#[derive(Debug, Clone)]
+pub(crate) struct Violation {
+    policy_id: String,
+    rule_id: Option<String>,
+    outcome: DecisionOutcome,
+    reason: String,
+    reason_code: Option<ReasonCode>,
+    user_id: Option<String>,
+    trace: Option<TraceContext>,
+}
+
+impl Violation {
+    /// Get the violation for an evaluation result, if it is a deny or an
+    /// error attributed to a policy for which `alerts` returns true.
+    pub(crate) fn from_result(
+        result: &Result<PolicyDecision>,
+        context: &EvaluationContext,
+        alerts: impl Fn(&str) -> bool,
+    ) -> Option<Self> {
+        let (policy_id, rule_id, outcome, reason, reason_code) = match result {
+            Ok(decision) if decision.decision == DecisionType::Deny => (
+                decision.matched_policies.first()?.clone(),
+                decision.matched_rules.first().cloned(),
+                DecisionOutcome::Deny,
+                decision.reason.clone().unwrap_or_default(),
+                decision.reason_code(),
+            ),
+            Err(Error::Evaluation {
+                message,
+                policy_id: Some(policy_id),
+                rule_id,
+            }) => (
+                policy_id.clone(),
+                rule_id.clone(),
+                DecisionOutcome::Error,
+                message.clone(),
+                Some(ReasonCode::EvaluationError),
+            ),
+            _ => return None,
+        };
+        if !alerts(&policy_id) {
+            return None;
+        }
+
+        Some(Self {
+            policy_id,
+            rule_id,
+            outcome,
+            reason,
+            reason_code,
+            user_id: context.user.as_ref().map(|user| user.id.clone()),
+            trace: context.trace.clone().or_else(|| {
+                context
+                    .metadata
+                    .get(TRACEPARENT_KEY)
+                    .and_then(|value| value.as_str())
+                    .and_then(|header| TraceContext::from_traceparent(header).ok())
+            }),
+        })
+    }
+
+    /// Identity used to coalesce repeated violations.
+    fn fingerprint(&self) -> String {
+        let outcome = match self.outcome {
+            DecisionOutcome::Error => "error",
+            _ => "deny",
+        };
+        format!(
+            "{}:{}:{}",
+            self.policy_id,
+            self.rule_id.as_deref().unwrap_or_default(),
+            outcome
+        )
+    }
+
+    fn into_request(self) -> CreateIncidentRequest {
+        let (severity, tag) = match self.outcome {
+            DecisionOutcome::Error => (IncidentSeverity::Medium, "error"),
+            _ => (IncidentSeverity::High, "deny"),
+        };
+        let mut request = CreateIncidentRequest::from_policy_violation(
+            &self.policy_id,
+            self.rule_id.as_deref().unwrap_or_default(),
+            &self.reason,
+            severity,
+        );
+        request.rule_id = self.rule_id;
+        request.user_id = self.user_id;
+        request.tags.push(tag.to_string());
+        request.context = serde_json::json!({
+            "decision": self.outcome,
+            "reason_code": self.reason_code,
+            "trace_id": self.trace.as_ref().map(|trace| &trace.trace_id),
+            "span_id": self.trace.as_ref().and_then(|trace| trace.parent_span_id.as_ref()),
+        });
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deny() -> Result<PolicyDecision> {
+        let mut decision = PolicyDecision::deny("Guests are not allowed");
+        decision.matched_policies = vec!["guests".to_string()];
+        decision.matched_rules = vec!["deny-guests".to_string()];
+        Ok(decision)
+    }
+
+    #[test]
+    fn test_violation_from_result() {
+        let context = EvaluationContext::builder()
+            .with_user("user-123", None, vec![])
+            .with_metadata(
+                "traceparent",
+                serde_json::json!("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            )
+            .build();
+
+        let violation = Violation::from_result(&deny(), &context, |_| true).unwrap();
+        assert_eq!(violation.policy_id, "guests");
+        assert_eq!(violation.rule_id.as_deref(), Some("deny-guests"));
+        assert_eq!(violation.outcome, DecisionOutcome::Deny);
+        assert_eq!(violation.user_id.as_deref(), Some("user-123"));
+        let request = violation.into_request();
+        assert_eq!(request.context["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(request.context["span_id"], "00f067aa0ba902b7");
+
+        let error = Err(Error::evaluation_with_context(
+            "bad regex",
+            "guests",
+            Some("deny-guests".to_string()),
+        ));
+        let violation = Violation::from_result(&error, &context, |_| true).unwrap();
+        assert_eq!(violation.outcome, DecisionOutcome::Error);
+        assert_eq!(violation.reason, "bad regex");
+        assert_eq!(violation.reason_code, Some(ReasonCode::EvaluationError));
+
+        assert!(Violation::from_result(&deny(), &context, |_| false).is_none());
+        assert!(Violation::from_result(&Ok(PolicyDecision::allow()), &context, |_| true).is_none());
+        let unattributed = Err(Error::evaluation("boom"));
+        assert!(Violation::from_result(&unattributed, &context, |_| true).is_none());
+    }
+
+    #[test]
+    fn test_dedup_window() {
+        let client = Arc::new(IncidentManagerClient::new(
+            "http://incidents".to_string(),
+            Duration::from_secs(1),
+        ));
+        let alerter = ViolationAlerter::new(client.clone());
+        assert!(alerter.first_in_window("guests:deny-guests:deny"));
+        assert!(!alerter.first_in_window("guests:deny-guests:deny"));
+        assert!(alerter.first_in_window("guests:deny-guests:error"));
+
+        let alerter = ViolationAlerter::new(client).with_dedup_window(Duration::ZERO);
+        assert!(alerter.first_in_window("guests:deny-guests:deny"));
+        assert!(alerter.first_in_window("guests:deny-guests:deny"));
+    }
+}
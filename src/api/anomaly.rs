@@ -0,0 +1,332 @@
+//! Sentinel security-monitoring events for anomalous activity.
+//!
+//! An [`AnomalyMonitor`] counts denies, Shield injection flags and
+//! authentication failures per subject over a sliding window. When a count
+//! reaches its threshold, a security event is reported to Sentinel with the
+//! subject, the count and the window. Reports are rate-limited: at most one
+//! per subject and kind per window, and at most `max_events_per_window`
+//! across all subjects, so a burst never floods Sentinel.
+
+use super::EvaluationContext;
+use crate::integration::{
+    RuleThresholds, SecurityEvent, SecurityEventType, SecuritySeverity, SentinelClient,
+};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default limit on events reported per window across all subjects.
+pub const DEFAULT_MAX_EVENTS_PER_WINDOW: usize = 100;
+
+/// Subject used for requests without a user.
+const ANONYMOUS_SUBJECT: &str = "anonymous";
+
+/// Thresholds at which per-subject activity is reported as anomalous.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnomalyThresholds {
+    /// Window over which activity is counted
+    pub window: Duration,
+    /// Denies within the window that signal a spike (0 disables)
+    pub deny_spike: u32,
+    /// Shield injection flags within the window (0 disables)
+    pub injection_flags: u32,
+    /// Authentication failures within the window (0 disables)
+    pub auth_failures: u32,
+}
+
+impl AnomalyThresholds {
+    /// Get the anomaly thresholds from Config Manager rule thresholds.
+    pub fn from_rule_thresholds(thresholds: &RuleThresholds) -> Self {
+        Self {
+            window: Duration::from_secs(thresholds.anomaly_window_secs),
+            deny_spike: thresholds.deny_spike_threshold,
+            injection_flags: thresholds.injection_flag_threshold,
+            auth_failures: thresholds.auth_failure_threshold,
+        }
+    }
+
+    fn threshold(&self, kind: AnomalyKind) -> u32 {
+        match kind {
+            AnomalyKind::DenySpike => self.deny_spike,
+            AnomalyKind::InjectionFlags => self.injection_flags,
+            AnomalyKind::AuthFailures => self.auth_failures,
+        }
+    }
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self::from_rule_thresholds(&RuleThresholds::default())
+    }
+}
+
+/// Kind of activity counted per subject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AnomalyKind {
+    /// Requests denied
+    DenySpike,
+    /// Prompts Shield flagged for injection or jailbreak
+    InjectionFlags,
+    /// Failed authentications
+    AuthFailures,
+}
+
+impl AnomalyKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AnomalyKind::DenySpike => "deny_spike",
+            AnomalyKind::InjectionFlags => "injection_flags",
+            AnomalyKind::AuthFailures => "auth_failures",
+        }
+    }
+
+    fn event_type(&self) -> SecurityEventType {
+        match self {
+            AnomalyKind::DenySpike => SecurityEventType::AnomalyDetected,
+            AnomalyKind::InjectionFlags => SecurityEventType::SuspiciousActivity,
+            AnomalyKind::AuthFailures => SecurityEventType::AuthFailure,
+        }
+    }
+
+    fn severity(&self) -> SecuritySeverity {
+        match self {
+            AnomalyKind::DenySpike => SecuritySeverity::Medium,
+            AnomalyKind::InjectionFlags | AnomalyKind::AuthFailures => SecuritySeverity::High,
+        }
+    }
+}
+
+/// Recent activity of one kind for one subject.
+#[derive(Debug, Default)]
+struct SubjectWindow {
+    /// Occurrences within the window, oldest first
+    occurrences: VecDeque<Instant>,
+    /// When this subject and kind was last reported
+    reported: Option<Instant>,
+}
+
+impl SubjectWindow {
+    /// Drop occurrences older than the window, returning whether anything
+    /// within it remains.
+    fn expire(&mut self, now: Instant, window: Duration) -> bool {
+        while self
+            .occurrences
+            .front()
+            .is_some_and(|occurred| now.duration_since(*occurred) >= window)
+        {
+            self.occurrences.pop_front();
+        }
+        !self.occurrences.is_empty()
+            || self
+                .reported
+                .is_some_and(|reported| now.duration_since(reported) < window)
+    }
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    windows: HashMap<(String, AnomalyKind), SubjectWindow>,
+    /// When recent events were reported, across all subjects
+    reported: VecDeque<Instant>,
+}
+
+/// Reports anomalous per-subject activity to Sentinel.
+pub struct AnomalyMonitor {
+    sentinel: Arc<SentinelClient>,
+    max_events_per_window: usize,
+    state: Mutex<MonitorState>,
+}
+
+impl AnomalyMonitor {
+    /// Create a monitor reporting to Sentinel.
+    pub fn new(sentinel: Arc<SentinelClient>) -> Self {
+        Self {
+            sentinel,
+            max_events_per_window: DEFAULT_MAX_EVENTS_PER_WINDOW,
+            state: Mutex::new(MonitorState::default()),
+        }
+    }
+
+    /// Set the limit on events reported per window across all subjects.
+    pub fn with_max_events_per_window(mut self, max: usize) -> Self {
+        self.max_events_per_window = max;
+        self
+    }
+
+    /// Count an occurrence of `kind` for the request's subject, reporting
+    /// it to Sentinel in the background if it crosses the threshold.
+    pub(crate) fn observe(
+        &self,
+        kind: AnomalyKind,
+        context: &EvaluationContext,
+        thresholds: &AnomalyThresholds,
+    ) {
+        let subject = context
+            .user
+            .as_ref()
+            .map(|user| user.id.as_str())
+            .unwrap_or(ANONYMOUS_SUBJECT);
+        let Some(count) = self.record(kind, subject, thresholds, Instant::now()) else {
+            return;
+        };
+
+        let event = SecurityEvent {
+            event_type: kind.event_type(),
+            description: format!(
+                "{} {} for {} within {}s",
+                count,
+                kind.as_str().replace('_', " "),
+                subject,
+                thresholds.window.as_secs()
+            ),
+            source_ip: context
+                .request
+                .as_ref()
+                .and_then(|request| request.ip_address.clone()),
+            user_id: context.user.as_ref().map(|user| user.id.clone()),
+            resource: context.llm.as_ref().and_then(|llm| llm.model.clone()),
+            severity: kind.severity(),
+            context: serde_json::json!({
+                "anomaly": kind.as_str(),
+                "subject": subject,
+                "count": count,
+                "threshold": thresholds.threshold(kind),
+                "window_secs": thresholds.window.as_secs(),
+                "request_id": context.request.as_ref().map(|request| &request.id),
+                "traceparent": context.metadata.get("traceparent"),
+            }),
+            timestamp: Some(chrono::Utc::now().to_rfc3339()),
+        };
+        let sentinel = self.sentinel.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sentinel.report_event(&event).await {
+                tracing::warn!(error = %e, "Failed to report anomaly to Sentinel");
+            }
+        });
+    }
+
+    /// Count an occurrence, returning the count within the window if it
+    /// should be reported.
+    fn record(
+        &self,
+        kind: AnomalyKind,
+        subject: &str,
+        thresholds: &AnomalyThresholds,
+        now: Instant,
+    ) -> Option<usize> {
+        let threshold = thresholds.threshold(kind) as usize;
+        if threshold == 0 {
+            return None;
+        }
+        let window = thresholds.window;
+        let mut state = self.state.lock();
+
+        let key = (subject.to_string(), kind);
+        if !state.windows.contains_key(&key) {
+            // Forget idle subjects so the map stays bounded.
+            state.windows.retain(|_, entry| entry.expire(now, window));
+        }
+        let entry = state.windows.entry(key).or_default();
+        entry.expire(now, window);
+        entry.occurrences.push_back(now);
+        let count = entry.occurrences.len();
+        if count < threshold || entry.reported.is_some_and(|at| now.duration_since(at) < window) {
+            return None;
+        }
+        entry.reported = Some(now);
+
+        while state
+            .reported
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= window)
+        {
+            state.reported.pop_front();
+        }
+        if state.reported.len() >= self.max_events_per_window {
+            tracing::debug!(subject, anomaly = kind.as_str(), "Anomaly report rate-limited");
+            return None;
+        }
+        state.reported.push_back(now);
+        Some(count)
+    }
+}
+
+impl std::fmt::Debug for AnomalyMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnomalyMonitor")
+            .field("max_events_per_window", &self.max_events_per_window)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> AnomalyMonitor {
+        AnomalyMonitor::new(Arc::new(SentinelClient::new(
+            "http://sentinel".to_string(),
+            Duration::from_secs(1),
+        )))
+    }
+
+    fn thresholds() -> AnomalyThresholds {
+        AnomalyThresholds {
+            window: Duration::from_secs(60),
+            deny_spike: 3,
+            injection_flags: 1,
+            auth_failures: 0,
+        }
+    }
+
+    #[test]
+    fn test_one_report_per_window() {
+        let monitor = monitor();
+        let thresholds = thresholds();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let reports: Vec<_> = (0..6)
+            .map(|i| monitor.record(AnomalyKind::DenySpike, "user-1", &thresholds, at(i)))
+            .collect();
+        assert_eq!(reports, vec![None, None, Some(3), None, None, None]);
+
+        // Other subjects and kinds are counted separately.
+        assert_eq!(
+            monitor.record(AnomalyKind::InjectionFlags, "user-1", &thresholds, at(6)),
+            Some(1)
+        );
+        assert_eq!(
+            monitor.record(AnomalyKind::DenySpike, "user-2", &thresholds, at(6)),
+            None
+        );
+        assert_eq!(
+            monitor.record(AnomalyKind::AuthFailures, "user-1", &thresholds, at(6)),
+            None
+        );
+
+        // Once the window has passed the subject can be reported again.
+        assert_eq!(
+            monitor.record(AnomalyKind::DenySpike, "user-1", &thresholds, at(63)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_reports_rate_limited_across_subjects() {
+        let monitor = monitor().with_max_events_per_window(2);
+        let thresholds = thresholds();
+        let now = Instant::now();
+
+        let reported = (0..5)
+            .filter_map(|i| {
+                let subject = format!("user-{}", i);
+                monitor.record(AnomalyKind::InjectionFlags, &subject, &thresholds, now)
+            })
+            .count();
+        assert_eq!(reported, 2);
+    }
+}
@@ -0,0 +1,358 @@
+//! Audit logging of policy decisions.
+//!
+//! Every evaluation is offered to an [`AuditLog`], which records it at the
+//! [`AuditLevel`] of the current engine config: as a structured `INFO` event
+//! on the `audit` tracing target and, if a Governance client is configured,
+//! as an [`AuditEvent`] sent in the background. Audit failures are logged
+//! and never affect the decision.
+
+use super::{EvaluationContext, PolicyDecision};
+use crate::integration::{redact, AuditEvent, AuditOutcome, DecisionOutcome, GovernanceClient};
+use crate::policy::{DecisionReason, ReasonCode};
+use crate::Result;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// Tracing target of local audit records.
+pub const AUDIT_TARGET: &str = "audit";
+
+/// Input fields redacted from verbose records unless configured otherwise.
+const DEFAULT_REDACT_PATHS: &[&str] = &[
+    "$.metadata.api_key",
+    "$.metadata.authorization",
+    "$.metadata.password",
+    "$.metadata.token",
+];
+
+/// How much of each decision is audited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditLevel {
+    /// Nothing is audited
+    Off,
+    /// Denials and evaluation errors only
+    Minimal,
+    /// Every decision, with its reason and matched policies
+    #[default]
+    Standard,
+    /// Every decision, adding matched rules and the redacted input
+    Verbose,
+}
+
+impl AuditLevel {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditLevel::Off => "off",
+            AuditLevel::Minimal => "minimal",
+            AuditLevel::Standard => "standard",
+            AuditLevel::Verbose => "verbose",
+        }
+    }
+
+    /// Check whether a decision with this outcome is audited.
+    pub fn includes(&self, outcome: DecisionOutcome) -> bool {
+        match self {
+            AuditLevel::Off => false,
+            AuditLevel::Minimal => {
+                matches!(outcome, DecisionOutcome::Deny | DecisionOutcome::Error)
+            }
+            AuditLevel::Standard | AuditLevel::Verbose => true,
+        }
+    }
+}
+
+impl fmt::Display for AuditLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for AuditLevel {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" => Ok(AuditLevel::Off),
+            "minimal" => Ok(AuditLevel::Minimal),
+            "standard" => Ok(AuditLevel::Standard),
+            "verbose" => Ok(AuditLevel::Verbose),
+            _ => Err(crate::Error::parse(format!("Unknown audit level: {}", s))),
+        }
+    }
+}
+
+/// One audited decision.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditRecord {
+    /// Outcome of the evaluation
+    pub outcome: DecisionOutcome,
+    /// Reason for the decision, or the evaluation error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Machine-readable code for the reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
+    /// ID of the requesting user, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// IDs of policies that matched
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matched_policies: Vec<String>,
+    /// IDs of rules that matched (verbose only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matched_rules: Vec<String>,
+    /// Redacted evaluation context (verbose only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<serde_json::Value>,
+}
+
+/// Emitter of audit records.
+#[derive(Clone)]
+pub struct AuditLog {
+    governance: Option<Arc<GovernanceClient>>,
+    redact_paths: Vec<String>,
+}
+
+impl AuditLog {
+    /// Create an audit log writing local records only.
+    pub fn new() -> Self {
+        Self {
+            governance: None,
+            redact_paths: DEFAULT_REDACT_PATHS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    /// Also send records to Governance.
+    pub fn with_governance(mut self, governance: Arc<GovernanceClient>) -> Self {
+        self.governance = Some(governance);
+        self
+    }
+
+    /// Set the input fields redacted from verbose records, as paths like
+    /// `$.metadata.api_key`; replaces the defaults.
+    ///
+    /// Config Manager secret values are always redacted.
+    pub fn with_redact_paths(mut self, paths: Vec<String>) -> Self {
+        self.redact_paths = paths;
+        self
+    }
+
+    /// Audit the result of an evaluation at `level`.
+    pub fn record(
+        &self,
+        level: AuditLevel,
+        context: &EvaluationContext,
+        result: &Result<PolicyDecision>,
+    ) {
+        let Some(record) = self.build_record(level, context, result) else {
+            return;
+        };
+        let details = serde_json::to_value(&record).unwrap_or_default();
+        tracing::info!(target: AUDIT_TARGET, record = %details, "Policy decision audited");
+
+        if let Some(governance) = &self.governance {
+            let event = AuditEvent {
+                event_type: "policy_decision".to_string(),
+                user_id: record.user_id.clone().unwrap_or_default(),
+                action: "evaluate".to_string(),
+                resource: record.matched_policies.first().cloned().unwrap_or_default(),
+                outcome: match record.outcome {
+                    DecisionOutcome::Deny => AuditOutcome::Denied,
+                    DecisionOutcome::Error => AuditOutcome::Failure,
+                    _ => AuditOutcome::Success,
+                },
+                details,
+                timestamp: Some(chrono::Utc::now().to_rfc3339()),
+            };
+            let governance = governance.clone();
+            tokio::spawn(async move {
+                if let Err(e) = governance.log_audit(&event).await {
+                    tracing::warn!(error = %e, "Failed to send audit event to Governance");
+                }
+            });
+        }
+    }
+
+    /// Build the record for an evaluation result, if `level` audits it.
+    pub fn build_record(
+        &self,
+        level: AuditLevel,
+        context: &EvaluationContext,
+        result: &Result<PolicyDecision>,
+    ) -> Option<AuditRecord> {
+        let outcome = match result {
+            Ok(decision) => decision.decision.into(),
+            Err(_) => DecisionOutcome::Error,
+        };
+        if !level.includes(outcome) {
+            return None;
+        }
+
+        let (reason, reason_code, matched_policies, matched_rules) = match result {
+            Ok(decision) => (
+                decision.reason.clone(),
+                decision.reason_code(),
+                decision.matched_policies.clone(),
+                decision.matched_rules.clone(),
+            ),
+            Err(e) => (
+                Some(e.to_string()),
+                Some(DecisionReason::from_error(e).code),
+                Vec::new(),
+                Vec::new(),
+            ),
+        };
+        let verbose = level == AuditLevel::Verbose;
+
+        Some(AuditRecord {
+            outcome,
+            reason,
+            reason_code,
+            user_id: context.user.as_ref().map(|user| user.id.clone()),
+            matched_policies,
+            matched_rules: if verbose { matched_rules } else { Vec::new() },
+            input: verbose.then(|| {
+                let mut input = context.to_json();
+                redact(&mut input, &self.redact_paths);
+                input
+            }),
+        })
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("governance", &self.governance.is_some())
+            .field("redact_paths", &self.redact_paths)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{IntegrationClient, MockTransport};
+    use std::time::Duration;
+
+    fn context() -> EvaluationContext {
+        EvaluationContext::builder()
+            .with_user("user-123", None, vec!["guest".to_string()])
+            .with_metadata("api_key", serde_json::json!("sk-secret"))
+            .with_metadata("region", serde_json::json!("eu"))
+            .build()
+    }
+
+    fn results() -> Vec<Result<PolicyDecision>> {
+        let mut deny = PolicyDecision::deny("Guests are not allowed");
+        deny.matched_policies = vec!["guests".to_string()];
+        deny.matched_rules = vec!["deny-guests".to_string()];
+        vec![
+            Ok(PolicyDecision::allow()),
+            Ok(PolicyDecision::warn("Large prompt")),
+            Ok(deny),
+            Err(crate::Error::evaluation("boom")),
+        ]
+    }
+
+    fn audited(level: AuditLevel) -> Vec<AuditRecord> {
+        let log = AuditLog::new();
+        results()
+            .iter()
+            .filter_map(|result| log.build_record(level, &context(), result))
+            .collect()
+    }
+
+    #[test]
+    fn test_volume_per_level() {
+        let outcomes = |level| -> Vec<DecisionOutcome> {
+            audited(level).iter().map(|record| record.outcome).collect()
+        };
+        assert!(outcomes(AuditLevel::Off).is_empty());
+        assert_eq!(
+            outcomes(AuditLevel::Minimal),
+            vec![DecisionOutcome::Deny, DecisionOutcome::Error]
+        );
+        let all = vec![
+            DecisionOutcome::Allow,
+            DecisionOutcome::Warn,
+            DecisionOutcome::Deny,
+            DecisionOutcome::Error,
+        ];
+        assert_eq!(outcomes(AuditLevel::Standard), all);
+        assert_eq!(outcomes(AuditLevel::Verbose), all);
+    }
+
+    #[test]
+    fn test_content_per_level() {
+        let standard = &audited(AuditLevel::Standard)[2];
+        assert_eq!(standard.reason.as_deref(), Some("Guests are not allowed"));
+        assert_eq!(standard.user_id.as_deref(), Some("user-123"));
+        assert_eq!(standard.matched_policies, vec!["guests"]);
+        assert!(standard.matched_rules.is_empty());
+        assert!(standard.input.is_none());
+        assert_eq!(audited(AuditLevel::Minimal)[0], *standard);
+
+        let verbose = &audited(AuditLevel::Verbose)[2];
+        assert_eq!(verbose.matched_rules, vec!["deny-guests"]);
+        let input = verbose.input.as_ref().unwrap();
+        assert_eq!(input["metadata"]["api_key"], "***");
+        assert_eq!(input["metadata"]["region"], "eu");
+        assert_eq!(input["user"]["id"], "user-123");
+
+        let error = &audited(AuditLevel::Minimal)[1];
+        assert!(error.reason.as_deref().unwrap().contains("boom"));
+        assert_eq!(error.reason_code, Some(ReasonCode::EvaluationError));
+    }
+
+    #[test]
+    fn test_audit_level_from_str() {
+        assert_eq!("Verbose".parse::<AuditLevel>().unwrap(), AuditLevel::Verbose);
+        assert_eq!("none".parse::<AuditLevel>().unwrap(), AuditLevel::Off);
+        assert!("loud".parse::<AuditLevel>().is_err());
+        assert_eq!(AuditLevel::default(), AuditLevel::Standard);
+    }
+
+    #[tokio::test]
+    async fn test_records_sent_to_governance() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/audit/log",
+            serde_json::json!({ "success": true }),
+        ));
+        let client =
+            IntegrationClient::new("http://governance".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        let log = AuditLog::new().with_governance(Arc::new(GovernanceClient::from_client(client)));
+
+        for result in results() {
+            log.record(AuditLevel::Minimal, &context(), &result);
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        while transport.requests().len() < 2 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut outcomes: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|request| {
+                let body = request.json().unwrap();
+                assert_eq!(body["event_type"], "policy_decision");
+                assert_eq!(body["user_id"], "user-123");
+                body["outcome"].as_str().unwrap().to_string()
+            })
+            .collect();
+        outcomes.sort();
+        assert_eq!(outcomes, vec!["denied", "failure"]);
+    }
+}
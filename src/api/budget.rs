@@ -0,0 +1,294 @@
+//! CostOps budget enforcement.
+//!
+//! Requests carrying a cost or token estimate are checked before any policy
+//! is evaluated. The estimated tokens must not exceed the token limit, and
+//! the estimated cost must fit within the available budget: the share of the
+//! CostOps budget allowed by the cost threshold, minus what has been spent.
+//! Budget lookups are cached briefly per user, team and project.
+//!
+//! Estimates are read from context metadata: `estimated_cost_cents` and
+//! `estimated_tokens`, the latter falling back to the request's max tokens.
+
+use super::failure::OnFailure;
+use super::{EngineConfig, EvaluationContext, PolicyDecision};
+use crate::integration::{BudgetCheckRequest, BudgetCheckResponse, CostOpsClient};
+use crate::policy::{DecisionReason, ReasonCode};
+use crate::{Error, Result};
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default time a budget lookup is reused.
+pub const DEFAULT_BUDGET_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Default time allowed for a budget lookup.
+pub const DEFAULT_BUDGET_LOOKUP_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Context metadata key holding the estimated cost in cents.
+pub(crate) const ESTIMATED_COST_KEY: &str = "estimated_cost_cents";
+
+/// Context metadata key holding the estimated token count.
+pub(crate) const ESTIMATED_TOKENS_KEY: &str = "estimated_tokens";
+
+/// Budget owner: user, team and project IDs.
+type BudgetKey = (Option<String>, Option<String>, Option<String>);
+
+/// Denies requests that would exceed CostOps budgets or token limits.
+pub struct BudgetEnforcer {
+    client: Arc<CostOpsClient>,
+    cache_ttl: Duration,
+    lookup_timeout: Duration,
+    /// Recent budget lookups and when they were made
+    budgets: Mutex<HashMap<BudgetKey, (Instant, BudgetCheckResponse)>>,
+}
+
+impl BudgetEnforcer {
+    /// Create an enforcer with the default cache TTL and lookup timeout.
+    pub fn new(client: Arc<CostOpsClient>) -> Self {
+        Self {
+            client,
+            cache_ttl: DEFAULT_BUDGET_CACHE_TTL,
+            lookup_timeout: DEFAULT_BUDGET_LOOKUP_TIMEOUT,
+            budgets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set how long a budget lookup is reused.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Set the time allowed for a budget lookup.
+    pub fn with_lookup_timeout(mut self, timeout: Duration) -> Self {
+        self.lookup_timeout = timeout;
+        self
+    }
+
+    /// Check a request against its budget, returning a deny decision if it
+    /// would exceed it.
+    ///
+    /// If the budget cannot be looked up in time, the check is skipped, the
+    /// request denied or an error returned, as `on_failure` says.
+    pub(crate) async fn check(
+        &self,
+        context: &EvaluationContext,
+        config: &EngineConfig,
+        on_failure: OnFailure,
+    ) -> Result<Option<PolicyDecision>> {
+        let tokens = estimated_tokens(context);
+        if let Some(tokens) = tokens.filter(|tokens| *tokens > config.token_limit) {
+            let reason = format!(
+                "Estimated {} tokens exceed the limit of {}",
+                tokens, config.token_limit
+            );
+            let decision_reason = DecisionReason::new(ReasonCode::TokenLimitExceeded, &reason)
+                .with_detail("estimated_tokens", tokens.into())
+                .with_detail("token_limit", config.token_limit.into());
+            return Ok(Some(PolicyDecision::deny(reason).with_decision_reason(decision_reason)));
+        }
+
+        let Some(projected) = estimated_cost(context) else {
+            return Ok(None);
+        };
+        let budget = match self.budget(context).await {
+            Ok(budget) => budget,
+            Err(e) => {
+                on_failure.log("costops", &e);
+                return match on_failure {
+                    OnFailure::Proceed => Ok(None),
+                    OnFailure::Deny => Ok(Some(OnFailure::denial("costops", &e))),
+                    OnFailure::Fail => Err(e),
+                };
+            }
+        };
+
+        let status = &budget.status;
+        let available = status.limit_cents * config.cost_threshold / 100.0 - status.used_cents;
+        if !budget.allowed || projected > available {
+            let reason = format!(
+                "Projected cost of {:.2} cents exceeds the available {} budget of {:.2} cents",
+                projected,
+                status.period,
+                available.max(0.0)
+            );
+            let decision_reason = DecisionReason::new(ReasonCode::BudgetExceeded, &reason)
+                .with_detail("projected_cents", projected.into())
+                .with_detail("available_cents", available.max(0.0).into())
+                .with_detail("period", status.period.clone().into());
+            return Ok(Some(PolicyDecision::deny(reason).with_decision_reason(decision_reason)));
+        }
+        Ok(None)
+    }
+
+    /// Get the budget for the request's owner, from the cache if recent.
+    async fn budget(&self, context: &EvaluationContext) -> Result<BudgetCheckResponse> {
+        let key: BudgetKey = (
+            context.user.as_ref().map(|user| user.id.clone()),
+            context.team.as_ref().map(|team| team.id.clone()),
+            context.project.as_ref().map(|project| project.id.clone()),
+        );
+        if let Some((fetched, budget)) = self.budgets.lock().get(&key) {
+            if fetched.elapsed() < self.cache_ttl {
+                return Ok(budget.clone());
+            }
+        }
+
+        let request = BudgetCheckRequest {
+            user_id: key.0.clone(),
+            team_id: key.1.clone(),
+            project_id: key.2.clone(),
+        };
+        let budget = tokio::time::timeout(self.lookup_timeout, self.client.check_budget(&request))
+            .await
+            .map_err(|_| {
+                let timeout_ms = self.lookup_timeout.as_millis() as u64;
+                Error::timeout("CostOps budget lookup timed out", timeout_ms)
+            })?
+            .map_err(|e| Error::integration("costops", e.to_string()))?;
+
+        let mut budgets = self.budgets.lock();
+        budgets.retain(|_, (fetched, _)| fetched.elapsed() < self.cache_ttl);
+        budgets.insert(key, (Instant::now(), budget.clone()));
+        Ok(budget)
+    }
+}
+
+impl std::fmt::Debug for BudgetEnforcer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BudgetEnforcer")
+            .field("cache_ttl", &self.cache_ttl)
+            .field("lookup_timeout", &self.lookup_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+fn estimated_cost(context: &EvaluationContext) -> Option<f64> {
+    context.metadata.get(ESTIMATED_COST_KEY)?.as_f64()
+}
+
+fn estimated_tokens(context: &EvaluationContext) -> Option<u64> {
+    context
+        .metadata
+        .get(ESTIMATED_TOKENS_KEY)
+        .and_then(|tokens| tokens.as_u64())
+        .or_else(|| context.llm.as_ref()?.max_tokens.map(u64::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{IntegrationClient, MockTransport};
+
+    fn enforcer(transport: &Arc<MockTransport>) -> BudgetEnforcer {
+        let client = IntegrationClient::new("http://costops".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        BudgetEnforcer::new(Arc::new(CostOpsClient::from_client(client)))
+    }
+
+    /// A CostOps mock reporting a monthly budget with `used_cents` spent.
+    fn costops(used_cents: f64) -> Arc<MockTransport> {
+        Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/budget/check",
+            serde_json::json!({
+                "status": {
+                    "limit_cents": 1000.0,
+                    "used_cents": used_cents,
+                    "remaining_cents": 1000.0 - used_cents,
+                    "percentage_used": used_cents / 10.0,
+                    "period": "monthly"
+                },
+                "allowed": true
+            }),
+        ))
+    }
+
+    fn request(cost_cents: f64) -> EvaluationContext {
+        EvaluationContext::builder()
+            .with_user("user-123", None, vec![])
+            .with_metadata("estimated_cost_cents", serde_json::json!(cost_cents))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_over_budget_denied() {
+        let transport = costops(950.0);
+        let decision = enforcer(&transport)
+            .check(&request(75.0), &EngineConfig::default(), OnFailure::Fail)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!decision.allowed);
+        let details = &decision.decision_reason.as_ref().unwrap().details;
+        assert_eq!(details["period"], "monthly");
+        assert_eq!(decision.reason_code(), Some(ReasonCode::BudgetExceeded));
+        let reason = decision.reason.unwrap();
+        assert!(reason.contains("Projected cost of 75.00 cents"));
+        assert!(reason.contains("available monthly budget of 50.00 cents"));
+        assert_eq!(transport.requests()[0].json().unwrap()["user_id"], "user-123");
+    }
+
+    #[tokio::test]
+    async fn test_under_budget_allowed_and_cached() {
+        let transport = costops(100.0);
+        let enforcer = enforcer(&transport);
+        let config = EngineConfig::default();
+
+        for _ in 0..3 {
+            let check = enforcer.check(&request(75.0), &config, OnFailure::Fail);
+            assert!(check.await.unwrap().is_none());
+        }
+        assert_eq!(transport.requests().len(), 1);
+
+        // A lower cost threshold shrinks the available budget.
+        let strict = EngineConfig {
+            cost_threshold: 15.0,
+            ..EngineConfig::default()
+        };
+        let check = enforcer.check(&request(75.0), &strict, OnFailure::Fail);
+        assert!(check.await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_token_limit() {
+        let transport = costops(0.0);
+        let context = EvaluationContext::builder().with_max_tokens(4096).build();
+        let config = EngineConfig {
+            token_limit: 1024,
+            ..EngineConfig::default()
+        };
+
+        let decision = enforcer(&transport)
+            .check(&context, &config, OnFailure::Fail)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            decision.reason.as_deref(),
+            Some("Estimated 4096 tokens exceed the limit of 1024")
+        );
+        assert_eq!(decision.reason_code(), Some(ReasonCode::TokenLimitExceeded));
+        // Without a cost estimate CostOps is not consulted.
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_costops_unavailable() {
+        // No route is mocked, so every lookup fails.
+        let transport = Arc::new(MockTransport::new());
+        let enforcer = enforcer(&transport);
+
+        let (context, config) = (request(75.0), EngineConfig::default());
+
+        let check = |on_failure| enforcer.check(&context, &config, on_failure);
+        assert!(check(OnFailure::Proceed).await.unwrap().is_none());
+        assert!(check(OnFailure::Fail).await.is_err());
+        let denial = check(OnFailure::Deny).await.unwrap().unwrap();
+        assert_eq!(denial.metadata["failed_integration"], "costops");
+        assert_eq!(denial.reason_code(), Some(ReasonCode::IntegrationUnavailable));
+    }
+}
@@ -0,0 +1,304 @@
+//! Governance compliance checks.
+//!
+//! Requests are checked by Governance against every active policy with a
+//! [`ComplianceMode`], after the policies have been evaluated. Enforced
+//! checks deny non-compliant requests; warning checks downgrade an allow to a
+//! warning. Both wait for Governance. Audit-only checks run in the
+//! background and never change the decision. Every check is recorded in the
+//! Governance audit log with the policy ID, subject and outcome.
+
+use super::failure::OnFailure;
+use super::{EvaluationContext, PolicyDecision};
+use crate::integration::{
+    AuditEvent, AuditOutcome, ComplianceCheckRequest, ComplianceCheckResponse, GovernanceClient,
+};
+use crate::policy::{ComplianceMode, DecisionReason, DecisionType, ReasonCode};
+use crate::{Error, Result};
+
+use std::sync::Arc;
+
+/// Checks decisions for compliance with Governance.
+pub struct ComplianceChecker {
+    governance: Arc<GovernanceClient>,
+}
+
+impl ComplianceChecker {
+    /// Create a checker using a Governance client.
+    pub fn new(governance: Arc<GovernanceClient>) -> Self {
+        Self { governance }
+    }
+
+    /// Check a decision against each policy's compliance requirements.
+    ///
+    /// Denied decisions are returned unchanged. If a waiting check fails,
+    /// the request is failed, denied or the check skipped, as `on_failure`
+    /// says.
+    pub(crate) async fn apply(
+        &self,
+        policies: &[(String, ComplianceMode)],
+        context: &EvaluationContext,
+        mut decision: PolicyDecision,
+        on_failure: OnFailure,
+    ) -> Result<PolicyDecision> {
+        if decision.decision == DecisionType::Deny {
+            return Ok(decision);
+        }
+
+        for (policy_id, mode) in policies {
+            let request = compliance_request(policy_id, context);
+            if *mode == ComplianceMode::Audit {
+                let governance = self.governance.clone();
+                tokio::spawn(async move {
+                    match governance.check_compliance(&request).await {
+                        Ok(response) => {
+                            audit(&governance, &request, ComplianceMode::Audit, &response)
+                        }
+                        Err(e) => tracing::warn!(
+                            policy_id = %request.resource,
+                            error = %e,
+                            "Compliance check failed"
+                        ),
+                    }
+                });
+                continue;
+            }
+
+            let response = match self.governance.check_compliance(&request).await {
+                Ok(response) => response,
+                Err(e) => {
+                    on_failure.log("governance", &e);
+                    match on_failure {
+                        OnFailure::Proceed => continue,
+                        OnFailure::Deny => {
+                            let mut denial = OnFailure::denial("governance", &e);
+                            denial.matched_policies = vec![policy_id.clone()];
+                            denial.evaluation_time_ms = decision.evaluation_time_ms;
+                            return Ok(denial);
+                        }
+                        OnFailure::Fail => {
+                            return Err(Error::integration("governance", e.to_string()));
+                        }
+                    }
+                }
+            };
+            audit(&self.governance, &request, *mode, &response);
+            if response.compliant {
+                continue;
+            }
+
+            let reason = format!(
+                "Request is not compliant with {}: {}",
+                policy_id,
+                response
+                    .violations
+                    .iter()
+                    .map(|violation| violation.description.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            );
+            let codes: Vec<&str> = response.violations.iter().map(|v| v.code.as_str()).collect();
+            let decision_reason = DecisionReason::new(ReasonCode::ComplianceViolation, &reason)
+                .with_detail("policy_id", policy_id.clone().into())
+                .with_detail("violations", serde_json::json!(codes));
+            if *mode == ComplianceMode::Enforce {
+                let mut denial = PolicyDecision::deny(reason).with_decision_reason(decision_reason);
+                denial.matched_policies = vec![policy_id.clone()];
+                denial.evaluation_time_ms = decision.evaluation_time_ms;
+                denial
+                    .metadata
+                    .insert("compliance_violations".to_string(), serde_json::json!(codes));
+                return Ok(denial);
+            }
+
+            if decision.decision == DecisionType::Allow {
+                decision.decision = DecisionType::Warn;
+                decision.reason = Some(reason);
+                decision.decision_reason = Some(decision_reason);
+            }
+            decision
+                .metadata
+                .insert("compliance_violations".to_string(), serde_json::json!(codes));
+        }
+
+        Ok(decision)
+    }
+}
+
+impl std::fmt::Debug for ComplianceChecker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComplianceChecker").finish_non_exhaustive()
+    }
+}
+
+fn compliance_request(policy_id: &str, context: &EvaluationContext) -> ComplianceCheckRequest {
+    ComplianceCheckRequest {
+        user_id: context
+            .user
+            .as_ref()
+            .map(|user| user.id.clone())
+            .unwrap_or_default(),
+        action: "llm_request".to_string(),
+        resource: policy_id.to_string(),
+        model: context.llm.as_ref().and_then(|llm| llm.model.clone()),
+        context: context.to_json(),
+    }
+}
+
+/// Record a compliance check in the Governance audit log, in the background.
+fn audit(
+    governance: &Arc<GovernanceClient>,
+    request: &ComplianceCheckRequest,
+    mode: ComplianceMode,
+    response: &ComplianceCheckResponse,
+) {
+    let event = AuditEvent {
+        event_type: "compliance_check".to_string(),
+        user_id: request.user_id.clone(),
+        action: request.action.clone(),
+        resource: request.resource.clone(),
+        outcome: if !response.compliant && mode == ComplianceMode::Enforce {
+            AuditOutcome::Denied
+        } else {
+            AuditOutcome::Success
+        },
+        details: serde_json::json!({
+            "mode": mode,
+            "compliant": response.compliant,
+            "violations": response.violations,
+        }),
+        timestamp: Some(chrono::Utc::now().to_rfc3339()),
+    };
+    let governance = governance.clone();
+    tokio::spawn(async move {
+        if let Err(e) = governance.log_audit(&event).await {
+            tracing::warn!(error = %e, "Failed to record compliance check");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{IntegrationClient, MockTransport};
+    use std::time::Duration;
+
+    /// A Governance mock answering compliance checks with `response`.
+    fn governance(response: serde_json::Value) -> (ComplianceChecker, Arc<MockTransport>) {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_json(reqwest::Method::POST, "/api/v1/compliance/check", response)
+                .with_json(
+                    reqwest::Method::POST,
+                    "/api/v1/audit/log",
+                    serde_json::json!({ "success": true }),
+                ),
+        );
+        let client = IntegrationClient::new("http://governance".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        let checker = ComplianceChecker::new(Arc::new(GovernanceClient::from_client(client)));
+        (checker, transport)
+    }
+
+    fn non_compliant() -> serde_json::Value {
+        serde_json::json!({
+            "compliant": false,
+            "violations": [{
+                "code": "DATA_RESIDENCY",
+                "severity": "high",
+                "description": "Data must stay in the EU"
+            }]
+        })
+    }
+
+    fn context() -> EvaluationContext {
+        EvaluationContext::builder()
+            .with_user("user-123", None, vec![])
+            .with_model("gpt-4")
+            .build()
+    }
+
+    /// Wait for the background audit record and return it.
+    async fn audit_record(transport: &MockTransport) -> serde_json::Value {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        loop {
+            let record = transport
+                .requests()
+                .into_iter()
+                .find(|request| request.path == "/api/v1/audit/log");
+            if let Some(record) = record {
+                return record.json().unwrap();
+            }
+            assert!(tokio::time::Instant::now() < deadline, "no audit record");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_compliant_request_denied() {
+        let (checker, transport) = governance(non_compliant());
+        let policies = [("eu-residency".to_string(), ComplianceMode::Enforce)];
+
+        let decision = checker
+            .apply(&policies, &context(), PolicyDecision::allow(), OnFailure::Proceed)
+            .await
+            .unwrap();
+        assert!(!decision.allowed);
+        assert_eq!(
+            decision.reason.as_deref(),
+            Some("Request is not compliant with eu-residency: Data must stay in the EU")
+        );
+        assert_eq!(decision.matched_policies, vec!["eu-residency"]);
+        assert_eq!(decision.reason_code(), Some(ReasonCode::ComplianceViolation));
+
+        let record = audit_record(&transport).await;
+        assert_eq!(record["resource"], "eu-residency");
+        assert_eq!(record["outcome"], "denied");
+    }
+
+    #[tokio::test]
+    async fn test_compliant_request_allowed_and_audited() {
+        let (checker, transport) = governance(serde_json::json!({ "compliant": true }));
+        let policies = [("eu-residency".to_string(), ComplianceMode::Enforce)];
+
+        let decision = checker
+            .apply(&policies, &context(), PolicyDecision::allow(), OnFailure::Proceed)
+            .await
+            .unwrap();
+        assert!(decision.allowed);
+        assert_eq!(decision.decision, DecisionType::Allow);
+
+        let check = transport.requests()[0].json().unwrap();
+        assert_eq!(check["user_id"], "user-123");
+        assert_eq!(check["model"], "gpt-4");
+        let record = audit_record(&transport).await;
+        assert_eq!(record["event_type"], "compliance_check");
+        assert_eq!(record["resource"], "eu-residency");
+        assert_eq!(record["user_id"], "user-123");
+        assert_eq!(record["outcome"], "success");
+    }
+
+    #[tokio::test]
+    async fn test_warn_and_audit_modes() {
+        let (checker, transport) = governance(non_compliant());
+
+        let warn = [("eu-residency".to_string(), ComplianceMode::Warn)];
+        let decision = checker
+            .apply(&warn, &context(), PolicyDecision::allow(), OnFailure::Proceed)
+            .await
+            .unwrap();
+        assert_eq!(decision.decision, DecisionType::Warn);
+        assert!(decision.allowed);
+        assert_eq!(
+            decision.metadata["compliance_violations"],
+            serde_json::json!(["DATA_RESIDENCY"])
+        );
+
+        let audit_only = [("eu-residency".to_string(), ComplianceMode::Audit)];
+        let decision = checker
+            .apply(&audit_only, &context(), PolicyDecision::allow(), OnFailure::Proceed)
+            .await
+            .unwrap();
+        assert_eq!(decision.decision, DecisionType::Allow);
+        audit_record(&transport).await;
+    }
+}
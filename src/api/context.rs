@@ -3,6 +3,8 @@
 //! This module defines the context structures passed to policy evaluation,
 //! matching the LLM Dev Ops platform conventions.
 
+use super::{Claims, SignalValues};
+use crate::integration::{RuleThresholds, TraceContext};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -27,6 +29,23 @@ pub struct EvaluationContext {
     /// Additional metadata
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Evaluate every policy in shadow mode: report decisions without
+    /// enforcing them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shadow: bool,
+    /// Trace of the request, correlating emitted events and alerts with
+    /// the caller's trace; not an evaluation input
+    #[serde(skip)]
+    pub trace: Option<TraceContext>,
+    /// Live telemetry signals, set by the engine for CEL expressions when
+    /// it has a [`TelemetryProvider`](super::TelemetryProvider); not an
+    /// evaluation input
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub signals: Option<SignalValues>,
+    /// Rule thresholds, set by the engine along with `signals`; not an
+    /// evaluation input
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub thresholds: Option<RuleThresholds>,
 }
 
 impl EvaluationContext {
@@ -239,6 +258,8 @@ pub struct EvaluationContextBuilder {
     project: Option<ProjectContext>,
     request: Option<RequestContext>,
     metadata: HashMap<String, serde_json::Value>,
+    shadow: bool,
+    trace: Option<TraceContext>,
 }
 
 impl EvaluationContextBuilder {
@@ -288,6 +309,18 @@ impl EvaluationContextBuilder {
         self
     }
 
+    /// Set the user from verified token claims: the subject becomes the user
+    /// ID and the scopes become permissions.
+    pub fn with_claims(mut self, claims: &Claims) -> Self {
+        self.user = Some(UserContext {
+            id: claims.sub.clone(),
+            email: claims.email.clone(),
+            roles: claims.roles.clone(),
+            permissions: claims.scopes(),
+        });
+        self
+    }
+
     /// Set user details.
     pub fn with_user(
         mut self,
@@ -362,6 +395,18 @@ impl EvaluationContextBuilder {
         self
     }
 
+    /// Evaluate every policy in shadow mode.
+    pub fn with_shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    /// Attach the request's trace context.
+    pub fn with_trace(mut self, trace: TraceContext) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
     /// Build the evaluation context.
     pub fn build(self) -> EvaluationContext {
         EvaluationContext {
@@ -371,6 +416,10 @@ impl EvaluationContextBuilder {
             project: self.project,
             request: self.request,
             metadata: self.metadata,
+            shadow: self.shadow,
+            trace: self.trace,
+            signals: None,
+            thresholds: None,
         }
     }
 }
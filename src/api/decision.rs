@@ -1,6 +1,6 @@
 //! Policy decision types.
 
-use crate::policy::DecisionType;
+use crate::policy::{DecisionReason, DecisionType, Obligations, ReasonCode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
@@ -15,6 +15,9 @@ pub struct PolicyDecision {
     /// Human-readable reason for the decision
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
+    /// Machine-readable reason for the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_reason: Option<DecisionReason>,
     /// IDs of policies that matched
     #[serde(default)]
     pub matched_policies: Vec<String>,
@@ -26,12 +29,21 @@ pub struct PolicyDecision {
     /// Modifications to apply (for modify decisions)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub modifications: HashMap<String, serde_json::Value>,
+    /// Obligations the caller must fulfil (for modify decisions)
+    #[serde(default, skip_serializing_if = "Obligations::is_empty")]
+    pub obligations: Obligations,
     /// Additional metadata
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
     /// Evaluation trace for debugging
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trace: Option<EvaluationTrace>,
+    /// Whether the decision was served from the decision cache
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// What shadow policies would have decided; never enforced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<Box<PolicyDecision>>,
 }
 
 impl PolicyDecision {
@@ -41,12 +53,16 @@ impl PolicyDecision {
             decision: DecisionType::Allow,
             allowed: true,
             reason: None,
+            decision_reason: None,
             matched_policies: Vec::new(),
             matched_rules: Vec::new(),
             evaluation_time_ms: 0.0,
             modifications: HashMap::new(),
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
             trace: None,
+            cached: false,
+            shadow: None,
         }
     }
 
@@ -56,12 +72,16 @@ impl PolicyDecision {
             decision: DecisionType::Deny,
             allowed: false,
             reason: Some(reason.into()),
+            decision_reason: None,
             matched_policies: Vec::new(),
             matched_rules: Vec::new(),
             evaluation_time_ms: 0.0,
             modifications: HashMap::new(),
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
             trace: None,
+            cached: false,
+            shadow: None,
         }
     }
 
@@ -71,12 +91,16 @@ impl PolicyDecision {
             decision: DecisionType::Warn,
             allowed: true,
             reason: Some(reason.into()),
+            decision_reason: None,
             matched_policies: Vec::new(),
             matched_rules: Vec::new(),
             evaluation_time_ms: 0.0,
             modifications: HashMap::new(),
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
             trace: None,
+            cached: false,
+            shadow: None,
         }
     }
 
@@ -86,12 +110,16 @@ impl PolicyDecision {
             decision: DecisionType::Modify,
             allowed: true,
             reason: None,
+            decision_reason: None,
             matched_policies: Vec::new(),
             matched_rules: Vec::new(),
             evaluation_time_ms: 0.0,
             modifications,
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
             trace: None,
+            cached: false,
+            shadow: None,
         }
     }
 
@@ -101,6 +129,23 @@ impl PolicyDecision {
         self
     }
 
+    /// Set the machine-readable reason.
+    pub fn with_decision_reason(mut self, reason: DecisionReason) -> Self {
+        self.decision_reason = Some(reason);
+        self
+    }
+
+    /// Set the obligations.
+    pub fn with_obligations(mut self, obligations: Obligations) -> Self {
+        self.obligations = obligations;
+        self
+    }
+
+    /// Get the code of the machine-readable reason, if any.
+    pub fn reason_code(&self) -> Option<ReasonCode> {
+        self.decision_reason.as_ref().map(|reason| reason.code)
+    }
+
     /// Add a matched policy.
     pub fn with_matched_policy(mut self, policy_id: impl Into<String>) -> Self {
         self.matched_policies.push(policy_id.into());
@@ -0,0 +1,245 @@
+//! Policy distribution to edge locations.
+//!
+//! When policies are loaded, updated or unloaded, a [`PolicyDistributor`]
+//! pushes the change to Edge Agent, stamped with a version that increases
+//! with every change. Pushes run in the background so local evaluation never
+//! waits for them; failed pushes are retried with backoff and counted in
+//! metrics. New edge locations are brought up to date with a full resync,
+//! and the versions reported by each location reveal drift.
+
+use crate::integration::{EdgeAgentClient, EdgeVersion, PolicyDistributionRequest, RetryPolicy};
+use crate::policy::Policy;
+use crate::{Error, Result};
+
+use prometheus::{IntCounter, IntGauge};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Distributes policy changes to Edge Agent.
+pub struct PolicyDistributor {
+    client: Arc<EdgeAgentClient>,
+    retry_policy: RetryPolicy,
+    /// Version of the most recent change
+    version: AtomicU64,
+}
+
+impl PolicyDistributor {
+    /// Create a distributor retrying failed pushes with the default policy.
+    pub fn new(client: Arc<EdgeAgentClient>) -> Self {
+        Self {
+            client,
+            retry_policy: RetryPolicy::default(),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// Set how failed pushes are retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Get the version of the most recent change.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// Push changed and removed policies in the background, returning the
+    /// version they are stamped with.
+    pub(crate) fn distribute(
+        self: &Arc<Self>,
+        policies: Vec<Policy>,
+        removed: Vec<String>,
+    ) -> u64 {
+        let version = self.version.fetch_add(1, Ordering::SeqCst) + 1;
+        let request = PolicyDistributionRequest {
+            version,
+            policies,
+            removed,
+            full: false,
+        };
+        let distributor = self.clone();
+        tokio::spawn(async move { distributor.push(request).await });
+        version
+    }
+
+    /// Push a change, retrying with backoff until the retry policy gives up.
+    async fn push(&self, request: PolicyDistributionRequest) {
+        let mut attempt = 1;
+        loop {
+            let error = match self.client.distribute_policies(&request).await {
+                Ok(response) if response.success => {
+                    edge_policy_version_gauge().set(request.version as i64);
+                    return;
+                }
+                Ok(_) => "Edge Agent rejected the policies".to_string(),
+                Err(e) => e.to_string(),
+            };
+            edge_push_failures_counter().inc();
+
+            if attempt >= self.retry_policy.max_attempts {
+                tracing::warn!(
+                    version = request.version,
+                    attempts = attempt,
+                    %error,
+                    "Failed to distribute policies to edge"
+                );
+                return;
+            }
+            tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Replace the policies of an edge location with the full policy set,
+    /// stamped with the current version.
+    pub(crate) async fn resync(&self, location_id: &str, policies: Vec<Policy>) -> Result<()> {
+        let request = PolicyDistributionRequest {
+            version: self.version(),
+            policies,
+            removed: Vec::new(),
+            full: true,
+        };
+        let response = self
+            .client
+            .resync_location(location_id, &request)
+            .await
+            .map_err(|e| Error::integration("edge_agent", e.to_string()))?;
+        if !response.success {
+            return Err(Error::integration(
+                "edge_agent",
+                format!("Edge Agent rejected the resync of {}", location_id),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the policy version applied at each edge location.
+    pub async fn list_edge_versions(&self) -> Result<Vec<EdgeVersion>> {
+        self.client
+            .list_edge_versions()
+            .await
+            .map(|response| response.locations)
+            .map_err(|e| Error::integration("edge_agent", e.to_string()))
+    }
+
+    /// Get the edge locations whose policy version differs from the current
+    /// version.
+    pub async fn drifted_locations(&self) -> Result<Vec<EdgeVersion>> {
+        let version = self.version();
+        let mut locations = self.list_edge_versions().await?;
+        locations.retain(|location| location.version != version);
+        Ok(locations)
+    }
+}
+
+impl std::fmt::Debug for PolicyDistributor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicyDistributor")
+            .field("retry_policy", &self.retry_policy)
+            .field("version", &self.version())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Gauge reporting the latest policy version Edge Agent accepted.
+fn edge_policy_version_gauge() -> &'static IntGauge {
+    static GAUGE: OnceLock<IntGauge> = OnceLock::new();
+    GAUGE.get_or_init(|| {
+        let gauge = IntGauge::new(
+            "policy_engine_edge_policy_version",
+            "Latest policy version accepted by Edge Agent",
+        )
+        .expect("Failed to create edge policy version gauge");
+        prometheus::register(Box::new(gauge.clone()))
+            .expect("Failed to register edge policy version gauge");
+        gauge
+    })
+}
+
+/// Counter of failed policy pushes to Edge Agent, including retried ones.
+fn edge_push_failures_counter() -> &'static IntCounter {
+    static COUNTER: OnceLock<IntCounter> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounter::new(
+            "policy_engine_edge_push_failures_total",
+            "Failed attempts to push policies to Edge Agent",
+        )
+        .expect("Failed to create edge push failures counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register edge push failures counter");
+        counter
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{IntegrationClient, MockTransport};
+    use std::time::Duration;
+
+    fn distributor(transport: &Arc<MockTransport>) -> Arc<PolicyDistributor> {
+        let client = IntegrationClient::new("http://edge".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        let retry_policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(1))
+            .with_jitter(0.0);
+        Arc::new(
+            PolicyDistributor::new(Arc::new(EdgeAgentClient::from_client(client)))
+                .with_retry_policy(retry_policy),
+        )
+    }
+
+    /// Wait until `transport` has received `count` requests.
+    async fn wait_for_requests(transport: &MockTransport, count: usize) {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        while transport.requests().len() < count {
+            assert!(tokio::time::Instant::now() < deadline, "expected {} requests", count);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_push_retried() {
+        let transport = Arc::new(MockTransport::new().with_status(
+            reqwest::Method::POST,
+            "/api/v1/policies/distribute",
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        ));
+        let distributor = distributor(&transport);
+        let failures = edge_push_failures_counter().get();
+
+        assert_eq!(distributor.distribute(Vec::new(), vec!["old".to_string()]), 1);
+        wait_for_requests(&transport, 3).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Three attempts, then the push is given up.
+        assert_eq!(transport.requests().len(), 3);
+        assert!(edge_push_failures_counter().get() >= failures + 3);
+        let body = transport.requests()[2].json().unwrap();
+        assert_eq!(body["version"], 1);
+        assert_eq!(body["removed"], serde_json::json!(["old"]));
+    }
+
+    #[tokio::test]
+    async fn test_drifted_locations() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/api/v1/locations/versions",
+            serde_json::json!({
+                "locations": [
+                    { "location_id": "eu-west", "version": 0 },
+                    { "location_id": "us-east", "version": 1 }
+                ]
+            }),
+        ));
+        let distributor = distributor(&transport);
+        assert_eq!(distributor.list_edge_versions().await.unwrap().len(), 2);
+
+        // Pushes fail (no route), but the version still advances.
+        distributor.distribute(Vec::new(), Vec::new());
+        let drifted = distributor.drifted_locations().await.unwrap();
+        assert_eq!(drifted.len(), 1);
+        assert_eq!(drifted[0].location_id, "eu-west");
+    }
+}
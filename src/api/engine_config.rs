@@ -0,0 +1,502 @@
+//! Runtime engine settings.
+//!
+//! An [`EngineConfig`] is an immutable snapshot of the settings that shape
+//! decisions. The engine swaps snapshots atomically when enforcement
+//! parameters or policy settings are reloaded; each evaluation reads the snapshot current when
+//! it starts, so a reload never changes an evaluation already in flight.
+
+use super::{AnomalyThresholds, AuditLevel, PolicyDecision};
+use crate::config::Config;
+use crate::integration::{EnforcementParams, FeatureFlags, PolicySettings, RuleThresholds};
+use crate::policy::{DecisionCombiner, DecisionReason, DecisionType, PolicyRule, ReasonCode};
+use crate::{Error, Result};
+
+use prometheus::{IntCounter, IntCounterVec, IntGauge, Opts};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Namespace of policies that do not set one.
+const DEFAULT_NAMESPACE: &str = "default";
+
+/// Snapshot of the settings applied to each evaluation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineConfig {
+    /// Escalate warn decisions to deny
+    pub strict_mode: bool,
+    /// Decision returned when no rule matches: allow or deny
+    pub default_decision: DecisionType,
+    /// Allow the request instead of returning an error when evaluation fails
+    pub fail_open: bool,
+    /// Evaluations taking longer than this are cancelled and fail, or fail
+    /// open
+    pub max_evaluation_time: Duration,
+    /// Evaluate policies concurrently
+    pub parallel_evaluation: bool,
+    /// Combiner for policies that do not set their own
+    pub decision_combiner: DecisionCombiner,
+    /// How much of each decision is audited
+    pub audit_level: AuditLevel,
+    /// Percentage of the CostOps budget requests may spend up to
+    pub cost_threshold: f64,
+    /// Maximum estimated tokens per request
+    pub token_limit: u64,
+    /// Per-subject activity reported to Sentinel as anomalous
+    pub anomaly_thresholds: AnomalyThresholds,
+    /// Rule thresholds exposed to CEL expressions as `thresholds`
+    #[serde(default)]
+    pub rule_thresholds: RuleThresholds,
+    /// Rule priorities replacing those set in policies, by rule ID
+    pub priority_overrides: HashMap<String, i32>,
+    /// IDs of policies skipped during evaluation; a trailing `*` matches
+    /// any ID with the preceding prefix, e.g. `experimental.*`
+    pub disabled_policies: Vec<String>,
+    /// IDs of policies evaluated in shadow mode, as patterns like
+    /// `disabled_policies`
+    pub shadow_policies: Vec<String>,
+    /// Namespaces whose policies are evaluated; empty means all. Policies
+    /// without a namespace belong to `default`
+    pub enabled_namespaces: Vec<String>,
+    /// Validate evaluation inputs against the input schema, if the engine
+    /// has one
+    pub input_validation: bool,
+    /// Custom feature flags gating policies that set `feature_flag`; flags
+    /// not listed are off
+    #[serde(default)]
+    pub custom_flags: HashMap<String, bool>,
+    /// Snapshot version, incremented on every reload (0 at startup)
+    pub version: u64,
+}
+
+impl EngineConfig {
+    /// Create the startup snapshot from static configuration.
+    ///
+    /// No rule matching means allow, matching the engine's behavior without
+    /// enforcement parameters.
+    pub fn from_config(config: &Config) -> Self {
+        let thresholds = RuleThresholds::default();
+        Self {
+            strict_mode: false,
+            default_decision: DecisionType::Allow,
+            fail_open: false,
+            max_evaluation_time: config.performance.max_evaluation_time(),
+            parallel_evaluation: config.performance.parallel_evaluation,
+            decision_combiner: DecisionCombiner::default(),
+            audit_level: AuditLevel::default(),
+            cost_threshold: thresholds.cost_threshold,
+            token_limit: thresholds.token_limit,
+            anomaly_thresholds: AnomalyThresholds::from_rule_thresholds(&thresholds),
+            rule_thresholds: thresholds,
+            priority_overrides: HashMap::new(),
+            disabled_policies: Vec::new(),
+            shadow_policies: Vec::new(),
+            enabled_namespaces: Vec::new(),
+            input_validation: true,
+            custom_flags: HashMap::new(),
+            version: 0,
+        }
+    }
+
+    /// Derive the next snapshot from Config Manager enforcement parameters.
+    ///
+    /// Fails if the default decision is not `allow` or `deny`, if the
+    /// decision combiner or audit level is unknown, or if the maximum
+    /// evaluation time is zero.
+    pub fn with_enforcement_params(&self, params: &EnforcementParams) -> Result<Self> {
+        let default_decision = parse_default_decision(&params.default_decision)?;
+        let decision_combiner: DecisionCombiner = params.decision_combiner.parse()?;
+        let audit_level: AuditLevel = params.audit_level.parse()?;
+        if params.max_evaluation_time_ms == 0 {
+            return Err(Error::config("max_evaluation_time_ms must be greater than 0"));
+        }
+
+        Ok(Self {
+            strict_mode: params.strict_mode,
+            default_decision,
+            fail_open: params.fail_open,
+            max_evaluation_time: Duration::from_millis(params.max_evaluation_time_ms),
+            parallel_evaluation: self.parallel_evaluation,
+            decision_combiner,
+            audit_level,
+            cost_threshold: self.cost_threshold,
+            token_limit: self.token_limit,
+            anomaly_thresholds: self.anomaly_thresholds.clone(),
+            rule_thresholds: self.rule_thresholds.clone(),
+            priority_overrides: self.priority_overrides.clone(),
+            disabled_policies: self.disabled_policies.clone(),
+            shadow_policies: self.shadow_policies.clone(),
+            enabled_namespaces: self.enabled_namespaces.clone(),
+            input_validation: self.input_validation,
+            custom_flags: self.custom_flags.clone(),
+            version: self.version + 1,
+        })
+    }
+
+    /// Derive the next snapshot from Config Manager policy settings.
+    pub fn with_policy_settings(&self, settings: &PolicySettings) -> Self {
+        Self {
+            priority_overrides: settings.priority_overrides.clone(),
+            disabled_policies: settings.disabled_policies.clone(),
+            shadow_policies: settings.shadow_policies.clone(),
+            enabled_namespaces: settings.enabled_namespaces.clone(),
+            version: self.version + 1,
+            ..self.clone()
+        }
+    }
+
+    /// Derive the next snapshot from Config Manager rule thresholds.
+    pub fn with_rule_thresholds(&self, thresholds: &RuleThresholds) -> Self {
+        Self {
+            cost_threshold: thresholds.cost_threshold,
+            token_limit: thresholds.token_limit,
+            anomaly_thresholds: AnomalyThresholds::from_rule_thresholds(thresholds),
+            rule_thresholds: thresholds.clone(),
+            version: self.version + 1,
+            ..self.clone()
+        }
+    }
+
+    /// Derive the next snapshot from Config Manager feature flags.
+    pub fn with_feature_flags(&self, flags: &FeatureFlags) -> Self {
+        Self {
+            input_validation: flags.input_validation,
+            custom_flags: flags.custom.clone(),
+            version: self.version + 1,
+            ..self.clone()
+        }
+    }
+
+    /// Check whether a policy is disabled by the policy settings.
+    pub fn is_policy_disabled(&self, policy_id: &str) -> bool {
+        matches_any(&self.disabled_policies, policy_id)
+    }
+
+    /// Check whether a policy is evaluated in shadow mode.
+    pub fn is_policy_shadowed(&self, policy_id: &str) -> bool {
+        matches_any(&self.shadow_policies, policy_id)
+    }
+
+    /// Check whether a policy's feature flag, if it has one, is on.
+    pub fn is_feature_enabled(&self, flag: Option<&str>) -> bool {
+        flag.map_or(true, |flag| self.custom_flags.get(flag) == Some(&true))
+    }
+
+    /// Check whether policies in a namespace are evaluated.
+    pub fn is_namespace_enabled(&self, namespace: Option<&str>) -> bool {
+        let namespace = namespace.unwrap_or(DEFAULT_NAMESPACE);
+        self.enabled_namespaces.is_empty()
+            || self.enabled_namespaces.iter().any(|n| n == namespace)
+    }
+
+    /// Get the effective priority of a rule, applying any override.
+    pub fn rule_priority(&self, rule: &PolicyRule) -> i32 {
+        self.priority_overrides
+            .get(&rule.id)
+            .copied()
+            .unwrap_or(rule.priority)
+    }
+
+    /// Apply the default decision and strict mode to an evaluation result.
+    pub(crate) fn finalize(&self, mut decision: PolicyDecision) -> PolicyDecision {
+        let unmatched = decision.matched_rules.is_empty() && decision.matched_policies.is_empty();
+        if unmatched && decision.decision == DecisionType::Allow {
+            let reason = format!(
+                "No policy matched; default decision is {}",
+                self.default_decision
+            );
+            let mut default = match self.default_decision {
+                DecisionType::Deny => PolicyDecision::deny(reason.clone()),
+                _ => PolicyDecision::allow(),
+            };
+            default.decision_reason = Some(
+                DecisionReason::new(ReasonCode::NoMatchDefault, reason)
+                    .with_detail("default_decision", self.default_decision.as_str().into()),
+            );
+            default.evaluation_time_ms = decision.evaluation_time_ms;
+            decision = default;
+        }
+
+        if self.strict_mode && decision.decision == DecisionType::Warn {
+            decision.decision = DecisionType::Deny;
+            decision.allowed = false;
+        }
+
+        decision
+    }
+
+    /// Handle an evaluation failure according to the fail-open setting.
+    pub(crate) fn on_error(&self, error: Error) -> Result<PolicyDecision> {
+        if !self.fail_open {
+            return Err(error);
+        }
+
+        tracing::warn!(error = %error, "Policy evaluation failed; failing open");
+        let reason = format!("Evaluation failed open: {}", error);
+        // Timeouts keep their own code, so slow evaluations stand out
+        let code = match error {
+            Error::Timeout { .. } => ReasonCode::Timeout,
+            _ => ReasonCode::FailedOpen,
+        };
+        Ok(PolicyDecision::allow()
+            .with_reason(reason.clone())
+            .with_decision_reason(
+                DecisionReason::new(code, reason).with_detail("error", error.category().into()),
+            ))
+    }
+}
+
+/// Parse the decision returned when no rule matches, which must be `allow`
+/// or `deny`.
+fn parse_default_decision(value: &str) -> Result<DecisionType> {
+    match value.parse() {
+        Ok(decision @ (DecisionType::Allow | DecisionType::Deny)) => Ok(decision),
+        _ => Err(Error::config(format!(
+            "default_decision must be allow or deny, got '{}'",
+            value
+        ))),
+    }
+}
+
+/// Check a policy ID against exact IDs and `prefix*` patterns.
+fn matches_any(patterns: &[String], policy_id: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => policy_id.starts_with(prefix),
+        None => policy_id == pattern,
+    })
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+/// Gauge reporting the version of the engine config currently applied.
+pub(crate) fn config_version_gauge() -> &'static IntGauge {
+    static GAUGE: OnceLock<IntGauge> = OnceLock::new();
+    GAUGE.get_or_init(|| {
+        let gauge = IntGauge::new(
+            "policy_engine_config_version",
+            "Version of the engine config currently applied",
+        )
+        .expect("Failed to create config version gauge");
+        prometheus::register(Box::new(gauge.clone()))
+            .expect("Failed to register config version gauge");
+        gauge
+    })
+}
+
+/// Record the outcome of a config (`kind` "config") or policy ("policies")
+/// reload: the time of a successful reload, or a rejected reload.
+pub(crate) fn record_reload<T>(kind: &str, result: &Result<T>) {
+    match result {
+        Ok(_) => last_reload_gauge().set(chrono::Utc::now().timestamp()),
+        Err(_) => rejected_reloads_counter().with_label_values(&[kind]).inc(),
+    }
+}
+
+/// Counter of evaluations that exceeded the maximum evaluation time.
+pub(crate) fn evaluation_timeouts_counter() -> &'static IntCounter {
+    static COUNTER: OnceLock<IntCounter> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounter::new(
+            "policy_engine_evaluation_timeouts_total",
+            "Evaluations that exceeded the maximum evaluation time",
+        )
+        .expect("Failed to create evaluation timeouts counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register evaluation timeouts counter");
+        counter
+    })
+}
+
+/// Gauge reporting the Config Manager config version last applied by hot
+/// reload.
+pub(crate) fn config_manager_version_gauge() -> &'static IntGauge {
+    static GAUGE: OnceLock<IntGauge> = OnceLock::new();
+    GAUGE.get_or_init(|| {
+        let gauge = IntGauge::new(
+            "policy_engine_config_manager_version",
+            "Config Manager config version last applied by hot reload",
+        )
+        .expect("Failed to create Config Manager version gauge");
+        prometheus::register(Box::new(gauge.clone()))
+            .expect("Failed to register Config Manager version gauge");
+        gauge
+    })
+}
+
+/// Gauge reporting when the config or policies were last reloaded.
+pub(crate) fn last_reload_gauge() -> &'static IntGauge {
+    static GAUGE: OnceLock<IntGauge> = OnceLock::new();
+    GAUGE.get_or_init(|| {
+        let gauge = IntGauge::new(
+            "policy_engine_last_reload_timestamp_seconds",
+            "Unix time of the last successful config or policy reload",
+        )
+        .expect("Failed to create last reload gauge");
+        prometheus::register(Box::new(gauge.clone()))
+            .expect("Failed to register last reload gauge");
+        gauge
+    })
+}
+
+/// Counter of rejected config and policy reloads.
+pub(crate) fn rejected_reloads_counter() -> &'static IntCounterVec {
+    static COUNTER: OnceLock<IntCounterVec> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounterVec::new(
+            Opts::new(
+                "policy_engine_rejected_reloads_total",
+                "Total number of rejected reloads by kind (config or policies)",
+            ),
+            &["kind"],
+        )
+        .expect("Failed to create rejected reloads counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register rejected reloads counter");
+        counter
+    })
+}
+
+/// Gauge reporting how many enabled policies are outside the enabled namespaces.
+pub(crate) fn gated_policies_gauge() -> &'static IntGauge {
+    static GAUGE: OnceLock<IntGauge> = OnceLock::new();
+    GAUGE.get_or_init(|| {
+        let gauge = IntGauge::new(
+            "policy_engine_gated_policies",
+            "Number of enabled policies skipped because their namespace is not enabled",
+        )
+        .expect("Failed to create gated policies gauge");
+        prometheus::register(Box::new(gauge.clone()))
+            .expect("Failed to register gated policies gauge");
+        gauge
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_enforcement_params() {
+        let params = EnforcementParams {
+            strict_mode: true,
+            max_evaluation_time_ms: 250,
+            decision_combiner: "permit_overrides".to_string(),
+            audit_level: "verbose".to_string(),
+            ..EnforcementParams::default()
+        };
+
+        let config = EngineConfig::default().with_enforcement_params(&params).unwrap();
+        assert!(config.strict_mode);
+        assert_eq!(config.default_decision, DecisionType::Deny);
+        assert_eq!(config.max_evaluation_time, Duration::from_millis(250));
+        assert_eq!(config.decision_combiner, DecisionCombiner::PermitOverrides);
+        assert_eq!(config.audit_level, AuditLevel::Verbose);
+        assert_eq!(config.version, 1);
+
+        let allow = EnforcementParams {
+            default_decision: "Allow".to_string(),
+            ..EnforcementParams::default()
+        };
+        let allowed = config.with_enforcement_params(&allow).unwrap();
+        assert_eq!(allowed.default_decision, DecisionType::Allow);
+
+        for invalid in ["denny", "warn", "modify", "error"] {
+            let params = EnforcementParams {
+                default_decision: invalid.to_string(),
+                ..EnforcementParams::default()
+            };
+            let err = config.with_enforcement_params(&params).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "Configuration error: default_decision must be allow or deny, got '{}'",
+                    invalid
+                )
+            );
+        }
+
+        let unknown_combiner = EnforcementParams {
+            decision_combiner: "majority".to_string(),
+            ..EnforcementParams::default()
+        };
+        assert!(config.with_enforcement_params(&unknown_combiner).is_err());
+
+        let unknown_audit_level = EnforcementParams {
+            audit_level: "loud".to_string(),
+            ..EnforcementParams::default()
+        };
+        assert!(config.with_enforcement_params(&unknown_audit_level).is_err());
+    }
+
+    #[test]
+    fn test_finalize_strict_mode_escalates_warnings() {
+        let config = EngineConfig {
+            strict_mode: true,
+            ..EngineConfig::default()
+        };
+        let mut warning = PolicyDecision::warn("suspicious");
+        warning.matched_rules = vec!["rule-1".to_string()];
+
+        let decision = config.finalize(warning);
+        assert_eq!(decision.decision, DecisionType::Deny);
+        assert!(!decision.allowed);
+        assert_eq!(decision.reason.as_deref(), Some("suspicious"));
+    }
+
+    #[test]
+    fn test_default_and_failed_open_reasons() {
+        let config = EngineConfig {
+            default_decision: DecisionType::Deny,
+            fail_open: true,
+            ..EngineConfig::default()
+        };
+        let decision = config.finalize(PolicyDecision::allow());
+        assert!(!decision.allowed);
+        let reason = decision.decision_reason.unwrap();
+        assert_eq!(reason.code, ReasonCode::NoMatchDefault);
+        assert_eq!(reason.details["default_decision"], "deny");
+
+        let decision = config.on_error(Error::evaluation("boom")).unwrap();
+        assert!(decision.allowed);
+        assert_eq!(decision.reason_code(), Some(ReasonCode::FailedOpen));
+    }
+
+    #[test]
+    fn test_disabled_policy_patterns() {
+        let settings = PolicySettings {
+            disabled_policies: vec!["legacy-pii".to_string(), "experimental.*".to_string()],
+            ..PolicySettings::default()
+        };
+        let config = EngineConfig::default().with_policy_settings(&settings);
+
+        assert!(config.is_policy_disabled("legacy-pii"));
+        assert!(config.is_policy_disabled("experimental.jailbreak"));
+        assert!(!config.is_policy_disabled("legacy-pii-v2"));
+        assert!(!config.is_policy_disabled("experimental"));
+        assert!(!config.is_policy_disabled("production.budget"));
+    }
+
+    #[test]
+    fn test_with_rule_thresholds() {
+        let config = EngineConfig::default();
+        assert_eq!(config.cost_threshold, 100.0);
+
+        let thresholds = RuleThresholds {
+            cost_threshold: 80.0,
+            token_limit: 8192,
+            anomaly_window_secs: 30,
+            deny_spike_threshold: 10,
+            ..RuleThresholds::default()
+        };
+        let config = config.with_rule_thresholds(&thresholds);
+        assert_eq!(config.cost_threshold, 80.0);
+        assert_eq!(config.token_limit, 8192);
+        assert_eq!(config.anomaly_thresholds.window, Duration::from_secs(30));
+        assert_eq!(config.anomaly_thresholds.deny_spike, 10);
+        assert_eq!(config.version, 1);
+    }
+}
@@ -0,0 +1,334 @@
+//! Typed evaluation input and decision.
+//!
+//! [`EvaluationInput`] and [`Decision`] are the flat request and result
+//! types for callers embedding the engine, evaluated with
+//! [`PolicyEngine::evaluate_input`](super::PolicyEngine::evaluate_input).
+//! An input converts into an [`EvaluationContext`], and a decision maps onto
+//! the records reported to Observatory with [`Decision::to_event`] and
+//! [`Decision::to_record`].
+//!
+//! ```
+//! use llm_policy_engine::api::EvaluationInput;
+//! use llm_policy_engine::{DecisionType, PolicyEngine};
+//!
+//! #[tokio::main]
+//! async fn main() -> llm_policy_engine::Result<()> {
+//!     let engine = PolicyEngine::builder().build().await?;
+//!
+//!     let input = EvaluationInput {
+//!         model: Some("gpt-4".to_string()),
+//!         provider: Some("openai".to_string()),
+//!         estimated_tokens: Some(1200),
+//!         ..EvaluationInput::for_subject("user-123")
+//!     };
+//!     let decision = engine.evaluate_input(&input).await?;
+//!     assert_eq!(decision.outcome, DecisionType::Allow);
+//!
+//!     let record = decision.to_record(&input);
+//!     assert_eq!(record.user_id.as_deref(), Some("user-123"));
+//!     Ok(())
+//! }
+//! ```
+
+use super::budget::{ESTIMATED_COST_KEY, ESTIMATED_TOKENS_KEY};
+use super::{EvaluationContext, PolicyDecision};
+use crate::integration::{PolicyDecisionRecord, PolicyEvaluationEvent, TraceContext};
+use crate::policy::{DecisionReason, DecisionType, Obligations, ReasonCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The subject a request is made for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subject {
+    /// Subject ID
+    pub id: String,
+    /// Roles of the subject
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+}
+
+/// Input to a policy evaluation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvaluationInput {
+    /// Who the request is made for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<Subject>,
+    /// Requested model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Model provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Prompt text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Additional metadata, visible to policies as `metadata`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Estimated tokens the request will use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_tokens: Option<u64>,
+    /// Estimated cost of the request in cents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost_cents: Option<f64>,
+    /// Trace of the request, correlating emitted events with the caller's
+    /// trace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<TraceContext>,
+}
+
+impl EvaluationInput {
+    /// Create an input for a subject without roles.
+    pub fn for_subject(id: impl Into<String>) -> Self {
+        Self {
+            subject: Some(Subject {
+                id: id.into(),
+                roles: Vec::new(),
+            }),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&EvaluationInput> for EvaluationContext {
+    /// Build the context policies see for an input. Estimates become the
+    /// `estimated_tokens` and `estimated_cost_cents` metadata read by budget
+    /// checks, overriding metadata of the same name.
+    fn from(input: &EvaluationInput) -> Self {
+        let mut builder = EvaluationContext::builder();
+        if let Some(subject) = &input.subject {
+            builder = builder.with_user(subject.id.clone(), None, subject.roles.clone());
+        }
+        if let Some(model) = &input.model {
+            builder = builder.with_model(model.clone());
+        }
+        if let Some(provider) = &input.provider {
+            builder = builder.with_provider(provider.clone());
+        }
+        if let Some(prompt) = &input.prompt {
+            builder = builder.with_prompt(prompt.clone());
+        }
+        for (key, value) in &input.metadata {
+            builder = builder.with_metadata(key.clone(), value.clone());
+        }
+        if let Some(tokens) = input.estimated_tokens {
+            builder = builder.with_metadata(ESTIMATED_TOKENS_KEY, tokens.into());
+        }
+        if let Some(cost) = input.estimated_cost_cents {
+            builder = builder.with_metadata(ESTIMATED_COST_KEY, cost.into());
+        }
+        if let Some(trace) = &input.trace {
+            builder = builder.with_trace(trace.clone());
+        }
+        builder.build()
+    }
+}
+
+/// Result of a policy evaluation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Decision {
+    /// The decision
+    pub outcome: DecisionType,
+    /// Human-readable reason for the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Machine-readable code for the reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
+    /// Obligations the caller must fulfil
+    #[serde(default, skip_serializing_if = "Obligations::is_empty")]
+    pub obligations: Obligations,
+    /// ID of the first matched policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<String>,
+    /// ID of the first matched rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// Time taken for evaluation in milliseconds
+    pub latency_ms: f64,
+    /// Whether the decision was served from the decision cache
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl Decision {
+    /// Check if the decision allows the request.
+    pub fn is_allowed(&self) -> bool {
+        self.outcome.is_allowed()
+    }
+
+    /// Build the evaluation event reporting this decision to Observatory,
+    /// correlated with the input's trace. Fails if the latency is negative.
+    pub fn to_event(&self, input: &EvaluationInput) -> crate::Result<PolicyEvaluationEvent> {
+        let mut builder = PolicyEvaluationEvent::builder(
+            self.policy_id.clone().unwrap_or_default(),
+            self.outcome.into(),
+        )
+        .duration_ms(self.latency_ms)
+        .cached(self.cached);
+        if let Some(trace) = &input.trace {
+            builder = builder.trace_id(trace.trace_id.clone());
+        }
+        if let Some(rule_id) = &self.rule_id {
+            builder = builder.rule_id(rule_id.clone());
+        }
+        if let Some(code) = self.reason_code {
+            let message = self.reason.clone().unwrap_or_default();
+            builder = builder.reason(DecisionReason::new(code, message));
+        }
+        if !self.obligations.is_empty() {
+            builder = builder.obligations(self.obligations.clone());
+        }
+        builder.build()
+    }
+
+    /// Build the analytics record of this decision for an input.
+    pub fn to_record(&self, input: &EvaluationInput) -> PolicyDecisionRecord {
+        let mut metadata = HashMap::new();
+        if let Some(rule_id) = &self.rule_id {
+            metadata.insert("rule_id".to_string(), rule_id.clone().into());
+        }
+        if self.cached {
+            metadata.insert("cached".to_string(), true.into());
+        }
+        PolicyDecisionRecord {
+            decision_id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: input.subject.as_ref().map(|subject| subject.id.clone()),
+            model: input.model.clone(),
+            provider: input.provider.clone(),
+            policy_id: self.policy_id.clone().unwrap_or_default(),
+            decision: self.outcome.into(),
+            latency_ms: self.latency_ms,
+            reason: self.reason.clone(),
+            reason_code: self.reason_code,
+            metadata,
+        }
+    }
+}
+
+impl From<&PolicyDecision> for Decision {
+    fn from(decision: &PolicyDecision) -> Self {
+        Self {
+            outcome: decision.decision,
+            reason: decision.reason.clone(),
+            reason_code: decision.reason_code(),
+            obligations: decision.obligations.clone(),
+            policy_id: decision.matched_policies.first().cloned(),
+            rule_id: decision.matched_rules.first().cloned(),
+            latency_ms: decision.evaluation_time_ms,
+            cached: decision.cached,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::DecisionOutcome;
+    use crate::policy::{Action, Condition, Policy, PolicyRule};
+    use crate::PolicyEngine;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    #[test]
+    fn test_input_serialization() {
+        let input: EvaluationInput = serde_json::from_value(serde_json::json!({
+            "subject": { "id": "user-1", "roles": ["admin"] },
+            "model": "gpt-4",
+            "estimated_tokens": 500,
+            "estimated_cost_cents": 1.5,
+            "trace": { "trace_id": "4bf92f3577b34da6a3ce929d0e0e4736" },
+        }))
+        .unwrap();
+        assert_eq!(input.subject.as_ref().unwrap().roles, vec!["admin"]);
+        assert!(input.metadata.is_empty());
+
+        let value = serde_json::to_value(&input).unwrap();
+        assert!(value.get("provider").is_none());
+        let round_trip: EvaluationInput = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&round_trip).unwrap(), value);
+
+        let context = EvaluationContext::from(&input);
+        assert_eq!(context.user.unwrap().id, "user-1");
+        assert_eq!(context.llm.unwrap().model.as_deref(), Some("gpt-4"));
+        assert_eq!(context.metadata[ESTIMATED_TOKENS_KEY], 500);
+        assert_eq!(context.metadata[ESTIMATED_COST_KEY], 1.5);
+        assert!(context.trace.is_some());
+    }
+
+    #[test]
+    fn test_decision_serialization() {
+        let decision = Decision {
+            outcome: DecisionType::Deny,
+            reason: Some("Model not allowed".to_string()),
+            reason_code: Some(ReasonCode::RuleMatched),
+            obligations: Obligations::default(),
+            policy_id: Some("models".to_string()),
+            rule_id: Some("deny-gpt-4".to_string()),
+            latency_ms: 0.4,
+            cached: true,
+        };
+        let value = serde_json::to_value(&decision).unwrap();
+        assert_eq!(value["outcome"], "deny");
+        assert!(value.get("obligations").is_none());
+        assert_eq!(serde_json::from_value::<Decision>(value).unwrap(), decision);
+
+        let input = EvaluationInput {
+            model: Some("gpt-4".to_string()),
+            trace: Some(TraceContext::new(TRACE_ID.to_string())),
+            ..EvaluationInput::for_subject("user-1")
+        };
+        let event = decision.to_event(&input).unwrap();
+        assert_eq!(event.policy_id, "models");
+        assert_eq!(event.rule_id.as_deref(), Some("deny-gpt-4"));
+        assert_eq!(event.decision, DecisionOutcome::Deny);
+        assert_eq!(event.trace_id.as_deref(), Some(TRACE_ID));
+        assert!(event.cached);
+
+        let record = decision.to_record(&input);
+        assert_eq!(record.user_id.as_deref(), Some("user-1"));
+        assert_eq!(record.model.as_deref(), Some("gpt-4"));
+        assert_eq!(record.reason_code, Some(ReasonCode::RuleMatched));
+        assert_eq!(record.metadata["cached"], true);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_input() {
+        let engine = PolicyEngine::builder()
+            .with_cache_enabled(true)
+            .with_policy(
+                Policy::builder("models")
+                    .rule(PolicyRule::new(
+                        "deny-gpt-4",
+                        "Deny GPT-4",
+                        Condition::expression("llm.model == 'gpt-4'"),
+                        Action::deny("Model not allowed"),
+                    ))
+                    .build(),
+            )
+            .build()
+            .await
+            .unwrap();
+        let input = EvaluationInput {
+            model: Some("gpt-4".to_string()),
+            ..EvaluationInput::for_subject("user-1")
+        };
+
+        let decision = engine.evaluate_input(&input).await.unwrap();
+        assert!(!decision.is_allowed());
+        assert_eq!(decision.policy_id.as_deref(), Some("models"));
+        assert_eq!(decision.rule_id.as_deref(), Some("deny-gpt-4"));
+        assert_eq!(decision.reason.as_deref(), Some("Model not allowed"));
+
+        // Allow decisions are cached, and cache hits are flagged.
+        let input = EvaluationInput {
+            model: Some("gpt-3.5-turbo".to_string()),
+            ..input
+        };
+        let decision = engine.evaluate_input(&input).await.unwrap();
+        assert!(decision.is_allowed());
+        assert!(!decision.cached);
+        assert!(engine.evaluate_input(&input).await.unwrap().cached);
+    }
+}
@@ -0,0 +1,224 @@
+//! Sampling of Observatory evaluation events.
+//!
+//! Events are sampled consistently with tracing: an event of a traced
+//! request is emitted if its trace is sampled, so Observatory sees whole
+//! traces or none of them. Events without a trace are kept if a hash of the
+//! event ID falls below `telemetry.trace_sampling_ratio`. Denials and
+//! errors, enforced or shadow, are always emitted.
+
+use crate::integration::{DecisionOutcome, PolicyEvaluationEvent, TraceContext};
+
+use prometheus::{Gauge, IntCounterVec, Opts};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+/// Decides which evaluation events are emitted.
+#[derive(Debug)]
+pub struct EventSampler {
+    ratio: f64,
+    kept: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl EventSampler {
+    /// Create a sampler keeping a `ratio` (0.0 to 1.0) of untraced events.
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            kept: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Decide whether to emit an event, given the request's trace, and
+    /// record the decision.
+    pub fn sample(&self, event: &PolicyEvaluationEvent, trace: Option<&TraceContext>) -> bool {
+        let keep = always_kept(event.decision)
+            || event.shadow_decision.is_some_and(always_kept)
+            || match trace {
+                Some(trace) => trace.is_sampled(),
+                None => hash_ratio(&event.event_id) < self.ratio,
+            };
+
+        let counter = if keep { &self.kept } else { &self.dropped };
+        counter.fetch_add(1, Ordering::Relaxed);
+        sampled_events_counter()
+            .with_label_values(&[if keep { "kept" } else { "dropped" }])
+            .inc();
+        sample_rate_gauge().set(self.sample_rate());
+        keep
+    }
+
+    /// Share of events kept so far; 1.0 before any event is seen.
+    pub fn sample_rate(&self) -> f64 {
+        let kept = self.kept.load(Ordering::Relaxed);
+        let total = kept + self.dropped.load(Ordering::Relaxed);
+        if total == 0 {
+            1.0
+        } else {
+            kept as f64 / total as f64
+        }
+    }
+}
+
+/// Whether events with this outcome are emitted regardless of sampling.
+fn always_kept(outcome: DecisionOutcome) -> bool {
+    matches!(outcome, DecisionOutcome::Deny | DecisionOutcome::Error)
+}
+
+/// Map an ID to a uniformly distributed value in `[0, 1)`.
+fn hash_ratio(id: &str) -> f64 {
+    let hash = blake3::hash(id.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    // The top 53 bits fill an f64 mantissa exactly.
+    (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Counter of evaluation events kept and dropped by sampling.
+fn sampled_events_counter() -> &'static IntCounterVec {
+    static COUNTER: OnceLock<IntCounterVec> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounterVec::new(
+            Opts::new(
+                "policy_engine_events_sampled_total",
+                "Total number of evaluation events by sampling result",
+            ),
+            &["result"],
+        )
+        .expect("Failed to create sampled events counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register sampled events counter");
+        counter
+    })
+}
+
+/// Gauge reporting the share of evaluation events emitted.
+fn sample_rate_gauge() -> &'static Gauge {
+    static GAUGE: OnceLock<Gauge> = OnceLock::new();
+    GAUGE.get_or_init(|| {
+        let gauge = Gauge::new(
+            "policy_engine_event_sample_rate",
+            "Share of evaluation events emitted to Observatory after sampling",
+        )
+        .expect("Failed to create event sample rate gauge");
+        prometheus::register(Box::new(gauge.clone()))
+            .expect("Failed to register event sample rate gauge");
+        gauge
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn event(decision: DecisionOutcome) -> PolicyEvaluationEvent {
+        PolicyEvaluationEvent {
+            event_id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            trace_id: None,
+            span_id: None,
+            policy_id: "gate".to_string(),
+            rule_id: None,
+            decision,
+            reason: None,
+            obligations: None,
+            shadow_decision: None,
+            duration_ms: 1.0,
+            cached: false,
+            context: HashMap::new(),
+            labels: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_keep_rate_approximates_ratio() {
+        let sampler = EventSampler::new(0.25);
+        let kept = (0..10_000)
+            .filter(|_| sampler.sample(&event(DecisionOutcome::Allow), None))
+            .count();
+
+        assert!((2_200..=2_800).contains(&kept), "kept {} of 10000", kept);
+        assert!((sampler.sample_rate() - kept as f64 / 10_000.0).abs() < 1e-9);
+
+        // The decision is deterministic for an event ID.
+        let allowed = event(DecisionOutcome::Allow);
+        let first = sampler.sample(&allowed, None);
+        assert!((0..10).all(|_| sampler.sample(&allowed, None) == first));
+    }
+
+    #[test]
+    fn test_denies_and_errors_never_dropped() {
+        let sampler = EventSampler::new(0.0);
+        let mut unsampled = TraceContext::new("4bf92f3577b34da6a3ce929d0e0e4736".to_string());
+        unsampled.trace_flags = 0;
+
+        for _ in 0..100 {
+            assert!(sampler.sample(&event(DecisionOutcome::Deny), None));
+            assert!(sampler.sample(&event(DecisionOutcome::Error), Some(&unsampled)));
+            assert!(!sampler.sample(&event(DecisionOutcome::Allow), None));
+        }
+        let mut shadow_deny = event(DecisionOutcome::Allow);
+        shadow_deny.shadow_decision = Some(DecisionOutcome::Deny);
+        assert!(sampler.sample(&shadow_deny, Some(&unsampled)));
+
+        // Traced events follow the trace's sampling decision.
+        let sampled = TraceContext::new("4bf92f3577b34da6a3ce929d0e0e4736".to_string());
+        assert!(sampler.sample(&event(DecisionOutcome::Allow), Some(&sampled)));
+        let everything = EventSampler::new(1.0);
+        assert!(!everything.sample(&event(DecisionOutcome::Warn), Some(&unsampled)));
+    }
+
+    #[tokio::test]
+    async fn test_engine_samples_emitted_events() {
+        use crate::api::{EvaluationContext, PolicyEngine};
+        use crate::config::Config;
+        use crate::integration::{BatchConfig, IntegrationClient, MockTransport, ObservatoryAdapter};
+        use crate::policy::{Action, Condition, Policy, PolicyRule};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/events/batch",
+            serde_json::json!({ "accepted_count": 1, "rejected_count": 0 }),
+        ));
+        let client =
+            IntegrationClient::new("http://observatory".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        let observatory = Arc::new(ObservatoryAdapter::from_client(client));
+        let sink = observatory.spawn_batching(BatchConfig::default());
+        let mut config = Config::default();
+        config.telemetry.trace_sampling_ratio = 0.0;
+        let engine = PolicyEngine::builder()
+            .with_config(config)
+            .with_policy(
+                Policy::builder("guests")
+                    .rule(PolicyRule::new(
+                        "deny-guests",
+                        "Deny guests",
+                        Condition::equals("user.roles", vec!["guest".to_string()]),
+                        Action::deny("Guests are not allowed"),
+                    ))
+                    .build(),
+            )
+            .with_event_sink(sink.clone())
+            .build()
+            .await
+            .unwrap();
+
+        for role in ["admin", "guest", "member"] {
+            let context = EvaluationContext::builder()
+                .with_user(role, None, vec![role.to_string()])
+                .build();
+            engine.evaluate(&context).await.unwrap();
+        }
+        sink.flush().await;
+
+        let body = transport.requests()[0].json().unwrap();
+        let events = body["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["decision"], "deny");
+    }
+}
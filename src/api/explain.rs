@@ -0,0 +1,91 @@
+//! Decision explanations.
+//!
+//! A [`DecisionExplanation`] records how a decision was reached: every
+//! policy evaluated, in order, with each rule's condition result and
+//! timing, the decision each policy contributed, and whether it settled the
+//! combined outcome. It is produced by the same evaluation code as normal
+//! decisions, so it always matches what evaluation would decide.
+
+use super::PolicyDecision;
+use crate::integration::DecisionOutcome;
+use crate::policy::{DecisionCombiner, DecisionType};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Why a decision was made.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionExplanation {
+    /// Final outcome
+    pub outcome: DecisionOutcome,
+    /// The decision itself
+    pub decision: PolicyDecision,
+    /// Combiner used to combine policy decisions
+    pub combiner: DecisionCombiner,
+    /// Policies evaluated, in evaluation order
+    pub policies: Vec<PolicyExplanation>,
+    /// Policies not evaluated because their feature flag is off: the
+    /// policy's feature flag by policy ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub flagged_off: BTreeMap<String, String>,
+}
+
+impl DecisionExplanation {
+    /// Get the policy and rule that produced the final decision: the first
+    /// matched rule, in a contributing policy, whose decision is the final
+    /// decision. `None` if no rule matched.
+    pub fn responsible_rule(&self) -> Option<(&str, &str)> {
+        self.policies
+            .iter()
+            .filter(|policy| policy.contributed)
+            .find_map(|policy| {
+                policy
+                    .rules
+                    .iter()
+                    .find(|rule| rule.decision == Some(self.decision.decision))
+                    .map(|rule| (policy.policy_id.as_str(), rule.rule_id.as_str()))
+            })
+    }
+}
+
+/// How one policy was evaluated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExplanation {
+    /// Policy ID
+    pub policy_id: String,
+    /// Combiner used to combine the policy's rule decisions
+    pub combiner: DecisionCombiner,
+    /// Rules evaluated, in evaluation order
+    pub rules: Vec<RuleExplanation>,
+    /// The policy's decision
+    pub decision: DecisionType,
+    /// Whether any rule matched, or the policy decided anything but allow
+    pub applicable: bool,
+    /// Whether the policy's decision fixed the combined outcome, so later
+    /// policies were not evaluated
+    pub settled: bool,
+    /// Whether the policy's decision is part of the final decision
+    pub contributed: bool,
+    /// Time taken to evaluate the policy in microseconds
+    pub duration_us: u64,
+}
+
+/// How one rule was evaluated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleExplanation {
+    /// Rule ID
+    pub rule_id: String,
+    /// Whether the rule's condition held
+    pub matched: bool,
+    /// The rule's decision, if it matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision: Option<DecisionType>,
+    /// Whether the rule's decision fixed the policy's outcome, so later
+    /// rules were not evaluated
+    pub settled: bool,
+    /// Custom CEL functions called by the rule's condition
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<String>,
+    /// Time taken to evaluate the condition in microseconds
+    pub duration_us: u64,
+}
@@ -0,0 +1,72 @@
+//! Integration failure handling.
+//!
+//! When an integration a request depends on fails, the request either
+//! proceeds without it (fails open) or is denied (fails closed), as set per
+//! integration by `integrations.failure_policies`. Integrations without a
+//! policy of their own keep the global behavior: the request fails with the
+//! integration error if `fail_on_error` is set, or, for CostOps, unless the
+//! enforcement parameters fail open, and proceeds otherwise.
+//!
+//! Incident Manager failures have no decision to deny, so failing closed
+//! fails the request with the error. Observatory events are delivered in the
+//! background and always fail open.
+
+use super::PolicyDecision;
+use crate::config::FailurePolicy;
+use crate::policy::{DecisionReason, ReasonCode};
+
+use std::fmt::Display;
+
+/// What happens to a request when an integration fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OnFailure {
+    /// Proceed without the integration
+    Proceed,
+    /// Deny the request
+    Deny,
+    /// Fail the request with the integration error
+    Fail,
+}
+
+impl OnFailure {
+    /// Resolve an integration's failure policy, if it has one, or else fail
+    /// if `fail` is set and proceed otherwise.
+    pub(crate) fn resolve(policy: Option<FailurePolicy>, fail: bool) -> Self {
+        match policy {
+            Some(FailurePolicy::FailOpen) => OnFailure::Proceed,
+            Some(FailurePolicy::FailClosed) => OnFailure::Deny,
+            None if fail => OnFailure::Fail,
+            None => OnFailure::Proceed,
+        }
+    }
+
+    /// Log a failure that is not returned as an error, with its outcome.
+    pub(crate) fn log(self, integration: &str, error: &impl Display) {
+        match self {
+            OnFailure::Proceed => tracing::warn!(
+                integration,
+                error = %error,
+                "Integration failed; failing open and proceeding without it"
+            ),
+            OnFailure::Deny => tracing::warn!(
+                integration,
+                error = %error,
+                "Integration failed; failing closed and denying the request"
+            ),
+            OnFailure::Fail => {}
+        }
+    }
+
+    /// The decision denying a request because an integration failed.
+    pub(crate) fn denial(integration: &str, error: &impl Display) -> PolicyDecision {
+        let reason = format!("{} is unavailable: {}", integration, error);
+        let mut decision = PolicyDecision::deny(reason.clone()).with_decision_reason(
+            DecisionReason::new(ReasonCode::IntegrationUnavailable, reason)
+                .with_detail("integration", integration.into()),
+        );
+        decision
+            .metadata
+            .insert("failed_integration".to_string(), serde_json::json!(integration));
+        decision
+    }
+}
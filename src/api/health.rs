@@ -0,0 +1,185 @@
+//! Liveness and readiness reporting.
+//!
+//! Liveness only says the engine is running and never calls upstreams, so a
+//! slow integration cannot get a healthy process restarted. Readiness says
+//! whether the engine should receive traffic: policies are loaded (and so
+//! compiled), the L2 cache is reachable if one is configured, and the
+//! integrations that gate readiness are healthy. Integrations outside
+//! `integrations.readiness_integrations` are reported but cannot fail the
+//! probe.
+
+use crate::integration::HealthReport;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Readiness of one engine component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentStatus {
+    /// Whether the component is ready
+    pub ready: bool,
+    /// What was checked, or why the component is not ready
+    pub detail: String,
+}
+
+impl ComponentStatus {
+    /// A ready component.
+    pub fn ready(detail: impl Into<String>) -> Self {
+        Self {
+            ready: true,
+            detail: detail.into(),
+        }
+    }
+
+    /// A component that is not ready.
+    pub fn not_ready(detail: impl Into<String>) -> Self {
+        Self {
+            ready: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Whether the engine is ready to serve, with component-level detail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessReport {
+    /// Whether every component is ready
+    pub ready: bool,
+    /// Engine components (`policies`, `cache`, `integrations`) by name
+    pub components: BTreeMap<String, ComponentStatus>,
+    /// Health of every configured integration
+    pub integrations: HealthReport,
+}
+
+impl ReadinessReport {
+    /// Build a report from component statuses and integration health, which
+    /// is added as the `integrations` component.
+    pub fn new(
+        mut components: BTreeMap<String, ComponentStatus>,
+        integrations: HealthReport,
+    ) -> Self {
+        let status = if integrations.healthy {
+            ComponentStatus::ready(format!("{} configured", integrations.integrations.len()))
+        } else {
+            ComponentStatus::not_ready(format!(
+                "unhealthy: {}",
+                integrations.unhealthy().join(", ")
+            ))
+        };
+        components.insert("integrations".to_string(), status);
+        let ready = components.values().all(|component| component.ready);
+
+        Self {
+            ready,
+            components,
+            integrations,
+        }
+    }
+
+    /// Names of components that are not ready.
+    pub fn not_ready(&self) -> Vec<&str> {
+        self.components
+            .iter()
+            .filter(|(_, status)| !status.ready)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// HTTP status for a readiness probe: 200 if ready, 503 otherwise.
+    pub fn http_status(&self) -> u16 {
+        if self.ready {
+            200
+        } else {
+            503
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::PolicyEngine;
+    use crate::cache::l2::tests::UnavailableStore;
+    use crate::config::{Config, IntegrationsConfig};
+    use crate::integration::{HealthStatus, Integrations};
+    use crate::policy::{Action, Condition, Policy, PolicyRule};
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn health_server(status: u16) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn policy() -> Policy {
+        Policy::builder("cel-gate")
+            .rule(PolicyRule::new(
+                "deny-guests",
+                "Deny guests",
+                Condition::expression("'guest' in user.roles"),
+                Action::deny("Guests are not allowed"),
+            ))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_ready_with_optional_integration_down() {
+        let healthy = health_server(200).await;
+        let unhealthy = health_server(503).await;
+        let integrations_config = IntegrationsConfig {
+            shield_url: Some(healthy.uri()),
+            observatory_url: Some(unhealthy.uri()),
+            readiness_integrations: vec!["shield".to_string()],
+            ..Default::default()
+        };
+        let integrations = Integrations::from_config(&integrations_config);
+        let config = Config {
+            integrations: integrations_config,
+            ..Default::default()
+        };
+        let engine = PolicyEngine::builder()
+            .with_config(config)
+            .with_policy(policy())
+            .build()
+            .await
+            .unwrap();
+
+        assert!(engine.liveness());
+        let report = engine.readiness(&integrations).await;
+        assert!(report.ready, "{:?}", report);
+        assert_eq!(report.http_status(), 200);
+        assert_eq!(
+            report.components["policies"].detail,
+            "1 policies loaded, 1 CEL expressions compiled"
+        );
+        // Observatory does not gate readiness, but is still reported.
+        assert_eq!(report.integrations.status("observatory"), Some(HealthStatus::Unhealthy));
+    }
+
+    #[tokio::test]
+    async fn test_not_ready_components_reported() {
+        let mut config = Config::default();
+        config.integrations.readiness_integrations = vec!["shield".to_string()];
+        let integrations = Integrations::from_config(&config.integrations);
+        let engine = PolicyEngine::builder()
+            .with_config(config)
+            .with_l2_store(Arc::new(UnavailableStore))
+            .build()
+            .await
+            .unwrap();
+
+        // Liveness does not depend on readiness.
+        assert!(engine.liveness());
+        let report = engine.readiness(&integrations).await;
+        assert!(!report.ready);
+        assert_eq!(report.http_status(), 503);
+        assert_eq!(report.not_ready(), vec!["cache", "integrations", "policies"]);
+        assert_eq!(report.integrations.status("shield"), Some(HealthStatus::Unknown));
+    }
+}
@@ -0,0 +1,390 @@
+//! Policy hot reload.
+//!
+//! A [`PolicyReloader`] loads policies from one or more [`PolicySource`]s
+//! and replaces the engine's policies with them, either on demand or
+//! whenever a source reports a change. Sources are merged by priority: if
+//! two sources define a policy with the same ID, the higher-priority
+//! source's policy is loaded. Each reload is validated, optionally against
+//! the Schema Registry policy schema, and compiled before the new policy
+//! set is swapped in; a reload that fails is rejected with an error logged,
+//! and the current policies stay active. Successful and rejected reloads
+//! are reported by the `policy_engine_last_reload_timestamp_seconds` and
+//! `policy_engine_rejected_reloads_total` metrics.
+
+use super::engine_config::record_reload;
+use super::{FilePolicySource, PolicyEngine, PolicySource};
+use crate::integration::{PolicyDocumentSchema, SchemaRegistryAdapter};
+use crate::policy::PolicyDocument;
+use crate::{Error, Result};
+
+use futures::StreamExt;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// A policy source and its priority.
+struct PrioritizedSource {
+    priority: i32,
+    source: Arc<dyn PolicySource>,
+}
+
+/// Reloads policies from policy sources into a policy engine.
+pub struct PolicyReloader {
+    sources: Vec<PrioritizedSource>,
+    schema_registry: Option<Arc<SchemaRegistryAdapter>>,
+}
+
+impl PolicyReloader {
+    /// Create a reloader for policy files (YAML or JSON), which together
+    /// make up the full policy set.
+    pub fn new(files: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self::from_sources().with_source(Arc::new(FilePolicySource::new(files)), 0)
+    }
+
+    /// Create a reloader without sources; add them with
+    /// [`with_source`](Self::with_source).
+    pub fn from_sources() -> Self {
+        Self {
+            sources: Vec::new(),
+            schema_registry: None,
+        }
+    }
+
+    /// Add a policy source. Policies from higher-priority sources replace
+    /// those with the same ID from lower-priority sources; among sources
+    /// with equal priority, the one added first wins.
+    pub fn with_source(mut self, source: Arc<dyn PolicySource>, priority: i32) -> Self {
+        let index = self
+            .sources
+            .iter()
+            .position(|existing| existing.priority < priority)
+            .unwrap_or(self.sources.len());
+        self.sources.insert(index, PrioritizedSource { priority, source });
+        self
+    }
+
+    /// Validate reloaded policies against the Schema Registry policy schema.
+    pub fn with_schema_registry(mut self, schema_registry: Arc<SchemaRegistryAdapter>) -> Self {
+        self.schema_registry = Some(schema_registry);
+        self
+    }
+
+    /// Load, validate and swap in the policies from every source, returning
+    /// the IDs of the loaded policies. On failure the error is logged and
+    /// returned, and the engine keeps its current policies.
+    pub async fn reload(&self, engine: &PolicyEngine) -> Result<Vec<String>> {
+        let result = self.try_reload(engine).await;
+        record_reload("policies", &result);
+        match &result {
+            Ok(ids) => tracing::info!(policies = ids.len(), "Reloaded policies"),
+            Err(e) => tracing::error!(
+                error = %e,
+                "Rejected policy reload; keeping current policies"
+            ),
+        }
+        result
+    }
+
+    async fn try_reload(&self, engine: &PolicyEngine) -> Result<Vec<String>> {
+        let document = self.load().await?;
+        self.validate_schema(&document).await?;
+        engine.replace_policies(document).await
+    }
+
+    /// Load every source and merge their policies by priority.
+    async fn load(&self) -> Result<PolicyDocument> {
+        let mut document = PolicyDocument::new();
+        let mut seen = HashSet::new();
+        for PrioritizedSource { source, .. } in &self.sources {
+            let policies = source.load().await?;
+            // Duplicates within one source are left for validation to reject.
+            let ids: Vec<String> = policies.iter().map(|policy| policy.id.clone()).collect();
+            document
+                .policies
+                .extend(policies.into_iter().filter(|policy| !seen.contains(&policy.id)));
+            seen.extend(ids);
+        }
+        Ok(document)
+    }
+
+    async fn validate_schema(&self, document: &PolicyDocument) -> Result<()> {
+        let Some(schema_registry) = &self.schema_registry else {
+            return Ok(());
+        };
+        let schema = PolicyDocumentSchema {
+            api_version: document.api_version.clone(),
+            kind: document.kind.clone(),
+            policies: document
+                .policies
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<std::result::Result<_, _>>()?,
+        };
+        let result = schema_registry
+            .validate_policy_document(&schema)
+            .await
+            .map_err(|e| Error::integration("schema_registry", e.to_string()))?;
+        if !result.valid {
+            return Err(Error::validation(format!(
+                "Policies do not match the policy schema: {}",
+                result
+                    .errors
+                    .iter()
+                    .map(|error| format!("{}: {}", error.path, error.message))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reload whenever any source reports a change.
+    ///
+    /// Runs until every source's watch stream has ended.
+    pub async fn watch(&self, engine: &PolicyEngine) {
+        let mut changes = futures::stream::select_all(
+            self.sources
+                .iter()
+                .map(|PrioritizedSource { source, .. }| source.watch().map(move |()| source)),
+        );
+
+        while let Some(source) = changes.next().await {
+            tracing::debug!(source = source.name(), "Reloading policies for source change");
+            // Failures are logged; the next change is tried again.
+            let _ = self.reload(engine).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for PolicyReloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sources: Vec<_> = self
+            .sources
+            .iter()
+            .map(|PrioritizedSource { priority, source }| (source.name(), priority))
+            .collect();
+        f.debug_struct("PolicyReloader")
+            .field("sources", &sources)
+            .field("schema_registry", &self.schema_registry.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::EvaluationContext;
+    use crate::policy::{Action, Condition, Policy, PolicyRule};
+    use futures::future::BoxFuture;
+    use futures::stream::BoxStream;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tokio::sync::Notify;
+
+    const ALLOW_ALL: &str = r#"
+policies:
+  - id: gate
+    metadata:
+      name: Gate
+    rules:
+      - id: allow
+        name: Allow everyone
+        condition:
+          operator: exists
+          field: user.id
+        action:
+          type: allow
+          decision: allow
+"#;
+
+    const DENY_ALL: &str = r#"
+policies:
+  - id: gate
+    metadata:
+      name: Gate
+    rules:
+      - id: deny
+        name: Deny everyone
+        condition:
+          operator: expression
+          value: "user.id != ''"
+        action:
+          type: deny
+          decision: deny
+          reason: Closed
+"#;
+
+    const INVALID_REGEX: &str = r#"
+policies:
+  - id: gate
+    metadata:
+      name: Gate
+    rules:
+      - id: deny
+        name: Deny bad prompts
+        condition:
+          operator: matches
+          field: llm.prompt
+          value: "(unclosed"
+        action:
+          type: deny
+          decision: deny
+      - id: warn
+        name: Warn on long prompts
+        condition:
+          operator: expression
+          value: "size(llm.prompt) > 1000"
+        action:
+          type: warn
+          decision: warn
+"#;
+
+    /// Source whose policies are set by the test, which reports a change
+    /// when told to.
+    #[derive(Default)]
+    struct MockSource {
+        policies: Mutex<Vec<Policy>>,
+        changed: Notify,
+    }
+
+    impl MockSource {
+        fn with_policies(policies: Vec<Policy>) -> Self {
+            Self {
+                policies: Mutex::new(policies),
+                changed: Notify::new(),
+            }
+        }
+
+        fn update(&self, policies: Vec<Policy>) {
+            *self.policies.lock().unwrap() = policies;
+            self.changed.notify_one();
+        }
+    }
+
+    impl PolicySource for MockSource {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn load(&self) -> BoxFuture<'_, Result<Vec<Policy>>> {
+            let policies = self.policies.lock().unwrap().clone();
+            Box::pin(async move { Ok(policies) })
+        }
+
+        fn watch(&self) -> BoxStream<'_, ()> {
+            futures::stream::unfold((), move |()| async move {
+                self.changed.notified().await;
+                Some(((), ()))
+            })
+            .boxed()
+        }
+    }
+
+    fn gate(id: &str, action: Action) -> Policy {
+        Policy::builder(id)
+            .rule(PolicyRule::new("gate", "Gate", Condition::exists("user.id"), action))
+            .build()
+    }
+
+    fn temp_policy_file() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "policy-engine-{}-{}-policies.yaml",
+            std::process::id(),
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_reload_swaps_policies() {
+        let path = temp_policy_file();
+        std::fs::write(&path, ALLOW_ALL).unwrap();
+        let reloader = PolicyReloader::new([&path]);
+        let engine = PolicyEngine::builder().build().await.unwrap();
+        engine.load_policy_yaml(DENY_ALL.replace("gate", "stale").as_str()).await.unwrap();
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+
+        assert_eq!(reloader.reload(&engine).await.unwrap(), vec!["gate"]);
+        assert_eq!(engine.list_policies(), vec!["gate"]);
+        assert!(engine.evaluate(&context).await.unwrap().allowed);
+
+        std::fs::write(&path, DENY_ALL).unwrap();
+        reloader.reload(&engine).await.unwrap();
+        assert!(!engine.evaluate(&context).await.unwrap().allowed);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_reload_rejected() {
+        let path = temp_policy_file();
+        std::fs::write(&path, DENY_ALL).unwrap();
+        let reloader = PolicyReloader::new([&path]);
+        let engine = PolicyEngine::builder().build().await.unwrap();
+        reloader.reload(&engine).await.unwrap();
+        assert_eq!(engine.expression_count(), 1);
+
+        let rejected = || {
+            crate::api::engine_config::rejected_reloads_counter()
+                .with_label_values(&["policies"])
+                .get()
+        };
+        let before = rejected();
+        std::fs::write(&path, INVALID_REGEX).unwrap();
+        assert!(reloader.reload(&engine).await.is_err());
+        assert!(rejected() > before);
+        let missing_id = "policies: [{ id: '', metadata: { name: Gate }, rules: [] }]";
+        std::fs::write(&path, missing_id).unwrap();
+        assert!(reloader.reload(&engine).await.is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        // The previous policies are still enforced, with their expressions
+        // still compiled and none of the rejected document's.
+        let policy = engine.get_policy("gate").unwrap();
+        assert_eq!(policy.rules[0].id, "deny");
+        assert_eq!(engine.expression_count(), 1);
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+        assert!(!engine.evaluate(&context).await.unwrap().allowed);
+        assert_eq!(engine.expression_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_source_change_triggers_reload() {
+        let source = Arc::new(MockSource::default());
+        let reloader = PolicyReloader::from_sources().with_source(source.clone(), 0);
+        let engine = PolicyEngine::builder().build().await.unwrap();
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+
+        let update = async {
+            source.update(vec![gate("gate", Action::deny("Closed"))]);
+            while engine.list_policies().is_empty() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+        tokio::select! {
+            _ = reloader.watch(&engine) => panic!("watch ended"),
+            result = tokio::time::timeout(Duration::from_secs(5), update) => result.unwrap(),
+        }
+
+        assert_eq!(engine.list_policies(), vec!["gate"]);
+        assert!(!engine.evaluate(&context).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn test_sources_merged_by_priority() {
+        let base = MockSource::with_policies(vec![
+            gate("gate", Action::allow()),
+            gate("extra", Action::allow()),
+        ]);
+        let overrides = MockSource::with_policies(vec![gate("gate", Action::deny("Closed"))]);
+        let reloader = PolicyReloader::from_sources()
+            .with_source(Arc::new(base), 0)
+            .with_source(Arc::new(overrides), 10);
+        let engine = PolicyEngine::builder().build().await.unwrap();
+
+        let mut ids = reloader.reload(&engine).await.unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["extra", "gate"]);
+        // The higher-priority source's deny replaces the base allow.
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+        assert!(!engine.evaluate(&context).await.unwrap().allowed);
+    }
+}
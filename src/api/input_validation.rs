@@ -0,0 +1,257 @@
+//! Evaluation input validation.
+//!
+//! When an [`InputValidator`] is configured, each evaluation context is
+//! checked against a JSON Schema before anything else runs, so malformed
+//! inputs fail with field-level errors instead of surfacing as confusing
+//! CEL errors. The schema is either given locally or fetched from Schema
+//! Registry, and compiled once per schema version. Validation is skipped
+//! while the `input_validation` feature flag is off.
+
+use super::failure::OnFailure;
+use super::{EvaluationContext, PolicyDecision};
+use crate::cache::NumberNormalization;
+use crate::integration::{
+    compile_json_schema, validate_compiled, SchemaDefinition, SchemaRegistryAdapter,
+};
+use crate::{Error, Result};
+
+use jsonschema::JSONSchema;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Validates evaluation contexts against a JSON Schema.
+pub struct InputValidator {
+    source: SchemaSource,
+    /// Compiled schema, with the ID and version it was compiled from
+    compiled: RwLock<Option<(String, u32, Arc<JSONSchema>)>>,
+}
+
+/// Where the input schema comes from.
+enum SchemaSource {
+    Local(SchemaDefinition),
+    Registry {
+        registry: Arc<SchemaRegistryAdapter>,
+        subject: String,
+    },
+}
+
+impl InputValidator {
+    /// Create a validator for a local JSON Schema.
+    ///
+    /// Fails if the schema is not a JSON Schema or cannot be compiled.
+    pub fn local(schema: SchemaDefinition) -> Result<Self> {
+        let compiled = compile_json_schema(&schema)?;
+        let entry = (schema.id.clone(), schema.version, Arc::new(compiled));
+        Ok(Self {
+            source: SchemaSource::Local(schema),
+            compiled: RwLock::new(Some(entry)),
+        })
+    }
+
+    /// Create a validator for the latest schema of a Schema Registry
+    /// subject.
+    ///
+    /// The schema is fetched on first use and then served from the
+    /// registry adapter's schema cache; it is recompiled only when a new
+    /// version is published.
+    pub fn from_registry(registry: Arc<SchemaRegistryAdapter>, subject: impl Into<String>) -> Self {
+        Self {
+            source: SchemaSource::Registry {
+                registry,
+                subject: subject.into(),
+            },
+            compiled: RwLock::new(None),
+        }
+    }
+
+    /// Validate a context, failing with [`Error::InvalidInput`] if it does
+    /// not conform to the schema. Numbers are normalized by the `numbers`
+    /// rule first, so validation sees the numbers cache keys are built from.
+    ///
+    /// If the schema cannot be fetched, the request fails, is denied, or is
+    /// evaluated unvalidated, following the Schema Registry failure policy;
+    /// a denial is returned as the decision.
+    pub(crate) async fn check(
+        &self,
+        context: &EvaluationContext,
+        on_failure: OnFailure,
+        numbers: NumberNormalization,
+    ) -> Result<Option<PolicyDecision>> {
+        let schema = match self.schema().await {
+            Ok(schema) => schema,
+            Err(e) => {
+                on_failure.log("schema_registry", &e);
+                return match on_failure {
+                    OnFailure::Proceed => Ok(None),
+                    OnFailure::Deny => Ok(Some(OnFailure::denial("schema_registry", &e))),
+                    OnFailure::Fail => Err(e),
+                };
+            }
+        };
+
+        let mut input = context.to_json();
+        numbers.apply(&mut input);
+        let result = validate_compiled(&schema, &input);
+        if result.valid {
+            Ok(None)
+        } else {
+            Err(Error::invalid_input(result.errors))
+        }
+    }
+
+    /// Get the compiled schema, fetching and compiling a new version if the
+    /// registry has one.
+    async fn schema(&self) -> Result<Arc<JSONSchema>> {
+        let (registry, subject) = match &self.source {
+            SchemaSource::Local(_) => {
+                let compiled = self.compiled.read();
+                let (_, _, schema) = compiled.as_ref().expect("local schemas are compiled");
+                return Ok(schema.clone());
+            }
+            SchemaSource::Registry { registry, subject } => (registry, subject),
+        };
+
+        let definition = registry
+            .get_schema(subject)
+            .await
+            .map_err(|e| Error::integration("schema_registry", e.to_string()))?;
+        if let Some((id, version, schema)) = self.compiled.read().as_ref() {
+            if *id == definition.id && *version == definition.version {
+                return Ok(schema.clone());
+            }
+        }
+
+        let schema = Arc::new(compile_json_schema(&definition)?);
+        *self.compiled.write() = Some((definition.id, definition.version, schema.clone()));
+        Ok(schema)
+    }
+}
+
+impl std::fmt::Debug for InputValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let subject = match &self.source {
+            SchemaSource::Local(schema) => &schema.subject,
+            SchemaSource::Registry { subject, .. } => subject,
+        };
+        let version = self
+            .compiled
+            .read()
+            .as_ref()
+            .map(|(_, version, _)| *version);
+        f.debug_struct("InputValidator")
+            .field("subject", subject)
+            .field("version", &version)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::PolicyEngine;
+    use crate::integration::{IntegrationClient, MockTransport, SchemaType};
+    use crate::policy::{Action, Condition, Policy, PolicyRule};
+    use std::time::Duration;
+
+    fn input_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["user", "llm"],
+            "properties": {
+                "user": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": { "id": { "type": "string", "minLength": 1 } }
+                },
+                "llm": {
+                    "type": "object",
+                    "properties": { "max_tokens": { "type": "integer", "maximum": 8192 } }
+                }
+            }
+        })
+    }
+
+    async fn engine(validator: InputValidator) -> PolicyEngine {
+        PolicyEngine::builder()
+            .with_input_validator(validator)
+            .with_policy(
+                Policy::builder("guests")
+                    .rule(PolicyRule::new(
+                        "deny-guests",
+                        "Deny guests",
+                        Condition::contains("user.roles", "guest"),
+                        Action::deny("Guests are not allowed"),
+                    ))
+                    .build(),
+            )
+            .build()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_conforming_input_evaluated() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/api/v1/schemas/evaluation-input/latest",
+            serde_json::json!({
+                "id": "schema-1",
+                "subject": "evaluation-input",
+                "version": 1,
+                "schema_type": "json-schema",
+                "schema": input_schema(),
+            }),
+        ));
+        let client = IntegrationClient::new("http://registry".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        let registry = Arc::new(SchemaRegistryAdapter::from_client(client));
+        let engine = engine(InputValidator::from_registry(registry, "evaluation-input")).await;
+
+        for role in ["guest", "member"] {
+            let context = EvaluationContext::builder()
+                .with_user("user-1", None, vec![role.to_string()])
+                .with_max_tokens(1024)
+                .build();
+            let decision = engine.evaluate(&context).await.unwrap();
+            assert_eq!(decision.allowed, role == "member");
+        }
+        // The schema is fetched once and served from the registry cache.
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_input_rejected() {
+        let validator = InputValidator::local(SchemaDefinition {
+            id: "schema-1".to_string(),
+            subject: "evaluation-input".to_string(),
+            version: 1,
+            schema_type: SchemaType::JsonSchema,
+            schema: input_schema(),
+            metadata: Default::default(),
+        })
+        .unwrap();
+        let engine = engine(validator).await;
+        let context = EvaluationContext::builder()
+            .with_user("", None, vec!["guest".to_string()])
+            .with_max_tokens(100_000)
+            .build();
+
+        let error = engine.evaluate(&context).await.unwrap_err();
+        assert_eq!(error.category(), "invalid_input");
+        let paths: Vec<_> = error
+            .validation_errors()
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect();
+        assert_eq!(paths.len(), 2, "{}", error);
+        assert!(paths.contains(&"/user/id"), "{}", error);
+        assert!(paths.contains(&"/llm/max_tokens"), "{}", error);
+
+        // Validation is skipped while the feature flag is off.
+        engine.apply_feature_flags(&crate::integration::FeatureFlags {
+            input_validation: false,
+            ..Default::default()
+        });
+        assert!(!engine.evaluate(&context).await.unwrap().allowed);
+    }
+}
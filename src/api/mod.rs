@@ -3,10 +3,62 @@
 //! This module provides the main interface for interacting with the policy engine,
 //! including the `PolicyEngine` struct and evaluation context types.
 
+mod alerts;
+mod anomaly;
+mod audit;
+mod auth;
+mod budget;
+mod compliance;
 mod context;
 mod decision;
+mod distribution;
 mod engine;
+mod engine_config;
+mod evaluation;
+mod event_sampling;
+mod explain;
+mod failure;
+mod health;
+mod hot_reload;
+mod input_validation;
+mod policy_source;
+mod rate_limit;
+mod rbac;
+mod shield_check;
+mod simulation;
+mod telemetry_signals;
+mod trace_context;
 
-pub use context::{EvaluationContext, EvaluationContextBuilder, LlmContext, RequestContext, UserContext};
-pub use decision::PolicyDecision;
+pub use alerts::{ViolationAlerter, DEFAULT_DEDUP_WINDOW};
+pub use anomaly::{AnomalyMonitor, AnomalyThresholds, DEFAULT_MAX_EVENTS_PER_WINDOW};
+pub use audit::{AuditLevel, AuditLog, AuditRecord, AUDIT_TARGET};
+pub use auth::{ApiKeyAuth, ApiKeyStore, Claims, JwtVerifier};
+pub use budget::{BudgetEnforcer, DEFAULT_BUDGET_CACHE_TTL, DEFAULT_BUDGET_LOOKUP_TIMEOUT};
+pub use compliance::ComplianceChecker;
+pub use context::{
+    EvaluationContext, EvaluationContextBuilder, LlmContext, ProjectContext, RequestContext,
+    TeamContext, UserContext,
+};
+pub use decision::{PolicyDecision, TraceStepType};
+pub use distribution::PolicyDistributor;
 pub use engine::{PolicyEngine, PolicyEngineBuilder};
+pub use engine_config::EngineConfig;
+pub use evaluation::{Decision, EvaluationInput, Subject};
+pub use event_sampling::EventSampler;
+pub use explain::{DecisionExplanation, PolicyExplanation, RuleExplanation};
+pub use health::{ComponentStatus, ReadinessReport};
+pub use hot_reload::PolicyReloader;
+pub use input_validation::InputValidator;
+pub use policy_source::{
+    ConfigManagerPolicySource, FilePolicySource, PolicySource, DEFAULT_POLICY_CONFIG_KEY,
+    DEFAULT_POLL_INTERVAL,
+};
+pub use rate_limit::{RateLimitMode, RateLimiter, DEFAULT_MAX_BUCKETS};
+pub use rbac::{PolicyAccessGuard, PolicyAction, DEFAULT_ACCESS_CACHE_TTL};
+pub use simulation::{SimulatedDecision, SimulationInput, SimulationReport, RECORD_INPUT_KEY};
+pub use telemetry_signals::{
+    SignalValues, TelemetryProvider, DEFAULT_SIGNALS_CACHE_TTL, DEFAULT_SIGNALS_LOOKUP_TIMEOUT,
+};
+pub use trace_context::{
+    RequestTracer, BAGGAGE_HEADER, EVALUATION_SPAN_NAME, TRACEPARENT_HEADER, TRACESTATE_HEADER,
+};
@@ -0,0 +1,208 @@
+//! Pluggable policy sources.
+//!
+//! A [`PolicySource`] loads a set of policies and notifies when they may
+//! have changed. A [`PolicyReloader`](super::PolicyReloader) merges any
+//! number of sources by priority and reloads the engine whenever one of
+//! them reports a change, so policy files and the Config Manager are
+//! handled the same way.
+
+use crate::integration::ConfigManagerAdapter;
+use crate::policy::{Policy, PolicyDocument};
+use crate::{Error, Result};
+
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Default interval at which policy files are checked for changes.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default Config Manager key holding a policy document.
+pub const DEFAULT_POLICY_CONFIG_KEY: &str = "policies";
+
+/// Source of policies for a [`PolicyReloader`](super::PolicyReloader).
+pub trait PolicySource: Send + Sync {
+    /// Name of the source, used in logs.
+    fn name(&self) -> &str;
+
+    /// Load the source's current policies.
+    fn load(&self) -> BoxFuture<'_, Result<Vec<Policy>>>;
+
+    /// Stream that yields whenever the source's policies may have changed.
+    ///
+    /// The stream may end if the source can no longer be watched.
+    fn watch(&self) -> BoxStream<'_, ()>;
+}
+
+/// Policies read from YAML or JSON files.
+#[derive(Debug, Clone)]
+pub struct FilePolicySource {
+    files: Vec<PathBuf>,
+    poll_interval: Duration,
+}
+
+impl FilePolicySource {
+    /// Create a source for policy files, which together make up the
+    /// source's policy set.
+    pub fn new(files: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self {
+            files: files.into_iter().map(Into::into).collect(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Set how often the files are checked for changes.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Policy files read by this source.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    fn modified_times(&self) -> Vec<Option<SystemTime>> {
+        self.files
+            .iter()
+            .map(|path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .collect()
+    }
+}
+
+impl PolicySource for FilePolicySource {
+    fn name(&self) -> &str {
+        "files"
+    }
+
+    fn load(&self) -> BoxFuture<'_, Result<Vec<Policy>>> {
+        Box::pin(async move {
+            let mut policies = Vec::new();
+            for path in &self.files {
+                policies.extend(PolicyDocument::from_file(path)?.policies);
+            }
+            Ok(policies)
+        })
+    }
+
+    /// Yields whenever a file is modified, created or removed, checking the
+    /// files every poll interval.
+    fn watch(&self) -> BoxStream<'_, ()> {
+        let modified = self.modified_times();
+
+        futures::stream::unfold(modified, move |modified| async move {
+            loop {
+                tokio::time::sleep(self.poll_interval).await;
+                let current = self.modified_times();
+                if current != modified {
+                    return Some(((), current));
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// Policies stored as a document under a Config Manager key.
+///
+/// The value is either a YAML or JSON document as a string, or the
+/// document itself as a JSON object.
+pub struct ConfigManagerPolicySource {
+    config_manager: Arc<ConfigManagerAdapter>,
+    key: String,
+}
+
+impl ConfigManagerPolicySource {
+    /// Create a source reading the [`DEFAULT_POLICY_CONFIG_KEY`] key.
+    pub fn new(config_manager: Arc<ConfigManagerAdapter>) -> Self {
+        Self {
+            config_manager,
+            key: DEFAULT_POLICY_CONFIG_KEY.to_string(),
+        }
+    }
+
+    /// Read the policy document from another key.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+}
+
+impl PolicySource for ConfigManagerPolicySource {
+    fn name(&self) -> &str {
+        "config_manager"
+    }
+
+    fn load(&self) -> BoxFuture<'_, Result<Vec<Policy>>> {
+        Box::pin(async move {
+            let value = self
+                .config_manager
+                .get_config(&self.key)
+                .await
+                .map_err(|e| Error::integration("config_manager", e.to_string()))?;
+            // JSON documents are valid YAML.
+            let document = match value.value.as_str() {
+                Some(text) => PolicyDocument::from_yaml(text)?,
+                None => serde_json::from_value(value.value)?,
+            };
+            Ok(document.policies)
+        })
+    }
+
+    /// Yields whenever the Config Manager version changes, except while the
+    /// policy settings disable hot reload. Ends when the version watch ends.
+    fn watch(&self) -> BoxStream<'_, ()> {
+        let config_manager = &self.config_manager;
+        config_manager
+            .watch_config()
+            .filter_map(move |version| async move {
+                if let Ok((settings, _)) = config_manager.get_policy_settings_or_cached().await {
+                    if !settings.hot_reload_enabled {
+                        tracing::debug!(version = version.version, "Hot reload disabled; skipping");
+                        return None;
+                    }
+                }
+                tracing::debug!(version = version.version, "Config version changed");
+                Some(())
+            })
+            .boxed()
+    }
+}
+
+impl std::fmt::Debug for ConfigManagerPolicySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigManagerPolicySource")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{IntegrationClient, MockTransport};
+
+    #[tokio::test]
+    async fn test_config_manager_source_loads_document() {
+        let yaml = "policies: [{ id: gate, metadata: { name: Gate }, rules: [] }]";
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/api/v1/config/policy-engine/policies",
+            serde_json::json!({ "key": "policies", "value": yaml, "value_type": "string" }),
+        ));
+        let client =
+            IntegrationClient::new("http://config-manager".to_string(), Duration::from_secs(1))
+                .with_transport(transport);
+        let source = ConfigManagerPolicySource::new(Arc::new(ConfigManagerAdapter::from_client(
+            client,
+        )));
+
+        let policies = source.load().await.unwrap();
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].id, "gate");
+        assert!(source.with_key("missing").load().await.is_err());
+    }
+}
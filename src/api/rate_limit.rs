@@ -0,0 +1,241 @@
+//! Request rate limiting.
+//!
+//! A [`RateLimiter`] keeps a token bucket per key, holding `rate_limit_burst`
+//! tokens and refilling at `rate_limit_rps`. Requests are keyed by the
+//! configured [`RateLimitMode`]: one bucket for all requests, one per
+//! subject, or one per API key. Over-limit requests are rejected with
+//! [`Error::RateLimited`] carrying how long to wait before retrying. The
+//! number of buckets is bounded; the least recently used is evicted first.
+
+use super::EvaluationContext;
+use crate::config::SecurityConfig;
+use crate::{Error, Result};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Default maximum number of buckets kept.
+pub const DEFAULT_MAX_BUCKETS: usize = 10_000;
+
+/// Bucket key for requests without a subject or API key.
+const ANONYMOUS_KEY: &str = "anonymous";
+
+/// How requests are grouped into rate limit buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitMode {
+    /// One bucket shared by all requests
+    Global,
+    /// One bucket per subject (user ID)
+    #[default]
+    PerSubject,
+    /// One bucket per API key
+    PerApiKey,
+}
+
+impl RateLimitMode {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitMode::Global => "global",
+            RateLimitMode::PerSubject => "per_subject",
+            RateLimitMode::PerApiKey => "per_api_key",
+        }
+    }
+}
+
+impl fmt::Display for RateLimitMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RateLimitMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "global" => Ok(RateLimitMode::Global),
+            "per_subject" | "subject" => Ok(RateLimitMode::PerSubject),
+            "per_api_key" | "api_key" => Ok(RateLimitMode::PerApiKey),
+            _ => Err(Error::parse(format!("Unknown rate limit mode: {}", s))),
+        }
+    }
+}
+
+/// A token bucket.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket rate limiter keyed by subject, API key, or neither.
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    /// Tokens added per second
+    rate: f64,
+    capacity: f64,
+    buckets: Mutex<LruCache<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter admitting `requests_per_second` per key, with bursts
+    /// of up to `burst` requests (minimum 1).
+    pub fn new(requests_per_second: u32, burst: u32, mode: RateLimitMode) -> Self {
+        Self {
+            mode,
+            rate: f64::from(requests_per_second),
+            capacity: f64::from(burst.max(1)),
+            buckets: Mutex::new(LruCache::new(
+                NonZeroUsize::new(DEFAULT_MAX_BUCKETS).unwrap_or(NonZeroUsize::MIN),
+            )),
+        }
+    }
+
+    /// Create a limiter from the security configuration's rate limit
+    /// settings, or `None` if rate limiting is disabled.
+    pub fn from_config(config: &SecurityConfig, mode: RateLimitMode) -> Option<Self> {
+        config
+            .rate_limit_enabled
+            .then(|| Self::new(config.rate_limit_rps, config.rate_limit_burst, mode))
+    }
+
+    /// Set the maximum number of buckets kept (minimum 1).
+    pub fn with_max_buckets(self, max_buckets: usize) -> Self {
+        let capacity = NonZeroUsize::new(max_buckets).unwrap_or(NonZeroUsize::MIN);
+        self.buckets.lock().resize(capacity);
+        self
+    }
+
+    /// Get how requests are grouped into buckets.
+    pub fn mode(&self) -> RateLimitMode {
+        self.mode
+    }
+
+    /// Take a token for a request, failing with [`Error::RateLimited`] if
+    /// its bucket is empty.
+    pub fn check(&self, context: &EvaluationContext, api_key: Option<&str>) -> Result<()> {
+        let key = match self.mode {
+            RateLimitMode::Global => None,
+            RateLimitMode::PerSubject => context.user.as_ref().map(|user| user.id.clone()),
+            // Keys are bucketed by hash so they are not kept in memory.
+            RateLimitMode::PerApiKey => {
+                api_key.map(|key| blake3::hash(key.as_bytes()).to_hex().to_string())
+            }
+        };
+        self.check_key(key.as_deref().unwrap_or(ANONYMOUS_KEY), Instant::now())
+    }
+
+    fn check_key(&self, key: &str, now: Instant) -> Result<()> {
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets.get_or_insert_mut(key.to_string(), || Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        let retry_after = if self.rate > 0.0 {
+            Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+        } else {
+            Duration::MAX
+        };
+        Err(Error::rate_limited(
+            format!("Too many requests for {} '{}'", self.mode, key),
+            retry_after,
+        ))
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("mode", &self.mode)
+            .field("rate", &self.rate)
+            .field("capacity", &self.capacity)
+            .field("buckets", &self.buckets.lock().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retry_after(result: Result<()>) -> Duration {
+        match result {
+            Err(Error::RateLimited { retry_after_ms, .. }) => {
+                Duration::from_millis(retry_after_ms)
+            }
+            other => panic!("expected rate limited, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_steady_state_allowed() {
+        let limiter = RateLimiter::new(10, 1, RateLimitMode::PerSubject);
+        let start = Instant::now();
+
+        // One request every 100ms matches the refill rate exactly.
+        for i in 0..50 {
+            let now = start + Duration::from_millis(100 * i);
+            assert!(limiter.check_key("user-1", now).is_ok(), "request {}", i);
+        }
+    }
+
+    #[test]
+    fn test_burst_absorbed_then_rejected() {
+        let limiter = RateLimiter::new(10, 5, RateLimitMode::PerSubject);
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            assert!(limiter.check_key("user-1", now).is_ok());
+        }
+        let wait = retry_after(limiter.check_key("user-1", now));
+        assert_eq!(wait, Duration::from_millis(100));
+
+        // Other subjects have their own buckets.
+        assert!(limiter.check_key("user-2", now).is_ok());
+        // After the hinted wait a token is available again.
+        assert!(limiter.check_key("user-1", now + wait).is_ok());
+        assert!(limiter.check_key("user-1", now + wait).is_err());
+    }
+
+    #[test]
+    fn test_keying_modes_and_eviction() {
+        let alice = EvaluationContext::builder().with_user_id("alice").build();
+        let bob = EvaluationContext::builder().with_user_id("bob").build();
+
+        let global = RateLimiter::new(1, 1, RateLimitMode::Global);
+        assert!(global.check(&alice, None).is_ok());
+        assert!(global.check(&bob, None).is_err());
+
+        let per_key = RateLimiter::new(1, 1, RateLimitMode::PerApiKey);
+        assert!(per_key.check(&alice, Some("key-1")).is_ok());
+        assert!(per_key.check(&alice, Some("key-2")).is_ok());
+        assert!(per_key.check(&bob, Some("key-1")).is_err());
+
+        let bounded = RateLimiter::new(1, 1, RateLimitMode::PerSubject).with_max_buckets(1);
+        let now = Instant::now();
+        assert!(bounded.check_key("alice", now).is_ok());
+        assert!(bounded.check_key("bob", now).is_ok());
+        // Alice's bucket was evicted, so she starts with a full one.
+        assert!(bounded.check_key("alice", now).is_ok());
+        assert_eq!(bounded.buckets.lock().len(), 1);
+
+        assert_eq!("per_api_key".parse::<RateLimitMode>().unwrap(), RateLimitMode::PerApiKey);
+        assert!("per_team".parse::<RateLimitMode>().is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Role-based access control for policy management.
+//!
+//! A [`PolicyAccessGuard`] asks Config Manager whether a subject may create,
+//! update or delete a policy, with the policy ID as the resource. Allowed
+//! operations are cached briefly so repeated changes by the same subject do
+//! not each cost a round-trip; denials are never cached. If Config Manager
+//! cannot be reached, the operation is denied.
+
+use crate::integration::{AccessValidationRequest, ConfigManagerAdapter};
+use crate::{Error, Result};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default time an allowed operation is cached.
+pub const DEFAULT_ACCESS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// A policy management operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// Load a policy that is not loaded yet
+    Create,
+    /// Replace a loaded policy
+    Update,
+    /// Unload a policy
+    Delete,
+}
+
+impl PolicyAction {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyAction::Create => "create",
+            PolicyAction::Update => "update",
+            PolicyAction::Delete => "delete",
+        }
+    }
+}
+
+impl fmt::Display for PolicyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+type AccessKey = (String, String, PolicyAction);
+
+/// Checks policy management operations with Config Manager RBAC.
+pub struct PolicyAccessGuard {
+    config_manager: Arc<ConfigManagerAdapter>,
+    cache_ttl: Duration,
+    /// When each allowed subject, policy and action was checked
+    allowed: Mutex<HashMap<AccessKey, Instant>>,
+}
+
+impl PolicyAccessGuard {
+    /// Create a guard validating access with Config Manager.
+    pub fn new(config_manager: Arc<ConfigManagerAdapter>) -> Self {
+        Self {
+            config_manager,
+            cache_ttl: DEFAULT_ACCESS_CACHE_TTL,
+            allowed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set how long allowed operations are cached. Zero disables caching.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Check that `subject` may perform `action` on a policy, failing with
+    /// [`Error::Forbidden`] if Config Manager denies it or cannot be reached.
+    pub async fn authorize(
+        &self,
+        subject: &str,
+        policy_id: &str,
+        action: PolicyAction,
+    ) -> Result<()> {
+        let key = (subject.to_string(), policy_id.to_string(), action);
+        if self.is_cached(&key, Instant::now()) {
+            return Ok(());
+        }
+
+        let request = AccessValidationRequest {
+            subject: key.0.clone(),
+            resource: key.1.clone(),
+            action: action.as_str().to_string(),
+            context: HashMap::new(),
+        };
+        let result = match self.config_manager.validate_access(&request).await {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!(
+                    subject,
+                    policy_id,
+                    action = action.as_str(),
+                    error = %e,
+                    "Access validation failed; denying policy change"
+                );
+                return Err(Error::forbidden(format!(
+                    "Cannot {} policy {}: access validation unavailable",
+                    action, policy_id
+                )));
+            }
+        };
+
+        if !result.allowed {
+            tracing::warn!(
+                subject,
+                policy_id,
+                action = action.as_str(),
+                policies = ?result.policies,
+                reason = result.reason.as_deref().unwrap_or_default(),
+                "Policy change denied"
+            );
+            return Err(Error::forbidden(format!(
+                "{} may not {} policy {}{}",
+                subject,
+                action,
+                policy_id,
+                result
+                    .reason
+                    .map(|reason| format!(": {}", reason))
+                    .unwrap_or_default()
+            )));
+        }
+
+        tracing::debug!(
+            subject,
+            policy_id,
+            action = action.as_str(),
+            policies = ?result.policies,
+            "Policy change allowed"
+        );
+        if !self.cache_ttl.is_zero() {
+            let now = Instant::now();
+            let mut allowed = self.allowed.lock();
+            allowed.retain(|_, checked| now.duration_since(*checked) < self.cache_ttl);
+            allowed.insert(key, now);
+        }
+        Ok(())
+    }
+
+    fn is_cached(&self, key: &AccessKey, now: Instant) -> bool {
+        self.allowed
+            .lock()
+            .get(key)
+            .is_some_and(|checked| now.duration_since(*checked) < self.cache_ttl)
+    }
+}
+
+impl fmt::Debug for PolicyAccessGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyAccessGuard")
+            .field("cache_ttl", &self.cache_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{IntegrationClient, MockTransport};
+
+    fn guard(transport: &Arc<MockTransport>) -> PolicyAccessGuard {
+        let client = IntegrationClient::new("http://config".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        PolicyAccessGuard::new(Arc::new(ConfigManagerAdapter::from_client(client)))
+    }
+
+    #[tokio::test]
+    async fn test_allowed_operations_cached() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/rbac/validate",
+            serde_json::json!({ "allowed": true, "policies": ["policy-admins"] }),
+        ));
+        let guard = guard(&transport);
+
+        guard.authorize("alice", "pii-filter", PolicyAction::Update).await.unwrap();
+        guard.authorize("alice", "pii-filter", PolicyAction::Update).await.unwrap();
+        assert_eq!(transport.requests().len(), 1);
+        let request = transport.requests()[0].json().unwrap();
+        assert_eq!(request["subject"], "alice");
+        assert_eq!(request["resource"], "pii-filter");
+        assert_eq!(request["action"], "update");
+
+        // Other actions are checked separately.
+        guard.authorize("alice", "pii-filter", PolicyAction::Delete).await.unwrap();
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_denied_operations_not_cached() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/rbac/validate",
+            serde_json::json!({ "allowed": false, "reason": "read-only role" }),
+        ));
+        let guard = guard(&transport);
+
+        for _ in 0..2 {
+            let err = guard
+                .authorize("bob", "pii-filter", PolicyAction::Delete)
+                .await
+                .unwrap_err();
+            assert_eq!(err.category(), "forbidden");
+            assert_eq!(
+                err.to_string(),
+                "Forbidden: bob may not delete policy pii-filter: read-only role"
+            );
+        }
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_config_manager_denies() {
+        let transport = Arc::new(MockTransport::new().with_status(
+            reqwest::Method::POST,
+            "/api/v1/rbac/validate",
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        ));
+        let guard = guard(&transport);
+
+        let err = guard
+            .authorize("alice", "pii-filter", PolicyAction::Create)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Forbidden { .. }));
+    }
+}
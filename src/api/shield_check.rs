@@ -0,0 +1,167 @@
+//! Shield prompt pre-check.
+//!
+//! When a Shield client is configured, prompts are scanned before any policy
+//! is evaluated. A prompt Shield considers unsafe is denied outright. If the
+//! scan itself fails, the request is failed, denied or evaluated without the
+//! check, following the Shield failure policy.
+
+use super::failure::OnFailure;
+use super::{EvaluationContext, PolicyDecision};
+use crate::integration::{ShieldClient, ShieldScanRequest, ShieldScanResponse, ThreatType};
+use crate::policy::{DecisionReason, ReasonCode};
+use crate::{Error, Result};
+
+use std::collections::HashMap;
+
+/// Outcome of scanning a request's prompt with Shield.
+#[derive(Debug, Clone)]
+pub(crate) enum ShieldCheck {
+    /// Shield found the prompt safe
+    Clean(ShieldScanResponse),
+    /// Shield detected threats in the prompt
+    Flagged(ShieldScanResponse),
+    /// The scan failed and evaluation proceeds without it
+    Unavailable(String),
+    /// The scan failed and the request is denied
+    Down(String),
+}
+
+impl ShieldCheck {
+    /// Scan the prompt of `context`, if it has one.
+    ///
+    /// Returns an error only if the scan fails and `on_failure` is
+    /// [`OnFailure::Fail`].
+    pub(crate) async fn run(
+        shield: &ShieldClient,
+        context: &EvaluationContext,
+        on_failure: OnFailure,
+    ) -> Result<Option<Self>> {
+        let Some(llm) = &context.llm else {
+            return Ok(None);
+        };
+        let Some(prompt) = llm.prompt.as_deref().filter(|prompt| !prompt.is_empty()) else {
+            return Ok(None);
+        };
+
+        let mut request = ShieldScanRequest::new(prompt);
+        request.user_id = context.user.as_ref().map(|user| user.id.clone());
+        request.model = llm.model.clone();
+
+        match shield.scan_prompt(&request).await {
+            Ok(scan) if scan.safe => Ok(Some(ShieldCheck::Clean(scan))),
+            Ok(scan) => Ok(Some(ShieldCheck::Flagged(scan))),
+            Err(e) => {
+                on_failure.log("shield", &e);
+                match on_failure {
+                    OnFailure::Proceed => Ok(Some(ShieldCheck::Unavailable(e.to_string()))),
+                    OnFailure::Deny => Ok(Some(ShieldCheck::Down(e.to_string()))),
+                    OnFailure::Fail => Err(Error::integration("shield", e.to_string())),
+                }
+            }
+        }
+    }
+
+    /// The deny decision for a flagged prompt, or a failed scan failing
+    /// closed.
+    pub(crate) fn denial(&self) -> Option<PolicyDecision> {
+        let scan = match self {
+            ShieldCheck::Flagged(scan) => scan,
+            ShieldCheck::Down(error) => return Some(OnFailure::denial("shield", error)),
+            _ => return None,
+        };
+        let threats = threat_names(&scan.threats);
+        let reason = if threats.is_empty() {
+            "Shield flagged the prompt as unsafe".to_string()
+        } else {
+            format!("Shield detected threats in the prompt: {}", threats)
+        };
+        let code = if self.is_injection() {
+            ReasonCode::InjectionDetected
+        } else {
+            ReasonCode::UnsafePrompt
+        };
+        let decision_reason = DecisionReason::new(code, reason.clone())
+            .with_detail("threats", threats.into())
+            .with_detail("safety_score", scan.safety_score.into());
+        Some(PolicyDecision::deny(reason).with_decision_reason(decision_reason))
+    }
+
+    /// Whether Shield flagged the prompt for injection or a jailbreak.
+    pub(crate) fn is_injection(&self) -> bool {
+        let ShieldCheck::Flagged(scan) = self else {
+            return false;
+        };
+        scan.threats
+            .iter()
+            .any(|threat| matches!(threat, ThreatType::PromptInjection | ThreatType::Jailbreak))
+    }
+
+    /// Entries describing the check for an evaluation event's context.
+    pub(crate) fn event_context(&self) -> HashMap<String, String> {
+        let mut context = HashMap::new();
+        match self {
+            ShieldCheck::Clean(scan) | ShieldCheck::Flagged(scan) => {
+                context.insert("shield_safe".to_string(), scan.safe.to_string());
+                context.insert(
+                    "shield_safety_score".to_string(),
+                    scan.safety_score.to_string(),
+                );
+                if !scan.threats.is_empty() {
+                    context.insert("shield_threats".to_string(), threat_names(&scan.threats));
+                }
+            }
+            ShieldCheck::Unavailable(error) | ShieldCheck::Down(error) => {
+                context.insert("shield_error".to_string(), error.clone());
+            }
+        }
+        context
+    }
+}
+
+/// Comma-separated snake_case names of threat types.
+fn threat_names(threats: &[ThreatType]) -> String {
+    threats
+        .iter()
+        .filter_map(|threat| serde_json::to_value(threat).ok())
+        .filter_map(|value| value.as_str().map(str::to_string))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flagged_scan_denies() {
+        let check = ShieldCheck::Flagged(ShieldScanResponse {
+            safe: false,
+            safety_score: 0.1,
+            threats: vec![ThreatType::PromptInjection, ThreatType::Jailbreak],
+            details: Vec::new(),
+        });
+
+        let denial = check.denial().unwrap();
+        assert!(!denial.allowed);
+        assert_eq!(
+            denial.reason.as_deref(),
+            Some("Shield detected threats in the prompt: prompt_injection,jailbreak")
+        );
+        assert_eq!(denial.reason_code(), Some(ReasonCode::InjectionDetected));
+        let context = check.event_context();
+        assert_eq!(context["shield_safe"], "false");
+        assert_eq!(context["shield_threats"], "prompt_injection,jailbreak");
+        assert!(check.is_injection());
+
+        let toxic = ShieldCheck::Flagged(ShieldScanResponse {
+            safe: false,
+            threats: vec![ThreatType::ToxicContent],
+            ..ShieldScanResponse::default()
+        });
+        assert_eq!(toxic.denial().unwrap().reason_code(), Some(ReasonCode::UnsafePrompt));
+
+        let clean = ShieldCheck::Clean(ShieldScanResponse::default());
+        assert!(clean.denial().is_none());
+        assert!(!clean.event_context().contains_key("shield_threats"));
+    }
+}
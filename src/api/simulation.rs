@@ -0,0 +1,270 @@
+//! Policy simulation.
+//!
+//! [`PolicyEngine::simulate`](super::PolicyEngine::simulate) replays inputs,
+//! typically recorded by Observatory, against a candidate policy set and
+//! reports which decisions would change. It is an offline harness for
+//! policy authors: candidates are compiled and evaluated by a separate
+//! evaluator with the engine's current settings, and nothing is enforced.
+//! A simulation emits no events, alerts, audit records or metrics, and
+//! leaves the cache, rate limits and loaded policies untouched.
+//!
+//! Only policy decisions are compared: the Shield and budget prechecks and
+//! compliance checks call out to integrations and are skipped.
+
+use super::{EvaluationContext, PolicyDecision};
+use crate::integration::{DecisionOutcome, PolicyDecisionRecord};
+use crate::policy::ReasonCode;
+use crate::Result;
+
+use serde::{Deserialize, Serialize};
+
+/// Key of a decision record's metadata holding the evaluation context.
+pub const RECORD_INPUT_KEY: &str = "input";
+
+/// One input to replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationInput {
+    /// Identifier reported with the input's result, such as a decision ID
+    pub id: String,
+    /// Context to evaluate
+    pub context: EvaluationContext,
+    /// Outcome recorded when the input was first evaluated; if unset, the
+    /// input is evaluated against the loaded policies for comparison
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded: Option<DecisionOutcome>,
+}
+
+impl SimulationInput {
+    /// Create an input compared against the loaded policies.
+    pub fn new(id: impl Into<String>, context: EvaluationContext) -> Self {
+        Self {
+            id: id.into(),
+            context,
+            recorded: None,
+        }
+    }
+
+    /// Compare against a recorded outcome instead of the loaded policies.
+    pub fn with_recorded(mut self, outcome: DecisionOutcome) -> Self {
+        self.recorded = Some(outcome);
+        self
+    }
+
+    /// Create an input from an Observatory decision record, compared against
+    /// the recorded outcome.
+    ///
+    /// The context is read from the record's `input` metadata if present;
+    /// otherwise it is rebuilt from the record's user, model and provider.
+    pub fn from_record(record: &PolicyDecisionRecord) -> Self {
+        let context = record
+            .metadata
+            .get(RECORD_INPUT_KEY)
+            .and_then(|input| serde_json::from_value(input.clone()).ok())
+            .unwrap_or_else(|| {
+                let mut builder = EvaluationContext::builder();
+                if let Some(user_id) = &record.user_id {
+                    builder = builder.with_user_id(user_id);
+                }
+                if let Some(model) = &record.model {
+                    builder = builder.with_model(model);
+                }
+                if let Some(provider) = &record.provider {
+                    builder = builder.with_provider(provider);
+                }
+                builder.build()
+            });
+
+        Self::new(&record.decision_id, context).with_recorded(record.decision)
+    }
+}
+
+/// How one input's decision compares under the candidate policies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedDecision {
+    /// Input ID
+    pub id: String,
+    /// Outcome before the change
+    pub old: DecisionOutcome,
+    /// Outcome under the candidate policies
+    pub new: DecisionOutcome,
+    /// Reason code of the candidate decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
+    /// Evaluation error under the candidate policies, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SimulatedDecision {
+    /// Compare an old outcome with a candidate evaluation result.
+    pub(crate) fn new(
+        id: impl Into<String>,
+        old: DecisionOutcome,
+        new: &Result<PolicyDecision>,
+    ) -> Self {
+        let (outcome, reason_code, error) = match new {
+            Ok(decision) => (decision.decision.into(), decision.reason_code(), None),
+            Err(e) => (DecisionOutcome::Error, None, Some(e.to_string())),
+        };
+        Self {
+            id: id.into(),
+            old,
+            new: outcome,
+            reason_code,
+            error,
+        }
+    }
+
+    /// Whether the outcome changed.
+    pub fn changed(&self) -> bool {
+        self.old != self.new
+    }
+}
+
+/// Result of replaying inputs against candidate policies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Number of inputs replayed
+    pub total: usize,
+    /// Number of inputs whose outcome changed
+    pub changed: usize,
+    /// Per-input results, in input order
+    pub results: Vec<SimulatedDecision>,
+}
+
+impl SimulationReport {
+    /// Build a report from per-input results.
+    pub(crate) fn new(results: Vec<SimulatedDecision>) -> Self {
+        Self {
+            total: results.len(),
+            changed: results.iter().filter(|result| result.changed()).count(),
+            results,
+        }
+    }
+
+    /// Results whose outcome changed.
+    pub fn changes(&self) -> impl Iterator<Item = &SimulatedDecision> {
+        self.results.iter().filter(|result| result.changed())
+    }
+
+    /// Number of inputs that went from `old` to `new`.
+    pub fn count(&self, old: DecisionOutcome, new: DecisionOutcome) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.old == old && result.new == new)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::PolicyEngine;
+    use crate::integration::{BatchConfig, IntegrationClient, MockTransport, ObservatoryAdapter};
+    use crate::policy::{Action, Condition, Policy, PolicyRule};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn deny_guests() -> PolicyRule {
+        PolicyRule::new(
+            "deny-guests",
+            "Deny guests",
+            Condition::equals("user.roles", vec!["guest".to_string()]),
+            Action::deny("Guests are not allowed"),
+        )
+    }
+
+    fn input(id: &str, role: &str, model: &str) -> SimulationInput {
+        let context = EvaluationContext::builder()
+            .with_user(id, None, vec![role.to_string()])
+            .with_model(model)
+            .build();
+        SimulationInput::new(id, context)
+    }
+
+    #[tokio::test]
+    async fn test_rule_change_flips_decisions() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/events/batch",
+            serde_json::json!({ "accepted_count": 1, "rejected_count": 0 }),
+        ));
+        let client =
+            IntegrationClient::new("http://observatory".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        let observatory = Arc::new(ObservatoryAdapter::from_client(client));
+        let sink = observatory.spawn_batching(BatchConfig::default());
+        let engine = PolicyEngine::builder()
+            .with_policy(Policy::builder("access").rule(deny_guests()).build())
+            .with_event_sink(sink.clone())
+            .build()
+            .await
+            .unwrap();
+
+        let candidate = Policy::builder("access")
+            .rule(deny_guests())
+            .rule(PolicyRule::new(
+                "deny-gpt4",
+                "Deny GPT-4",
+                Condition::equals("llm.model", "gpt-4"),
+                Action::deny("GPT-4 is not allowed"),
+            ))
+            .build();
+        let inputs = [
+            input("admin-gpt4", "admin", "gpt-4"),
+            input("admin-gpt35", "admin", "gpt-3.5-turbo"),
+            input("guest-gpt4", "guest", "gpt-4"),
+        ];
+
+        let report = engine.simulate(&[candidate], &inputs).unwrap();
+        assert_eq!(report.total, 3);
+        assert_eq!(report.changed, 1);
+        assert_eq!(report.count(DecisionOutcome::Allow, DecisionOutcome::Deny), 1);
+        let changed: Vec<&SimulatedDecision> = report.changes().collect();
+        assert_eq!(changed[0].id, "admin-gpt4");
+        assert_eq!(changed[0].reason_code, Some(ReasonCode::RuleMatched));
+        assert_eq!(report.results[1].new, DecisionOutcome::Allow);
+        assert_eq!(report.results[2].old, DecisionOutcome::Deny);
+
+        // Nothing is enforced, cached or reported.
+        assert!(engine.evaluate(&inputs[0].context).await.unwrap().allowed);
+        sink.flush().await;
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(engine.cache_stats().unwrap().misses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_replay_decision_records() {
+        let engine = PolicyEngine::builder().build().await.unwrap();
+        let record = |id: &str, user_id: &str, decision: DecisionOutcome| PolicyDecisionRecord {
+            decision_id: id.to_string(),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            user_id: Some(user_id.to_string()),
+            model: Some("gpt-4".to_string()),
+            provider: Some("openai".to_string()),
+            policy_id: String::new(),
+            decision,
+            latency_ms: 1.0,
+            reason: None,
+            reason_code: None,
+            metadata: HashMap::new(),
+        };
+        let mut guest = record("dec-2", "user-2", DecisionOutcome::Allow);
+        let context = EvaluationContext::builder()
+            .with_user("user-2", None, vec!["guest".to_string()])
+            .build();
+        guest.metadata.insert(RECORD_INPUT_KEY.to_string(), context.to_json());
+
+        let admin = record("dec-1", "user-1", DecisionOutcome::Allow);
+        let inputs: Vec<SimulationInput> =
+            [admin, guest].iter().map(SimulationInput::from_record).collect();
+        assert_eq!(inputs[0].context.llm.as_ref().unwrap().provider.as_deref(), Some("openai"));
+
+        let candidate = Policy::builder("access").rule(deny_guests()).build();
+        let report = engine.simulate(&[candidate], &inputs).unwrap();
+        let ids: Vec<&str> = report.changes().map(|result| result.id.as_str()).collect();
+        assert_eq!(ids, vec!["dec-2"]);
+        assert_eq!(report.results[1].new, DecisionOutcome::Deny);
+    }
+}
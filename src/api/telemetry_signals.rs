@@ -0,0 +1,268 @@
+//! Live telemetry for CEL expressions.
+//!
+//! When a [`TelemetryProvider`] is configured, CEL expressions can refer to
+//! the service's Observatory telemetry as `signals` and to the Config
+//! Manager rule thresholds as `thresholds`, e.g.
+//! `signals.error_rate > thresholds.error_rate_threshold`. Signals are
+//! fetched at most once per cache TTL, not on every evaluation.
+//!
+//! References never fail for lack of telemetry. Signals Observatory does
+//! not report, or reports too late, take neutral defaults: rates,
+//! latencies, token counts and cost are 0 and availability is 100.
+//! `signals.available` tells whether signals were fetched at all.
+
+use crate::integration::{ObservatoryAdapter, TelemetrySignalRequest, TelemetrySignals};
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default time fetched signals are reused.
+pub const DEFAULT_SIGNALS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Default time allowed for fetching signals.
+pub const DEFAULT_SIGNALS_LOOKUP_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Telemetry signals as seen by CEL expressions, under `signals`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalValues {
+    /// Whether signals were fetched from Observatory
+    pub available: bool,
+    /// Error rate percentage
+    pub error_rate: f64,
+    /// Requests per second
+    pub request_rate: f64,
+    /// Median latency in milliseconds
+    pub latency_p50: f64,
+    /// 95th percentile latency in milliseconds
+    pub latency_p95: f64,
+    /// 99th percentile latency in milliseconds
+    pub latency_p99: f64,
+    /// Tokens used in the time window
+    pub total_tokens: u64,
+    /// Cost in the time window
+    pub cost: f64,
+    /// Availability percentage
+    pub availability: f64,
+}
+
+impl Default for SignalValues {
+    /// Values used when no signals could be fetched.
+    fn default() -> Self {
+        Self {
+            available: false,
+            error_rate: 0.0,
+            request_rate: 0.0,
+            latency_p50: 0.0,
+            latency_p95: 0.0,
+            latency_p99: 0.0,
+            total_tokens: 0,
+            cost: 0.0,
+            availability: 100.0,
+        }
+    }
+}
+
+impl From<&TelemetrySignals> for SignalValues {
+    fn from(signals: &TelemetrySignals) -> Self {
+        let defaults = Self::default();
+        let latency = &signals.latency_percentiles;
+        Self {
+            available: true,
+            error_rate: signals.error_rate.unwrap_or(defaults.error_rate),
+            request_rate: signals.request_rate.unwrap_or(defaults.request_rate),
+            latency_p50: latency.p50.unwrap_or(defaults.latency_p50),
+            latency_p95: latency.p95.unwrap_or(defaults.latency_p95),
+            latency_p99: latency.p99.unwrap_or(defaults.latency_p99),
+            total_tokens: signals
+                .token_usage
+                .as_ref()
+                .map_or(defaults.total_tokens, |usage| usage.total_tokens),
+            cost: signals.cost.unwrap_or(defaults.cost),
+            availability: signals.availability.unwrap_or(defaults.availability),
+        }
+    }
+}
+
+/// Fetches telemetry signals from Observatory for CEL expressions.
+pub struct TelemetryProvider {
+    observatory: Arc<ObservatoryAdapter>,
+    request: TelemetrySignalRequest,
+    cache_ttl: Duration,
+    lookup_timeout: Duration,
+    /// Last fetched signals and when they were fetched
+    cached: tokio::sync::Mutex<Option<(Instant, SignalValues)>>,
+}
+
+impl TelemetryProvider {
+    /// Create a provider querying Observatory with `request`, with the
+    /// default cache TTL and lookup timeout.
+    pub fn new(observatory: Arc<ObservatoryAdapter>, request: TelemetrySignalRequest) -> Self {
+        Self {
+            observatory,
+            request,
+            cache_ttl: DEFAULT_SIGNALS_CACHE_TTL,
+            lookup_timeout: DEFAULT_SIGNALS_LOOKUP_TIMEOUT,
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Set how long fetched signals are reused.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Set the time allowed for fetching signals.
+    pub fn with_lookup_timeout(mut self, timeout: Duration) -> Self {
+        self.lookup_timeout = timeout;
+        self
+    }
+
+    /// Get the current signals, from the cache if recent.
+    ///
+    /// Concurrent callers share one fetch. A failed fetch yields the
+    /// defaults, which are cached like fetched signals so that an outage
+    /// does not cost a lookup per evaluation.
+    pub(crate) async fn signals(&self) -> SignalValues {
+        let mut cached = self.cached.lock().await;
+        if let Some((fetched, signals)) = cached.as_ref() {
+            if fetched.elapsed() < self.cache_ttl {
+                return signals.clone();
+            }
+        }
+
+        let lookup = self.observatory.get_telemetry_signals(&self.request);
+        let signals = match tokio::time::timeout(self.lookup_timeout, lookup).await {
+            Ok(Ok(signals)) => SignalValues::from(&signals),
+            Ok(Err(e)) => {
+                tracing::warn!(
+                    service = %self.request.service,
+                    error = %e,
+                    "Failed to fetch telemetry signals, using defaults"
+                );
+                SignalValues::default()
+            }
+            Err(_) => {
+                tracing::warn!(
+                    service = %self.request.service,
+                    timeout_ms = self.lookup_timeout.as_millis() as u64,
+                    "Telemetry signals lookup timed out, using defaults"
+                );
+                SignalValues::default()
+            }
+        };
+        *cached = Some((Instant::now(), signals.clone()));
+        signals
+    }
+}
+
+impl std::fmt::Debug for TelemetryProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelemetryProvider")
+            .field("service", &self.request.service)
+            .field("cache_ttl", &self.cache_ttl)
+            .field("lookup_timeout", &self.lookup_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{EvaluationContext, PolicyEngine};
+    use crate::integration::{IntegrationClient, MockTransport, RuleThresholds};
+    use crate::policy::{Action, Condition, Policy, PolicyRule};
+
+    fn provider(transport: &Arc<MockTransport>) -> TelemetryProvider {
+        let client =
+            IntegrationClient::new("http://observatory".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        let request = TelemetrySignalRequest {
+            service: "gateway".to_string(),
+            model: None,
+            provider: None,
+            time_window_seconds: 300,
+            signal_types: Vec::new(),
+        };
+        TelemetryProvider::new(Arc::new(ObservatoryAdapter::from_client(client)), request)
+    }
+
+    async fn engine(provider: TelemetryProvider, expression: &str) -> PolicyEngine {
+        PolicyEngine::builder()
+            .with_telemetry_provider(provider)
+            .with_policy(
+                Policy::builder("error-budget")
+                    .rule(PolicyRule::new(
+                        "deny-on-errors",
+                        "Deny while the error rate is high",
+                        Condition::expression(expression),
+                        Action::deny("Error rate too high"),
+                    ))
+                    .build(),
+            )
+            .build()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_policy_denies_when_error_rate_exceeds_threshold() {
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/signals/query",
+            serde_json::json!({
+                "timestamp": "2025-01-01T00:00:00Z",
+                "time_window_seconds": 300,
+                "error_rate": 12.5,
+            }),
+        ));
+        let engine = engine(
+            provider(&transport),
+            "signals.error_rate > thresholds.error_rate_threshold",
+        )
+        .await;
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+
+        // The default error rate threshold is 5%.
+        for _ in 0..2 {
+            let decision = engine.evaluate(&context).await.unwrap();
+            assert!(!decision.allowed);
+        }
+        // Signals are fetched once and then served from the cache.
+        assert_eq!(transport.requests().len(), 1);
+        let request = transport.requests()[0].json().unwrap();
+        assert_eq!(request["service"], "gateway");
+
+        engine.apply_rule_thresholds(&RuleThresholds {
+            error_rate_threshold: 20.0,
+            ..RuleThresholds::default()
+        });
+        assert!(engine.evaluate(&context).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn test_missing_signals_use_defaults() {
+        // No route registered, so Observatory answers 404.
+        let transport = Arc::new(MockTransport::new());
+        let engine = engine(
+            provider(&transport),
+            "signals.available || signals.error_rate > 0.0 || signals.availability < 100.0",
+        )
+        .await;
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+        assert!(engine.evaluate(&context).await.unwrap().allowed);
+
+        let reported: TelemetrySignals = serde_json::from_value(serde_json::json!({
+            "timestamp": "2025-01-01T00:00:00Z",
+            "time_window_seconds": 300,
+            "latency_percentiles": { "p99": 850.0 },
+        }))
+        .unwrap();
+        let signals = SignalValues::from(&reported);
+        assert!(signals.available);
+        assert_eq!(signals.latency_p99, 850.0);
+        assert_eq!(signals.error_rate, 0.0);
+        assert_eq!(signals.availability, 100.0);
+    }
+}
@@ -0,0 +1,249 @@
+//! Trace context of incoming requests.
+//!
+//! A [`RequestTracer`] turns the W3C `traceparent`, `tracestate` and
+//! `baggage` headers of an incoming request into the [`TraceContext`] of its
+//! evaluation. The evaluation gets its own span, a child of the caller's
+//! span, registered with Observatory when the trace is sampled. Requests
+//! without a valid `traceparent` start a new root trace, sampled with
+//! probability `telemetry.trace_sampling_ratio`.
+//!
+//! Attach the result to the evaluation context with
+//! [`EvaluationContextBuilder::with_trace`](super::EvaluationContextBuilder::with_trace);
+//! emitted events then carry the trace ID and the evaluation's span ID.
+
+use crate::integration::{ObservatoryAdapter, PolicySpan, SpanKind, TraceContext};
+
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// W3C trace parent header.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// W3C trace state header.
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+/// W3C baggage header.
+pub const BAGGAGE_HEADER: &str = "baggage";
+
+/// Name of the span registered for each evaluation.
+pub const EVALUATION_SPAN_NAME: &str = "policy.evaluate";
+
+/// Extracts trace context from requests and starts evaluation spans.
+#[derive(Debug, Clone)]
+pub struct RequestTracer {
+    observatory: Option<Arc<ObservatoryAdapter>>,
+    sampling_ratio: f64,
+}
+
+impl RequestTracer {
+    /// Create a tracer sampling new root traces with probability
+    /// `sampling_ratio` (0.0 to 1.0).
+    pub fn new(sampling_ratio: f64) -> Self {
+        Self {
+            observatory: None,
+            sampling_ratio: sampling_ratio.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Register evaluation spans with Observatory.
+    pub fn with_observatory(mut self, observatory: Arc<ObservatoryAdapter>) -> Self {
+        self.observatory = Some(observatory);
+        self
+    }
+
+    /// Parse the caller's trace context from request headers.
+    ///
+    /// A missing or malformed `traceparent` starts a new root trace, without
+    /// a parent span, sampled according to the sampling ratio; `tracestate`
+    /// and `baggage` are only read alongside a valid `traceparent`.
+    pub fn extract(&self, headers: &HeaderMap) -> TraceContext {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let parsed = header(TRACEPARENT_HEADER).and_then(|traceparent| {
+            TraceContext::from_headers(traceparent, header(TRACESTATE_HEADER))
+                .map_err(|e| tracing::debug!(error = %e, "Ignoring invalid traceparent header"))
+                .ok()
+        });
+
+        match parsed {
+            Some(context) => match header(BAGGAGE_HEADER) {
+                Some(baggage) => context.with_baggage_header(baggage),
+                None => context,
+            },
+            None => {
+                let mut context = TraceContext::new(format!("{:032x}", random_id::<u128>()));
+                if rand::random::<f64>() >= self.sampling_ratio {
+                    context.trace_flags = 0;
+                }
+                context
+            }
+        }
+    }
+
+    /// Start the evaluation span of a request.
+    ///
+    /// Returns the request's trace context with the evaluation span as
+    /// parent, for attaching to the evaluation context and propagating
+    /// downstream. Sampled spans are registered with Observatory, which
+    /// assigns the span ID; unsampled spans, and spans whose registration
+    /// fails, get a locally generated ID so events still correlate.
+    pub async fn start_span(&self, headers: &HeaderMap) -> TraceContext {
+        let mut context = self.extract(headers);
+        let registered = match &self.observatory {
+            Some(observatory) if context.is_sampled() => {
+                let span = PolicySpan {
+                    name: EVALUATION_SPAN_NAME.to_string(),
+                    trace_id: context.trace_id.clone(),
+                    parent_span_id: context.parent_span_id.clone(),
+                    start_time: chrono::Utc::now().to_rfc3339(),
+                    kind: SpanKind::Server,
+                    attributes: HashMap::new(),
+                };
+                match observatory.register_span(&span).await {
+                    Ok(registration) => Some(registration.span_id),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to register evaluation span");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let span_id = registered.unwrap_or_else(|| format!("{:016x}", random_id::<u64>()));
+        context.parent_span_id = Some(span_id);
+        context
+    }
+}
+
+/// Random non-zero trace or span ID; all-zero IDs are invalid.
+fn random_id<T>() -> T
+where
+    T: Default + PartialEq,
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    loop {
+        let id = rand::random::<T>();
+        if id != T::default() {
+            return id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{EvaluationContext, PolicyEngine};
+    use crate::integration::{BatchConfig, IntegrationClient, MockTransport};
+    use reqwest::header::HeaderValue;
+    use std::time::Duration;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_ID: &str = "00f067aa0ba902b7";
+    const SPAN_ID: &str = "b7ad6b7169203331";
+
+    fn observatory() -> (Arc<ObservatoryAdapter>, Arc<MockTransport>) {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_json(
+                    reqwest::Method::POST,
+                    "/api/v1/spans/register",
+                    serde_json::json!({ "span_id": SPAN_ID, "registered_at": "now" }),
+                )
+                .with_json(
+                    reqwest::Method::POST,
+                    "/api/v1/events/batch",
+                    serde_json::json!({ "accepted_count": 1, "rejected_count": 0 }),
+                ),
+        );
+        let client =
+            IntegrationClient::new("http://observatory".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        (Arc::new(ObservatoryAdapter::from_client(client)), transport)
+    }
+
+    /// Evaluate with a trace and return the emitted event.
+    async fn emitted_event(
+        observatory: &Arc<ObservatoryAdapter>,
+        transport: &MockTransport,
+        trace: TraceContext,
+    ) -> serde_json::Value {
+        let sink = observatory.spawn_batching(BatchConfig::default());
+        let engine = PolicyEngine::builder()
+            .with_event_sink(sink.clone())
+            .build()
+            .await
+            .unwrap();
+        let context = EvaluationContext::builder()
+            .with_user_id("user-1")
+            .with_trace(trace)
+            .build();
+        engine.evaluate(&context).await.unwrap();
+        sink.flush().await;
+
+        let batch = transport
+            .requests()
+            .into_iter()
+            .find(|request| request.path == "/api/v1/events/batch")
+            .unwrap();
+        batch.json().unwrap()["events"][0].clone()
+    }
+
+    #[tokio::test]
+    async fn test_child_span_of_incoming_trace() {
+        let (observatory, transport) = observatory();
+        let tracer = RequestTracer::new(0.0).with_observatory(observatory.clone());
+        let mut headers = HeaderMap::new();
+        let traceparent = format!("00-{}-{}-01", TRACE_ID, PARENT_ID);
+        headers.insert(TRACEPARENT_HEADER, HeaderValue::from_str(&traceparent).unwrap());
+        headers.insert(TRACESTATE_HEADER, HeaderValue::from_static("vendor=abc"));
+        headers.insert(BAGGAGE_HEADER, HeaderValue::from_static("tenant=acme"));
+
+        // The caller's sampling decision wins over the ratio.
+        let trace = tracer.start_span(&headers).await;
+        assert_eq!(trace.trace_id, TRACE_ID);
+        assert_eq!(trace.parent_span_id.as_deref(), Some(SPAN_ID));
+        assert_eq!(trace.trace_state.as_deref(), Some("vendor=abc"));
+        let span = transport.requests()[0].json().unwrap();
+        assert_eq!(span["parent_span_id"], PARENT_ID);
+        assert_eq!(span["name"], EVALUATION_SPAN_NAME);
+
+        let event = emitted_event(&observatory, &transport, trace).await;
+        assert_eq!(event["trace_id"], TRACE_ID);
+        assert_eq!(event["span_id"], SPAN_ID);
+        assert_eq!(event["context"]["baggage.tenant"], "acme");
+    }
+
+    #[tokio::test]
+    async fn test_root_span_without_trace_header() {
+        let (observatory, transport) = observatory();
+        let sampled = RequestTracer::new(1.0).with_observatory(observatory.clone());
+
+        let trace = sampled.start_span(&HeaderMap::new()).await;
+        assert!(trace.is_sampled());
+        assert_eq!(trace.trace_id.len(), 32);
+        assert_eq!(trace.parent_span_id.as_deref(), Some(SPAN_ID));
+        let span = transport.requests()[0].json().unwrap();
+        assert_eq!(span["trace_id"], trace.trace_id.as_str());
+        assert!(span.get("parent_span_id").is_none());
+
+        let event = emitted_event(&observatory, &transport, trace.clone()).await;
+        assert_eq!(event["trace_id"], trace.trace_id.as_str());
+        assert_eq!(event["span_id"], SPAN_ID);
+
+        // Unsampled root traces are not registered, but still get a span.
+        let mut invalid = HeaderMap::new();
+        invalid.insert(TRACEPARENT_HEADER, HeaderValue::from_static("not-a-traceparent"));
+        let unsampled = RequestTracer::new(0.0).with_observatory(observatory);
+        let trace = unsampled.start_span(&invalid).await;
+        assert!(!trace.is_sampled());
+        assert_ne!(trace.trace_id, TRACE_ID);
+        assert_eq!(trace.parent_span_id.as_ref().unwrap().len(), 16);
+        let registrations = transport
+            .requests()
+            .iter()
+            .filter(|request| request.path == "/api/v1/spans/register")
+            .count();
+        assert_eq!(registrations, 1);
+    }
+}
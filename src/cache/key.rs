@@ -0,0 +1,137 @@
+//! Decision cache keys.
+//!
+//! A cache key is a BLAKE3 hash of the evaluation context in canonical form:
+//! object keys are sorted, numbers are normalized by a
+//! [`NumberNormalization`] rule, and volatile fields are removed. Under the
+//! default rule, integral numbers are written as integers, so `1`, `1.0` and
+//! `1.00` agree; under [`NumberNormalization::Exact`], `1` and `1.0` get
+//! different keys. Volatile fields, such as
+//! request and trace IDs, change on every request without changing the
+//! decision; keeping them in the key would make every request a miss, and
+//! would let callers fill the cache with entries that are never reused.
+//!
+//! Excluded fields are configured with `cache.excluded_fields`, defaulting to
+//! [`DEFAULT_EXCLUDED_FIELDS`]. Policies must not depend on excluded fields:
+//! requests that differ only in them share a cached decision.
+
+use super::l1::hash_value;
+use super::NumberNormalization;
+use crate::api::EvaluationContext;
+
+/// Context fields left out of cache keys by default, as dotted paths.
+pub const DEFAULT_EXCLUDED_FIELDS: &[&str] = &[
+    "request.id",
+    "request.timestamp",
+    "metadata.trace_id",
+    "metadata.span_id",
+];
+
+/// Compute the cache key for a context, leaving out the `excluded` fields
+/// (dotted paths such as `request.id`) and normalizing numbers by the
+/// `numbers` rule.
+pub fn cache_key(
+    context: &EvaluationContext,
+    excluded: &[String],
+    numbers: NumberNormalization,
+) -> String {
+    let mut value = serde_json::to_value(context).unwrap_or_default();
+    for path in excluded {
+        remove_field(&mut value, path);
+    }
+    hash_value(value, numbers)
+}
+
+/// Remove the field at a dotted path, if present.
+fn remove_field(value: &mut serde_json::Value, path: &str) {
+    let (parent, field) = match path.rsplit_once('.') {
+        Some((parent, field)) => (
+            parent.split('.').try_fold(value, |value, key| value.get_mut(key)),
+            field,
+        ),
+        None => (Some(value), path),
+    };
+    if let Some(serde_json::Value::Object(fields)) = parent {
+        fields.remove(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn excluded() -> Vec<String> {
+        DEFAULT_EXCLUDED_FIELDS.iter().map(|field| field.to_string()).collect()
+    }
+
+    fn key(context: &EvaluationContext) -> String {
+        cache_key(context, &excluded(), NumberNormalization::default())
+    }
+
+    fn context(request_id: &str, trace_id: &str) -> EvaluationContext {
+        EvaluationContext::builder()
+            .with_user_id("user-1")
+            .with_model("gpt-4")
+            .with_max_tokens(1000)
+            .with_request_details(request_id, Some("10.0.0.1".to_string()), None)
+            .with_metadata("trace_id", serde_json::json!(trace_id))
+            .build()
+    }
+
+    #[test]
+    fn test_equal_contexts_share_key() {
+        let first = context("req-1", "trace-1");
+        let second = context("req-2", "trace-2");
+        assert_eq!(key(&first), key(&second));
+
+        // Metadata order and number representation do not matter.
+        let mut reordered = first.clone();
+        reordered.metadata.insert("tier".to_string(), serde_json::json!(2.0));
+        reordered.metadata.insert("region".to_string(), serde_json::json!("eu"));
+        let mut other_order = first.clone();
+        other_order.metadata.insert("region".to_string(), serde_json::json!("eu"));
+        other_order.metadata.insert("tier".to_string(), serde_json::json!(2));
+        assert_eq!(key(&reordered), key(&other_order));
+    }
+
+    #[test]
+    fn test_meaningful_fields_change_key() {
+        let base = context("req-1", "trace-1");
+        let base_key = key(&base);
+
+        let mut model = base.clone();
+        model.llm.as_mut().unwrap().model = Some("gpt-3.5-turbo".to_string());
+        assert_ne!(key(&model), base_key);
+
+        let mut ip = base.clone();
+        ip.request.as_mut().unwrap().ip_address = Some("10.0.0.2".to_string());
+        assert_ne!(key(&ip), base_key);
+
+        // Without exclusions, volatile fields are part of the key.
+        let other = context("req-2", "trace-2");
+        let unexcluded =
+            |context: &EvaluationContext| cache_key(context, &[], NumberNormalization::default());
+        assert_ne!(unexcluded(&base), unexcluded(&other));
+    }
+
+    #[test]
+    fn test_number_normalization() {
+        let mut contexts = ["1", "1.0", "1.00", "1e0"].map(|tier| {
+            let mut context = context("req-1", "trace-1");
+            let tier: serde_json::Value = serde_json::from_str(tier).unwrap();
+            context.metadata.insert("tier".to_string(), tier);
+            context
+        });
+        let unified: Vec<_> = contexts.iter().map(key).collect();
+        assert!(unified.iter().all(|k| *k == unified[0]));
+
+        let exact = |context: &EvaluationContext| {
+            cache_key(context, &excluded(), NumberNormalization::Exact)
+        };
+        assert_ne!(exact(&contexts[0]), exact(&contexts[1]));
+        assert_eq!(exact(&contexts[1]), exact(&contexts[2]));
+        assert_eq!(exact(&contexts[1]), exact(&contexts[3]));
+
+        contexts[0].metadata.insert("tier".to_string(), serde_json::json!(1.5));
+        assert_ne!(key(&contexts[0]), unified[0]);
+    }
+}
@@ -0,0 +1,341 @@
+//! L1 in-memory cache.
+//!
+//! An [`L1Cache`] is a size-bounded LRU whose entries also expire after a
+//! fixed TTL. Keys are stable hashes of the cached input (see
+//! [`input_hash`]), so equal inputs hit the same entry regardless of map
+//! ordering.
+//!
+//! Besides the entry count, the cache can be bounded by the approximate
+//! bytes its entries take, as estimated per value (e.g. by
+//! [`serialized_size`]). Least recently used entries are evicted until both
+//! limits hold; a value larger than the byte limit on its own is not cached.
+
+use super::NumberNormalization;
+use crate::config::CacheConfig;
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+/// LRU cache with per-entry TTL expiry.
+pub struct L1Cache<V> {
+    entries: Mutex<Entries<V>>,
+    ttl: Duration,
+    /// Byte limit and the estimator sizing values against it
+    byte_limit: Option<(usize, fn(&V) -> usize)>,
+}
+
+/// Entries in LRU order, with their total estimated size.
+struct Entries<V> {
+    lru: LruCache<String, CacheEntry<V>>,
+    bytes: usize,
+}
+
+/// A cached value with its expiry time and estimated size.
+struct CacheEntry<V> {
+    value: V,
+    expires_at: Instant,
+    size: usize,
+}
+
+impl<V> Entries<V> {
+    fn put(&mut self, key: String, entry: CacheEntry<V>) {
+        self.bytes += entry.size;
+        // Returns the replaced entry, or the one evicted at capacity.
+        if let Some((_, old)) = self.lru.push(key, entry) {
+            self.bytes -= old.size;
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry<V>> {
+        let entry = self.lru.pop(key)?;
+        self.bytes -= entry.size;
+        Some(entry)
+    }
+
+    fn remove_lru(&mut self) -> bool {
+        match self.lru.pop_lru() {
+            Some((_, entry)) => {
+                self.bytes -= entry.size;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<V: Clone> L1Cache<V> {
+    /// Create a cache holding at most `max_entries` (minimum 1) for `ttl`.
+    pub fn new(max_entries: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(Entries {
+                lru: LruCache::new(capacity),
+                bytes: 0,
+            }),
+            ttl,
+            byte_limit: None,
+        }
+    }
+
+    /// Create a cache sized by `l1_max_entries` and `l1_ttl_seconds`.
+    pub fn from_config(config: &CacheConfig) -> Self {
+        Self::new(config.l1_max_entries, config.l1_ttl())
+    }
+
+    /// Also bound the cache by the approximate bytes of its entries, each
+    /// sized as its key plus the `estimate` of its value.
+    pub fn with_max_bytes(mut self, max_bytes: usize, estimate: fn(&V) -> usize) -> Self {
+        self.byte_limit = Some((max_bytes, estimate));
+        self
+    }
+
+    /// Get a live entry, marking it most recently used.
+    ///
+    /// An expired entry is removed and treated as a miss.
+    pub fn get(&self, key: &str) -> Option<V> {
+        let mut entries = self.entries.lock();
+        match entries.lru.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Insert an entry, evicting the least recently used ones if full.
+    pub fn insert(&self, key: String, value: V) {
+        self.insert_with_ttl(key, value, self.ttl);
+    }
+
+    /// Insert an entry that stays live for `ttl` instead of the cache TTL.
+    ///
+    /// A value too large for the byte limit is not cached, and replaces
+    /// nothing but the entry under its key.
+    pub fn insert_with_ttl(&self, key: String, value: V, ttl: Duration) {
+        let size = self
+            .byte_limit
+            .map_or(0, |(_, estimate)| key.len() + estimate(&value));
+        let mut entries = self.entries.lock();
+        if let Some((max_bytes, _)) = self.byte_limit {
+            if size > max_bytes {
+                entries.remove(&key);
+                return;
+            }
+        }
+
+        let entry = CacheEntry {
+            value,
+            expires_at: Instant::now() + ttl,
+            size,
+        };
+        entries.put(key, entry);
+        if let Some((max_bytes, _)) = self.byte_limit {
+            while entries.bytes > max_bytes && entries.remove_lru() {}
+        }
+    }
+
+    /// Remove an entry.
+    pub fn remove(&self, key: &str) -> Option<V> {
+        self.entries.lock().remove(key).map(|entry| entry.value)
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock();
+        entries.lru.clear();
+        entries.bytes = 0;
+    }
+
+    /// Number of entries, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries.lock().lru.len()
+    }
+
+    /// Check if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.entries.lock().lru.cap().get()
+    }
+
+    /// Approximate bytes taken by the entries, if the cache has a byte
+    /// limit.
+    pub fn size_bytes(&self) -> Option<usize> {
+        self.byte_limit.map(|_| self.entries.lock().bytes)
+    }
+
+    /// Maximum approximate bytes of the entries, if limited.
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.byte_limit.map(|(max_bytes, _)| max_bytes)
+    }
+
+    /// Time an entry stays live after insertion.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+/// Estimate the size of a value as the length of its JSON serialization.
+pub fn serialized_size<V: Serialize>(value: &V) -> usize {
+    serde_json::to_vec(value).map_or(0, |bytes| bytes.len())
+}
+
+/// Compute a stable cache key for a serializable input.
+///
+/// Object keys are sorted before hashing, so inputs holding `HashMap`s hash
+/// the same however their entries are ordered, and integral floats are
+/// written as integers, so `1.0` and `1` hash the same.
+pub fn input_hash<T: Serialize>(input: &T) -> String {
+    let value = serde_json::to_value(input).unwrap_or(serde_json::Value::Null);
+    hash_value(value, NumberNormalization::Unified)
+}
+
+/// Hash a JSON value with sorted object keys and numbers normalized by the
+/// given rule.
+pub(crate) fn hash_value(value: serde_json::Value, numbers: NumberNormalization) -> String {
+    blake3::hash(canonicalize(value, numbers).to_string().as_bytes())
+        .to_hex()
+        .to_string()
+}
+
+/// Rebuild objects with sorted keys and normalized numbers, recursively.
+fn canonicalize(value: serde_json::Value, numbers: NumberNormalization) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let sorted: BTreeMap<String, serde_json::Value> = map
+                .into_iter()
+                .map(|(key, value)| (key, canonicalize(value, numbers)))
+                .collect();
+            serde_json::Value::Object(sorted.into_iter().collect())
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .into_iter()
+                .map(|item| canonicalize(item, numbers))
+                .collect(),
+        ),
+        serde_json::Value::Number(number) => serde_json::Value::Number(numbers.normalize(number)),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::thread;
+
+    #[test]
+    fn test_hit_and_miss() {
+        let cache = L1Cache::new(10, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1);
+
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.remove("a"), Some(1));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let cache = L1Cache::new(10, Duration::from_millis(50));
+        cache.insert("a".to_string(), 1);
+        assert_eq!(cache.get("a"), Some(1));
+
+        thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(cache.get("a"), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_lru_eviction_at_capacity() {
+        let cache = L1Cache::from_config(&CacheConfig {
+            l1_max_entries: 2,
+            ..CacheConfig::default()
+        });
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.ttl(), Duration::from_secs(300));
+
+        cache.insert("a".to_string(), 1);
+        cache.insert("b".to_string(), 2);
+        // Touch "a" so "b" becomes least recently used.
+        assert_eq!(cache.get("a"), Some(1));
+        cache.insert("c".to_string(), 3);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("c"), Some(3));
+    }
+
+    #[test]
+    fn test_input_hash_ignores_map_order() {
+        let mut first = HashMap::new();
+        let mut second = HashMap::new();
+        for i in 0..32 {
+            first.insert(format!("key{}", i), i);
+            second.insert(format!("key{}", 31 - i), 31 - i);
+        }
+
+        assert_eq!(input_hash(&first), input_hash(&second));
+        second.insert("extra".to_string(), 0);
+        assert_ne!(input_hash(&first), input_hash(&second));
+    }
+
+    #[test]
+    fn test_byte_limit_evicts_before_count_limit() {
+        let cache = L1Cache::new(100, Duration::from_secs(60))
+            .with_max_bytes(100, serialized_size::<String>);
+        assert_eq!(cache.size_bytes(), Some(0));
+
+        // Each entry takes its 1-byte key plus 32 bytes of JSON.
+        for key in ["a", "b", "c"] {
+            cache.insert(key.to_string(), "x".repeat(30));
+        }
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.size_bytes(), Some(99));
+
+        // A fourth entry would exceed the byte limit, so the least recently
+        // used entry goes, although the count limit is far off.
+        assert!(cache.get("a").is_some());
+        cache.insert("d".to_string(), "x".repeat(30));
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("a").is_some());
+
+        // A large entry evicts as many entries as it needs.
+        cache.insert("e".to_string(), "x".repeat(60));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("e").is_some());
+        assert!(cache.size_bytes().unwrap() <= 100);
+
+        // Replacing an entry releases the old value's bytes.
+        cache.insert("e".to_string(), String::new());
+        assert_eq!(cache.size_bytes(), Some(1 + 2 + 33));
+    }
+
+    #[test]
+    fn test_oversized_entries_not_cached() {
+        let cache = L1Cache::new(100, Duration::from_secs(60))
+            .with_max_bytes(100, serialized_size::<String>);
+        cache.insert("a".to_string(), "small".to_string());
+        cache.insert("b".to_string(), "x".repeat(200));
+
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some("small".to_string()));
+        // An oversized value still replaces the stale one under its key.
+        cache.insert("a".to_string(), "x".repeat(200));
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.size_bytes(), Some(0));
+    }
+}
@@ -0,0 +1,324 @@
+//! L2 shared cache.
+//!
+//! An [`L2Cache`] stores serialized values in a shared key-value store,
+//! normally Redis, so decisions can be reused across engine instances. Keys
+//! are written as `{redis_prefix}{key}` and expire after `l2_ttl_seconds`.
+//!
+//! The L2 tier is best effort: store errors and slow responses are counted
+//! and treated as misses, so an unavailable Redis degrades the engine to
+//! L1-only caching instead of failing evaluations.
+
+use crate::config::CacheConfig;
+use crate::Result;
+
+use futures::future::BoxFuture;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default time allowed for each L2 store operation.
+pub const DEFAULT_L2_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Time allowed to establish the initial Redis connection.
+#[cfg(feature = "redis-cache")]
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Key-value store backing an [`L2Cache`].
+///
+/// Implemented for Redis; tests can provide an in-memory implementation.
+pub trait L2Store: Send + Sync {
+    /// Get the value stored under `key`, if present and not expired.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>>>;
+
+    /// Store `value` under `key`, expiring after `ttl`.
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Duration) -> BoxFuture<'a, Result<()>>;
+}
+
+/// L2 hit, miss and error counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L2Stats {
+    /// Lookups that found a value
+    pub hits: u64,
+    /// Lookups that found nothing
+    pub misses: u64,
+    /// Operations that failed or timed out
+    pub errors: u64,
+}
+
+/// Shared cache tier in front of an [`L2Store`].
+pub struct L2Cache {
+    store: Arc<dyn L2Store>,
+    prefix: String,
+    ttl: Duration,
+    timeout: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl L2Cache {
+    /// Create a cache over `store` using the prefix and TTL from `config`.
+    pub fn new(store: Arc<dyn L2Store>, config: &CacheConfig) -> Self {
+        Self {
+            store,
+            prefix: config.redis_prefix.clone(),
+            ttl: config.l2_ttl(),
+            timeout: DEFAULT_L2_TIMEOUT,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+        }
+    }
+
+    /// Connect to the Redis server at `redis_url`.
+    ///
+    /// Fails if no URL is configured or the server cannot be reached.
+    #[cfg(feature = "redis-cache")]
+    pub async fn connect(config: &CacheConfig) -> Result<Self> {
+        let url = config
+            .redis_url
+            .as_deref()
+            .ok_or_else(|| crate::Error::config("cache.redis_url is required for the L2 cache"))?;
+        let store = RedisStore::connect(url).await?;
+        Ok(Self::new(Arc::new(store), config))
+    }
+
+    /// Set the time allowed for each store operation.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Look up a value, treating store errors as misses.
+    pub async fn get<V: DeserializeOwned>(&self, key: &str) -> Option<V> {
+        let key = self.full_key(key);
+        let bytes = match tokio::time::timeout(self.timeout, self.store.get(&key)).await {
+            Ok(Ok(Some(bytes))) => bytes,
+            Ok(Ok(None)) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            Ok(Err(e)) => {
+                self.record_error(&key, &e.to_string());
+                return None;
+            }
+            Err(_) => {
+                self.record_error(&key, "operation timed out");
+                return None;
+            }
+        };
+
+        match serde_json::from_slice(&bytes) {
+            Ok(value) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(value)
+            }
+            Err(e) => {
+                self.record_error(&key, &e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Store a value; failures are counted and logged but not returned.
+    pub async fn set<V: Serialize>(&self, key: &str, value: &V) {
+        let key = self.full_key(key);
+        let bytes = match serde_json::to_vec(value) {
+            Ok(bytes) => bytes,
+            Err(e) => return self.record_error(&key, &e.to_string()),
+        };
+
+        match tokio::time::timeout(self.timeout, self.store.set(&key, bytes, self.ttl)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => self.record_error(&key, &e.to_string()),
+            Err(_) => self.record_error(&key, "operation timed out"),
+        }
+    }
+
+    /// Get hit, miss and error counts.
+    pub fn stats(&self) -> L2Stats {
+        L2Stats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Check that the store can be reached, by looking up a probe key.
+    ///
+    /// Unlike lookups, failures are returned and not counted.
+    pub async fn ping(&self) -> Result<()> {
+        let key = self.full_key("readiness-probe");
+        match tokio::time::timeout(self.timeout, self.store.get(&key)).await {
+            Ok(result) => result.map(|_| ()),
+            Err(_) => Err(crate::Error::cache("L2 cache ping timed out")),
+        }
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    fn record_error(&self, key: &str, error: &str) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(key, error, "L2 cache unavailable, falling back to L1");
+    }
+}
+
+/// Redis-backed [`L2Store`].
+#[cfg(feature = "redis-cache")]
+#[derive(Clone)]
+pub struct RedisStore {
+    connection: redis::aio::ConnectionManager,
+}
+
+#[cfg(feature = "redis-cache")]
+impl RedisStore {
+    /// Connect to a Redis server; the connection is re-established on failure.
+    ///
+    /// Fails if the server cannot be reached within one second.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| crate::Error::cache(format!("Invalid Redis URL: {}", e)))?;
+        let connection =
+            tokio::time::timeout(CONNECT_TIMEOUT, redis::aio::ConnectionManager::new(client))
+                .await
+                .map_err(|_| crate::Error::cache("Timed out connecting to Redis"))?
+                .map_err(|e| crate::Error::cache(format!("Failed to connect to Redis: {}", e)))?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "redis-cache")]
+impl L2Store for RedisStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>>> {
+        let mut connection = self.connection.clone();
+        Box::pin(async move {
+            redis::cmd("GET")
+                .arg(key)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| crate::Error::cache(format!("Redis GET failed: {}", e)))
+        })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Duration) -> BoxFuture<'a, Result<()>> {
+        let mut connection = self.connection.clone();
+        let ttl_ms = ttl.as_millis().max(1) as u64;
+        Box::pin(async move {
+            redis::cmd("SET")
+                .arg(key)
+                .arg(value)
+                .arg("PX")
+                .arg(ttl_ms)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| crate::Error::cache(format!("Redis SET failed: {}", e)))
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    /// In-memory store honoring TTLs, standing in for Redis.
+    #[derive(Default)]
+    pub(crate) struct MemoryStore {
+        entries: Mutex<HashMap<String, (Vec<u8>, Instant)>>,
+    }
+
+    impl L2Store for MemoryStore {
+        fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>>> {
+            let entries = self.entries.lock();
+            let value = entries
+                .get(key)
+                .filter(|(_, expires_at)| *expires_at > Instant::now())
+                .map(|(value, _)| value.clone());
+            Box::pin(async move { Ok(value) })
+        }
+
+        fn set<'a>(
+            &'a self,
+            key: &'a str,
+            value: Vec<u8>,
+            ttl: Duration,
+        ) -> BoxFuture<'a, Result<()>> {
+            self.entries
+                .lock()
+                .insert(key.to_string(), (value, Instant::now() + ttl));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// Store whose every operation fails, like an unreachable Redis.
+    pub(crate) struct UnavailableStore;
+
+    impl L2Store for UnavailableStore {
+        fn get<'a>(&'a self, _key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>>> {
+            Box::pin(async { Err(crate::Error::cache("Connection refused")) })
+        }
+
+        fn set<'a>(
+            &'a self,
+            _key: &'a str,
+            _value: Vec<u8>,
+            _ttl: Duration,
+        ) -> BoxFuture<'a, Result<()>> {
+            Box::pin(async { Err(crate::Error::cache("Connection refused")) })
+        }
+    }
+
+    fn config(ttl_seconds: u64) -> CacheConfig {
+        CacheConfig {
+            l2_enabled: true,
+            redis_prefix: "test:".to_string(),
+            l2_ttl_seconds: ttl_seconds,
+            ..CacheConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_and_get_under_prefix() {
+        let store = Arc::new(MemoryStore::default());
+        let cache = L2Cache::new(store.clone(), &config(60));
+
+        assert_eq!(cache.get::<String>("key").await, None);
+        cache.set("key", &"value".to_string()).await;
+        assert_eq!(cache.get::<String>("key").await.as_deref(), Some("value"));
+        assert!(store.entries.lock().contains_key("test:key"));
+
+        assert_eq!(
+            cache.stats(),
+            L2Stats {
+                hits: 1,
+                misses: 1,
+                errors: 0
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire_after_ttl() {
+        let cache = L2Cache::new(Arc::new(MemoryStore::default()), &config(0));
+
+        cache.set("key", &1u32).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(cache.get::<u32>("key").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_store_degrades_to_miss() {
+        let cache = L2Cache::new(Arc::new(UnavailableStore), &config(60));
+
+        cache.set("key", &1u32).await;
+        assert_eq!(cache.get::<u32>("key").await, None);
+        assert_eq!(cache.stats().errors, 2);
+        assert_eq!(cache.stats().hits + cache.stats().misses, 0);
+    }
+}
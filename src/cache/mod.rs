@@ -3,80 +3,323 @@
 //! This module provides multi-layer caching for policy decisions to improve
 //! evaluation performance.
 
+mod key;
+mod l1;
+pub(crate) mod l2;
+mod numbers;
+mod single_flight;
+
+pub use key::{cache_key, DEFAULT_EXCLUDED_FIELDS};
+pub use l1::{input_hash, serialized_size, L1Cache};
+#[cfg(feature = "redis-cache")]
+pub use l2::RedisStore;
+pub use l2::{L2Cache, L2Stats, L2Store, DEFAULT_L2_TIMEOUT};
+pub use numbers::NumberNormalization;
+
 use crate::api::{EvaluationContext, PolicyDecision};
 use crate::api::engine::CacheStats;
+use crate::{Error, Result};
+use single_flight::{Flight, SingleFlight};
 
-use lru::LruCache;
-use parking_lot::Mutex;
-use std::num::NonZeroUsize;
+use std::future::Future;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{Duration, Instant};
+use std::time::Duration;
+
+/// Default time negative results are cached.
+pub const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(30);
+
+/// How a computed result is cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retention {
+    /// Cache decisions in L1 and L2 for the full TTL; errors are not cached
+    Positive,
+    /// Cache decisions and evaluation errors in L1 for the negative TTL
+    Negative,
+    /// Do not cache the result
+    Never,
+}
+
+/// A cached evaluation error.
+#[derive(Clone)]
+struct CachedError {
+    message: String,
+    policy_id: Option<String>,
+    rule_id: Option<String>,
+}
 
 /// A cache for policy decisions.
+///
+/// Decisions are kept in an in-process L1 cache and, if configured, a shared
+/// L2 cache. Lookups consult L1 first, then L2, back-filling L1 on an L2 hit.
+/// Concurrent misses on the same context are coalesced by
+/// [`get_or_compute`](Self::get_or_compute).
+///
+/// Negative results, such as denials and evaluation errors, can be cached in
+/// L1 for a shorter TTL than other decisions with
+/// [`get_or_compute_with`](Self::get_or_compute_with), so repeated bad
+/// requests do not re-run expensive checks but recover quickly.
 pub struct DecisionCache {
-    /// L1 in-memory cache
-    l1: Mutex<LruCache<String, CachedDecision>>,
-    /// TTL for cached entries
-    ttl: Duration,
+    /// L1 in-memory cache, keyed by evaluation input hash
+    l1: L1Cache<PolicyDecision>,
+    /// Optional shared L2 cache
+    l2: Option<L2Cache>,
+    /// Cached evaluation errors, keyed by cache key
+    errors: L1Cache<CachedError>,
+    /// Time negative results are cached
+    negative_ttl: Duration,
+    /// Evaluations in progress, keyed by cache key
+    flights: SingleFlight<PolicyDecision>,
+    /// Context fields left out of cache keys
+    excluded_fields: Vec<String>,
+    /// How numbers are normalized in cache keys
+    numbers: NumberNormalization,
+    /// Generation of the policies and settings decisions are cached for,
+    /// part of every cache key
+    generation: AtomicU64,
     /// Cache hit counter
     hits: AtomicU64,
     /// Cache miss counter
     misses: AtomicU64,
 }
 
-/// A cached decision with expiration time.
-struct CachedDecision {
-    decision: PolicyDecision,
-    expires_at: Instant,
-}
-
 impl DecisionCache {
     /// Create a new decision cache.
     pub fn new(max_entries: usize, ttl: Duration) -> Self {
-        let capacity = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::new(1).unwrap());
         Self {
-            l1: Mutex::new(LruCache::new(capacity)),
-            ttl,
+            l1: L1Cache::new(max_entries, ttl),
+            l2: None,
+            errors: L1Cache::new(max_entries, DEFAULT_NEGATIVE_TTL),
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+            flights: SingleFlight::new(),
+            excluded_fields: DEFAULT_EXCLUDED_FIELDS.iter().map(|f| f.to_string()).collect(),
+            numbers: NumberNormalization::default(),
+            generation: AtomicU64::new(0),
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
         }
     }
 
-    /// Get a cached decision for the given context.
-    pub fn get(&self, context: &EvaluationContext) -> Option<PolicyDecision> {
-        let key = self.compute_key(context);
-        let mut cache = self.l1.lock();
-
-        if let Some(cached) = cache.get(&key) {
-            if cached.expires_at > Instant::now() {
-                self.hits.fetch_add(1, Ordering::Relaxed);
-                return Some(cached.decision.clone());
-            } else {
-                // Entry expired, remove it
-                cache.pop(&key);
+    /// Add a shared L2 tier behind the L1 cache.
+    pub fn with_l2(mut self, l2: L2Cache) -> Self {
+        self.l2 = Some(l2);
+        self
+    }
+
+    /// Bound the L1 cache by the approximate bytes of cached decisions, as
+    /// well as by entry count.
+    pub fn with_l1_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.l1 = self.l1.with_max_bytes(max_bytes, serialized_size::<PolicyDecision>);
+        self
+    }
+
+    /// Set how long negative results are cached.
+    pub fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Set the context fields left out of cache keys, as dotted paths,
+    /// replacing [`DEFAULT_EXCLUDED_FIELDS`].
+    pub fn with_excluded_fields(mut self, fields: Vec<String>) -> Self {
+        self.excluded_fields = fields;
+        self
+    }
+
+    /// Get the context fields left out of cache keys.
+    pub fn excluded_fields(&self) -> &[String] {
+        &self.excluded_fields
+    }
+
+    /// Set how numbers are normalized in cache keys.
+    pub fn with_number_normalization(mut self, numbers: NumberNormalization) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Get a decision from L1, then L2, for the given context.
+    ///
+    /// An L2 hit is copied into L1. L2 failures count as misses.
+    pub async fn lookup(&self, context: &EvaluationContext) -> Option<PolicyDecision> {
+        let decision = self.read(&self.cache_key(context)).await.and_then(Result::ok);
+        self.record(decision.is_some());
+        decision
+    }
+
+    /// Cache a decision in L1 and L2 for the given context.
+    pub async fn store(&self, context: &EvaluationContext, decision: &PolicyDecision) {
+        self.write(self.cache_key(context), decision).await;
+    }
+
+    /// Get a cached decision, or compute and cache it on a miss.
+    ///
+    /// Concurrent misses for the same context share a single call to
+    /// `compute`; the returned flag is `true` for every caller that did not
+    /// run it. If the computing caller fails, panics or is cancelled, the
+    /// callers waiting on it try again. Errors are returned, not cached.
+    pub async fn get_or_compute<F, Fut>(
+        &self,
+        context: &EvaluationContext,
+        compute: F,
+    ) -> Result<(PolicyDecision, bool)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<PolicyDecision>>,
+    {
+        self.get_or_compute_with(context, compute, |_| Retention::Positive).await
+    }
+
+    /// Like [`get_or_compute`](Self::get_or_compute), with `retention`
+    /// choosing how the computed result is cached.
+    ///
+    /// Cached errors are returned to every caller until they expire.
+    pub async fn get_or_compute_with<F, Fut, R>(
+        &self,
+        context: &EvaluationContext,
+        compute: F,
+        retention: R,
+    ) -> Result<(PolicyDecision, bool)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<PolicyDecision>>,
+        R: FnOnce(&Result<PolicyDecision>) -> Retention,
+    {
+        let key = self.cache_key(context);
+        let mut compute = Some(compute);
+        let mut retention = Some(retention);
+
+        loop {
+            if let Some(result) = self.read(&key).await {
+                self.record(true);
+                return result.map(|decision| (decision, true));
+            }
+
+            match self.flights.join(&key) {
+                Flight::Waiter(receiver) => {
+                    if let Some(decision) = single_flight::wait(receiver).await {
+                        self.record(true);
+                        return Ok((decision, true));
+                    }
+                }
+                Flight::Leader(leader) => {
+                    // A previous flight may have finished since the lookup.
+                    if let Some(result) = self.read_l1(&key) {
+                        self.record(true);
+                        return result.map(|decision| (decision, true));
+                    }
+
+                    self.record(false);
+                    let compute = compute.take().expect("a caller leads at most once");
+                    let retention = retention.take().expect("a caller leads at most once");
+                    let result = compute().await;
+                    match (retention(&result), &result) {
+                        (Retention::Positive, Ok(decision)) => {
+                            self.write(key, decision).await;
+                        }
+                        (Retention::Negative, _) => self.write_negative(key, &result),
+                        _ => {}
+                    }
+                    let decision = result?;
+                    leader.complete(decision.clone());
+                    return Ok((decision, false));
+                }
             }
         }
+    }
+
+    async fn read(&self, key: &str) -> Option<Result<PolicyDecision>> {
+        if let Some(result) = self.read_l1(key) {
+            return Some(result);
+        }
 
-        self.misses.fetch_add(1, Ordering::Relaxed);
-        None
+        let decision = self.l2.as_ref()?.get::<PolicyDecision>(key).await?;
+        self.l1.insert(key.to_string(), decision.clone());
+        Some(Ok(decision))
     }
 
-    /// Cache a decision for the given context.
-    pub fn put(&self, context: &EvaluationContext, decision: &PolicyDecision) {
-        let key = self.compute_key(context);
-        let cached = CachedDecision {
-            decision: decision.clone(),
-            expires_at: Instant::now() + self.ttl,
-        };
+    fn read_l1(&self, key: &str) -> Option<Result<PolicyDecision>> {
+        if let Some(decision) = self.l1.get(key) {
+            return Some(Ok(decision));
+        }
+        let error = self.errors.get(key)?;
+        Some(Err(Error::Evaluation {
+            message: error.message,
+            policy_id: error.policy_id,
+            rule_id: error.rule_id,
+        }))
+    }
+
+    async fn write(&self, key: String, decision: &PolicyDecision) {
+        if let Some(l2) = &self.l2 {
+            l2.set(&key, decision).await;
+        }
+        self.l1.insert(key, decision.clone());
+    }
 
-        let mut cache = self.l1.lock();
-        cache.put(key, cached);
+    /// Cache a negative result in L1 only, for the negative TTL. Only
+    /// evaluation errors are cached; other errors are transient.
+    fn write_negative(&self, key: String, result: &Result<PolicyDecision>) {
+        match result {
+            Ok(decision) => self.l1.insert_with_ttl(key, decision.clone(), self.negative_ttl),
+            Err(Error::Evaluation {
+                message,
+                policy_id,
+                rule_id,
+            }) => {
+                let error = CachedError {
+                    message: message.clone(),
+                    policy_id: policy_id.clone(),
+                    rule_id: rule_id.clone(),
+                };
+                self.errors.insert_with_ttl(key, error, self.negative_ttl);
+            }
+            Err(_) => {}
+        }
     }
 
-    /// Clear all cached entries.
+    fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get a cached decision for the given context from L1 only.
+    pub fn get(&self, context: &EvaluationContext) -> Option<PolicyDecision> {
+        let decision = self.l1.get(&self.cache_key(context));
+        self.record(decision.is_some());
+        decision
+    }
+
+    /// Cache a decision for the given context in L1 only.
+    pub fn put(&self, context: &EvaluationContext, decision: &PolicyDecision) {
+        self.l1.insert(self.cache_key(context), decision.clone());
+    }
+
+    /// Clear all L1 entries.
+    ///
+    /// L2 entries are shared with other engines and expire on their own;
+    /// use [`set_generation`](Self::set_generation) to stop reading them.
     pub fn clear(&self) {
-        let mut cache = self.l1.lock();
-        cache.clear();
+        self.l1.clear();
+        self.errors.clear();
+    }
+
+    /// Cache decisions under a new generation, e.g. once the policies or
+    /// settings they are made under change, and clear all L1 entries.
+    ///
+    /// The generation is part of every cache key, so decisions cached in L2
+    /// under other generations are no longer read; they expire on their own.
+    pub fn set_generation(&self, generation: u64) {
+        self.generation.store(generation, Ordering::Relaxed);
+        self.clear();
+    }
+
+    /// Get the generation decisions are cached under.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Check that the L2 store can be reached, if there is one.
+    pub async fn ping_l2(&self) -> Option<Result<()>> {
+        Some(self.l2.as_ref()?.ping().await)
     }
 
     /// Get cache statistics.
@@ -93,17 +336,22 @@ impl DecisionCache {
         CacheStats {
             hits,
             misses,
-            size: self.l1.lock().len(),
+            size: self.l1.len() + self.errors.len(),
+            size_bytes: self.l1.size_bytes(),
             hit_rate,
+            l2: self.l2.as_ref().map(L2Cache::stats),
         }
     }
 
-    /// Compute a cache key for the given context.
-    fn compute_key(&self, context: &EvaluationContext) -> String {
-        // Use blake3 for fast, consistent hashing
-        let json = serde_json::to_string(context).unwrap_or_default();
-        let hash = blake3::hash(json.as_bytes());
-        hash.to_hex().to_string()
+    /// Compute the cache key for the given context.
+    ///
+    /// Contexts that differ only in excluded fields, map ordering, or the
+    /// representation of numbers equal under the number normalization rule
+    /// share a key; see [`crate::cache::cache_key`]. Keys start with the
+    /// cache's generation.
+    pub fn cache_key(&self, context: &EvaluationContext) -> String {
+        let key = key::cache_key(context, &self.excluded_fields, self.numbers);
+        format!("{:016x}:{}", self.generation(), key)
     }
 }
 
@@ -146,6 +394,28 @@ mod tests {
         assert_eq!(stats.hits, 0);
     }
 
+    #[test]
+    fn test_decision_byte_limit() {
+        let decision = PolicyDecision::deny("x".repeat(1000));
+        let context = |user: &str| EvaluationContext::builder().with_user_id(user).build();
+        let cache = DecisionCache::new(100, Duration::from_secs(60))
+            .with_l1_max_bytes(serialized_size(&decision) * 3);
+        assert_eq!(cache.stats().size_bytes, Some(0));
+
+        for user in ["user-1", "user-2", "user-3"] {
+            cache.put(&context(user), &decision);
+        }
+        // Keys take bytes too, so only two decisions fit.
+        let stats = cache.stats();
+        assert_eq!(stats.size, 2);
+        assert!(stats.size_bytes.unwrap() <= serialized_size(&decision) * 3);
+        assert!(cache.get(&context("user-1")).is_none());
+        assert!(cache.get(&context("user-3")).is_some());
+
+        let unbounded = DecisionCache::new(100, Duration::from_secs(60));
+        assert_eq!(unbounded.stats().size_bytes, None);
+    }
+
     #[test]
     fn test_cache_expiration() {
         let cache = DecisionCache::new(100, Duration::from_millis(50));
@@ -183,6 +453,167 @@ mod tests {
         assert!(cache.get(&context).is_none());
     }
 
+    #[tokio::test]
+    async fn test_l2_hit_back_fills_l1() {
+        use super::l2::tests::MemoryStore;
+        use crate::config::CacheConfig;
+        use std::sync::Arc;
+
+        let store = Arc::new(MemoryStore::default());
+        let config = CacheConfig::default();
+        let writer = DecisionCache::new(100, Duration::from_secs(60))
+            .with_l2(L2Cache::new(store.clone(), &config));
+        let reader = DecisionCache::new(100, Duration::from_secs(60))
+            .with_l2(L2Cache::new(store, &config));
+
+        let context = EvaluationContext::builder().with_user_id("user-123").build();
+        writer.store(&context, &PolicyDecision::deny("blocked")).await;
+
+        // The second engine misses L1 but finds the decision in L2...
+        assert!(reader.get(&context).is_none());
+        let decision = reader.lookup(&context).await.unwrap();
+        assert_eq!(decision.decision, DecisionType::Deny);
+
+        // ...and now serves it from L1.
+        assert!(reader.get(&context).is_some());
+        let stats = reader.stats();
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.l2.unwrap().hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_new_generation_ignores_l2_entries() {
+        use super::l2::tests::MemoryStore;
+        use crate::config::CacheConfig;
+        use std::sync::Arc;
+
+        let store = Arc::new(MemoryStore::default());
+        let cache = DecisionCache::new(100, Duration::from_secs(60))
+            .with_l2(L2Cache::new(store, &CacheConfig::default()));
+        let context = EvaluationContext::builder()
+            .with_user_id("user-123")
+            .build();
+        cache.store(&context, &PolicyDecision::allow()).await;
+
+        cache.set_generation(1);
+        assert!(cache.lookup(&context).await.is_none());
+
+        // Returning to a generation finds its decisions again.
+        cache.set_generation(0);
+        assert!(cache.lookup(&context).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_compute_once() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let cache = Arc::new(DecisionCache::new(100, Duration::from_secs(60)));
+        let context = EvaluationContext::builder().with_user_id("user-123").build();
+        let evaluations = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..16)
+            .map(|_| {
+                let cache = cache.clone();
+                let context = context.clone();
+                let evaluations = evaluations.clone();
+                tokio::spawn(async move {
+                    cache
+                        .get_or_compute(&context, || async move {
+                            evaluations.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok(PolicyDecision::deny("blocked"))
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        let mut computed = 0;
+        for task in tasks {
+            let (decision, cached) = task.await.unwrap().unwrap();
+            assert_eq!(decision.decision, DecisionType::Deny);
+            computed += usize::from(!cached);
+        }
+
+        assert_eq!(evaluations.load(Ordering::SeqCst), 1);
+        assert_eq!(computed, 1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (15, 1));
+    }
+
+    #[tokio::test]
+    async fn test_failed_leader_lets_waiters_retry() {
+        use std::sync::Arc;
+
+        let cache = Arc::new(DecisionCache::new(100, Duration::from_secs(60)));
+        let context = EvaluationContext::builder().with_user_id("user-123").build();
+
+        let leader = {
+            let cache = cache.clone();
+            let context = context.clone();
+            tokio::spawn(async move {
+                cache
+                    .get_or_compute(&context, || async {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        panic!("evaluation crashed");
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let waiter = cache.get_or_compute(&context, || async {
+            Ok(PolicyDecision::allow())
+        });
+        let (decision, cached) = waiter.await.unwrap();
+
+        assert!(leader.await.unwrap_err().is_panic());
+        assert_eq!(decision.decision, DecisionType::Allow);
+        assert!(!cached);
+    }
+
+    #[tokio::test]
+    async fn test_negative_results_expire_sooner() {
+        let cache = DecisionCache::new(100, Duration::from_secs(60))
+            .with_negative_ttl(Duration::from_millis(50));
+        let allowed = EvaluationContext::builder().with_user_id("user-1").build();
+        let denied = EvaluationContext::builder().with_user_id("user-2").build();
+        let failed = EvaluationContext::builder().with_user_id("user-3").build();
+        let negative = |_: &Result<PolicyDecision>| Retention::Negative;
+
+        cache
+            .get_or_compute(&allowed, || async { Ok(PolicyDecision::allow()) })
+            .await
+            .unwrap();
+        cache
+            .get_or_compute_with(&denied, || async { Ok(PolicyDecision::deny("no")) }, negative)
+            .await
+            .unwrap();
+        let error = || async { Err(Error::evaluation_with_context("bad input", "limits", None)) };
+        assert!(cache.get_or_compute_with(&failed, error, negative).await.is_err());
+
+        let (_, cached) = cache
+            .get_or_compute_with(&denied, || async { unreachable!() }, negative)
+            .await
+            .unwrap();
+        assert!(cached);
+        let err = cache
+            .get_or_compute_with(&failed, || async { unreachable!() }, negative)
+            .await
+            .unwrap_err();
+        assert_eq!(err.policy_id(), Some("limits"));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(cache.get(&allowed).is_some());
+        assert!(cache.get(&denied).is_none());
+        let (decision, cached) = cache
+            .get_or_compute_with(&failed, || async { Ok(PolicyDecision::allow()) }, negative)
+            .await
+            .unwrap();
+        assert!(decision.allowed && !cached);
+    }
+
     #[test]
     fn test_cache_stats() {
         let cache = DecisionCache::new(100, Duration::from_secs(60));
@@ -0,0 +1,91 @@
+//! JSON number normalization.
+//!
+//! JSON producers write the same number differently, e.g. `1`, `1.0` and
+//! `1.00`. Textual differences between floats never survive parsing: `1.0`,
+//! `1.00` and `1e0` parse to the same float, which is written back in its
+//! shortest form. What remains is whether a number was parsed as an integer
+//! or as a float; [`NumberNormalization`] decides whether that difference
+//! counts. Cache keys and input validation see numbers normalized by the
+//! rule set with `cache.number_normalization`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+/// How integers and floats are told apart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberNormalization {
+    /// Floats without a fractional part and of magnitude below 2^53 are
+    /// written as integers, so `1`, `1.0` and `1.00` are the same number.
+    /// Larger floats stay floats, as they may not be exact integers.
+    #[default]
+    Unified,
+    /// Integers and floats are kept apart: `1` differs from `1.0`, while
+    /// `1.0` and `1.00` still agree.
+    Exact,
+}
+
+impl NumberNormalization {
+    /// Normalize a number.
+    pub fn normalize(self, number: Number) -> Number {
+        // Largest magnitude below which every integer is an exact f64.
+        const EXACT: f64 = 9_007_199_254_740_992.0;
+        match (self, number.as_f64()) {
+            (Self::Unified, Some(float))
+                if number.is_f64() && float.fract() == 0.0 && float.abs() < EXACT =>
+            {
+                Number::from(float as i64)
+            }
+            _ => number,
+        }
+    }
+
+    /// Normalize every number in a value, recursively.
+    pub fn apply(self, value: &mut Value) {
+        match value {
+            Value::Number(number) => *number = self.normalize(number.clone()),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply(item)),
+            Value::Object(fields) => fields.values_mut().for_each(|field| self.apply(field)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(numbers: NumberNormalization, text: &str) -> Value {
+        let mut value: Value = serde_json::from_str(text).unwrap();
+        numbers.apply(&mut value);
+        value
+    }
+
+    #[test]
+    fn test_unified_numbers_collide() {
+        let numbers = NumberNormalization::Unified;
+        let one = normalized(numbers, r#"{"tier": 1, "ratio": [0.5]}"#);
+        for text in [
+            r#"{"tier": 1.0, "ratio": [0.50]}"#,
+            r#"{"tier": 1.00, "ratio": [5e-1]}"#,
+            r#"{"tier": 1e0, "ratio": [0.500]}"#,
+        ] {
+            assert_eq!(normalized(numbers, text), one, "{}", text);
+        }
+        assert_eq!(normalized(numbers, "-0.0"), normalized(numbers, "0"));
+        assert_ne!(normalized(numbers, "1.5"), normalized(numbers, "1"));
+        // Floats too large to be exact integers are left alone.
+        assert!(normalized(numbers, "1e300").is_f64());
+    }
+
+    #[test]
+    fn test_exact_numbers_keep_integers_apart() {
+        let numbers = NumberNormalization::Exact;
+        assert_ne!(normalized(numbers, "1"), normalized(numbers, "1.0"));
+        assert_eq!(normalized(numbers, "1.0"), normalized(numbers, "1.00"));
+        assert_eq!(
+            serde_json::to_value(numbers).unwrap(),
+            serde_json::json!("exact")
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Coalescing of concurrent cache misses.
+//!
+//! When several callers miss on the same key at once, a [`SingleFlight`]
+//! lets the first one (the leader) compute the value while the others wait
+//! for its result. If the leader fails, panics or is cancelled before
+//! completing, its waiters are released empty-handed so they can try again.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use tokio::sync::watch;
+
+/// In-flight computations keyed by cache key.
+pub(crate) struct SingleFlight<V> {
+    flights: Mutex<HashMap<String, watch::Sender<Option<V>>>>,
+}
+
+/// A caller's role in a flight.
+pub(crate) enum Flight<'a, V> {
+    /// The caller must compute the value and complete the flight
+    Leader(FlightLeader<'a, V>),
+    /// Another caller is computing the value
+    Waiter(watch::Receiver<Option<V>>),
+}
+
+impl<V: Clone> SingleFlight<V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            flights: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Join the flight for `key`, leading it if none is in progress.
+    pub(crate) fn join(&self, key: &str) -> Flight<'_, V> {
+        let mut flights = self.flights.lock();
+        if let Some(sender) = flights.get(key) {
+            return Flight::Waiter(sender.subscribe());
+        }
+
+        let (sender, _) = watch::channel(None);
+        flights.insert(key.to_string(), sender);
+        Flight::Leader(FlightLeader {
+            group: self,
+            key: Some(key.to_string()),
+        })
+    }
+
+    /// Number of keys currently being computed.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.flights.lock().len()
+    }
+}
+
+/// Wait for the leader's value.
+///
+/// Returns `None` if the leader went away without completing.
+pub(crate) async fn wait<V: Clone>(mut receiver: watch::Receiver<Option<V>>) -> Option<V> {
+    loop {
+        if let Some(value) = receiver.borrow_and_update().clone() {
+            return Some(value);
+        }
+        if receiver.changed().await.is_err() {
+            return receiver.borrow().clone();
+        }
+    }
+}
+
+/// Leadership of a flight; dropping it without completing releases waiters.
+pub(crate) struct FlightLeader<'a, V> {
+    group: &'a SingleFlight<V>,
+    key: Option<String>,
+}
+
+impl<V> FlightLeader<'_, V> {
+    /// Hand the computed value to every waiter and end the flight.
+    pub(crate) fn complete(mut self, value: V) {
+        if let Some(key) = self.key.take() {
+            if let Some(sender) = self.group.flights.lock().remove(&key) {
+                sender.send_replace(Some(value));
+            }
+        }
+    }
+}
+
+impl<V> Drop for FlightLeader<'_, V> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.group.flights.lock().remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_waiters_receive_leader_value() {
+        let group = SingleFlight::new();
+        let Flight::Leader(leader) = group.join("key") else {
+            panic!("first caller should lead");
+        };
+        let Flight::Waiter(waiter) = group.join("key") else {
+            panic!("second caller should wait");
+        };
+
+        leader.complete(42);
+        assert_eq!(wait(waiter).await, Some(42));
+        assert_eq!(group.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_flight_releases_waiters() {
+        let group = SingleFlight::<u32>::new();
+        let leader = group.join("key");
+        let Flight::Waiter(waiter) = group.join("key") else {
+            panic!("second caller should wait");
+        };
+
+        drop(leader);
+        assert_eq!(wait(waiter).await, None);
+        assert!(matches!(group.join("key"), Flight::Leader(_)));
+    }
+}
@@ -2,10 +2,86 @@
 //!
 //! This module provides hierarchical configuration support with environment
 //! variable overrides, following the LLM Dev Ops platform configuration patterns.
+//!
+//! Configuration is resolved in order: built-in defaults, then an optional
+//! TOML/YAML/JSON file, then environment variables. With
+//! [`Config::from_layered`], an environment-specific overlay file is merged
+//! between the base file and environment variables.
+
+use crate::integration::{ValidationError, ValidationResult, ValidationSource};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+/// Environment variable naming the configuration file to load.
+pub const CONFIG_PATH_ENV: &str = "POLICY_ENGINE_CONFIG";
+
+/// Configuration file loaded by [`Config::load`] when `POLICY_ENGINE_CONFIG` is unset.
+pub const DEFAULT_CONFIG_PATH: &str = "config/policy-engine.toml";
+
+/// A sensitive value that is redacted from `Debug`, `Display` and serialized output.
+///
+/// Deserializes transparently from the wrapped value, so secrets can still be
+/// read from configuration files. Use [`Secret::expose`] to access the value.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Secret<T = String>(T);
+
+impl<T> Secret<T> {
+    /// Wrap a sensitive value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Access the wrapped value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the sensitive value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl From<String> for Secret<String> {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret<String> {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("REDACTED")
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("REDACTED")
+    }
+}
+
+impl<T> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str("***")
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Secret)
+    }
+}
+
 /// Main configuration structure for the policy engine.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -77,8 +153,15 @@ pub struct CacheConfig {
     pub enabled: bool,
     /// Maximum number of entries in L1 (in-memory) cache
     pub l1_max_entries: usize,
+    /// Maximum approximate bytes of L1 cache entries, sized as serialized
+    /// JSON; unlimited if unset. Evicts least recently used entries along
+    /// with `l1_max_entries`, and decisions larger than this are not cached
+    pub l1_max_bytes: Option<usize>,
     /// L1 cache TTL in seconds
     pub l1_ttl_seconds: u64,
+    /// TTL in seconds of cached denials and errors, for policies that opt
+    /// in to negative caching; kept in L1 only
+    pub negative_ttl_seconds: u64,
     /// Whether L2 (Redis) cache is enabled
     pub l2_enabled: bool,
     /// Redis URL for L2 cache
@@ -87,6 +170,13 @@ pub struct CacheConfig {
     pub redis_prefix: String,
     /// L2 cache TTL in seconds
     pub l2_ttl_seconds: u64,
+    /// Context fields left out of cache keys, as dotted paths. Requests that
+    /// differ only in these fields share a cached decision.
+    pub excluded_fields: Vec<String>,
+    /// How numbers are normalized in cache keys and before input
+    /// validation. Under `unified`, `1` and `1.0` are the same number and
+    /// share a cached decision; under `exact`, they do not.
+    pub number_normalization: crate::cache::NumberNormalization,
 }
 
 impl Default for CacheConfig {
@@ -94,11 +184,18 @@ impl Default for CacheConfig {
         Self {
             enabled: true,
             l1_max_entries: 10000,
+            l1_max_bytes: None,
             l1_ttl_seconds: 300,
+            negative_ttl_seconds: 30,
             l2_enabled: false,
             redis_url: None,
             redis_prefix: "llm-policy:".to_string(),
             l2_ttl_seconds: 600,
+            excluded_fields: crate::cache::DEFAULT_EXCLUDED_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+            number_normalization: crate::cache::NumberNormalization::default(),
         }
     }
 }
@@ -113,6 +210,11 @@ impl CacheConfig {
     pub fn l2_ttl(&self) -> Duration {
         Duration::from_secs(self.l2_ttl_seconds)
     }
+
+    /// Get the negative caching TTL as Duration.
+    pub fn negative_ttl(&self) -> Duration {
+        Duration::from_secs(self.negative_ttl_seconds)
+    }
 }
 
 /// Telemetry and observability configuration.
@@ -152,6 +254,64 @@ impl Default for TelemetryConfig {
     }
 }
 
+impl TelemetryConfig {
+    /// Log levels accepted in `log_level` (case-insensitive).
+    pub const LOG_LEVELS: &'static [&'static str] = &["trace", "debug", "info", "warn", "error"];
+
+    /// Validate numeric ranges and names, returning the first problem.
+    pub fn validate(&self) -> crate::Result<()> {
+        match self.errors().into_iter().next() {
+            Some(error) => Err(crate::Error::config(error.message)),
+            None => Ok(()),
+        }
+    }
+
+    fn errors(&self) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+
+        if !(0.0..=1.0).contains(&self.trace_sampling_ratio) {
+            errors.push(ConfigError::new(
+                "telemetry.trace_sampling_ratio",
+                format!(
+                    "trace_sampling_ratio must be between 0.0 and 1.0, got {}",
+                    self.trace_sampling_ratio
+                ),
+                "use a ratio between 0.0 (no traces) and 1.0 (all traces)",
+            ));
+        }
+        if self.metrics_port == 0 {
+            errors.push(ConfigError::new(
+                "telemetry.metrics_port",
+                "metrics_port must be greater than 0",
+                "choose a free port for the Prometheus endpoint",
+            ));
+        }
+        if self.service_name.trim().is_empty() {
+            errors.push(ConfigError::new(
+                "telemetry.service_name",
+                "service_name must not be empty",
+                "set a service name to identify this engine in traces",
+            ));
+        }
+        if !Self::LOG_LEVELS
+            .iter()
+            .any(|level| level.eq_ignore_ascii_case(&self.log_level))
+        {
+            errors.push(ConfigError::new(
+                "telemetry.log_level",
+                format!(
+                    "log_level must be one of {}, got '{}'",
+                    Self::LOG_LEVELS.join(", "),
+                    self.log_level
+                ),
+                "set LOG_LEVEL to a recognized level",
+            ));
+        }
+
+        errors
+    }
+}
+
 /// External service integration configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -181,6 +341,20 @@ pub struct IntegrationsConfig {
     pub timeout_ms: u64,
     /// Whether to fail evaluation if integration fails
     pub fail_on_error: bool,
+    /// Integrations that must be healthy for the engine to be ready; every
+    /// configured integration if empty
+    pub readiness_integrations: Vec<String>,
+    /// Failure policies by integration (`shield`, `costops`, `governance`,
+    /// `incident_manager`, `observatory`), overriding `fail_on_error`
+    pub failure_policies: HashMap<String, FailurePolicy>,
+    /// Maximum idle connections kept per upstream host
+    pub pool_max_idle_per_host: usize,
+    /// Idle connection timeout in milliseconds
+    pub pool_idle_timeout_ms: u64,
+    /// Disable all integration network calls
+    pub offline: bool,
+    /// Retries allowed per call to an integration, averaged over time
+    pub retry_budget_ratio: f64,
 }
 
 impl Default for IntegrationsConfig {
@@ -198,6 +372,12 @@ impl Default for IntegrationsConfig {
             observatory_url: None,
             timeout_ms: 5000,
             fail_on_error: false,
+            readiness_integrations: Vec::new(),
+            failure_policies: HashMap::new(),
+            pool_max_idle_per_host: 32,
+            pool_idle_timeout_ms: 90000,
+            offline: false,
+            retry_budget_ratio: crate::integration::DEFAULT_RETRY_BUDGET_RATIO,
         }
     }
 }
@@ -207,6 +387,26 @@ impl IntegrationsConfig {
     pub fn timeout(&self) -> Duration {
         Duration::from_millis(self.timeout_ms)
     }
+
+    /// Get the idle connection timeout as Duration.
+    pub fn pool_idle_timeout(&self) -> Duration {
+        Duration::from_millis(self.pool_idle_timeout_ms)
+    }
+
+    /// Get the failure policy configured for an integration, if any.
+    pub fn failure_policy(&self, integration: &str) -> Option<FailurePolicy> {
+        self.failure_policies.get(integration).copied()
+    }
+}
+
+/// How requests are decided when an integration fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Proceed without the integration
+    FailOpen,
+    /// Deny the request
+    FailClosed,
 }
 
 /// Performance tuning configuration.
@@ -224,6 +424,9 @@ pub struct PerformanceConfig {
     /// WASM sandbox memory limit in MB
     pub wasm_memory_limit_mb: usize,
     /// CEL expression evaluation timeout in milliseconds
+    ///
+    /// Each CEL condition running longer fails its evaluation with a
+    /// timeout error.
     pub cel_timeout_ms: u64,
 }
 
@@ -259,7 +462,7 @@ pub struct SecurityConfig {
     /// Whether authentication is required
     pub auth_enabled: bool,
     /// JWT secret (should be provided via environment variable)
-    pub jwt_secret: Option<String>,
+    pub jwt_secret: Option<Secret<String>>,
     /// JWT algorithm
     pub jwt_algorithm: String,
     /// JWT expiration in seconds
@@ -290,107 +493,907 @@ impl Default for SecurityConfig {
 }
 
 impl Config {
+    /// Create a builder starting from the default configuration.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+
     /// Load configuration from environment variables.
     pub fn from_env() -> crate::Result<Self> {
         let mut config = Self::default();
+        config.apply_env_overrides();
+        config.telemetry.validate()?;
+        Ok(config)
+    }
+
+    /// Load configuration from a file.
+    ///
+    /// The format is detected from the extension: `.toml`, `.yaml`/`.yml` or `.json`.
+    pub fn from_file(path: &Path) -> crate::Result<Self> {
+        let value = Self::read_value(path)?;
+        let config = Self::from_value(value, path)?;
+        config.telemetry.validate()?;
+        Ok(config)
+    }
+
+    /// Load a base configuration file merged with an environment overlay.
+    ///
+    /// The overlay is the file next to `base` named `<stem>.<overlay_env>.<ext>`,
+    /// e.g. `config.prod.toml` for `config.toml`. Keys present in the overlay
+    /// override the base; nested sections merge field-by-field. A missing
+    /// overlay file is ignored.
+    ///
+    /// Precedence: base file < environment overlay < environment variables.
+    pub fn from_layered(base: &Path, overlay_env: &str) -> crate::Result<Self> {
+        let mut value = Self::read_value(base)?;
+
+        let overlay = Self::overlay_path(base, overlay_env);
+        if overlay.exists() {
+            merge_values(&mut value, Self::read_value(&overlay)?);
+        } else {
+            tracing::debug!(path = %overlay.display(), "No configuration overlay found");
+        }
+
+        let mut config = Self::from_value(value, base)?;
+        config.apply_env_overrides();
+        config.telemetry.validate()?;
+        Ok(config)
+    }
+
+    /// Get the overlay file path for an environment.
+    pub fn overlay_path(base: &Path, overlay_env: &str) -> PathBuf {
+        let stem = base.file_stem().and_then(|s| s.to_str()).unwrap_or("config");
+        let file_name = match base.extension().and_then(|e| e.to_str()) {
+            Some(extension) => format!("{}.{}.{}", stem, overlay_env, extension),
+            None => format!("{}.{}", stem, overlay_env),
+        };
+        base.with_file_name(file_name)
+    }
 
+    /// Read a configuration file into an untyped value.
+    ///
+    /// The format is detected from the extension: `.toml`, `.yaml`/`.yml` or `.json`.
+    fn read_value(path: &Path) -> crate::Result<serde_json::Value> {
+        if !path.exists() {
+            return Err(crate::Error::config(format!(
+                "Configuration file not found: {}",
+                path.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        match extension {
+            "toml" => toml::from_str(&content).map_err(|e| {
+                crate::Error::config(format!("Invalid TOML in {}: {}", path.display(), e))
+            }),
+            "yaml" | "yml" => serde_yaml::from_str(&content).map_err(|e| {
+                crate::Error::config(format!("Invalid YAML in {}: {}", path.display(), e))
+            }),
+            "json" => serde_json::from_str(&content).map_err(|e| {
+                crate::Error::config(format!("Invalid JSON in {}: {}", path.display(), e))
+            }),
+            _ => Err(crate::Error::config(format!(
+                "Unsupported configuration file extension: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Deserialize a configuration from an untyped value read from `path`.
+    fn from_value(value: serde_json::Value, path: &Path) -> crate::Result<Self> {
+        serde_json::from_value(value).map_err(|e| {
+            crate::Error::config(format!("Invalid configuration in {}: {}", path.display(), e))
+        })
+    }
+
+    /// Load configuration from a file and apply environment overrides.
+    ///
+    /// The file is taken from `POLICY_ENGINE_CONFIG` if set (and must exist),
+    /// otherwise from `config/policy-engine.toml` if present. Environment
+    /// variables always take precedence over file values.
+    pub fn load() -> crate::Result<Self> {
+        let mut config = match std::env::var(CONFIG_PATH_ENV) {
+            Ok(path) => Self::from_file(Path::new(&path))?,
+            Err(_) => {
+                let default_path = Path::new(DEFAULT_CONFIG_PATH);
+                if default_path.exists() {
+                    Self::from_file(default_path)?
+                } else {
+                    Self::default()
+                }
+            }
+        };
+
+        config.apply_env_overrides();
+        config.telemetry.validate()?;
+        Ok(config)
+    }
+
+    /// List every effective setting with the layer it came from.
+    ///
+    /// The configuration is resolved like [`load`](Self::load). Each entry
+    /// is a dotted path (e.g. `integrations.timeout_ms`), its value and its
+    /// source: a set environment variable, the configuration file or the
+    /// built-in default. Secrets and passwords in URLs are redacted, so the
+    /// result can be shared in support tickets.
+    pub fn effective_with_sources() -> crate::Result<Vec<(String, String, ConfigSource)>> {
+        let path = match std::env::var(CONFIG_PATH_ENV) {
+            Ok(path) => Some(PathBuf::from(path)),
+            Err(_) => Some(PathBuf::from(DEFAULT_CONFIG_PATH)).filter(|path| path.exists()),
+        };
+        Self::effective_from(path.as_deref())
+    }
+
+    /// List effective settings and their sources, reading `path` if given.
+    fn effective_from(path: Option<&Path>) -> crate::Result<Vec<(String, String, ConfigSource)>> {
+        let (file, mut config) = match path {
+            Some(path) => {
+                let file = Self::read_value(path)?;
+                let config = Self::from_value(file.clone(), path)?;
+                (Some(file), config)
+            }
+            None => (None, Self::default()),
+        };
+        config.apply_env_overrides();
+        config.telemetry.validate()?;
+
+        let from_env: Vec<&str> = ENV_OVERRIDES
+            .iter()
+            .filter(|(var, _)| std::env::var(var).is_ok())
+            .flat_map(|(_, paths)| paths.iter().copied())
+            .collect();
+        let mut settings = Vec::new();
+        let effective = serde_json::to_value(&config).unwrap_or_default();
+        flatten_settings(&effective, "", "", &mut settings);
+        Ok(settings
+            .into_iter()
+            .map(|(path, pointer, value)| {
+                let source = if from_env.contains(&path.as_str()) {
+                    ConfigSource::Env
+                } else if file
+                    .as_ref()
+                    .is_some_and(|file| file.pointer(&pointer).is_some())
+                {
+                    ConfigSource::File
+                } else {
+                    ConfigSource::Default
+                };
+                (path, value, source)
+            })
+            .collect())
+    }
+
+    /// Apply environment variable overrides on top of the current values.
+    pub fn apply_env_overrides(&mut self) {
         // Server config
         if let Ok(port) = std::env::var("PORT") {
-            config.server.port = port.parse().unwrap_or(3000);
+            self.server.port = port.parse().unwrap_or(3000);
         }
         if let Ok(grpc_port) = std::env::var("GRPC_PORT") {
-            config.server.grpc_port = grpc_port.parse().unwrap_or(50051);
+            self.server.grpc_port = grpc_port.parse().unwrap_or(50051);
         }
         if let Ok(host) = std::env::var("HOST") {
-            config.server.host = host;
+            self.server.host = host;
         }
 
         // Cache config
         if let Ok(enabled) = std::env::var("CACHE_ENABLED") {
-            config.cache.enabled = enabled.parse().unwrap_or(true);
+            self.cache.enabled = enabled.parse().unwrap_or(true);
         }
         if let Ok(redis_url) = std::env::var("REDIS_URL") {
-            config.cache.redis_url = Some(redis_url);
-            config.cache.l2_enabled = true;
+            self.cache.redis_url = Some(redis_url);
+            self.cache.l2_enabled = true;
         }
 
         // Telemetry config
         if let Ok(enabled) = std::env::var("TELEMETRY_ENABLED") {
-            config.telemetry.enabled = enabled.parse().unwrap_or(true);
+            self.telemetry.enabled = enabled.parse().unwrap_or(true);
         }
         if let Ok(endpoint) = std::env::var("OTLP_ENDPOINT") {
-            config.telemetry.otlp_endpoint = Some(endpoint);
+            self.telemetry.otlp_endpoint = Some(endpoint);
         }
         if let Ok(level) = std::env::var("LOG_LEVEL") {
-            config.telemetry.log_level = level;
+            self.telemetry.log_level = level;
         }
 
         // Integration URLs
         if let Ok(url) = std::env::var("LLM_SHIELD_URL") {
-            config.integrations.shield_url = Some(url);
+            self.integrations.shield_url = Some(url);
         }
         if let Ok(url) = std::env::var("LLM_COSTOPS_URL") {
-            config.integrations.costops_url = Some(url);
+            self.integrations.costops_url = Some(url);
         }
         if let Ok(url) = std::env::var("LLM_GOVERNANCE_URL") {
-            config.integrations.governance_url = Some(url);
+            self.integrations.governance_url = Some(url);
         }
         if let Ok(url) = std::env::var("LLM_EDGE_AGENT_URL") {
-            config.integrations.edge_agent_url = Some(url);
+            self.integrations.edge_agent_url = Some(url);
         }
         if let Ok(url) = std::env::var("INCIDENT_MANAGER_URL") {
-            config.integrations.incident_manager_url = Some(url);
+            self.integrations.incident_manager_url = Some(url);
         }
         if let Ok(url) = std::env::var("SENTINEL_URL") {
-            config.integrations.sentinel_url = Some(url);
+            self.integrations.sentinel_url = Some(url);
         }
 
         // Phase 2B: Upstream consumption adapter URLs
         if let Ok(url) = std::env::var("LLM_SCHEMA_REGISTRY_URL") {
-            config.integrations.schema_registry_url = Some(url);
+            self.integrations.schema_registry_url = Some(url);
         }
         if let Ok(url) = std::env::var("LLM_CONFIG_MANAGER_URL") {
-            config.integrations.config_manager_url = Some(url);
+            self.integrations.config_manager_url = Some(url);
         }
         if let Ok(url) = std::env::var("LLM_OBSERVATORY_URL") {
-            config.integrations.observatory_url = Some(url);
+            self.integrations.observatory_url = Some(url);
+        }
+        if let Ok(offline) = std::env::var("INTEGRATIONS_OFFLINE") {
+            self.integrations.offline = offline.parse().unwrap_or(false);
         }
 
         // Security config
         if let Ok(secret) = std::env::var("JWT_SECRET") {
-            config.security.jwt_secret = Some(secret);
+            self.security.jwt_secret = Some(Secret::new(secret));
         }
         if let Ok(enabled) = std::env::var("AUTH_ENABLED") {
-            config.security.auth_enabled = enabled.parse().unwrap_or(false);
+            self.security.auth_enabled = enabled.parse().unwrap_or(false);
         }
-
-        Ok(config)
     }
 
     /// Validate the configuration.
+    ///
+    /// Returns the first hard error found by [`validate_all`](Self::validate_all).
     pub fn validate(&self) -> crate::Result<()> {
-        // Validate security config
+        self.validate_all()
+            .map(|_| ())
+            .map_err(|errors| crate::Error::config(errors[0].message.clone()))
+    }
+
+    /// Check the whole configuration and report every problem at once.
+    ///
+    /// Returns the warnings if there are no hard errors; otherwise returns
+    /// all errors.
+    pub fn validate_all(&self) -> std::result::Result<Vec<ConfigWarning>, Vec<ConfigError>> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        // Security
         if self.security.auth_enabled && self.security.jwt_secret.is_none() {
-            return Err(crate::Error::config(
+            errors.push(ConfigError::new(
+                "security.jwt_secret",
                 "JWT_SECRET must be set when authentication is enabled",
+                "set JWT_SECRET or disable authentication",
             ));
         }
 
-        // Validate cache config
+        // Cache
         if self.cache.l2_enabled && self.cache.redis_url.is_none() {
-            return Err(crate::Error::config(
+            errors.push(ConfigError::new(
+                "cache.redis_url",
                 "REDIS_URL must be set when L2 cache is enabled",
+                "set REDIS_URL or disable the L2 cache",
+            ));
+        }
+        if self.cache.l1_max_entries == 0 {
+            warnings.push(ConfigWarning::new(
+                "cache.l1_max_entries",
+                "l1_max_entries is 0, so the L1 cache holds nothing",
+                "set a positive capacity or disable the cache",
+            ));
+        }
+        if self.cache.l1_max_bytes == Some(0) {
+            warnings.push(ConfigWarning::new(
+                "cache.l1_max_bytes",
+                "l1_max_bytes is 0, so the L1 cache holds nothing",
+                "set a positive byte limit, leave it unset, or disable the cache",
+            ));
+        }
+        if self.cache.negative_ttl_seconds > self.cache.l1_ttl_seconds {
+            warnings.push(ConfigWarning::new(
+                "cache.negative_ttl_seconds",
+                "negative_ttl_seconds exceeds l1_ttl_seconds, so denials outlive allows",
+                "set a negative TTL no longer than the L1 TTL",
+            ));
+        }
+
+        // Telemetry
+        errors.extend(self.telemetry.errors());
+
+        // Integrations
+        if self.integrations.timeout_ms == 0 {
+            warnings.push(ConfigWarning::new(
+                "integrations.timeout_ms",
+                "integration timeout is 0, so every integration call times out",
+                "set integrations.timeout_ms to a positive value",
             ));
         }
 
-        // Validate performance config
+        // Performance
         if self.performance.max_evaluation_time_ms == 0 {
-            return Err(crate::Error::config(
+            errors.push(ConfigError::new(
+                "performance.max_evaluation_time_ms",
                 "max_evaluation_time_ms must be greater than 0",
+                "set a positive evaluation time budget",
+            ));
+        }
+
+        if self.performance.cel_timeout_ms == 0 {
+            errors.push(ConfigError::new(
+                "performance.cel_timeout_ms",
+                "cel_timeout_ms must be greater than 0",
+                "set a positive CEL evaluation time budget",
             ));
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// JSON Schema (draft 2020-12) describing the configuration file format.
+    ///
+    /// Every setting is optional and defaults to the value in
+    /// [`Config::default`], which the schema also lists. Unknown keys are
+    /// rejected, so misspelled settings are reported instead of silently
+    /// ignored. Range checks that depend on other settings are left to
+    /// [`validate_all`](Self::validate_all).
+    pub fn json_schema() -> serde_json::Value {
+        let string = || serde_json::json!({ "type": "string" });
+        let optional_string = || serde_json::json!({ "type": ["string", "null"] });
+        let boolean = || serde_json::json!({ "type": "boolean" });
+        let strings = || serde_json::json!({ "type": "array", "items": { "type": "string" } });
+        let unsigned =
+            |max: u64| serde_json::json!({ "type": "integer", "minimum": 0, "maximum": max });
+        let port = || unsigned(u16::MAX.into());
+        let count = || unsigned(u64::MAX);
+
+        let server = schema_object(vec![
+            ("host", string()),
+            ("port", port()),
+            ("grpc_port", port()),
+            ("request_timeout_ms", count()),
+        ]);
+        let cache = schema_object(vec![
+            ("enabled", boolean()),
+            ("l1_max_entries", count()),
+            (
+                "l1_max_bytes",
+                serde_json::json!({ "type": ["integer", "null"], "minimum": 0 }),
+            ),
+            ("l1_ttl_seconds", count()),
+            ("negative_ttl_seconds", count()),
+            ("l2_enabled", boolean()),
+            ("redis_url", optional_string()),
+            ("redis_prefix", string()),
+            ("l2_ttl_seconds", count()),
+            ("excluded_fields", strings()),
+            (
+                "number_normalization",
+                serde_json::json!({ "type": "string", "enum": ["unified", "exact"] }),
+            ),
+        ]);
+        let telemetry = schema_object(vec![
+            ("enabled", boolean()),
+            ("service_name", serde_json::json!({ "type": "string", "minLength": 1 })),
+            ("otlp_endpoint", optional_string()),
+            (
+                "metrics_port",
+                serde_json::json!({ "type": "integer", "minimum": 1, "maximum": 65535 }),
+            ),
+            ("metrics_path", string()),
+            ("log_level", string()),
+            ("json_logs", boolean()),
+            (
+                "trace_sampling_ratio",
+                serde_json::json!({ "type": "number", "minimum": 0, "maximum": 1 }),
+            ),
+        ]);
+        let failure_policy = serde_json::json!({ "enum": ["fail_open", "fail_closed"] });
+        let integrations = schema_object(vec![
+            ("shield_url", optional_string()),
+            ("costops_url", optional_string()),
+            ("governance_url", optional_string()),
+            ("edge_agent_url", optional_string()),
+            ("incident_manager_url", optional_string()),
+            ("sentinel_url", optional_string()),
+            ("schema_registry_url", optional_string()),
+            ("config_manager_url", optional_string()),
+            ("observatory_url", optional_string()),
+            ("timeout_ms", count()),
+            ("fail_on_error", boolean()),
+            ("readiness_integrations", strings()),
+            (
+                "failure_policies",
+                serde_json::json!({ "type": "object", "additionalProperties": failure_policy }),
+            ),
+            ("pool_max_idle_per_host", count()),
+            ("pool_idle_timeout_ms", count()),
+            ("offline", boolean()),
+            (
+                "retry_budget_ratio",
+                serde_json::json!({ "type": "number", "minimum": 0, "maximum": 1 }),
+            ),
+        ]);
+        let performance = schema_object(vec![
+            ("max_policy_size_mb", count()),
+            ("max_evaluation_time_ms", serde_json::json!({ "type": "integer", "minimum": 1 })),
+            ("parallel_evaluation", boolean()),
+            ("max_concurrent_evaluations", count()),
+            ("wasm_memory_limit_mb", count()),
+            ("cel_timeout_ms", serde_json::json!({ "type": "integer", "minimum": 1 })),
+        ]);
+        let security = schema_object(vec![
+            ("auth_enabled", boolean()),
+            ("jwt_secret", optional_string()),
+            ("jwt_algorithm", string()),
+            ("jwt_expiration_seconds", count()),
+            ("api_key_header", string()),
+            ("rate_limit_enabled", boolean()),
+            ("rate_limit_rps", unsigned(u32::MAX.into())),
+            ("rate_limit_burst", unsigned(u32::MAX.into())),
+        ]);
+
+        let mut schema = schema_object(vec![
+            ("server", server),
+            ("cache", cache),
+            ("telemetry", telemetry),
+            ("integrations", integrations),
+            ("performance", performance),
+            ("security", security),
+        ]);
+        // Secrets serialize redacted, so their defaults would be misleading.
+        let mut defaults = serde_json::to_value(Self::default()).unwrap_or_default();
+        if let Some(security) = defaults.get_mut("security").and_then(|s| s.as_object_mut()) {
+            security.remove("jwt_secret");
+        }
+        add_schema_defaults(&mut schema, &defaults);
+
+        schema["$schema"] = "https://json-schema.org/draft/2020-12/schema".into();
+        schema["title"] = "Policy engine configuration".into();
+        schema
+    }
+
+    /// Check a parsed configuration document against [`json_schema`],
+    /// before deserializing it.
+    ///
+    /// Errors carry the JSON pointer of the offending value (e.g.
+    /// `/integrations/timeout_ms`) and the failed schema keyword as code.
+    ///
+    /// [`json_schema`]: Self::json_schema
+    pub fn validate_value(value: &serde_json::Value) -> ValidationResult {
+        let schema = Self::json_schema();
+        let compiled = jsonschema::JSONSchema::compile(&schema)
+            .expect("the configuration schema is a valid JSON Schema");
+
+        let errors: Vec<ValidationError> = match compiled.validate(value) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors
+                .map(|e| ValidationError {
+                    path: e.instance_path.to_string(),
+                    message: e.to_string(),
+                    code: e.schema_path.to_string().rsplit('/').next().map(str::to_string),
+                })
+                .collect(),
+        };
+
+        ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings: Vec::new(),
+            source: ValidationSource::Local,
+        }
+    }
+}
+
+/// Where an effective configuration value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    /// Built-in default
+    Default,
+    /// Configuration file
+    File,
+    /// Environment variable
+    Env,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Default => "default",
+            Self::File => "file",
+            Self::Env => "env",
+        })
+    }
+}
+
+/// Settings set by each environment variable read in
+/// [`Config::apply_env_overrides`].
+const ENV_OVERRIDES: &[(&str, &[&str])] = &[
+    ("PORT", &["server.port"]),
+    ("GRPC_PORT", &["server.grpc_port"]),
+    ("HOST", &["server.host"]),
+    ("CACHE_ENABLED", &["cache.enabled"]),
+    ("REDIS_URL", &["cache.redis_url", "cache.l2_enabled"]),
+    ("TELEMETRY_ENABLED", &["telemetry.enabled"]),
+    ("OTLP_ENDPOINT", &["telemetry.otlp_endpoint"]),
+    ("LOG_LEVEL", &["telemetry.log_level"]),
+    ("LLM_SHIELD_URL", &["integrations.shield_url"]),
+    ("LLM_COSTOPS_URL", &["integrations.costops_url"]),
+    ("LLM_GOVERNANCE_URL", &["integrations.governance_url"]),
+    ("LLM_EDGE_AGENT_URL", &["integrations.edge_agent_url"]),
+    (
+        "INCIDENT_MANAGER_URL",
+        &["integrations.incident_manager_url"],
+    ),
+    ("SENTINEL_URL", &["integrations.sentinel_url"]),
+    (
+        "LLM_SCHEMA_REGISTRY_URL",
+        &["integrations.schema_registry_url"],
+    ),
+    (
+        "LLM_CONFIG_MANAGER_URL",
+        &["integrations.config_manager_url"],
+    ),
+    ("LLM_OBSERVATORY_URL", &["integrations.observatory_url"]),
+    ("INTEGRATIONS_OFFLINE", &["integrations.offline"]),
+    ("JWT_SECRET", &["security.jwt_secret"]),
+    ("AUTH_ENABLED", &["security.auth_enabled"]),
+];
+
+/// Collect the leaf settings of a configuration value as dotted path, JSON
+/// pointer and displayed value.
+///
+/// Secrets already serialize redacted; passwords in URLs are redacted here.
+fn flatten_settings(
+    value: &serde_json::Value,
+    path: &str,
+    pointer: &str,
+    settings: &mut Vec<(String, String, String)>,
+) {
+    match value {
+        serde_json::Value::Object(fields) if !fields.is_empty() => {
+            for (key, field) in fields {
+                let child_path = match path {
+                    "" => key.clone(),
+                    path => format!("{}.{}", path, key),
+                };
+                let child_pointer =
+                    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                flatten_settings(field, &child_path, &child_pointer, settings);
+            }
+        }
+        serde_json::Value::String(text) => {
+            let text = match reqwest::Url::parse(text) {
+                Ok(mut url) if url.password().is_some() => {
+                    let _ = url.set_password(Some("***"));
+                    url.to_string()
+                }
+                _ => text.clone(),
+            };
+            settings.push((path.to_string(), pointer.to_string(), text));
+        }
+        value => settings.push((path.to_string(), pointer.to_string(), value.to_string())),
+    }
+}
+
+/// A configuration problem that prevents startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// Dotted path of the offending setting (e.g. `security.jwt_secret`)
+    pub path: String,
+    /// Description of the problem
+    pub message: String,
+    /// How to fix it
+    pub hint: String,
+}
+
+impl ConfigError {
+    fn new(path: &str, message: impl Into<String>, hint: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+            hint: hint.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ({})", self.path, self.message, self.hint)
+    }
+}
+
+/// A suspicious configuration value that does not prevent startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// Dotted path of the offending setting (e.g. `cache.l1_max_entries`)
+    pub path: String,
+    /// Description of the problem
+    pub message: String,
+    /// How to fix it
+    pub hint: String,
+}
+
+impl ConfigWarning {
+    fn new(path: &str, message: impl Into<String>, hint: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+            hint: hint.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ({})", self.path, self.message, self.hint)
+    }
+}
+
+/// JSON Schema of an object with the given properties and no others.
+fn schema_object(properties: Vec<(&str, serde_json::Value)>) -> serde_json::Value {
+    let properties: serde_json::Map<_, _> = properties
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+/// Record `defaults` as the `default` of each matching schema property.
+fn add_schema_defaults(schema: &mut serde_json::Value, defaults: &serde_json::Value) {
+    let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) else {
+        return;
+    };
+    for (name, property) in properties {
+        if let Some(default) = defaults.get(name) {
+            if property.get("properties").is_some() {
+                add_schema_defaults(property, default);
+            } else if let Some(property) = property.as_object_mut() {
+                property.insert("default".to_string(), default.clone());
+            }
+        }
+    }
+}
+
+/// Deep-merge `overlay` into `base`.
+///
+/// Objects merge key-by-key; any other overlay value replaces the base value.
+fn merge_values(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Builder for [`Config`] with fluent overrides.
+///
+/// Options that depend on each other are set together (e.g. enabling the L2
+/// cache requires a Redis URL), and [`build`](Self::build) validates the
+/// result.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Create a new builder with default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing configuration.
+    pub fn from_config(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Set the HTTP server host.
+    pub fn server_host(mut self, host: impl Into<String>) -> Self {
+        self.config.server.host = host.into();
+        self
+    }
+
+    /// Set the HTTP server port.
+    pub fn server_port(mut self, port: u16) -> Self {
+        self.config.server.port = port;
+        self
+    }
+
+    /// Set the gRPC server port.
+    pub fn grpc_port(mut self, port: u16) -> Self {
+        self.config.server.grpc_port = port;
+        self
+    }
+
+    /// Set the server request timeout.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.server.request_timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// Enable or disable caching.
+    pub fn cache_enabled(mut self, enabled: bool) -> Self {
+        self.config.cache.enabled = enabled;
+        self
+    }
+
+    /// Set the L1 cache size and TTL.
+    pub fn l1_cache(mut self, max_entries: usize, ttl: Duration) -> Self {
+        self.config.cache.l1_max_entries = max_entries;
+        self.config.cache.l1_ttl_seconds = ttl.as_secs();
+        self
+    }
+
+    /// Enable the L2 (Redis) cache.
+    pub fn enable_l2_cache(mut self, redis_url: impl Into<String>) -> Self {
+        self.config.cache.l2_enabled = true;
+        self.config.cache.redis_url = Some(redis_url.into());
+        self
+    }
+
+    /// Disable the L2 (Redis) cache.
+    pub fn disable_l2_cache(mut self) -> Self {
+        self.config.cache.l2_enabled = false;
+        self.config.cache.redis_url = None;
+        self
+    }
+
+    /// Enable or disable telemetry.
+    pub fn telemetry_enabled(mut self, enabled: bool) -> Self {
+        self.config.telemetry.enabled = enabled;
+        self
+    }
+
+    /// Set the service name reported in telemetry.
+    pub fn service_name(mut self, name: impl Into<String>) -> Self {
+        self.config.telemetry.service_name = name.into();
+        self
+    }
+
+    /// Set the OpenTelemetry collector endpoint.
+    pub fn otlp_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.config.telemetry.otlp_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Set the log level.
+    pub fn log_level(mut self, level: impl Into<String>) -> Self {
+        self.config.telemetry.log_level = level.into();
+        self
+    }
+
+    /// Set the Shield service URL.
+    pub fn shield_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.shield_url = Some(url.into());
+        self
+    }
+
+    /// Set the CostOps service URL.
+    pub fn costops_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.costops_url = Some(url.into());
+        self
+    }
+
+    /// Set the Governance service URL.
+    pub fn governance_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.governance_url = Some(url.into());
+        self
+    }
+
+    /// Set the Edge Agent service URL.
+    pub fn edge_agent_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.edge_agent_url = Some(url.into());
+        self
+    }
+
+    /// Set the Incident Manager service URL.
+    pub fn incident_manager_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.incident_manager_url = Some(url.into());
+        self
+    }
+
+    /// Set the Sentinel service URL.
+    pub fn sentinel_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.sentinel_url = Some(url.into());
+        self
+    }
+
+    /// Set the Schema Registry service URL.
+    pub fn schema_registry_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.schema_registry_url = Some(url.into());
+        self
+    }
+
+    /// Set the Config Manager service URL.
+    pub fn config_manager_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.config_manager_url = Some(url.into());
+        self
+    }
+
+    /// Set the Observatory service URL.
+    pub fn observatory_url(mut self, url: impl Into<String>) -> Self {
+        self.config.integrations.observatory_url = Some(url.into());
+        self
+    }
+
+    /// Set the integration request timeout.
+    pub fn integration_timeout(mut self, timeout: Duration) -> Self {
+        self.config.integrations.timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// Set whether evaluation fails when an integration fails.
+    pub fn fail_on_integration_error(mut self, fail: bool) -> Self {
+        self.config.integrations.fail_on_error = fail;
+        self
+    }
+
+    /// Set the failure policy of an integration.
+    pub fn failure_policy(mut self, integration: impl Into<String>, policy: FailurePolicy) -> Self {
+        self.config.integrations.failure_policies.insert(integration.into(), policy);
+        self
+    }
+
+    /// Disable all integration network calls.
+    pub fn integrations_offline(mut self, offline: bool) -> Self {
+        self.config.integrations.offline = offline;
+        self
+    }
+
+    /// Set the maximum evaluation time.
+    pub fn max_evaluation_time(mut self, timeout: Duration) -> Self {
+        self.config.performance.max_evaluation_time_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// Enable or disable parallel policy evaluation.
+    pub fn parallel_evaluation(mut self, enabled: bool) -> Self {
+        self.config.performance.parallel_evaluation = enabled;
+        self
+    }
+
+    /// Enable authentication with the given JWT secret.
+    pub fn auth(mut self, jwt_secret: impl Into<Secret<String>>) -> Self {
+        self.config.security.auth_enabled = true;
+        self.config.security.jwt_secret = Some(jwt_secret.into());
+        self
+    }
+
+    /// Disable authentication.
+    pub fn no_auth(mut self) -> Self {
+        self.config.security.auth_enabled = false;
+        self.config.security.jwt_secret = None;
+        self
+    }
+
+    /// Enable rate limiting.
+    pub fn rate_limit(mut self, rps: u32, burst: u32) -> Self {
+        self.config.security.rate_limit_enabled = true;
+        self.config.security.rate_limit_rps = rps;
+        self.config.security.rate_limit_burst = burst;
+        self
+    }
+
+    /// Build and validate the configuration.
+    pub fn build(self) -> crate::Result<Config> {
+        self.config.validate()?;
+        Ok(self.config)
     }
 }
 
@@ -416,10 +1419,385 @@ mod tests {
         config.security.jwt_secret = None;
         assert!(config.validate().is_err());
 
-        config.security.jwt_secret = Some("secret".to_string());
+        config.security.jwt_secret = Some("secret".into());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_all_collects_every_problem() {
+        let mut config = Config::default();
+        assert_eq!(config.validate_all(), Ok(Vec::new()));
+
+        config.cache.l1_max_entries = 0;
+        config.integrations.timeout_ms = 0;
+
+        let warnings = config.validate_all().unwrap();
+        let paths: Vec<_> = warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, vec!["cache.l1_max_entries", "integrations.timeout_ms"]);
+        assert!(config.validate().is_ok());
+
+        config.security.auth_enabled = true;
+        config.telemetry.trace_sampling_ratio = 1.5;
+        config.performance.max_evaluation_time_ms = 0;
+
+        let errors = config.validate_all().unwrap_err();
+        let paths: Vec<_> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "security.jwt_secret",
+                "telemetry.trace_sampling_ratio",
+                "performance.max_evaluation_time_ms"
+            ]
+        );
+        assert!(errors.iter().all(|e| !e.hint.is_empty()));
+        assert!(errors[0].to_string().starts_with("security.jwt_secret: JWT_SECRET"));
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("JWT_SECRET must be set"));
+    }
+
+    #[test]
+    fn test_telemetry_range_validation() {
+        fn assert_rejected(mutate: impl FnOnce(&mut TelemetryConfig), expected: &str) {
+            let mut config = Config::default();
+            mutate(&mut config.telemetry);
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+
+        let ratio = "trace_sampling_ratio must be between";
+        assert_rejected(|t| t.trace_sampling_ratio = 5.0, ratio);
+        assert_rejected(|t| t.trace_sampling_ratio = -1.0, ratio);
+        assert_rejected(|t| t.trace_sampling_ratio = f64::NAN, ratio);
+        assert_rejected(|t| t.metrics_port = 0, "metrics_port must be greater than 0");
+        assert_rejected(|t| t.service_name = "  ".to_string(), "service_name must not be empty");
+        assert_rejected(|t| t.log_level = "verbose".to_string(), "log_level must be one of");
+
+        let mut config = Config::default();
+        config.telemetry.log_level = "DEBUG".to_string();
+        config.telemetry.trace_sampling_ratio = 0.0;
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_from_file_rejects_invalid_telemetry() {
+        let path = write_temp_config(
+            "invalid-telemetry.toml",
+            r#"
+[telemetry]
+trace_sampling_ratio = 5.0
+"#,
+        );
+
+        let err = Config::from_file(&path).unwrap_err();
+        assert!(err.to_string().contains("trace_sampling_ratio"));
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_json_schema_describes_defaults() {
+        let schema = Config::json_schema();
+        let integrations = &schema["properties"]["integrations"]["properties"];
+        assert_eq!(integrations["timeout_ms"]["default"], 5000);
+        let failure_policy = &integrations["failure_policies"]["additionalProperties"];
+        assert_eq!(failure_policy["enum"][1], "fail_closed");
+        let performance = &schema["properties"]["performance"]["properties"];
+        assert_eq!(performance["parallel_evaluation"]["default"], true);
+
+        // Every default setting is described by the schema.
+        let defaults = serde_json::to_value(Config::default()).unwrap();
+        let result = Config::validate_value(&defaults);
+        assert!(result.valid, "{:?}", result.errors);
+        assert!(Config::validate_value(&serde_json::json!({})).valid);
+    }
+
+    #[test]
+    fn test_validate_value_reports_paths() {
+        let document = serde_json::json!({
+            "server": { "prot": 8080 },
+            "integrations": {
+                "timeout_ms": "fast",
+                "failure_policies": { "shield": "retry" },
+            },
+            "performance": { "max_evaluation_time_ms": 0 },
+            "telemetry": { "trace_sampling_ratio": 1.5 },
+        });
+
+        let result = Config::validate_value(&document);
+        assert!(!result.valid);
+        let mut paths: Vec<&str> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "/integrations/failure_policies/shield",
+                "/integrations/timeout_ms",
+                "/performance/max_evaluation_time_ms",
+                "/server",
+                "/telemetry/trace_sampling_ratio",
+            ]
+        );
+        let unknown = result.errors.iter().find(|e| e.path == "/server").unwrap();
+        assert_eq!(unknown.code.as_deref(), Some("additionalProperties"));
+        assert!(unknown.message.contains("prot"));
+    }
+
+    #[test]
+    fn test_builder() {
+        let config = Config::builder()
+            .server_port(8080)
+            .enable_l2_cache("redis://localhost:6379")
+            .shield_url("http://shield:8080")
+            .integration_timeout(Duration::from_secs(2))
+            .auth("jwt-secret")
+            .build()
+            .unwrap();
+
+        assert_eq!(config.server.port, 8080);
+        assert!(config.cache.l2_enabled);
+        assert_eq!(config.cache.redis_url.as_deref(), Some("redis://localhost:6379"));
+        assert_eq!(config.integrations.shield_url.as_deref(), Some("http://shield:8080"));
+        assert_eq!(config.integrations.timeout_ms, 2000);
+        assert!(config.security.auth_enabled);
+        assert_eq!(config.security.jwt_secret.unwrap().expose(), "jwt-secret");
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        let result = Config::builder()
+            .max_evaluation_time(Duration::ZERO)
+            .build();
+        assert!(result.is_err());
+
+        let mut config = Config::default();
+        config.cache.l2_enabled = true;
+        let result = ConfigBuilder::from_config(config).build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_secret_redaction() {
+        let mut config = Config::default();
+        config.security.jwt_secret = Some(Secret::new("hunter2-jwt-secret".to_string()));
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("hunter2-jwt-secret"));
+        assert!(debug.contains("REDACTED"));
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("hunter2-jwt-secret"));
+        assert!(json.contains("\"jwt_secret\":\"***\""));
+
+        let secret = config.security.jwt_secret.unwrap();
+        assert_eq!(secret.to_string(), "REDACTED");
+        assert_eq!(secret.expose(), "hunter2-jwt-secret");
+    }
+
+    #[test]
+    fn test_secret_deserializes_from_plain_string() {
+        let security: SecurityConfig =
+            serde_json::from_str(r#"{"jwt_secret": "from-file"}"#).unwrap();
+        assert_eq!(security.jwt_secret.unwrap().expose(), "from-file");
+    }
+
+    fn write_temp_config(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "policy-engine-{}-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4(),
+            name
+        ));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_from_file_toml() {
+        let path = write_temp_config(
+            "config.toml",
+            r#"
+[server]
+port = 8080
+
+[cache]
+l1_max_entries = 42
+"#,
+        );
+
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.cache.l1_max_entries, 42);
+        // Unspecified values fall back to defaults
+        assert_eq!(config.server.grpc_port, 50051);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_from_file_yaml() {
+        let path = write_temp_config("config.yaml", "telemetry:\n  log_level: debug\n");
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.telemetry.log_level, "debug");
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_from_file_errors() {
+        let missing = Config::from_file(Path::new("/nonexistent/policy-engine.toml"));
+        assert!(matches!(missing, Err(crate::Error::Config { .. })));
+
+        let path = write_temp_config("broken.toml", "[server\nport = ");
+        let malformed = Config::from_file(&path);
+        assert!(matches!(malformed, Err(crate::Error::Config { .. })));
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_load_env_overrides_file() {
+        let path = write_temp_config(
+            "overlay.toml",
+            r#"
+[integrations]
+sentinel_url = "http://from-file:8080"
+timeout_ms = 1234
+"#,
+        );
+
+        std::env::set_var(CONFIG_PATH_ENV, &path);
+        std::env::set_var("SENTINEL_URL", "http://from-env:9090");
+        let config = Config::load();
+        std::env::remove_var(CONFIG_PATH_ENV);
+        std::env::remove_var("SENTINEL_URL");
+
+        let config = config.unwrap();
+        assert_eq!(
+            config.integrations.sentinel_url.as_deref(),
+            Some("http://from-env:9090")
+        );
+        assert_eq!(config.integrations.timeout_ms, 1234);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_effective_values_report_sources() {
+        let path = write_temp_config(
+            "sources.toml",
+            r#"
+[cache]
+redis_url = "redis://:hunter2@cache:6379"
+
+[integrations]
+costops_url = "http://costops-file:8080"
+timeout_ms = 1234
+
+[security]
+jwt_secret = "hunter2"
+"#,
+        );
+
+        std::env::set_var("LLM_COSTOPS_URL", "http://costops-env:9090");
+        let settings = Config::effective_from(Some(&path));
+        std::env::remove_var("LLM_COSTOPS_URL");
+        std::fs::remove_file(path).ok();
+
+        let settings = settings.unwrap();
+        let setting = |name: &str| {
+            settings
+                .iter()
+                .find(|(path, _, _)| path == name)
+                .map(|(_, value, source)| (value.as_str(), *source))
+                .unwrap()
+        };
+        assert_eq!(
+            setting("integrations.costops_url"),
+            ("http://costops-env:9090", ConfigSource::Env)
+        );
+        assert_eq!(
+            setting("integrations.timeout_ms"),
+            ("1234", ConfigSource::File)
+        );
+        assert_eq!(setting("server.port"), ("3000", ConfigSource::Default));
+        assert_eq!(setting("security.jwt_secret"), ("***", ConfigSource::File));
+        let (redis_url, source) = setting("cache.redis_url");
+        assert_eq!(source, ConfigSource::File);
+        assert!(redis_url.starts_with("redis://") && !redis_url.contains("hunter2"));
+        assert!(settings
+            .iter()
+            .all(|(_, value, _)| !value.contains("hunter2")));
+    }
+
+    #[test]
+    fn test_from_layered_merges_overlay() {
+        let base = write_temp_config(
+            "layered.toml",
+            r#"
+[server]
+port = 8080
+
+[cache]
+l1_max_entries = 500
+redis_prefix = "base:"
+
+[integrations]
+shield_url = "http://shield-base:8080"
+timeout_ms = 1000
+"#,
+        );
+        let overlay = Config::overlay_path(&base, "prod");
+        std::fs::write(
+            &overlay,
+            r#"
+[cache]
+l2_enabled = true
+redis_url = "redis://prod:6379"
+
+[integrations]
+timeout_ms = 250
+observatory_url = "http://observatory-overlay:8080"
+"#,
+        )
+        .unwrap();
+
+        std::env::set_var("LLM_OBSERVATORY_URL", "http://observatory-env:9090");
+        let config = Config::from_layered(&base, "prod");
+        std::env::remove_var("LLM_OBSERVATORY_URL");
+        let config = config.unwrap();
+
+        // Base values not present in the overlay are kept
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.cache.l1_max_entries, 500);
+        assert_eq!(config.cache.redis_prefix, "base:");
+        assert_eq!(
+            config.integrations.shield_url.as_deref(),
+            Some("http://shield-base:8080")
+        );
+        // Overlay values override the base
+        assert!(config.cache.l2_enabled);
+        assert_eq!(config.cache.redis_url.as_deref(), Some("redis://prod:6379"));
+        assert_eq!(config.integrations.timeout_ms, 250);
+        // Environment variables override the overlay
+        assert_eq!(
+            config.integrations.observatory_url.as_deref(),
+            Some("http://observatory-env:9090")
+        );
+
+        std::fs::remove_file(base).ok();
+        std::fs::remove_file(overlay).ok();
+    }
+
+    #[test]
+    fn test_from_layered_without_overlay() {
+        let base = write_temp_config("layered.yaml", "server:\n  port: 9000\n");
+        assert!(!Config::overlay_path(&base, "staging").exists());
+
+        let config = Config::from_layered(&base, "staging").unwrap();
+        assert_eq!(config.server.port, 9000);
+        std::fs::remove_file(base).ok();
+    }
+
     #[test]
     fn test_duration_helpers() {
         let config = CacheConfig::default();
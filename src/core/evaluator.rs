@@ -1,17 +1,53 @@
 //! Policy evaluator implementation.
 
-use crate::api::{EvaluationContext, PolicyDecision};
+use super::{CelFunctionRegistry, ExpressionCache};
+use crate::api::{
+    DecisionExplanation, EngineConfig, EvaluationContext, PolicyDecision, PolicyExplanation,
+    RuleExplanation,
+};
+use crate::error::ErrorContext;
+use crate::integration::DecisionOutcome;
 use crate::policy::{
-    Condition, ConditionOperator, ConditionValue, DecisionType, Policy, PolicyRule,
+    combine, Condition, ConditionOperator, ConditionValue, DecisionCombiner, DecisionReason,
+    DecisionType, Policy, PolicyRule, ReasonCode,
 };
-use crate::Result;
+use crate::telemetry::{PolicyMetrics, Stage};
+use crate::{Error, Result};
 
-use std::time::Instant;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
 /// The policy evaluator that processes policies against contexts.
+#[derive(Debug, Clone)]
 pub struct Evaluator {
     /// Whether to include trace information in decisions
     enable_tracing: bool,
+    /// Compiled CEL condition expressions, shared by clones
+    expressions: Arc<ExpressionCache>,
+    /// Per-policy metrics recorded for each policy evaluated
+    metrics: Option<Arc<PolicyMetrics>>,
+    /// When evaluation must stop, and the time limit that set it
+    deadline: Option<(Instant, Duration)>,
+}
+
+/// A rule whose condition failed to compile.
+#[derive(Debug)]
+pub struct CompileError {
+    /// ID of the policy containing the rule
+    pub policy_id: String,
+    /// ID of the rule
+    pub rule_id: String,
+    /// Why the condition failed to compile
+    pub error: Error,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}: {}", self.policy_id, self.rule_id, self.error)
+    }
 }
 
 impl Evaluator {
@@ -19,6 +55,9 @@ impl Evaluator {
     pub fn new() -> Self {
         Self {
             enable_tracing: false,
+            expressions: Arc::new(ExpressionCache::new()),
+            metrics: None,
+            deadline: None,
         }
     }
 
@@ -28,139 +67,383 @@ impl Evaluator {
         self
     }
 
-    /// Evaluate policies against the given context.
+    /// Make custom functions callable from CEL conditions.
+    ///
+    /// Expressions compiled so far are dropped.
+    pub fn with_cel_functions(mut self, functions: CelFunctionRegistry) -> Self {
+        let mut expressions = ExpressionCache::with_functions(functions);
+        if let Some(timeout) = self.expressions.timeout() {
+            expressions = expressions.with_timeout(timeout);
+        }
+        self.expressions = Arc::new(expressions);
+        self
+    }
+
+    /// Stop each CEL condition that runs longer than `timeout`, failing its
+    /// evaluation with a timeout error.
+    ///
+    /// Expressions compiled so far are dropped.
+    pub fn with_cel_timeout(mut self, timeout: Duration) -> Self {
+        let functions = self.expressions.functions().clone();
+        let expressions = ExpressionCache::with_functions(functions).with_timeout(timeout);
+        self.expressions = Arc::new(expressions);
+        self
+    }
+
+    /// Get the custom functions callable from CEL conditions.
+    pub fn cel_functions(&self) -> &CelFunctionRegistry {
+        self.expressions.functions()
+    }
+
+    /// Get the longest a CEL condition may run, if bounded.
+    pub fn cel_timeout(&self) -> Option<Duration> {
+        self.expressions.timeout()
+    }
+
+    /// Record each policy evaluated in per-policy metrics.
+    ///
+    /// Explanations are not recorded.
+    pub fn with_metrics(mut self, metrics: Arc<PolicyMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Stop recording per-policy metrics, e.g. for evaluations that are not
+    /// enforced.
+    pub fn without_metrics(mut self) -> Self {
+        self.metrics = None;
+        self
+    }
+
+    /// Stop evaluating once `limit` has passed since `start`, failing with a
+    /// timeout error.
+    ///
+    /// The deadline is checked before each policy and each rule, so a
+    /// single condition can still run past it; CEL conditions are bounded
+    /// by their own timeout instead.
+    pub fn with_time_limit(mut self, start: Instant, limit: Duration) -> Self {
+        self.deadline = Some((start + limit, limit));
+        self
+    }
+
+    /// Fail if evaluation is past its deadline.
+    fn check_deadline(&self) -> Result<()> {
+        match self.deadline {
+            Some((deadline, limit)) if Instant::now() > deadline => Err(Error::timeout(
+                "Policy evaluation exceeded the maximum evaluation time",
+                limit.as_millis() as u64,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Compile the conditions of every rule, returning the rules that fail.
+    ///
+    /// CEL expressions are parsed and type-checked, and kept compiled for
+    /// evaluation; regex patterns are compiled.
+    pub fn compile_policies(&self, policies: &[Policy]) -> Vec<CompileError> {
+        let mut errors = Vec::new();
+        for policy in policies {
+            for rule in &policy.rules {
+                let result = rule
+                    .compile()
+                    .and_then(|()| self.compile_expressions(&rule.condition));
+                if let Err(error) = result {
+                    errors.push(CompileError {
+                        policy_id: policy.id.clone(),
+                        rule_id: rule.id.clone(),
+                        error,
+                    });
+                }
+            }
+        }
+        errors
+    }
+
+    fn compile_expressions(&self, condition: &Condition) -> Result<()> {
+        if let (ConditionOperator::Expression, Some(ConditionValue::String(source))) =
+            (condition.operator, &condition.value)
+        {
+            self.expressions.check(source)?;
+        }
+        condition
+            .conditions
+            .iter()
+            .try_for_each(|nested| self.compile_expressions(nested))
+    }
+
+    /// Collect the custom CEL functions a condition calls into `functions`,
+    /// sorted and without duplicates.
+    fn custom_functions(&self, condition: &Condition, functions: &mut Vec<String>) -> Result<()> {
+        if let (ConditionOperator::Expression, Some(ConditionValue::String(source))) =
+            (condition.operator, &condition.value)
+        {
+            functions.extend(self.expressions.custom_functions(source)?);
+        }
+        for nested in &condition.conditions {
+            self.custom_functions(nested, functions)?;
+        }
+        functions.sort();
+        functions.dedup();
+        Ok(())
+    }
+
+    /// Drop every compiled CEL expression.
+    pub fn clear_expressions(&self) {
+        self.expressions.clear();
+    }
+
+    /// Drop compiled CEL expressions that no rule of `policies` uses, e.g.
+    /// once the policy set is replaced.
+    pub fn retain_expressions<'a>(&self, policies: impl IntoIterator<Item = &'a Policy>) {
+        let mut sources = HashSet::new();
+        for policy in policies {
+            for rule in &policy.rules {
+                expression_sources(&rule.condition, &mut sources);
+            }
+        }
+        self.expressions.retain(|source| sources.contains(source));
+    }
+
+    /// Get the number of compiled CEL expressions.
+    pub fn expression_count(&self) -> usize {
+        self.expressions.len()
+    }
+
+    /// Evaluate policies against the given context with default settings.
     ///
     /// Policies are evaluated in priority order (highest first).
     /// Rules within each policy are also evaluated in priority order.
     /// The first deny decision takes precedence.
     pub fn evaluate(&self, policies: &[Policy], context: &EvaluationContext) -> Result<PolicyDecision> {
-        let start = Instant::now();
-        let mut result = PolicyDecision::allow();
-        let mut matched_policies = Vec::new();
-        let mut matched_rules = Vec::new();
+        self.evaluate_with_config(policies, context, &EngineConfig::default())
+    }
 
-        for policy in policies {
-            if !policy.enabled {
-                continue;
-            }
+    /// Evaluate policies using the decision combiner and rule priority
+    /// overrides of an engine config snapshot.
+    ///
+    /// Policy decisions are combined with the config's combiner, in policy
+    /// order; evaluation stops once a decision settles the outcome.
+    pub fn evaluate_with_config(
+        &self,
+        policies: &[Policy],
+        context: &EvaluationContext,
+        config: &EngineConfig,
+    ) -> Result<PolicyDecision> {
+        self.evaluate_recorded(policies, context, config, None)
+    }
 
-            let policy_result = self.evaluate_policy(policy, context)?;
+    /// Evaluate policies like
+    /// [`evaluate_with_config`](Self::evaluate_with_config), recording every
+    /// policy and rule evaluated and how each contributed to the decision.
+    pub fn explain(
+        &self,
+        policies: &[Policy],
+        context: &EvaluationContext,
+        config: &EngineConfig,
+    ) -> Result<DecisionExplanation> {
+        let mut explained = Vec::new();
+        let decision = self.evaluate_recorded(policies, context, config, Some(&mut explained))?;
+
+        for policy in &mut explained {
+            policy.contributed = policy.applicable
+                && (decision.decision != DecisionType::Deny
+                    || decision.matched_policies.contains(&policy.policy_id));
+        }
+        Ok(DecisionExplanation {
+            outcome: decision.decision.into(),
+            decision,
+            combiner: config.decision_combiner,
+            policies: explained,
+            flagged_off: BTreeMap::new(),
+        })
+    }
+
+    /// Evaluate policies in order, recording each in `explained` if given.
+    fn evaluate_recorded(
+        &self,
+        policies: &[Policy],
+        context: &EvaluationContext,
+        config: &EngineConfig,
+        mut explained: Option<&mut Vec<PolicyExplanation>>,
+    ) -> Result<PolicyDecision> {
+        let start = Instant::now();
+        let mut results = Vec::new();
 
-            if policy_result.decision == DecisionType::Deny {
-                // Deny takes precedence
-                result = policy_result;
-                result.matched_policies = vec![policy.id.clone()];
+        for policy in policies.iter().filter(|p| p.enabled) {
+            self.check_deadline()?;
+            let policy_start = Instant::now();
+            let mut rules = explained.is_some().then(Vec::new);
+            let result = self.evaluate_policy_recorded(policy, context, config, rules.as_mut());
+            if explained.is_none() {
+                self.record_metrics(&policy.id, &result, policy_start.elapsed());
+            }
+            let result = result?;
+            let settled = settles_policies(config.decision_combiner, &result);
+            if let Some(explained) = explained.as_deref_mut() {
+                explained.push(PolicyExplanation {
+                    policy_id: policy.id.clone(),
+                    combiner: policy.combiner.unwrap_or(config.decision_combiner),
+                    rules: rules.unwrap_or_default(),
+                    decision: result.decision,
+                    applicable: is_applicable(&result),
+                    settled,
+                    contributed: false,
+                    duration_us: policy_start.elapsed().as_micros() as u64,
+                });
+            }
+            results.push((policy.id.clone(), result));
+            if settled {
                 break;
             }
+        }
 
-            if policy_result.decision == DecisionType::Warn {
-                // Collect warnings
-                if result.decision == DecisionType::Allow {
-                    result = policy_result;
-                }
-                matched_policies.push(policy.id.clone());
-            }
+        let mut result = combine_policy_decisions(results, config.decision_combiner);
+        result.evaluation_time_ms = start.elapsed().as_secs_f64() * 1000.0;
+        Ok(result)
+    }
 
-            if policy_result.decision == DecisionType::Modify {
-                // Merge modifications
-                result.decision = DecisionType::Modify;
-                for (key, value) in policy_result.modifications {
-                    result.modifications.insert(key, value);
-                }
-                matched_policies.push(policy.id.clone());
-            }
+    /// Evaluate policies concurrently on the blocking thread pool.
+    ///
+    /// Each policy holds a permit from `permits` while it is evaluated, so
+    /// the semaphore bounds in-flight work across all callers sharing it.
+    /// Decisions are combined in policy order, not completion order, so the
+    /// result is the same as [`evaluate_with_config`](Self::evaluate_with_config).
+    pub async fn evaluate_parallel(
+        &self,
+        policies: Vec<Policy>,
+        context: &EvaluationContext,
+        config: Arc<EngineConfig>,
+        permits: &Arc<Semaphore>,
+    ) -> Result<PolicyDecision> {
+        let start = Instant::now();
+        let context = Arc::new(context.clone());
 
-            matched_rules.extend(policy_result.matched_rules);
-        }
+        let tasks = policies.into_iter().filter(|p| p.enabled).map(|policy| {
+            let evaluator = self.clone();
+            let context = context.clone();
+            let config = config.clone();
+            let permits = permits.clone();
+            async move {
+                let permit = permits
+                    .acquire_owned()
+                    .await
+                    .map_err(|_| Error::evaluation("Evaluation semaphore closed"))?;
+                let id = policy.id.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let _permit = permit;
+                    evaluator.check_deadline()?;
+                    let start = Instant::now();
+                    let result = evaluator.evaluate_policy(&policy, &context, &config);
+                    evaluator.record_metrics(&policy.id, &result, start.elapsed());
+                    result
+                })
+                .await
+                .map_err(|e| Error::evaluation(format!("Policy evaluation task failed: {}", e)))?;
+                result.map(|decision| (id, decision))
+            }
+        });
 
-        if !matched_policies.is_empty() {
-            result.matched_policies = matched_policies;
-        }
-        if !matched_rules.is_empty() {
-            result.matched_rules = matched_rules;
+        // Replay results in policy order, as the sequential path would see them.
+        let mut results = Vec::new();
+        for result in futures::future::join_all(tasks).await {
+            let (id, decision) = result?;
+            let settled = settles_policies(config.decision_combiner, &decision);
+            results.push((id, decision));
+            if settled {
+                break;
+            }
         }
 
+        let mut result = combine_policy_decisions(results, config.decision_combiner);
         result.evaluation_time_ms = start.elapsed().as_secs_f64() * 1000.0;
         Ok(result)
     }
 
+    /// Record a policy evaluation in the per-policy metrics, if any.
+    fn record_metrics(&self, policy_id: &str, result: &Result<PolicyDecision>, elapsed: Duration) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        let outcome = match result {
+            Ok(decision) => decision.decision.into(),
+            Err(_) => DecisionOutcome::Error,
+        };
+        metrics.record_evaluation(policy_id, outcome, elapsed);
+    }
+
     /// Evaluate a single policy.
-    fn evaluate_policy(&self, policy: &Policy, context: &EvaluationContext) -> Result<PolicyDecision> {
-        let mut result = PolicyDecision::allow();
+    ///
+    /// Rules are evaluated by effective priority, highest first, with ties
+    /// broken by rule ID. Matching rules' decisions are collected until the
+    /// outcome is settled, then combined with the policy's combiner, or the
+    /// config's default combiner if the policy does not set one.
+    fn evaluate_policy(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+        config: &EngineConfig,
+    ) -> Result<PolicyDecision> {
+        self.evaluate_policy_recorded(policy, context, config, None)
+    }
+
+    /// Evaluate a single policy, recording each rule in `explained` if given.
+    fn evaluate_policy_recorded(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+        config: &EngineConfig,
+        mut explained: Option<&mut Vec<RuleExplanation>>,
+    ) -> Result<PolicyDecision> {
+        let combiner = policy.combiner.unwrap_or(config.decision_combiner);
+        let mut decisions = Vec::new();
         let mut matched_rules = Vec::new();
 
-        // Get enabled rules sorted by priority
         let mut rules: Vec<_> = policy.enabled_rules().collect();
-        rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+        rules.sort_by(|a, b| {
+            config
+                .rule_priority(b)
+                .cmp(&config.rule_priority(a))
+                .then_with(|| a.id.cmp(&b.id))
+        });
 
         for rule in rules {
-            let rule_matched = self.evaluate_condition(&rule.condition, context)?;
-
-            if rule_matched {
-                matched_rules.push(rule.id.clone());
-
-                // Apply the rule's action
-                let decision = match rule.action.decision {
-                    DecisionType::Allow => PolicyDecision::allow(),
-                    DecisionType::Deny => {
-                        let mut d = PolicyDecision::deny(
-                            rule.action.reason.clone().unwrap_or_else(|| {
-                                format!("Denied by rule: {}", rule.name)
-                            }),
-                        );
-                        d.metadata = rule
-                            .action
-                            .metadata
-                            .iter()
-                            .map(|(k, v)| (k.clone(), v.clone()))
-                            .collect();
-                        d
-                    }
-                    DecisionType::Warn => {
-                        let mut d = PolicyDecision::warn(
-                            rule.action.reason.clone().unwrap_or_else(|| {
-                                format!("Warning from rule: {}", rule.name)
-                            }),
-                        );
-                        d.metadata = rule
-                            .action
-                            .metadata
-                            .iter()
-                            .map(|(k, v)| (k.clone(), v.clone()))
-                            .collect();
-                        d
-                    }
-                    DecisionType::Modify => {
-                        let mut modifications = std::collections::HashMap::new();
-                        for modification in &rule.action.modifications {
-                            if let Some(value) = &modification.value {
-                                modifications.insert(modification.field.clone(), value.clone());
-                            }
-                        }
-                        PolicyDecision::modify(modifications)
-                    }
-                };
-
-                // Deny takes immediate precedence
-                if decision.decision == DecisionType::Deny {
-                    result = decision;
-                    result.matched_rules = matched_rules;
-                    return Ok(result);
-                }
+            self.check_deadline()?;
+            let start = Instant::now();
+            let matched = self
+                .evaluate_condition(&rule.condition, context)
+                .with_policy(&policy.id)?;
+            let settled = matched && settles(combiner, rule.action.decision);
+            if let Some(explained) = explained.as_deref_mut() {
+                let duration_us = start.elapsed().as_micros() as u64;
+                let mut functions = Vec::new();
+                self.custom_functions(&rule.condition, &mut functions)?;
+                explained.push(RuleExplanation {
+                    rule_id: rule.id.clone(),
+                    matched,
+                    decision: matched.then_some(rule.action.decision),
+                    settled,
+                    functions,
+                    duration_us,
+                });
+            }
+            if !matched {
+                continue;
+            }
+            matched_rules.push(rule.id.clone());
+            decisions.push(rule_decision(rule));
 
-                // Update result for non-deny decisions
-                if result.decision == DecisionType::Allow {
-                    result = decision;
-                } else if result.decision == DecisionType::Modify && decision.decision == DecisionType::Modify {
-                    // Merge modifications
-                    for (key, value) in decision.modifications {
-                        result.modifications.insert(key, value);
-                    }
-                }
+            // Later rules cannot change the outcome
+            if settled {
+                break;
             }
         }
 
+        let mut result = combine_decisions(decisions, combiner);
         result.matched_rules = matched_rules;
+        if let Some(reason) = &mut result.decision_reason {
+            reason.details.insert("policy_id".to_string(), policy.id.clone().into());
+        }
         Ok(result)
     }
 
@@ -189,6 +472,10 @@ impl Evaluator {
                 }
                 Ok(!self.evaluate_condition(&condition.conditions[0], context)?)
             }
+            ConditionOperator::Expression => match &condition.value {
+                Some(ConditionValue::String(source)) => self.expressions.evaluate(source, context),
+                _ => Err(crate::Error::evaluation("Expression condition requires an expression")),
+            },
             _ => self.evaluate_comparison(condition, context),
         }
     }
@@ -362,11 +649,180 @@ fn condition_value_to_json(value: &ConditionValue) -> serde_json::Value {
     }
 }
 
+/// Build the decision for a matched rule from its action.
+fn rule_decision(rule: &PolicyRule) -> PolicyDecision {
+    let metadata = || {
+        rule.action
+            .metadata
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    };
+
+    let mut decision = match rule.action.decision {
+        DecisionType::Allow => PolicyDecision::allow(),
+        DecisionType::Deny => {
+            let mut d = PolicyDecision::deny(
+                rule.action
+                    .reason
+                    .clone()
+                    .unwrap_or_else(|| format!("Denied by rule: {}", rule.name)),
+            );
+            d.metadata = metadata();
+            d
+        }
+        DecisionType::Warn => {
+            let mut d = PolicyDecision::warn(
+                rule.action
+                    .reason
+                    .clone()
+                    .unwrap_or_else(|| format!("Warning from rule: {}", rule.name)),
+            );
+            d.metadata = metadata();
+            d
+        }
+        DecisionType::Modify => {
+            let mut modifications = std::collections::HashMap::new();
+            for modification in &rule.action.modifications {
+                if let Some(value) = &modification.value {
+                    modifications.insert(modification.field.clone(), value.clone());
+                }
+            }
+            PolicyDecision::modify(modifications)
+                .with_obligations(rule.action.obligations.clone())
+        }
+    };
+
+    let message = decision
+        .reason
+        .clone()
+        .unwrap_or_else(|| format!("Matched rule: {}", rule.name));
+    decision.decision_reason = Some(
+        DecisionReason::new(ReasonCode::RuleMatched, message)
+            .with_detail("rule_id", rule.id.clone().into()),
+    );
+    decision
+}
+
+/// Check whether an applicable decision fixes the combined outcome, so later
+/// decisions need not be evaluated.
+fn settles(combiner: DecisionCombiner, decision: DecisionType) -> bool {
+    match combiner {
+        DecisionCombiner::DenyOverrides => decision == DecisionType::Deny,
+        DecisionCombiner::FirstApplicable => true,
+        DecisionCombiner::PermitOverrides | DecisionCombiner::UnlessPermit => false,
+    }
+}
+
+/// Check whether a policy decision takes part in combining: an allow
+/// without matched rules means the policy did not apply.
+fn is_applicable(decision: &PolicyDecision) -> bool {
+    decision.decision != DecisionType::Allow || !decision.matched_rules.is_empty()
+}
+
+/// Check whether a policy decision fixes the combined outcome.
+fn settles_policies(combiner: DecisionCombiner, decision: &PolicyDecision) -> bool {
+    is_applicable(decision) && settles(combiner, decision.decision)
+}
+
+/// Collect the sources of the CEL expressions of a condition.
+fn expression_sources<'a>(condition: &'a Condition, sources: &mut HashSet<&'a str>) {
+    if let (ConditionOperator::Expression, Some(ConditionValue::String(source))) =
+        (condition.operator, &condition.value)
+    {
+        sources.insert(source.as_str());
+    }
+    for nested in &condition.conditions {
+        expression_sources(nested, sources);
+    }
+}
+
+/// Combine policy decisions, in policy order, into the overall decision.
+///
+/// A deny result reports the denying policy and its rules. Otherwise the
+/// warning and modifying policies are reported, with the rules matched by
+/// every applicable policy.
+fn combine_policy_decisions(
+    results: Vec<(String, PolicyDecision)>,
+    combiner: DecisionCombiner,
+) -> PolicyDecision {
+    let stage = Stage::start("combiner");
+    let (ids, decisions): (Vec<_>, Vec<_>) =
+        results.into_iter().filter(|(_, d)| is_applicable(d)).unzip();
+    let matched_policies = ids
+        .iter()
+        .zip(&decisions)
+        .filter(|(_, d)| matches!(d.decision, DecisionType::Warn | DecisionType::Modify))
+        .map(|(id, _)| id.clone())
+        .collect();
+    let deny_policy = ids
+        .iter()
+        .zip(&decisions)
+        .find(|(_, d)| d.decision == DecisionType::Deny)
+        .map(|(id, _)| id.clone());
+    let matched_rules: Vec<String> = decisions
+        .iter()
+        .flat_map(|d| d.matched_rules.iter().cloned())
+        .collect();
+
+    let mut result = combine_decisions(decisions, combiner);
+    if result.decision == DecisionType::Deny {
+        // The first denying decision carries its own matched rules.
+        result.matched_policies = deny_policy.into_iter().collect();
+    } else {
+        result.matched_policies = matched_policies;
+        result.matched_rules = matched_rules;
+    }
+    stage.finish(result.decision.as_str());
+    result
+}
+
+/// Combine matched rule decisions into the policy's decision.
+///
+/// A deny result is the first deny decision. A modify result merges the
+/// modifications and obligations of every modify decision, in order, and
+/// keeps the first warning, if any, as its reason. Allow and warn results are the first decision of
+/// their type, keeping its reason.
+fn combine_decisions(decisions: Vec<PolicyDecision>, combiner: DecisionCombiner) -> PolicyDecision {
+    let outcomes: Vec<DecisionOutcome> = decisions.iter().map(|d| d.decision.into()).collect();
+    let first = |decision_type: DecisionType| {
+        decisions.iter().find(|d| d.decision == decision_type).cloned()
+    };
+
+    // Rule decisions never carry errors, so the combined outcome cannot be one.
+    match combine(&outcomes, combiner) {
+        DecisionOutcome::Allow | DecisionOutcome::Error => {
+            first(DecisionType::Allow).unwrap_or_else(PolicyDecision::allow)
+        }
+        DecisionOutcome::Deny => first(DecisionType::Deny).unwrap_or_else(|| {
+            let reason = "No rule permitted the request";
+            PolicyDecision::deny(reason).with_decision_reason(
+                DecisionReason::new(ReasonCode::NoMatchDefault, reason)
+                    .with_detail("combiner", combiner.as_str().into()),
+            )
+        }),
+        DecisionOutcome::Warn => first(DecisionType::Warn).unwrap_or_else(PolicyDecision::allow),
+        DecisionOutcome::Modify => {
+            let mut result = PolicyDecision::modify(std::collections::HashMap::new());
+            let reason_from = first(DecisionType::Warn).or_else(|| first(DecisionType::Modify));
+            result.reason = reason_from.as_ref().and_then(|d| d.reason.clone());
+            result.decision_reason = reason_from.and_then(|d| d.decision_reason);
+            for decision in decisions {
+                if decision.decision == DecisionType::Modify {
+                    result.modifications.extend(decision.modifications);
+                    result.obligations.merge(decision.obligations);
+                }
+            }
+            result
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::api::EvaluationContext;
-    use crate::policy::{Action, PolicyRule};
+    use crate::policy::{Action, Modification, Obligations, PolicyRule};
 
     fn sample_policy() -> Policy {
         Policy::builder("test-policy")
@@ -479,4 +935,260 @@ mod tests {
         let condition = Condition::exists("user.id");
         assert!(!evaluator.evaluate_condition(&condition, &context).unwrap());
     }
+
+    fn model_policy(combiner: Option<DecisionCombiner>) -> Policy {
+        let gpt4 = || Condition::equals("llm.model", "gpt-4");
+        let mut builder = Policy::builder("model-policy")
+            .rule(PolicyRule::new("warn", "Warn", gpt4(), Action::warn("Costly")).with_priority(3))
+            .rule(PolicyRule::new("deny", "Deny", gpt4(), Action::deny("Blocked")).with_priority(2))
+            .rule(
+                PolicyRule::new(
+                    "cap",
+                    "Cap tokens",
+                    gpt4(),
+                    Action::modify(vec![Modification::set("max_tokens", serde_json::json!(512))]),
+                )
+                .with_priority(1),
+            );
+        if let Some(combiner) = combiner {
+            builder = builder.combiner(combiner);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_policy_combiner_overrides_default() {
+        let evaluator = Evaluator::new();
+        let context = EvaluationContext::builder().with_model("gpt-4").build();
+
+        let result = evaluator.evaluate(&[model_policy(None)], &context).unwrap();
+        assert_eq!(result.decision, DecisionType::Deny);
+        assert_eq!(result.reason.as_deref(), Some("Blocked"));
+
+        let policies = [model_policy(Some(DecisionCombiner::PermitOverrides))];
+        let result = evaluator.evaluate(&policies, &context).unwrap();
+        assert_eq!(result.decision, DecisionType::Modify);
+        assert_eq!(result.reason.as_deref(), Some("Costly"));
+        assert_eq!(result.modifications["max_tokens"], 512);
+        assert_eq!(result.matched_rules, vec!["warn", "deny", "cap"]);
+
+        let config = EngineConfig {
+            decision_combiner: DecisionCombiner::FirstApplicable,
+            ..EngineConfig::default()
+        };
+        let result = evaluator
+            .evaluate_with_config(&[model_policy(None)], &context, &config)
+            .unwrap();
+        assert_eq!(result.decision, DecisionType::Warn);
+        assert_eq!(result.matched_rules, vec!["warn"]);
+    }
+
+    #[test]
+    fn test_unless_permit_denies_unmatched_policy() {
+        let evaluator = Evaluator::new();
+        let context = EvaluationContext::builder().with_model("claude-3").build();
+
+        let policies = [model_policy(Some(DecisionCombiner::UnlessPermit))];
+        let result = evaluator.evaluate(&policies, &context).unwrap();
+        assert_eq!(result.decision, DecisionType::Deny);
+        assert!(result.matched_rules.is_empty());
+    }
+
+    #[test]
+    fn test_priority_override_reorders_rules() {
+        let evaluator = Evaluator::new();
+        let context = EvaluationContext::builder().with_model("gpt-4").build();
+        let policies = [model_policy(Some(DecisionCombiner::FirstApplicable))];
+        let mut config = EngineConfig::default();
+
+        let result = evaluator.evaluate_with_config(&policies, &context, &config).unwrap();
+        assert_eq!(result.decision, DecisionType::Warn);
+
+        config.priority_overrides.insert("cap".to_string(), 10);
+        let result = evaluator.evaluate_with_config(&policies, &context, &config).unwrap();
+        assert_eq!(result.decision, DecisionType::Modify);
+        assert_eq!(result.matched_rules, vec!["cap"]);
+
+        // Equal priorities are ordered by rule ID.
+        config.priority_overrides.insert("cap".to_string(), 2);
+        let result = evaluator.evaluate_with_config(&policies, &context, &config).unwrap();
+        assert_eq!(result.matched_rules, vec!["warn"]);
+        config.priority_overrides.insert("warn".to_string(), 2);
+        let result = evaluator.evaluate_with_config(&policies, &context, &config).unwrap();
+        assert_eq!(result.matched_rules, vec!["cap"]);
+    }
+
+    #[test]
+    fn test_obligations_accumulated_across_rules() {
+        let modify = |id: &str, obligations: Obligations| {
+            PolicyRule::new(
+                id,
+                id,
+                Condition::exists("user.id"),
+                Action::modify(Vec::new()).with_obligations(obligations),
+            )
+        };
+        let strict = Policy::builder("strict")
+            .rule(modify("redact-email", Obligations::new().redact("user.email").max_tokens(2000)))
+            .rule(modify("tag", Obligations::new().set_header("x-policy", "strict")))
+            .build();
+        let lenient = Policy::builder("lenient")
+            .rule(modify(
+                "cap",
+                Obligations::new()
+                    .set_header("x-policy", "lenient")
+                    .max_tokens(500)
+                    .redact("user.email"),
+            ))
+            .rule(modify("prompt", Obligations::new().redact("llm.prompt").system_prompt("Hi")))
+            .build();
+        let context = EvaluationContext::builder().with_user_id("user-1").build();
+
+        // Earlier policies and rules win conflicting headers and prompts.
+        let result = Evaluator::new().evaluate(&[strict, lenient], &context).unwrap();
+        assert_eq!(result.decision, DecisionType::Modify);
+        let obligations = &result.obligations;
+        assert_eq!(obligations.redact_fields, vec!["user.email", "llm.prompt"]);
+        assert_eq!(obligations.set_headers["x-policy"], "strict");
+        assert_eq!(obligations.max_tokens_override, Some(500));
+        assert_eq!(obligations.inject_system_prompt.as_deref(), Some("Hi"));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["obligations"]["max_tokens_override"], 500);
+    }
+
+    fn guest_policies() -> Vec<Policy> {
+        let guests = || Condition::equals("user.roles", vec!["guest".to_string()]);
+        vec![
+            Policy::builder("warn-guests")
+                .rule(PolicyRule::new("warn", "Warn", guests(), Action::warn("Guest")))
+                .build(),
+            sample_policy(),
+            Policy::builder("cap-guests")
+                .rule(PolicyRule::new(
+                    "cap",
+                    "Cap tokens",
+                    guests(),
+                    Action::modify(vec![Modification::set("max_tokens", serde_json::json!(64))]),
+                ))
+                .build(),
+            model_policy(None),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_parallel_matches_sequential() {
+        let evaluator = Evaluator::new();
+        let permits = Arc::new(Semaphore::new(2));
+        let contexts = [
+            EvaluationContext::builder()
+                .with_user("user-1", None, vec!["guest".to_string()])
+                .build(),
+            EvaluationContext::builder()
+                .with_user("user-2", None, vec!["admin".to_string()])
+                .with_model("gpt-4")
+                .build(),
+            EvaluationContext::builder().build(),
+        ];
+        let combiners = [
+            DecisionCombiner::DenyOverrides,
+            DecisionCombiner::PermitOverrides,
+            DecisionCombiner::FirstApplicable,
+            DecisionCombiner::UnlessPermit,
+        ];
+
+        for context in &contexts {
+            for combiner in combiners {
+                let config = Arc::new(EngineConfig {
+                    decision_combiner: combiner,
+                    ..EngineConfig::default()
+                });
+                let sequential = evaluator
+                    .evaluate_with_config(&guest_policies(), context, &config)
+                    .unwrap();
+                let parallel = evaluator
+                    .evaluate_parallel(guest_policies(), context, config.clone(), &permits)
+                    .await
+                    .unwrap();
+
+                assert_eq!(parallel.decision, sequential.decision, "{}", combiner);
+                assert_eq!(parallel.reason, sequential.reason);
+                assert_eq!(parallel.matched_policies, sequential.matched_policies);
+                assert_eq!(parallel.matched_rules, sequential.matched_rules);
+                assert_eq!(parallel.modifications, sequential.modifications);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_evaluation_waits_for_permits() {
+        let evaluator = Evaluator::new();
+        let permits = Arc::new(Semaphore::new(1));
+        let config = Arc::new(EngineConfig::default());
+        let context = EvaluationContext::builder().build();
+
+        // With the only permit taken, no policy can be evaluated.
+        let held = permits.clone().acquire_owned().await.unwrap();
+        let blocked = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            evaluator.evaluate_parallel(guest_policies(), &context, config.clone(), &permits),
+        )
+        .await;
+        assert!(blocked.is_err());
+
+        drop(held);
+        let decision = evaluator
+            .evaluate_parallel(guest_policies(), &context, config, &permits)
+            .await
+            .unwrap();
+        assert!(decision.allowed);
+        assert_eq!(permits.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_cel_conditions_compiled_and_evaluated() {
+        let evaluator = Evaluator::new();
+        let policy = Policy::builder("cel-policy")
+            .name("CEL Policy")
+            .rule(PolicyRule::new(
+                "deny-long-prompts",
+                "Deny long prompts to GPT models",
+                Condition::expression("llm.model.startsWith('gpt') && size(llm.prompt) > 10"),
+                Action::deny("Prompt too long"),
+            ))
+            .build();
+        assert!(evaluator.compile_policies(std::slice::from_ref(&policy)).is_empty());
+
+        let context = EvaluationContext::builder()
+            .with_model("gpt-4")
+            .with_prompt("Summarize this very long document")
+            .build();
+        let decision = evaluator.evaluate(std::slice::from_ref(&policy), &context).unwrap();
+        assert!(!decision.allowed);
+
+        let broken = Policy::builder("broken")
+            .name("Broken")
+            .rule(PolicyRule::new(
+                "syntax",
+                "Syntax error",
+                Condition::expression("llm.model ==="),
+                Action::deny("Never"),
+            ))
+            .rule(PolicyRule::new(
+                "types",
+                "Type mismatch",
+                Condition::and(vec![
+                    Condition::exists("llm.model"),
+                    Condition::expression("llm.max_tokens + 'tokens' == 'many'"),
+                ]),
+                Action::deny("Never"),
+            ))
+            .build();
+        let errors = evaluator.compile_policies(&[policy, broken]);
+        let failed: Vec<_> = errors
+            .iter()
+            .map(|e| (e.policy_id.as_str(), e.rule_id.as_str()))
+            .collect();
+        assert_eq!(failed, vec![("broken", "syntax"), ("broken", "types")]);
+    }
 }
@@ -0,0 +1,456 @@
+//! CEL condition expressions.
+//!
+//! Conditions with the `expression` operator hold a CEL expression over the
+//! evaluation context, with `llm`, `user`, `team`, `project`, `request` and
+//! `metadata` as variables, plus `signals` and `thresholds` when the engine
+//! has a [`TelemetryProvider`](crate::api::TelemetryProvider). Expressions
+//! are compiled and checked when their policy is loaded, and the compiled
+//! programs are reused by every evaluation. Besides the standard CEL
+//! functions, expressions can call the custom functions of the cache's
+//! [`CelFunctionRegistry`].
+//!
+//! A cache may bound how long each evaluation runs. The deadline is checked
+//! each time a comprehension (`all`, `exists`, `exists_one`, `filter` or
+//! `map`) starts and each time a custom function is called, so a runaway
+//! expression stops on its own thread without leaving work behind. A
+//! comprehension does not check the deadline between its elements: one that
+//! has started runs over its whole list, and only the comprehensions and
+//! custom functions called in it stop.
+
+use super::functions::CelFunctionRegistry;
+use crate::api::{
+    EvaluationContext, LlmContext, ProjectContext, RequestContext, SignalValues, TeamContext,
+    UserContext,
+};
+use crate::integration::RuleThresholds;
+use crate::{Error, Result};
+
+use cel_interpreter::extractors::{Identifier, This};
+use cel_interpreter::{
+    functions, Context, ExecutionError, Expression, FunctionContext, Program, ResolveResult, Value,
+};
+use dashmap::DashMap;
+use prometheus::IntCounter;
+use std::cell::Cell;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// When the expression running on this thread must stop.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Compiled CEL programs, keyed by expression source.
+#[derive(Default)]
+pub struct ExpressionCache {
+    programs: DashMap<String, Arc<Program>>,
+    functions: CelFunctionRegistry,
+    timeout: Option<Duration>,
+}
+
+impl ExpressionCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty cache for expressions that may call custom
+    /// functions.
+    pub fn with_functions(functions: CelFunctionRegistry) -> Self {
+        Self {
+            programs: DashMap::new(),
+            functions,
+            timeout: None,
+        }
+    }
+
+    /// Stop each evaluation that runs longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Get the custom functions expressions may call.
+    pub fn functions(&self) -> &CelFunctionRegistry {
+        &self.functions
+    }
+
+    /// Get the longest an evaluation may run, if bounded.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Get the compiled program for an expression, compiling it on first
+    /// use.
+    pub fn compile(&self, source: &str) -> Result<Arc<Program>> {
+        if let Some(program) = self.programs.get(source) {
+            return Ok(program.clone());
+        }
+        let program = Program::compile(source).map_err(|e| {
+            Error::expression_with_expr(format!("Invalid CEL expression: {}", e), source)
+        })?;
+        let program = Arc::new(program);
+        self.programs.insert(source.to_string(), program.clone());
+        Ok(program)
+    }
+
+    /// Compile an expression and check its types, by running it against a
+    /// context with every field set: it must only call standard or
+    /// registered functions, must not apply an operator or function to
+    /// values of the wrong type, and must evaluate to a boolean.
+    pub fn check(&self, source: &str) -> Result<()> {
+        let program = self.compile(source)?;
+        let references = program.references();
+        if let Some(name) = references
+            .functions()
+            .into_iter()
+            .find(|name| !self.functions.is_callable(name))
+        {
+            return Err(Error::expression_with_expr(
+                format!("CEL function '{}' is not registered", name),
+                source,
+            ));
+        }
+
+        match self.execute(&program, &typed_variables(), true) {
+            Ok(Value::Bool(_)) => Ok(()),
+            // Metadata keys are not known ahead of time.
+            Err(ExecutionError::NoSuchKey(_)) => Ok(()),
+            Ok(other) => Err(Error::expression_with_expr(
+                format!("CEL condition must evaluate to a bool, not {:?}", other),
+                source,
+            )),
+            Err(e) => Err(Error::expression_with_expr(
+                format!("CEL type error: {}", e),
+                source,
+            )),
+        }
+    }
+
+    /// Evaluate an expression against a context.
+    ///
+    /// Expressions referring to fields the context does not have evaluate to
+    /// false, like comparisons of missing fields. An evaluation running past
+    /// the cache's timeout fails with a timeout error.
+    pub fn evaluate(&self, source: &str, context: &EvaluationContext) -> Result<bool> {
+        let program = self.compile(source)?;
+        let variables = context.to_json();
+        let start = Instant::now();
+        let result = self.execute(&program, &variables, false);
+        if let Some(timeout) = self.timeout.filter(|timeout| start.elapsed() > *timeout) {
+            cel_timeouts_counter().inc();
+            let timeout_ms = timeout.as_millis() as u64;
+            let message = format!("CEL expression exceeded its timeout of {}ms", timeout_ms);
+            return Err(Error::timeout(message, timeout_ms));
+        }
+        match result {
+            Ok(Value::Bool(result)) => Ok(result),
+            Err(ExecutionError::NoSuchKey(_) | ExecutionError::UndeclaredReference(_)) => Ok(false),
+            Ok(other) => Err(Error::expression_with_expr(
+                format!("CEL condition evaluated to {:?}, not a bool", other),
+                source,
+            )),
+            Err(e) => Err(Error::expression_with_expr(
+                format!("CEL evaluation failed: {}", e),
+                source,
+            )),
+        }
+    }
+
+    /// Get the custom functions an expression calls, by name.
+    pub fn custom_functions(&self, source: &str) -> Result<Vec<String>> {
+        let program = self.compile(source)?;
+        let mut names: Vec<String> = program
+            .references()
+            .functions()
+            .into_iter()
+            .filter(|name| self.functions.contains(name))
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Run a program with the top-level fields of a context as variables,
+    /// and the custom functions; when `checking`, custom functions only
+    /// check their arguments. Otherwise the program stops at the first
+    /// checkpoint past the cache's timeout.
+    fn execute(
+        &self,
+        program: &Program,
+        context: &serde_json::Value,
+        checking: bool,
+    ) -> std::result::Result<Value, ExecutionError> {
+        let deadline = self
+            .timeout
+            .filter(|_| !checking)
+            .map(|timeout| Instant::now() + timeout);
+        let mut variables = Context::default();
+        if deadline.is_some() {
+            install_checkpoints(&mut variables);
+        }
+        self.functions.install(&mut variables, checking);
+        if let Some(fields) = context.as_object() {
+            for (name, value) in fields {
+                // JSON values always convert to CEL values.
+                let _ = variables.add_variable(name.as_str(), value);
+            }
+        }
+        let previous = DEADLINE.with(|current| current.replace(deadline));
+        let result = program.execute(&variables);
+        DEADLINE.with(|current| current.set(previous));
+        result
+    }
+
+    /// Drop every compiled program.
+    pub fn clear(&self) {
+        self.programs.clear();
+    }
+
+    /// Keep only the compiled programs whose source `keep` accepts.
+    pub fn retain(&self, keep: impl Fn(&str) -> bool) {
+        self.programs.retain(|source, _| keep(source));
+    }
+
+    /// Get the number of compiled programs.
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    /// Check if no programs are compiled.
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+}
+
+impl std::fmt::Debug for ExpressionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExpressionCache")
+            .field("programs", &self.programs.len())
+            .field("functions", &self.functions)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// The variables of a context with every field set, for type checks.
+///
+/// Empty metadata is left out of serialized contexts, but `metadata` must
+/// still be declared; its keys are not known ahead of time.
+fn typed_variables() -> serde_json::Value {
+    let mut variables = typed_context().to_json();
+    variables["metadata"] = serde_json::json!({});
+    variables
+}
+
+/// A context with every optional field set, for type checks.
+fn typed_context() -> EvaluationContext {
+    let text = || Some(String::new());
+    EvaluationContext {
+        llm: Some(LlmContext {
+            provider: text(),
+            model: text(),
+            prompt: text(),
+            max_tokens: Some(0),
+            temperature: Some(0.0),
+            functions: Some(Vec::new()),
+        }),
+        user: Some(UserContext {
+            id: String::new(),
+            email: text(),
+            roles: Vec::new(),
+            permissions: Vec::new(),
+        }),
+        team: Some(TeamContext {
+            id: String::new(),
+            name: text(),
+            tier: text(),
+        }),
+        project: Some(ProjectContext {
+            id: String::new(),
+            name: text(),
+            environment: text(),
+        }),
+        request: Some(RequestContext {
+            id: String::new(),
+            timestamp: Some(0),
+            ip_address: text(),
+            user_agent: text(),
+        }),
+        signals: Some(SignalValues::default()),
+        thresholds: Some(RuleThresholds::default()),
+        ..EvaluationContext::default()
+    }
+}
+
+/// Counter of CEL evaluations stopped at their timeout.
+pub(crate) fn cel_timeouts_counter() -> &'static IntCounter {
+    static COUNTER: OnceLock<IntCounter> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounter::new(
+            "policy_engine_cel_timeouts_total",
+            "CEL evaluations stopped at their timeout",
+        )
+        .expect("Failed to create CEL timeouts counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register CEL timeouts counter");
+        counter
+    })
+}
+
+/// Fail the expression running on this thread if it is past its deadline.
+pub(crate) fn checkpoint(name: &str) -> std::result::Result<(), ExecutionError> {
+    match DEADLINE.with(Cell::get) {
+        Some(deadline) if Instant::now() > deadline => Err(ExecutionError::function_error(
+            name,
+            "CEL evaluation time exceeded",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Replace the comprehension macros with ones checking the deadline when
+/// they start. Nested comprehensions check it on every step of the outer
+/// ones, so the work between checks is bounded by the size of the context
+/// and of the expression.
+fn install_checkpoints(variables: &mut Context) {
+    variables.add_function("all", all);
+    variables.add_function("exists", exists);
+    variables.add_function("exists_one", exists_one);
+    variables.add_function("filter", filter);
+    variables.add_function("map", map);
+}
+
+/// Define comprehensions that check the deadline, then run the standard
+/// comprehension of the same name.
+macro_rules! checkpointed {
+    ($($name:ident),+) => {$(
+        fn $name(
+            ftx: &FunctionContext,
+            this: This<Value>,
+            ident: Identifier,
+            expr: Expression,
+        ) -> ResolveResult {
+            checkpoint(&ftx.name)?;
+            functions::$name(ftx, this, ident, expr)
+        }
+    )+};
+}
+
+checkpointed!(all, exists, exists_one, filter, map);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CelType;
+
+    #[test]
+    fn test_expressions_compiled_once() {
+        let cache = ExpressionCache::new();
+        let context = EvaluationContext::builder()
+            .with_user("user-1", None, vec!["admin".to_string()])
+            .with_model("gpt-4")
+            .build();
+
+        let source = "'admin' in user.roles && llm.model.startsWith('gpt-')";
+        assert!(cache.check(source).is_ok());
+        assert!(cache.evaluate(source, &context).unwrap());
+        assert!(!cache.evaluate("team.id == 'ml'", &context).unwrap());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_metadata_expressions_accepted() {
+        let cache = ExpressionCache::new();
+        let source = "metadata.tier == 'gold' && metadata.region.startsWith('eu-')";
+        cache.check(source).unwrap();
+
+        let context = EvaluationContext::builder()
+            .with_metadata("tier", serde_json::json!("gold"))
+            .with_metadata("region", serde_json::json!("eu-west-1"))
+            .build();
+        assert!(cache.evaluate(source, &context).unwrap());
+        let without_metadata = EvaluationContext::default();
+        assert!(!cache.evaluate(source, &without_metadata).unwrap());
+    }
+
+    #[test]
+    fn test_expensive_expression_times_out() {
+        let cache = ExpressionCache::new().with_timeout(Duration::from_millis(20));
+        let items: Vec<u32> = (0..300).collect();
+        let context = EvaluationContext::builder()
+            .with_metadata("items", serde_json::json!(items))
+            .build();
+        let source = "metadata.items.all(a, metadata.items.all(b, metadata.items.all(c, c == c)))";
+
+        let before = cel_timeouts_counter().get();
+        let result = cache.evaluate(source, &context);
+        assert!(matches!(result, Err(Error::Timeout { .. })), "{:?}", result);
+        assert!(cel_timeouts_counter().get() > before);
+
+        // The program stopped at a checkpoint instead of running to the end.
+        let program = cache.compile(source).unwrap();
+        let stopped = cache.execute(&program, &context.to_json(), false);
+        assert!(matches!(stopped, Err(ExecutionError::FunctionError { .. })));
+
+        // The deadline only applies to the evaluation that set it.
+        let size = "size(metadata.items) == 300";
+        assert!(cache.evaluate(size, &context).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_expressions_rejected() {
+        let cache = ExpressionCache::new();
+
+        let syntax = cache.check("user.roles.exists(r, r ==").unwrap_err();
+        assert!(syntax.to_string().contains("Invalid CEL expression"), "{}", syntax);
+
+        let types = cache.check("user.id > 5").unwrap_err();
+        assert!(types.to_string().contains("CEL type error"), "{}", types);
+
+        let not_bool = cache.check("llm.model").unwrap_err();
+        assert!(not_bool.to_string().contains("must evaluate to a bool"), "{}", not_bool);
+    }
+
+    #[test]
+    fn test_custom_functions() {
+        let functions = CelFunctionRegistry::standard().register(
+            "tier_rank",
+            vec![CelType::String],
+            CelType::Int,
+            |args| match args {
+                [Value::String(tier)] if tier.as_str() == "enterprise" => Ok(Value::Int(2)),
+                [Value::String(_)] => Ok(Value::Int(1)),
+                _ => Err("expected a tier".to_string()),
+            },
+        );
+        let cache = ExpressionCache::with_functions(functions);
+        let context = EvaluationContext::builder()
+            .with_team("ml", None, Some("enterprise".to_string()))
+            .with_prompt("four")
+            .build();
+
+        let source = "tier_rank(team.tier) > 1 && token_estimate(llm.prompt) == 1";
+        cache.check(source).unwrap();
+        assert!(cache.evaluate(source, &context).unwrap());
+        assert_eq!(
+            cache.custom_functions(source).unwrap(),
+            vec!["tier_rank".to_string(), "token_estimate".to_string()]
+        );
+
+        // Arguments are checked against the declared types.
+        let types = cache.check("tier_rank(request.timestamp) > 1").unwrap_err();
+        assert!(types.to_string().contains("must be a string"), "{}", types);
+    }
+
+    #[test]
+    fn test_unregistered_function_rejected() {
+        let cache = ExpressionCache::new();
+
+        let error = cache.check("false && ip_in_cidr(request.ip_address, '10.0.0.0/8')");
+        let error = error.unwrap_err();
+        assert!(error.to_string().contains("'ip_in_cidr' is not registered"), "{}", error);
+        assert!(cache.check("size(user.roles) > 0").is_ok());
+    }
+}
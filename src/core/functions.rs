@@ -0,0 +1,348 @@
+//! Custom CEL functions.
+//!
+//! CEL conditions can call the interpreter's standard functions, such as
+//! `size` or `startsWith`, and the host functions registered in a
+//! [`CelFunctionRegistry`]. Each host function declares the types of its
+//! arguments and result: arguments are checked on every call, and type
+//! checks at compile time run against the declared result instead of
+//! calling the function. Calls to any other function are rejected when the
+//! expression is compiled, so policies can only use functions the engine
+//! was built with.
+
+use super::expression::checkpoint;
+
+use cel_interpreter::extractors::Arguments;
+use cel_interpreter::{Context, ExecutionError, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/// Functions provided by the CEL interpreter, including macros.
+pub const BUILTIN_FUNCTIONS: &[&str] = &[
+    "all",
+    "bytes",
+    "contains",
+    "double",
+    "duration",
+    "endsWith",
+    "exists",
+    "exists_one",
+    "filter",
+    "getDate",
+    "getDayOfMonth",
+    "getDayOfWeek",
+    "getDayOfYear",
+    "getFullYear",
+    "getHours",
+    "getMilliseconds",
+    "getMinutes",
+    "getMonth",
+    "getSeconds",
+    "has",
+    "int",
+    "map",
+    "matches",
+    "max",
+    "min",
+    "size",
+    "startsWith",
+    "string",
+    "timestamp",
+    "uint",
+];
+
+/// Type of a custom function argument or result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CelType {
+    /// Boolean
+    Bool,
+    /// Signed integer
+    Int,
+    /// Unsigned integer; non-negative integers are accepted
+    UInt,
+    /// Floating point number; integers are accepted
+    Double,
+    /// String
+    String,
+    /// List of any values
+    List,
+    /// Any value
+    Any,
+}
+
+impl CelType {
+    /// Get the type's CEL name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::UInt => "uint",
+            Self::Double => "double",
+            Self::String => "string",
+            Self::List => "list",
+            Self::Any => "dyn",
+        }
+    }
+
+    /// Check if a value has this type.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Any, _) => true,
+            (Self::Bool, Value::Bool(_)) => true,
+            (Self::Int, Value::Int(_)) => true,
+            (Self::UInt, Value::UInt(_)) => true,
+            (Self::UInt, Value::Int(n)) => *n >= 0,
+            (Self::Double, Value::Float(_) | Value::Int(_) | Value::UInt(_)) => true,
+            (Self::String, Value::String(_)) => true,
+            (Self::List, Value::List(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// A value of this type, standing in for results during type checks.
+    fn placeholder(&self) -> Value {
+        match self {
+            Self::Bool => Value::Bool(false),
+            Self::Int => Value::Int(0),
+            Self::UInt => Value::UInt(0),
+            Self::Double => Value::Float(0.0),
+            Self::String => Value::String(Arc::new(String::new())),
+            Self::List => Value::List(Arc::new(Vec::new())),
+            Self::Any => Value::Null,
+        }
+    }
+}
+
+impl fmt::Display for CelType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Host implementation of a custom function, called with arguments that
+/// match its declared types. Errors are reported as evaluation errors.
+pub type CelHostFn = dyn Fn(&[Value]) -> Result<Value, String> + Send + Sync;
+
+/// A custom function callable from CEL conditions.
+pub struct CelFunction {
+    name: String,
+    params: Vec<CelType>,
+    returns: CelType,
+    host: Arc<CelHostFn>,
+}
+
+impl CelFunction {
+    /// Get the function name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the argument types.
+    pub fn params(&self) -> &[CelType] {
+        &self.params
+    }
+
+    /// Get the result type.
+    pub fn returns(&self) -> CelType {
+        self.returns
+    }
+
+    /// Get the signature, e.g. `ip_in_cidr(string, string) -> bool`.
+    pub fn signature(&self) -> String {
+        let params: Vec<_> = self.params.iter().map(CelType::as_str).collect();
+        format!("{}({}) -> {}", self.name, params.join(", "), self.returns)
+    }
+
+    /// Call the function, or only check its arguments and return a
+    /// placeholder result if `checking`.
+    fn call(&self, args: &[Value], checking: bool) -> Result<Value, ExecutionError> {
+        let error = |message: String| ExecutionError::function_error(&self.name, message);
+        if args.len() != self.params.len() {
+            return Err(error(format!(
+                "expected {} arguments, got {}",
+                self.params.len(),
+                args.len()
+            )));
+        }
+        if let Some((position, (ty, _))) = self
+            .params
+            .iter()
+            .zip(args)
+            .enumerate()
+            .find(|(_, (ty, arg))| !ty.accepts(arg))
+        {
+            return Err(error(format!("argument {} must be a {}", position + 1, ty)));
+        }
+        if checking {
+            return Ok(self.returns.placeholder());
+        }
+        checkpoint(&self.name)?;
+        (self.host)(args).map_err(error)
+    }
+}
+
+impl fmt::Debug for CelFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.signature())
+    }
+}
+
+/// Custom functions made available to CEL conditions, by name.
+///
+/// Registered functions take precedence over standard functions of the
+/// same name.
+#[derive(Debug, Clone, Default)]
+pub struct CelFunctionRegistry {
+    functions: BTreeMap<String, Arc<CelFunction>>,
+}
+
+impl CelFunctionRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the standard policy helpers:
+    ///
+    /// - `ip_in_cidr(ip, cidr)`: whether an IPv4 or IPv6 address is in a
+    ///   CIDR range
+    /// - `token_estimate(text)`: rough token count of a text, at four
+    ///   characters per token
+    /// - `now()`: current Unix time in seconds
+    pub fn standard() -> Self {
+        Self::new()
+            .register(
+                "ip_in_cidr",
+                vec![CelType::String, CelType::String],
+                CelType::Bool,
+                |args| match args {
+                    [Value::String(ip), Value::String(cidr)] => {
+                        ip_in_cidr(ip, cidr).map(Value::Bool)
+                    }
+                    _ => unreachable!("arguments are type-checked"),
+                },
+            )
+            .register(
+                "token_estimate",
+                vec![CelType::String],
+                CelType::Int,
+                |args| match args {
+                    [Value::String(text)] => {
+                        Ok(Value::Int(text.chars().count().div_ceil(4) as i64))
+                    }
+                    _ => unreachable!("arguments are type-checked"),
+                },
+            )
+            .register("now", Vec::new(), CelType::Int, |_| {
+                Ok(Value::Int(chrono::Utc::now().timestamp()))
+            })
+    }
+
+    /// Register a function, replacing any function of the same name.
+    pub fn register<F>(
+        mut self,
+        name: impl Into<String>,
+        params: Vec<CelType>,
+        returns: CelType,
+        host: F,
+    ) -> Self
+    where
+        F: Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let function = CelFunction {
+            name: name.clone(),
+            params,
+            returns,
+            host: Arc::new(host),
+        };
+        self.functions.insert(name, Arc::new(function));
+        self
+    }
+
+    /// Get a registered function.
+    pub fn get(&self, name: &str) -> Option<&CelFunction> {
+        self.functions.get(name).map(|function| function.as_ref())
+    }
+
+    /// Check if a function is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Check if a function may be called: registered or standard.
+    pub fn is_callable(&self, name: &str) -> bool {
+        self.contains(name) || BUILTIN_FUNCTIONS.contains(&name)
+    }
+
+    /// Iterate over the registered functions, by name.
+    pub fn iter(&self) -> impl Iterator<Item = &CelFunction> {
+        self.functions.values().map(|function| function.as_ref())
+    }
+
+    /// Get the number of registered functions.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Check if no functions are registered.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Add the registered functions to an interpreter context. When
+    /// `checking`, functions check their arguments and return a placeholder
+    /// without running.
+    pub(crate) fn install(&self, context: &mut Context, checking: bool) {
+        for function in self.functions.values() {
+            let name = function.name.clone();
+            let function = function.clone();
+            context.add_function(name.as_str(), move |Arguments(args): Arguments| {
+                function.call(&args, checking)
+            });
+        }
+    }
+}
+
+/// Whether an address is in a CIDR range; addresses of the other IP
+/// version never are.
+fn ip_in_cidr(ip: &str, cidr: &str) -> Result<bool, String> {
+    let ip: IpAddr = ip
+        .parse()
+        .map_err(|_| format!("invalid IP address '{}'", ip))?;
+    let invalid_cidr = || format!("invalid CIDR range '{}'", cidr);
+    let (network, prefix) = cidr.split_once('/').ok_or_else(invalid_cidr)?;
+    let network: IpAddr = network.parse().map_err(|_| invalid_cidr())?;
+    let prefix: u32 = prefix.parse().map_err(|_| invalid_cidr())?;
+
+    let bits = |addr: IpAddr| match addr {
+        IpAddr::V4(addr) => (u128::from(u32::from(addr)), 32),
+        IpAddr::V6(addr) => (u128::from(addr), 128),
+    };
+    let ((ip, ip_width), (network, width)) = (bits(ip), bits(network));
+    if prefix > width {
+        return Err(invalid_cidr());
+    }
+    if ip_width != width {
+        return Ok(false);
+    }
+    let host_bits = width - prefix;
+    Ok(ip.checked_shr(host_bits).unwrap_or(0) == network.checked_shr(host_bits).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_in_cidr() {
+        assert_eq!(ip_in_cidr("10.1.2.3", "10.0.0.0/8"), Ok(true));
+        assert_eq!(ip_in_cidr("11.1.2.3", "10.0.0.0/8"), Ok(false));
+        assert_eq!(ip_in_cidr("192.168.1.1", "0.0.0.0/0"), Ok(true));
+        assert_eq!(ip_in_cidr("2001:db8::1", "2001:db8::/32"), Ok(true));
+        assert_eq!(ip_in_cidr("2001:db8::1", "10.0.0.0/8"), Ok(false));
+        assert!(ip_in_cidr("10.1.2.3", "10.0.0.0/33").is_err());
+        assert!(ip_in_cidr("not-an-ip", "10.0.0.0/8").is_err());
+    }
+}
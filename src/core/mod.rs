@@ -1,5 +1,11 @@
 //! Core evaluation logic for the policy engine.
 
 mod evaluator;
+mod expression;
+mod functions;
+mod wasm;
 
-pub use evaluator::Evaluator;
+pub use evaluator::{CompileError, Evaluator};
+pub use expression::ExpressionCache;
+pub use functions::{CelFunction, CelFunctionRegistry, CelHostFn, CelType, BUILTIN_FUNCTIONS};
+pub use wasm::{WasmLimits, WasmPolicyPlugin, DEFAULT_WASM_FUEL};
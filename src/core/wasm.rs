@@ -0,0 +1,208 @@
+//! Sandboxed WASM policy plugins.
+//!
+//! A plugin is a WASM module evaluated against the request context. Each
+//! evaluation runs in a fresh instance whose linear memory is capped and
+//! whose execution is bounded by a fuel budget, so a misbehaving plugin is
+//! terminated and reported as [`DecisionOutcome::Error`] instead of
+//! affecting the engine.
+//!
+//! # Host ABI
+//!
+//! The module imports nothing and must export:
+//!
+//! - `memory`: its linear memory
+//! - `alloc(len: i32) -> i32`: reserve `len` bytes and return their offset
+//! - `evaluate(ptr: i32, len: i32) -> i32`: decide on the JSON-encoded
+//!   [`EvaluationContext`] written at `ptr`, returning `0` (allow), `1`
+//!   (deny), `2` (warn) or `3` (modify); any other value is an error
+
+use crate::api::EvaluationContext;
+use crate::config::PerformanceConfig;
+use crate::integration::{DecisionOutcome, FeatureFlags};
+use crate::{Error, Result};
+
+use wasmtime::{Engine, Instance, Module, Store, StoreLimitsBuilder};
+
+/// Default fuel budget for one plugin evaluation.
+///
+/// One unit of fuel is roughly one WASM instruction.
+pub const DEFAULT_WASM_FUEL: u64 = 10_000_000;
+
+/// Resource limits applied to each plugin evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Maximum size of the plugin's linear memory in bytes
+    pub memory_bytes: usize,
+    /// Fuel available to one evaluation
+    pub fuel: u64,
+}
+
+impl WasmLimits {
+    /// Derive limits from the performance configuration.
+    pub fn from_config(config: &PerformanceConfig) -> Self {
+        Self {
+            memory_bytes: config.wasm_memory_limit_mb * 1024 * 1024,
+            fuel: DEFAULT_WASM_FUEL,
+        }
+    }
+}
+
+/// A loaded WASM policy plugin.
+pub struct WasmPolicyPlugin {
+    engine: Engine,
+    module: Module,
+    limits: WasmLimits,
+}
+
+impl WasmPolicyPlugin {
+    /// Compile a plugin from WASM bytes (or WAT text).
+    ///
+    /// Fails if WASM plugins are disabled by the feature flags or if the
+    /// module does not compile.
+    pub fn load(bytes: &[u8], limits: WasmLimits, flags: &FeatureFlags) -> Result<Self> {
+        if !flags.wasm_enabled {
+            return Err(Error::config("WASM plugins are disabled"));
+        }
+
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)
+            .map_err(|e| Error::config(format!("Failed to create WASM engine: {}", e)))?;
+        let module = Module::new(&engine, bytes)
+            .map_err(|e| Error::config(format!("Invalid WASM plugin: {}", e)))?;
+
+        Ok(Self {
+            engine,
+            module,
+            limits,
+        })
+    }
+
+    /// Get the limits applied to each evaluation.
+    pub fn limits(&self) -> WasmLimits {
+        self.limits
+    }
+
+    /// Evaluate the plugin against a context.
+    ///
+    /// Traps, exhausted fuel, memory growth past the limit and ABI
+    /// violations all yield [`DecisionOutcome::Error`].
+    pub fn evaluate(&self, context: &EvaluationContext) -> DecisionOutcome {
+        let result = serde_json::to_vec(context)
+            .map_err(wasmtime::Error::from)
+            .and_then(|input| self.run(&input));
+
+        match result {
+            Ok(0) => DecisionOutcome::Allow,
+            Ok(1) => DecisionOutcome::Deny,
+            Ok(2) => DecisionOutcome::Warn,
+            Ok(3) => DecisionOutcome::Modify,
+            Ok(code) => {
+                tracing::warn!(code, "WASM plugin returned an unknown decision");
+                DecisionOutcome::Error
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "WASM plugin evaluation failed");
+                DecisionOutcome::Error
+            }
+        }
+    }
+
+    /// Instantiate the module in a fresh store and call `evaluate`.
+    fn run(&self, input: &[u8]) -> wasmtime::Result<i32> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.memory_bytes)
+            .instances(1)
+            .trap_on_grow_failure(true)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.limits.fuel)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("plugin does not export memory"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let evaluate = instance.get_typed_func::<(i32, i32), i32>(&mut store, "evaluate")?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, usize::try_from(ptr)?, input)?;
+        evaluate.call(&mut store, (ptr, len))
+    }
+}
+
+impl std::fmt::Debug for WasmPolicyPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmPolicyPlugin")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: WasmLimits = WasmLimits {
+        memory_bytes: 1024 * 1024,
+        fuel: 1_000_000,
+    };
+
+    fn enabled() -> FeatureFlags {
+        FeatureFlags {
+            wasm_enabled: true,
+            ..FeatureFlags::default()
+        }
+    }
+
+    /// A plugin whose `evaluate` body is `body`.
+    fn plugin(body: &str) -> WasmPolicyPlugin {
+        let wat = format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) i32.const 0)
+                (func (export "evaluate") (param i32 i32) (result i32) {})
+            )"#,
+            body
+        );
+        WasmPolicyPlugin::load(wat.as_bytes(), LIMITS, &enabled()).unwrap()
+    }
+
+    #[test]
+    fn test_allow_and_deny_plugins() {
+        let context = EvaluationContext::builder().with_model("gpt-4").build();
+        assert_eq!(plugin("i32.const 0").evaluate(&context), DecisionOutcome::Allow);
+        assert_eq!(plugin("i32.const 1").evaluate(&context), DecisionOutcome::Deny);
+        assert_eq!(plugin("i32.const 7").evaluate(&context), DecisionOutcome::Error);
+    }
+
+    #[test]
+    fn test_memory_limit_terminates_plugin() {
+        // 32 pages is 2 MiB, twice the limit.
+        let plugin = plugin("(drop (memory.grow (i32.const 32))) i32.const 0");
+        let context = EvaluationContext::builder().build();
+        assert_eq!(plugin.evaluate(&context), DecisionOutcome::Error);
+    }
+
+    #[test]
+    fn test_fuel_exhaustion_terminates_plugin() {
+        let plugin = plugin("(loop (br 0)) i32.const 0");
+        let context = EvaluationContext::builder().build();
+        assert_eq!(plugin.evaluate(&context), DecisionOutcome::Error);
+    }
+
+    #[test]
+    fn test_load_requires_wasm_enabled() {
+        let wat = "(module)";
+        assert!(WasmPolicyPlugin::load(wat.as_bytes(), LIMITS, &FeatureFlags::default()).is_err());
+    }
+
+    #[test]
+    fn test_limits_from_config() {
+        let limits = WasmLimits::from_config(&PerformanceConfig::default());
+        assert_eq!(limits.memory_bytes, 64 * 1024 * 1024);
+        assert_eq!(limits.fuel, DEFAULT_WASM_FUEL);
+    }
+}
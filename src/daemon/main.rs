@@ -2,12 +2,15 @@
 //!
 //! A standalone daemon that provides policy evaluation services via gRPC and HTTP APIs.
 
+use llm_policy_engine::config::TelemetryConfig;
+use llm_policy_engine::telemetry::otlp_layer;
 use llm_policy_engine::{Config, PolicyEngine, Result};
 
 use clap::Parser;
 use std::path::PathBuf;
-use tracing::{info, Level};
-use tracing_subscriber::FmtSubscriber;
+use tracing::{error, info, warn, Level};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
 
 /// Policy Engine Daemon
 #[derive(Parser, Debug)]
@@ -56,13 +59,15 @@ struct Args {
 async fn main() -> Result<()> {
     let args = Args::parse();
 
-    // Initialize logging
-    init_logging(&args.log_level, args.json_logs)?;
-
-    info!("Starting Policy Engine Daemon v{}", llm_policy_engine::VERSION);
-
-    // Load configuration
-    let mut config = Config::from_env()?;
+    // Load configuration: file (if any), then environment overrides
+    let mut config = match &args.config {
+        Some(path) => {
+            let mut config = Config::from_file(path)?;
+            config.apply_env_overrides();
+            config
+        }
+        None => Config::load()?,
+    };
 
     // Apply command line overrides
     config.server.port = args.port;
@@ -76,8 +81,28 @@ async fn main() -> Result<()> {
         config.telemetry.enabled = false;
     }
 
-    // Validate configuration
-    config.validate()?;
+    // Initialize logging, and trace export once telemetry is configured
+    init_logging(&args.log_level, args.json_logs, &config.telemetry)?;
+
+    info!("Starting Policy Engine Daemon v{}", llm_policy_engine::VERSION);
+    if let Some(path) = &args.config {
+        info!("Loaded configuration file: {:?}", path);
+    }
+
+    // Validate configuration, reporting every problem before exiting
+    match config.validate_all() {
+        Ok(warnings) => {
+            for warning in &warnings {
+                warn!("Configuration warning: {}", warning);
+            }
+        }
+        Err(errors) => {
+            for err in &errors {
+                error!("Configuration error: {}", err);
+            }
+            config.validate()?;
+        }
+    }
 
     // Build the policy engine
     let mut builder = PolicyEngine::builder()
@@ -117,11 +142,12 @@ async fn main() -> Result<()> {
     })?;
 
     info!("Shutting down Policy Engine Daemon");
+    opentelemetry::global::shutdown_tracer_provider();
     Ok(())
 }
 
 /// Initialize the logging system.
-fn init_logging(level: &str, _json_format: bool) -> Result<()> {
+fn init_logging(level: &str, _json_format: bool, telemetry: &TelemetryConfig) -> Result<()> {
     let level = match level.to_lowercase().as_str() {
         "trace" => Level::TRACE,
         "debug" => Level::DEBUG,
@@ -131,13 +157,15 @@ fn init_logging(level: &str, _json_format: bool) -> Result<()> {
         _ => Level::INFO,
     };
 
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(level)
+    let fmt = tracing_subscriber::fmt::layer()
         .with_target(true)
         .with_thread_ids(false)
         .with_file(false)
-        .with_line_number(false)
-        .finish();
+        .with_line_number(false);
+    let subscriber = tracing_subscriber::registry()
+        .with(otlp_layer(telemetry)?)
+        .with(fmt)
+        .with(LevelFilter::from_level(level));
 
     tracing::subscriber::set_global_default(subscriber).map_err(|e| {
         llm_policy_engine::Error::internal(format!("Failed to set logging subscriber: {}", e))
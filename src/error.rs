@@ -3,7 +3,10 @@
 //! This module defines all error types used throughout the crate, providing
 //! structured error handling with detailed context for debugging.
 
+use crate::integration::ValidationError;
+
 use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type alias using the crate's Error type.
@@ -50,6 +53,15 @@ pub enum Error {
         expression: Option<String>,
     },
 
+    /// Evaluation input that does not conform to the input schema
+    #[error("Invalid input: {message}")]
+    InvalidInput {
+        /// Detailed error message
+        message: String,
+        /// Field-level validation errors
+        errors: Vec<ValidationError>,
+    },
+
     /// Configuration error
     #[error("Configuration error: {message}")]
     Config {
@@ -84,6 +96,29 @@ pub enum Error {
         message: String,
     },
 
+    /// Authentication error: a missing, invalid or expired credential
+    #[error("Unauthorized: {message}")]
+    Unauthorized {
+        /// Detailed error message
+        message: String,
+    },
+
+    /// Authorization error: the subject may not perform the operation
+    #[error("Forbidden: {message}")]
+    Forbidden {
+        /// Detailed error message
+        message: String,
+    },
+
+    /// Request rejected for exceeding a rate limit
+    #[error("Rate limit exceeded: {message} (retry after {retry_after_ms}ms)")]
+    RateLimited {
+        /// Detailed error message
+        message: String,
+        /// Time in milliseconds before a retry may succeed
+        retry_after_ms: u64,
+    },
+
     /// Timeout error
     #[error("Operation timed out after {duration_ms}ms: {message}")]
     Timeout {
@@ -176,6 +211,21 @@ impl Error {
         }
     }
 
+    /// Create an invalid input error from schema validation errors.
+    pub fn invalid_input(errors: Vec<ValidationError>) -> Self {
+        let fields: Vec<_> = errors
+            .iter()
+            .map(|e| {
+                let path = if e.path.is_empty() { "/" } else { &e.path };
+                format!("{}: {}", path, e.message)
+            })
+            .collect();
+        Error::InvalidInput {
+            message: format!("does not conform to the input schema ({})", fields.join("; ")),
+            errors,
+        }
+    }
+
     /// Create a configuration error.
     pub fn config(message: impl Into<String>) -> Self {
         Error::Config {
@@ -207,6 +257,28 @@ impl Error {
         }
     }
 
+    /// Create an authentication error.
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Error::Unauthorized {
+            message: message.into(),
+        }
+    }
+
+    /// Create an authorization error.
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Error::Forbidden {
+            message: message.into(),
+        }
+    }
+
+    /// Create a rate limit error.
+    pub fn rate_limited(message: impl Into<String>, retry_after: Duration) -> Self {
+        Error::RateLimited {
+            message: message.into(),
+            retry_after_ms: retry_after.as_millis().try_into().unwrap_or(u64::MAX),
+        }
+    }
+
     /// Create a timeout error.
     pub fn timeout(message: impl Into<String>, duration_ms: u64) -> Self {
         Error::Timeout {
@@ -226,10 +298,39 @@ impl Error {
     pub fn is_recoverable(&self) -> bool {
         matches!(
             self,
-            Error::Cache { .. } | Error::Integration { .. } | Error::Timeout { .. }
+            Error::Cache { .. }
+                | Error::Integration { .. }
+                | Error::RateLimited { .. }
+                | Error::Timeout { .. }
         )
     }
 
+    /// Get how long to wait before retrying, for rate limit errors.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::RateLimited { retry_after_ms, .. } => {
+                Some(Duration::from_millis(*retry_after_ms))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the ID of the policy an evaluation error is attributed to.
+    pub fn policy_id(&self) -> Option<&str> {
+        match self {
+            Error::Evaluation { policy_id, .. } => policy_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the field-level errors of an invalid input error.
+    pub fn validation_errors(&self) -> &[ValidationError] {
+        match self {
+            Error::InvalidInput { errors, .. } => errors,
+            _ => &[],
+        }
+    }
+
     /// Get the error category for metrics.
     pub fn category(&self) -> &'static str {
         match self {
@@ -237,10 +338,14 @@ impl Error {
             Error::Parse { .. } => "parse",
             Error::Evaluation { .. } => "evaluation",
             Error::Expression { .. } => "expression",
+            Error::InvalidInput { .. } => "invalid_input",
             Error::Config { .. } => "config",
             Error::Cache { .. } => "cache",
             Error::Integration { .. } => "integration",
             Error::Telemetry { .. } => "telemetry",
+            Error::Unauthorized { .. } => "unauthorized",
+            Error::Forbidden { .. } => "forbidden",
+            Error::RateLimited { .. } => "rate_limited",
             Error::Timeout { .. } => "timeout",
             Error::Io(_) => "io",
             Error::Serialization(_) => "serialization",
@@ -301,6 +406,12 @@ mod tests {
         assert!(Error::integration("shield", "unavailable").is_recoverable());
         assert!(Error::timeout("test", 5000).is_recoverable());
         assert!(!Error::validation("test").is_recoverable());
+        assert!(!Error::unauthorized("expired token").is_recoverable());
+        assert!(!Error::forbidden("policy deletion").is_recoverable());
+
+        let limited = Error::rate_limited("too many requests", Duration::from_millis(250));
+        assert!(limited.is_recoverable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_millis(250)));
     }
 
     #[test]
@@ -0,0 +1,78 @@
+//! Authentication for integration calls.
+//!
+//! Credentials are attached to every outgoing request and are held as
+//! [`Secret`]s, redacted from `Debug` output so they never end up in logs.
+
+use crate::config::Secret;
+
+/// Authentication configuration for an upstream service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthConfig {
+    /// No authentication
+    None,
+    /// Bearer token sent in the `Authorization` header
+    Bearer(Secret<String>),
+    /// API key sent in a custom header
+    ApiKey {
+        /// Header name
+        header: String,
+        /// Header value
+        value: Secret<String>,
+    },
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl AuthConfig {
+    /// Create bearer token authentication.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::Bearer(Secret::new(token.into()))
+    }
+
+    /// Create API key authentication.
+    pub fn api_key(header: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::ApiKey {
+            header: header.into(),
+            value: Secret::new(value.into()),
+        }
+    }
+
+    /// Attach credentials to a request.
+    pub(crate) fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            AuthConfig::None => request,
+            AuthConfig::Bearer(token) => request.bearer_auth(token.expose()),
+            AuthConfig::ApiKey { header, value } => {
+                request.header(header.as_str(), value.expose().as_str())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_credentials() {
+        let bearer = AuthConfig::bearer("super-secret-token");
+        let debug = format!("{:?}", bearer);
+        assert!(!debug.contains("super-secret-token"));
+        assert_eq!(debug, "Bearer(REDACTED)");
+
+        let api_key = AuthConfig::api_key("X-API-Key", "super-secret-key");
+        let debug = format!("{:?}", api_key);
+        assert!(!debug.contains("super-secret-key"));
+        assert!(debug.contains("X-API-Key"));
+        assert!(debug.contains("REDACTED"));
+    }
+
+    #[test]
+    fn test_default_is_none() {
+        assert_eq!(AuthConfig::default(), AuthConfig::None);
+    }
+}
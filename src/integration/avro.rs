@@ -0,0 +1,276 @@
+//! Validation of JSON values against Avro schemas.
+//!
+//! Avro schemas are themselves JSON, so values are checked in-process
+//! against the schema's JSON form. Records must be objects containing every
+//! field without a default; fields a record does not declare are ignored,
+//! as Avro readers ignore them. A union accepts a value matching any of its
+//! branches. Named types (records, enums and fixed) may be referenced by
+//! name or full name anywhere in the schema.
+
+use super::schema_registry::{ValidationError, ValidationResult, ValidationSource};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Validate a value against an Avro schema.
+///
+/// Error paths are JSON Pointer locations within the value, and codes name
+/// the failed check (`type`, `required`, `enum`, `size` or `union`). Fails
+/// with a description if the schema is malformed.
+pub(crate) fn validate(schema: &Value, instance: &Value) -> Result<ValidationResult, String> {
+    let mut validator = Validator::default();
+    validator.define_names(schema, None)?;
+    validator.check(schema, instance, "")?;
+
+    let errors = validator.errors;
+    Ok(ValidationResult {
+        valid: errors.is_empty(),
+        errors,
+        warnings: Vec::new(),
+        source: ValidationSource::Local,
+    })
+}
+
+#[derive(Default)]
+struct Validator<'a> {
+    /// Named type definitions by name and full name
+    named: HashMap<String, &'a Value>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    /// Register the named types defined anywhere in `schema`.
+    fn define_names(&mut self, schema: &'a Value, namespace: Option<&str>) -> Result<(), String> {
+        match schema {
+            Value::Array(branches) => {
+                for branch in branches {
+                    self.define_names(branch, namespace)?;
+                }
+            }
+            Value::Object(object) => {
+                let kind = object.get("type").ok_or("schema object without a type")?;
+                let mut namespace = namespace.map(str::to_string);
+                if let Some("record" | "error" | "enum" | "fixed") = kind.as_str() {
+                    let name = object
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or("named type without a name")?;
+                    let full_name = match (name.contains('.'), object.get("namespace")) {
+                        (false, Some(Value::String(ns))) if !ns.is_empty() => {
+                            format!("{}.{}", ns, name)
+                        }
+                        (false, _) => match &namespace {
+                            Some(ns) if !ns.is_empty() => format!("{}.{}", ns, name),
+                            _ => name.to_string(),
+                        },
+                        (true, _) => name.to_string(),
+                    };
+                    let short_name = full_name.rsplit('.').next().unwrap_or(name);
+                    self.named.insert(short_name.to_string(), schema);
+                    namespace = full_name.rsplit_once('.').map(|(ns, _)| ns.to_string());
+                    self.named.insert(full_name, schema);
+                }
+
+                match kind.as_str() {
+                    Some("record" | "error") => {
+                        for field in fields(object)? {
+                            let ty = field.get("type").ok_or("record field without a type")?;
+                            self.define_names(ty, namespace.as_deref())?;
+                        }
+                    }
+                    Some("array") => {
+                        let items = object.get("items").ok_or("array without items")?;
+                        self.define_names(items, namespace.as_deref())?;
+                    }
+                    Some("map") => {
+                        let values = object.get("values").ok_or("map without values")?;
+                        self.define_names(values, namespace.as_deref())?;
+                    }
+                    Some(_) => {}
+                    None => self.define_names(kind, namespace.as_deref())?,
+                }
+            }
+            Value::String(_) => {}
+            _ => return Err(format!("invalid schema {}", schema)),
+        }
+        Ok(())
+    }
+
+    /// Check a value at `path` against a schema, recording mismatches.
+    fn check(&mut self, schema: &'a Value, value: &Value, path: &str) -> Result<(), String> {
+        let object = match schema {
+            Value::String(name) => return self.check_named(name, value, path),
+            Value::Array(branches) => return self.check_union(branches, value, path),
+            Value::Object(object) => object,
+            _ => return Err(format!("invalid schema {}", schema)),
+        };
+
+        let kind = object.get("type").ok_or("schema object without a type")?;
+        match kind.as_str() {
+            Some("record" | "error") => {
+                let Some(record) = value.as_object() else {
+                    self.mismatch("record", value, path);
+                    return Ok(());
+                };
+                for field in fields(object)? {
+                    let name = field
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or("record field without a name")?;
+                    let ty = field.get("type").ok_or("record field without a type")?;
+                    let field_path = format!("{}/{}", path, escape_pointer(name));
+                    match record.get(name) {
+                        Some(field_value) => self.check(ty, field_value, &field_path)?,
+                        None if field.get("default").is_some() => {}
+                        None => self.error(
+                            field_path,
+                            format!("missing required field '{}'", name),
+                            "required",
+                        ),
+                    }
+                }
+            }
+            Some("enum") => {
+                let symbols = object
+                    .get("symbols")
+                    .and_then(Value::as_array)
+                    .ok_or("enum without symbols")?;
+                match value.as_str() {
+                    Some(symbol) if symbols.iter().any(|s| s.as_str() == Some(symbol)) => {}
+                    Some(symbol) => self.error(
+                        path.to_string(),
+                        format!("'{}' is not a symbol of the enum", symbol),
+                        "enum",
+                    ),
+                    None => self.mismatch("enum", value, path),
+                }
+            }
+            Some("fixed") => {
+                let size = object
+                    .get("size")
+                    .and_then(Value::as_u64)
+                    .ok_or("fixed without a size")?;
+                match value.as_str() {
+                    Some(bytes) if bytes.chars().count() as u64 == size => {}
+                    Some(bytes) => self.error(
+                        path.to_string(),
+                        format!("expected {} bytes, found {}", size, bytes.chars().count()),
+                        "size",
+                    ),
+                    None => self.mismatch("fixed", value, path),
+                }
+            }
+            Some("array") => {
+                let items = object.get("items").ok_or("array without items")?;
+                let Some(elements) = value.as_array() else {
+                    self.mismatch("array", value, path);
+                    return Ok(());
+                };
+                for (index, element) in elements.iter().enumerate() {
+                    self.check(items, element, &format!("{}/{}", path, index))?;
+                }
+            }
+            Some("map") => {
+                let values = object.get("values").ok_or("map without values")?;
+                let Some(entries) = value.as_object() else {
+                    self.mismatch("map", value, path);
+                    return Ok(());
+                };
+                for (key, entry) in entries {
+                    self.check(values, entry, &format!("{}/{}", path, escape_pointer(key)))?;
+                }
+            }
+            // Primitive types written as objects, possibly with a logical type.
+            _ => self.check(kind, value, path)?,
+        }
+        Ok(())
+    }
+
+    /// Check a value against a primitive or named type.
+    fn check_named(&mut self, name: &str, value: &Value, path: &str) -> Result<(), String> {
+        let matches = match name {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "int" => value.as_i64().is_some_and(|n| i32::try_from(n).is_ok()),
+            "long" => value.as_i64().is_some(),
+            "float" | "double" => value.is_number(),
+            "bytes" | "string" => value.is_string(),
+            _ => {
+                let schema = self
+                    .named
+                    .get(name)
+                    .or_else(|| self.named.get(name.rsplit('.').next().unwrap_or(name)))
+                    .copied()
+                    .ok_or_else(|| format!("unknown type '{}'", name))?;
+                return self.check(schema, value, path);
+            }
+        };
+        if !matches {
+            self.mismatch(name, value, path);
+        }
+        Ok(())
+    }
+
+    /// Check a value against the branches of a union.
+    fn check_union(
+        &mut self,
+        branches: &'a [Value],
+        value: &Value,
+        path: &str,
+    ) -> Result<(), String> {
+        for branch in branches {
+            let errors = self.errors.len();
+            self.check(branch, value, path)?;
+            if self.errors.len() == errors {
+                return Ok(());
+            }
+            self.errors.truncate(errors);
+        }
+        self.error(
+            path.to_string(),
+            format!("{} matches no branch of the union", kind(value)),
+            "union",
+        );
+        Ok(())
+    }
+
+    fn mismatch(&mut self, expected: &str, value: &Value, path: &str) {
+        self.error(
+            path.to_string(),
+            format!("expected {}, found {}", expected, kind(value)),
+            "type",
+        );
+    }
+
+    fn error(&mut self, path: String, message: String, code: &str) {
+        self.errors.push(ValidationError {
+            path,
+            message,
+            code: Some(code.to_string()),
+        });
+    }
+}
+
+/// Get the fields of a record schema.
+fn fields(record: &serde_json::Map<String, Value>) -> Result<&Vec<Value>, String> {
+    record
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "record without fields".to_string())
+}
+
+/// Name of a JSON value's kind, for error messages.
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escape a key for use as a JSON Pointer segment.
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
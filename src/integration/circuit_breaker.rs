@@ -0,0 +1,222 @@
+//! Circuit breaker for integration calls.
+//!
+//! The breaker tracks consecutive failures against an upstream service and
+//! opens after a configurable threshold. While open, calls are rejected
+//! immediately instead of waiting for the upstream timeout. After a cooldown
+//! the breaker becomes half-open and lets a single trial call through; a
+//! successful trial closes the breaker again.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Circuit breaker configuration.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures before the breaker opens
+    pub failure_threshold: u32,
+    /// Time the breaker stays open before allowing a trial call
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Set the failure threshold.
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// Set the cooldown window.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+/// Circuit breaker state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls flow normally
+    Closed,
+    /// Calls are rejected until the cooldown elapses
+    Open,
+    /// A trial call is allowed to probe the upstream
+    HalfOpen,
+}
+
+impl Default for CircuitState {
+    fn default() -> Self {
+        Self::Closed
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_started_at: Option<Instant>,
+}
+
+/// Circuit breaker shared across clones of a client.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Arc<Mutex<BreakerState>>,
+}
+
+impl CircuitBreaker {
+    /// Create a new closed circuit breaker.
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Arc::new(Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                trial_started_at: None,
+            })),
+        }
+    }
+
+    /// Get the current state, accounting for an elapsed cooldown.
+    pub(crate) fn state(&self) -> CircuitState {
+        let mut inner = self.inner.lock();
+        self.refresh(&mut inner);
+        inner.state
+    }
+
+    /// Check whether a call may proceed.
+    pub(crate) fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock();
+        self.refresh(&mut inner);
+
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => {
+                // Only one trial at a time; a trial that never reported back
+                // (e.g. a cancelled future) is superseded after a cooldown.
+                let trial_active = inner
+                    .trial_started_at
+                    .map(|started| started.elapsed() < self.config.cooldown)
+                    .unwrap_or(false);
+                if trial_active {
+                    false
+                } else {
+                    inner.trial_started_at = Some(Instant::now());
+                    true
+                }
+            }
+        }
+    }
+
+    /// Record a successful call.
+    pub(crate) fn record_success(&self) {
+        let mut inner = self.inner.lock();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.trial_started_at = None;
+    }
+
+    /// Record a failed call.
+    pub(crate) fn record_failure(&self) {
+        let mut inner = self.inner.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        let trip = inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.config.failure_threshold;
+        if trip {
+            if inner.state != CircuitState::Open {
+                tracing::warn!(
+                    failures = inner.consecutive_failures,
+                    cooldown_ms = self.config.cooldown.as_millis() as u64,
+                    "Integration circuit breaker opened"
+                );
+            }
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            inner.trial_started_at = None;
+        }
+    }
+
+    fn refresh(&self, inner: &mut BreakerState) {
+        if inner.state == CircuitState::Open {
+            let cooled_down = inner
+                .opened_at
+                .map(|opened| opened.elapsed() >= self.config.cooldown)
+                .unwrap_or(true);
+            if cooled_down {
+                inner.state = CircuitState::HalfOpen;
+                inner.trial_started_at = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            CircuitBreakerConfig::default()
+                .with_failure_threshold(2)
+                .with_cooldown(cooldown),
+        )
+    }
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = breaker(Duration::from_secs(60));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.try_acquire());
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = breaker(Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_allows_single_trial() {
+        let breaker = breaker(Duration::ZERO);
+        breaker.record_failure();
+        breaker.record_failure();
+
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.try_acquire());
+        breaker.record_failure();
+        assert_eq!(breaker.inner.lock().state, CircuitState::Open);
+    }
+
+    #[test]
+    fn test_shared_across_clones() {
+        let breaker = breaker(Duration::from_secs(60));
+        let clone = breaker.clone();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(clone.state(), CircuitState::Open);
+    }
+}
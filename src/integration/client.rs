@@ -1,66 +1,301 @@
 //! Base integration client functionality.
 
-use crate::Result;
+use super::auth::AuthConfig;
+use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+use super::config_manager::RateLimitConfig;
+use super::error::IntegrationError;
+use super::health::DEFAULT_HEALTH_CHECK_TIMEOUT;
+use super::logging::RequestLogging;
+use super::metrics::{path_label, MetricsRecorder};
+use super::ndjson::{NdjsonOptions, NdjsonReader};
+use super::observatory::TraceContext;
+use super::request_id::{current_request_id, REQUEST_ID_HEADER};
+use super::retry::{parse_retry_after, retry_budget_exhausted_counter, RetryBudget, RetryPolicy};
+use super::throttle::Throttle;
+use super::transport::Transport;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::{Stream, StreamExt};
 use serde::{de::DeserializeOwned, Serialize};
-use std::time::Duration;
+use std::future::Future;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default upper bound for per-request timeout overrides.
+pub const DEFAULT_MAX_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Result from an integration call.
+pub type IntegrationResult<T> = std::result::Result<T, IntegrationError>;
+
+/// Response to a conditional GET request.
 #[derive(Debug, Clone)]
-pub enum IntegrationResult<T> {
-    /// Successful result
-    Success(T),
-    /// Service unavailable (graceful degradation)
-    Unavailable,
-    /// Error occurred
-    Error(String),
+pub enum ConditionalResponse<T> {
+    /// The resource was returned, along with its ETag if the server sent one
+    Modified {
+        /// Response body
+        value: T,
+        /// Entity tag for subsequent conditional requests
+        etag: Option<String>,
+    },
+    /// The resource has not changed since the supplied ETag
+    NotModified,
 }
 
-impl<T> IntegrationResult<T> {
-    /// Check if the result is successful.
-    pub fn is_success(&self) -> bool {
-        matches!(self, IntegrationResult::Success(_))
-    }
+/// Base client for integrations.
+///
+/// Clones share the underlying connection pool and circuit breaker state.
+#[derive(Clone)]
+pub struct IntegrationClient {
+    base_url: String,
+    timeout: Duration,
+    max_timeout: Duration,
+    health_check_timeout: Duration,
+    client: Arc<reqwest::Client>,
+    transport: Arc<dyn Transport>,
+    retry_policy: RetryPolicy,
+    retry_budget: Option<RetryBudget>,
+    circuit_breaker: Option<CircuitBreaker>,
+    throttle: Option<Throttle>,
+    offline: bool,
+    auth: AuthConfig,
+    trace_headers: Option<TraceHeaders>,
+    metrics: Option<ClientMetrics>,
+    logging: Option<Arc<RequestLogging>>,
+    compression_min_bytes: Option<usize>,
+}
 
-    /// Get the value if successful.
-    pub fn value(&self) -> Option<&T> {
-        match self {
-            IntegrationResult::Success(v) => Some(v),
-            _ => None,
-        }
-    }
+/// HTTP connection pool configuration.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Maximum idle connections kept per host
+    pub max_idle_per_host: usize,
+    /// How long an idle connection is kept before closing
+    pub idle_timeout: Duration,
+}
 
-    /// Get the value or a default.
-    pub fn unwrap_or(self, default: T) -> T {
-        match self {
-            IntegrationResult::Success(v) => v,
-            _ => default,
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: 32,
+            idle_timeout: Duration::from_secs(90),
         }
     }
 }
 
-/// Base client for integrations.
-pub struct IntegrationClient {
-    base_url: String,
-    timeout: Duration,
-    client: reqwest::Client,
-}
+impl PoolConfig {
+    /// Set the maximum idle connections per host.
+    pub fn with_max_idle_per_host(mut self, max_idle_per_host: usize) -> Self {
+        self.max_idle_per_host = max_idle_per_host;
+        self
+    }
 
-impl IntegrationClient {
-    /// Create a new integration client.
-    pub fn new(base_url: String, timeout: Duration) -> Self {
+    /// Set the idle connection timeout.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Build an HTTP client with this pool configuration.
+    ///
+    /// The client can be shared across integration clients with
+    /// [`IntegrationClient::from_http_client`].
+    pub fn build_http_client(&self) -> Arc<reqwest::Client> {
         let client = reqwest::Client::builder()
-            .timeout(timeout)
             .user_agent("LLM-Policy-Engine/1.0")
+            .pool_max_idle_per_host(self.max_idle_per_host)
+            .pool_idle_timeout(self.idle_timeout)
             .build()
             .expect("Failed to create HTTP client");
+        Arc::new(client)
+    }
+}
 
+/// Metrics reporting configuration for a client.
+#[derive(Clone)]
+struct ClientMetrics {
+    integration: String,
+    path_templates: &'static [&'static str],
+    recorder: Arc<dyn MetricsRecorder>,
+}
+
+/// W3C trace context headers attached to outgoing requests.
+#[derive(Debug, Clone)]
+struct TraceHeaders {
+    traceparent: String,
+    tracestate: Option<String>,
+}
+
+impl IntegrationClient {
+    /// Create a new integration client with its own connection pool.
+    pub fn new(base_url: String, timeout: Duration) -> Self {
+        Self::from_http_client(base_url, timeout, PoolConfig::default().build_http_client())
+    }
+
+    /// Create an integration client on a shared HTTP client.
+    ///
+    /// Clients created from the same HTTP client share its connection pool.
+    pub fn from_http_client(
+        base_url: String,
+        timeout: Duration,
+        client: Arc<reqwest::Client>,
+    ) -> Self {
         Self {
             base_url,
             timeout,
+            max_timeout: DEFAULT_MAX_REQUEST_TIMEOUT,
+            health_check_timeout: DEFAULT_HEALTH_CHECK_TIMEOUT,
+            transport: client.clone(),
             client,
+            retry_policy: RetryPolicy::default(),
+            retry_budget: None,
+            circuit_breaker: None,
+            throttle: None,
+            offline: false,
+            auth: AuthConfig::None,
+            trace_headers: None,
+            metrics: None,
+            logging: None,
+            compression_min_bytes: None,
         }
     }
 
+    /// Use a dedicated connection pool with the given configuration.
+    pub fn with_pool_config(mut self, pool: PoolConfig) -> Self {
+        self.client = pool.build_http_client();
+        self.transport = self.client.clone();
+        self
+    }
+
+    /// Send requests through a custom transport instead of the HTTP client.
+    ///
+    /// Requests are still built (URL, headers, body, timeout) as usual; only
+    /// sending is delegated. Use [`MockTransport`](super::MockTransport) to
+    /// unit test adapters without a server.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Set the retry policy for idempotent requests.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limit retries to a share of calls with a retry budget.
+    ///
+    /// Every call earns its share of a retry; once the budget is exhausted,
+    /// failures are returned without retrying until calls earn more. Clones
+    /// of the client share the budget.
+    pub fn with_retry_budget(mut self, budget: RetryBudget) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// Set the credentials attached to every request.
+    pub fn with_auth(mut self, auth: AuthConfig) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Enable a circuit breaker for this client.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Throttle requests on the client side.
+    ///
+    /// Each call takes a token from a bucket holding `burst_size` tokens that
+    /// refills at `requests_per_second`; retries of a call do not take more.
+    /// Up to `burst_size` calls wait for a token, and calls beyond that fail
+    /// with [`IntegrationError::RateLimited`]. A `429` response with
+    /// `Retry-After` also holds new calls until the window elapses. If the
+    /// configuration is disabled, only the `Retry-After` pauses apply.
+    pub fn with_client_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.throttle = Some(Throttle::new(&config));
+        self
+    }
+
+    /// Disable all network calls.
+    ///
+    /// An offline client fails every call with [`IntegrationError::Offline`]
+    /// without sending a request, and reports itself healthy.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Check if the client is in offline mode.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Set the upper bound for per-request timeout overrides.
+    pub fn with_max_timeout(mut self, max_timeout: Duration) -> Self {
+        self.max_timeout = max_timeout;
+        self
+    }
+
+    /// Set the timeout for health checks.
+    ///
+    /// Health checks use this instead of the client default timeout.
+    pub fn with_health_check_timeout(mut self, timeout: Duration) -> Self {
+        self.health_check_timeout = timeout;
+        self
+    }
+
+    /// Gzip-compress POST bodies of at least `min_bytes`.
+    ///
+    /// Compressed requests carry `Content-Encoding: gzip`; only enable this
+    /// for services that accept compressed request bodies.
+    pub fn with_compression(mut self, min_bytes: usize) -> Self {
+        self.compression_min_bytes = Some(min_bytes);
+        self
+    }
+
+    /// Report every call to a metrics recorder.
+    ///
+    /// Calls are labeled with `integration` and the matching entry of
+    /// `path_templates`; any path containing identifiers must have a template
+    /// (e.g. `/api/v1/schemas/{subject}/latest`).
+    pub fn with_metrics(
+        mut self,
+        integration: impl Into<String>,
+        path_templates: &'static [&'static str],
+        recorder: Arc<dyn MetricsRecorder>,
+    ) -> Self {
+        self.metrics = Some(ClientMetrics {
+            integration: integration.into(),
+            path_templates,
+            recorder,
+        });
+        self
+    }
+
+    /// Log every request attempt with `tracing`.
+    ///
+    /// See [`RequestLogging`] for what is logged and how bodies are redacted.
+    pub fn with_request_logging(mut self, logging: RequestLogging) -> Self {
+        self.logging = Some(Arc::new(logging));
+        self
+    }
+
+    /// Get a client that propagates a trace context on every request.
+    ///
+    /// The returned client sends `traceparent` (and `tracestate`, if set)
+    /// headers for `span_id` and shares this client's connection pool and
+    /// circuit breaker.
+    pub fn traced(&self, trace: &TraceContext, span_id: &str) -> Self {
+        let mut client = self.clone();
+        client.trace_headers = Some(TraceHeaders {
+            traceparent: trace.to_traceparent(span_id),
+            tracestate: trace.trace_state.clone(),
+        });
+        client
+    }
+
     /// Get the base URL.
     pub fn base_url(&self) -> &str {
         &self.base_url
@@ -71,67 +306,1219 @@ impl IntegrationClient {
         self.timeout
     }
 
+    /// Get the upper bound for per-request timeout overrides.
+    pub fn max_timeout(&self) -> Duration {
+        self.max_timeout
+    }
+
+    /// Check if this client shares its connection pool with `other`.
+    pub fn shares_connection_pool(&self, other: &IntegrationClient) -> bool {
+        Arc::ptr_eq(&self.client, &other.client)
+    }
+
+    /// Get the retry policy.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Get the retry budget, if any.
+    pub fn retry_budget(&self) -> Option<&RetryBudget> {
+        self.retry_budget.as_ref()
+    }
+
+    /// Get the circuit breaker state.
+    ///
+    /// Always `Closed` when no circuit breaker is configured.
+    pub fn circuit_state(&self) -> CircuitState {
+        self.circuit_breaker
+            .as_ref()
+            .map(|breaker| breaker.state())
+            .unwrap_or(CircuitState::Closed)
+    }
+
     /// Perform a GET request.
+    ///
+    /// GET requests are retried according to the configured retry policy.
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> IntegrationResult<T> {
+        self.get_inner(path, None).await
+    }
+
+    /// Perform a GET request with a per-request timeout.
+    ///
+    /// The timeout applies to each attempt and is capped at
+    /// [`max_timeout`](Self::max_timeout).
+    pub async fn get_with_timeout<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        timeout: Duration,
+    ) -> IntegrationResult<T> {
+        self.get_inner(path, Some(timeout)).await
+    }
+
+    async fn get_inner<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        timeout: Option<Duration>,
+    ) -> IntegrationResult<T> {
         let url = format!("{}{}", self.base_url, path);
+        let timeout = timeout.map(|timeout| self.bounded_timeout(timeout));
+        self.instrumented(path, async {
+            let response = self
+                .execute(|| with_timeout(self.request(reqwest::Method::GET, &url), timeout), true)
+                .await?;
+            Self::parse_response(response).await
+        })
+        .await
+    }
 
-        match self.client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<T>().await {
-                        Ok(data) => IntegrationResult::Success(data),
-                        Err(e) => IntegrationResult::Error(format!("Failed to parse response: {}", e)),
-                    }
-                } else {
-                    IntegrationResult::Error(format!("HTTP error: {}", response.status()))
-                }
+    /// Perform a GET request for a resource that may not exist.
+    ///
+    /// A `404 Not Found` or `204 No Content` response yields `Ok(None)`
+    /// instead of an error.
+    pub async fn get_optional<T: DeserializeOwned>(&self, path: &str) -> IntegrationResult<Option<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        self.instrumented(path, async {
+            let response = self.execute(|| self.request(reqwest::Method::GET, &url), true).await?;
+
+            let status = response.status();
+            if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NO_CONTENT
+            {
+                return Ok(None);
             }
-            Err(e) => {
-                if e.is_timeout() || e.is_connect() {
-                    IntegrationResult::Unavailable
-                } else {
-                    IntegrationResult::Error(format!("Request failed: {}", e))
-                }
+            Self::parse_response(response).await.map(Some)
+        })
+        .await
+    }
+
+    /// Perform a conditional GET request using `If-None-Match`.
+    ///
+    /// A `304 Not Modified` response yields [`ConditionalResponse::NotModified`].
+    pub async fn get_conditional<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        etag: Option<&str>,
+    ) -> IntegrationResult<ConditionalResponse<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        self.instrumented(path, async {
+            let response = self
+                .execute(
+                    || {
+                        let request = self.request(reqwest::Method::GET, &url);
+                        match etag {
+                            Some(etag) => request.header(reqwest::header::IF_NONE_MATCH, etag),
+                            None => request,
+                        }
+                    },
+                    true,
+                )
+                .await?;
+
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                return Ok(ConditionalResponse::NotModified);
             }
-        }
+
+            let etag = response
+                .headers()
+                .get(reqwest::header::ETAG)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let value = Self::parse_response(response).await?;
+            Ok(ConditionalResponse::Modified { value, etag })
+        })
+        .await
+    }
+
+    /// Perform a GET request for a newline-delimited JSON response.
+    ///
+    /// Items are parsed as the body arrives, so memory stays bounded however
+    /// many records the response holds. A malformed line yields an error for
+    /// that item and the stream continues; see
+    /// [`get_stream_with`](Self::get_stream_with) to stop at the first error.
+    /// A failed request or non-success status yields a single error.
+    pub fn get_stream<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        path: &'a str,
+    ) -> impl Stream<Item = IntegrationResult<T>> + 'a {
+        self.get_stream_with(path, NdjsonOptions::default())
+    }
+
+    /// Perform a GET request for a newline-delimited JSON response with
+    /// custom options.
+    pub fn get_stream_with<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        path: &'a str,
+        options: NdjsonOptions,
+    ) -> impl Stream<Item = IntegrationResult<T>> + 'a {
+        let url = format!("{}{}", self.base_url, path);
+        let response = async move {
+            self.instrumented(path, async {
+                let response = self
+                    .execute(
+                        || {
+                            self.request(reqwest::Method::GET, &url)
+                                .header(reqwest::header::ACCEPT, "application/x-ndjson")
+                        },
+                        true,
+                    )
+                    .await?;
+
+                let status = response.status();
+                if !status.is_success() {
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(IntegrationError::http_status(status.as_u16(), body));
+                }
+                Ok(response)
+            })
+            .await
+        };
+
+        futures::stream::once(response).flat_map(move |result| match result {
+            Ok(response) => NdjsonReader::new(response, &options).into_stream().left_stream(),
+            Err(e) => futures::stream::once(futures::future::ready(Err(e))).right_stream(),
+        })
     }
 
     /// Perform a POST request.
+    ///
+    /// POST requests are sent once; use [`post_idempotent`](Self::post_idempotent)
+    /// for requests that are safe to retry.
     pub async fn post<T: DeserializeOwned, B: Serialize>(
         &self,
         path: &str,
         body: &B,
     ) -> IntegrationResult<T> {
+        self.post_inner(path, body, false, None).await
+    }
+
+    /// Perform a POST request with a per-request timeout.
+    ///
+    /// The timeout is capped at [`max_timeout`](Self::max_timeout). Like
+    /// [`post`](Self::post), the request is sent once.
+    pub async fn post_with_timeout<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        timeout: Duration,
+    ) -> IntegrationResult<T> {
+        self.post_inner(path, body, false, Some(timeout)).await
+    }
+
+    /// Perform a POST request that is safe to retry.
+    ///
+    /// Use this for read-only queries and for writes the upstream service
+    /// deduplicates (e.g. events carrying a unique ID).
+    pub async fn post_idempotent<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> IntegrationResult<T> {
+        self.post_inner(path, body, true, None).await
+    }
+
+    async fn post_inner<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        retryable: bool,
+        timeout: Option<Duration>,
+    ) -> IntegrationResult<T> {
+        self.send_json(reqwest::Method::POST, path, body, retryable, timeout)
+            .await
+    }
+
+    /// Perform a PUT request.
+    ///
+    /// Like [`post`](Self::post), the request is sent once.
+    pub async fn put<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> IntegrationResult<T> {
+        self.send_json(reqwest::Method::PUT, path, body, false, None)
+            .await
+    }
+
+    /// Perform a DELETE request.
+    ///
+    /// DELETE is idempotent, so it is retried like GET. Any successful
+    /// status counts as success and the response body is ignored.
+    pub async fn delete(&self, path: &str) -> IntegrationResult<()> {
         let url = format!("{}{}", self.base_url, path);
+        self.instrumented(path, async {
+            let response = self
+                .execute(|| self.request(reqwest::Method::DELETE, &url), true)
+                .await?;
 
-        match self.client.post(&url).json(body).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<T>().await {
-                        Ok(data) => IntegrationResult::Success(data),
-                        Err(e) => IntegrationResult::Error(format!("Failed to parse response: {}", e)),
-                    }
-                } else {
-                    IntegrationResult::Error(format!("HTTP error: {}", response.status()))
-                }
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(IntegrationError::http_status(status.as_u16(), body));
             }
-            Err(e) => {
-                if e.is_timeout() || e.is_connect() {
-                    IntegrationResult::Unavailable
-                } else {
-                    IntegrationResult::Error(format!("Request failed: {}", e))
-                }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Send a request with a JSON body.
+    async fn send_json<T: DeserializeOwned, B: Serialize>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: &B,
+        retryable: bool,
+        timeout: Option<Duration>,
+    ) -> IntegrationResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let timeout = timeout.map(|timeout| self.bounded_timeout(timeout));
+        self.instrumented(path, async {
+            let (body, compressed) = self.encode_body(body)?;
+            let response = self
+                .execute(
+                    || {
+                        let mut request = self
+                            .request(method.clone(), &url)
+                            .header(reqwest::header::CONTENT_TYPE, "application/json")
+                            .body(body.clone());
+                        if compressed {
+                            request = request.header(reqwest::header::CONTENT_ENCODING, "gzip");
+                        }
+                        with_timeout(request, timeout)
+                    },
+                    retryable,
+                )
+                .await?;
+            Self::parse_response(response).await
+        })
+        .await
+    }
+
+    /// Serialize a JSON body, compressing it if it exceeds the threshold.
+    ///
+    /// Returns the encoded body and whether it was compressed.
+    fn encode_body<B: Serialize>(&self, body: &B) -> IntegrationResult<(Vec<u8>, bool)> {
+        let json = serde_json::to_vec(body)
+            .map_err(|e| IntegrationError::Request(format!("Failed to serialize body: {}", e)))?;
+
+        match self.compression_min_bytes {
+            Some(min_bytes) if json.len() >= min_bytes => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                let compressed = encoder
+                    .write_all(&json)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|e| {
+                        IntegrationError::Request(format!("Failed to compress body: {}", e))
+                    })?;
+                Ok((compressed, true))
             }
+            _ => Ok((json, false)),
         }
     }
 
     /// Check if the service is healthy.
+    ///
+    /// Health checks are never retried so they reflect the current state,
+    /// and use the short health check timeout rather than the client default.
+    /// An offline client is always healthy.
     pub async fn health_check(&self) -> bool {
+        if self.offline {
+            return true;
+        }
+
         let url = format!("{}/health", self.base_url);
+        let request = self.request(reqwest::Method::GET, &url).timeout(self.health_check_timeout);
 
-        match self.client.get(&url).send().await {
+        match self.send(request).await {
             Ok(response) => response.status().is_success(),
             Err(_) => false,
         }
     }
+
+    /// Run a call and report its outcome to the metrics recorder, if any.
+    async fn instrumented<T, F>(&self, path: &str, call: F) -> IntegrationResult<T>
+    where
+        F: Future<Output = IntegrationResult<T>>,
+    {
+        let Some(metrics) = &self.metrics else {
+            return call.await;
+        };
+
+        let started = Instant::now();
+        let result = call.await;
+        metrics.recorder.record_request(
+            &metrics.integration,
+            path_label(path, metrics.path_templates),
+            started.elapsed(),
+            result.as_ref().err(),
+        );
+        result
+    }
+
+    /// Send a request through the throttle and circuit breaker.
+    ///
+    /// Returns the final response regardless of its status; transport
+    /// failures are mapped to the matching [`IntegrationError`]. Offline
+    /// clients fail here before anything is sent.
+    async fn execute<F>(&self, build: F, retryable: bool) -> IntegrationResult<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        if self.offline {
+            return Err(IntegrationError::Offline);
+        }
+        if let Some(throttle) = &self.throttle {
+            throttle.acquire().await?;
+        }
+
+        let Some(breaker) = &self.circuit_breaker else {
+            return self.execute_with_retry(build, retryable).await;
+        };
+
+        if !breaker.try_acquire() {
+            return Err(IntegrationError::CircuitOpen);
+        }
+
+        let result = self.execute_with_retry(build, retryable).await;
+        let failed = match &result {
+            Ok(response) => RetryPolicy::is_retryable_status(response.status()),
+            Err(_) => true,
+        };
+        if failed {
+            breaker.record_failure();
+        } else {
+            breaker.record_success();
+        }
+        result
+    }
+
+    /// Send a request, retrying transient failures when `retryable` is set.
+    async fn execute_with_retry<F>(
+        &self,
+        build: F,
+        retryable: bool,
+    ) -> IntegrationResult<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let max_attempts = if retryable {
+            self.retry_policy.max_attempts.max(1)
+        } else {
+            1
+        };
+        if let Some(budget) = &self.retry_budget {
+            budget.deposit();
+        }
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.send(build()).await {
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        response
+                            .headers()
+                            .get(reqwest::header::RETRY_AFTER)
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(parse_retry_after)
+                    } else {
+                        None
+                    };
+                    if let (Some(throttle), Some(delay)) = (&self.throttle, retry_after) {
+                        throttle.pause_for(self.retry_policy.clamp_delay(delay));
+                    }
+
+                    if attempt >= max_attempts
+                        || !RetryPolicy::is_retryable_status(status)
+                        || !self.retry_allowed()
+                    {
+                        return Ok(response);
+                    }
+
+                    let delay = match retry_after {
+                        Some(delay) => self.retry_policy.clamp_delay(delay),
+                        None => self.retry_policy.backoff(attempt),
+                    };
+
+                    tracing::debug!(
+                        url = %response.url(),
+                        status = %status,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying integration request"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    let transient =
+                        matches!(e, IntegrationError::Timeout | IntegrationError::Connection(_));
+                    if !transient || attempt >= max_attempts || !self.retry_allowed() {
+                        return Err(e);
+                    }
+
+                    let delay = self.retry_policy.backoff(attempt);
+                    tracing::debug!(
+                        error = %e,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying integration request"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Take a retry from the retry budget, if any, counting retries it
+    /// does not allow.
+    fn retry_allowed(&self) -> bool {
+        let Some(budget) = &self.retry_budget else {
+            return true;
+        };
+        if budget.try_withdraw() {
+            return true;
+        }
+        let integration = self
+            .metrics
+            .as_ref()
+            .map_or("unknown", |metrics| metrics.integration.as_str());
+        retry_budget_exhausted_counter()
+            .with_label_values(&[integration])
+            .inc();
+        tracing::debug!(integration, "Retry budget exhausted, not retrying");
+        false
+    }
+
+    /// Prepare a request and send it through the transport, logging it if
+    /// request logging is enabled.
+    async fn send(&self, request: reqwest::RequestBuilder) -> IntegrationResult<reqwest::Response> {
+        let request = self.prepare(request).build()?;
+        match &self.logging {
+            Some(logging) => logging.execute(self.transport.as_ref(), request).await,
+            None => self.transport.execute(request).await,
+        }
+    }
+
+    /// Start a request with the client default timeout.
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.client.request(method, url).timeout(self.timeout)
+    }
+
+    /// Cap a per-request timeout override at the configured maximum.
+    fn bounded_timeout(&self, timeout: Duration) -> Duration {
+        timeout.min(self.max_timeout)
+    }
+
+    /// Attach credentials, trace headers and the current request ID to a
+    /// request.
+    fn prepare(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let mut request = self.auth.apply(request);
+        if let Some(request_id) = current_request_id() {
+            request = request.header(REQUEST_ID_HEADER, request_id);
+        }
+        if let Some(trace) = &self.trace_headers {
+            request = request.header("traceparent", trace.traceparent.as_str());
+            if let Some(tracestate) = &trace.tracestate {
+                request = request.header("tracestate", tracestate.as_str());
+            }
+        }
+        request
+    }
+
+    /// Map a response to a typed result based on its status.
+    async fn parse_response<T: DeserializeOwned>(response: reqwest::Response) -> IntegrationResult<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(IntegrationError::http_status(status.as_u16(), body));
+        }
+
+        let bytes = response.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(|e| IntegrationError::Deserialize(e.to_string()))
+    }
+}
+
+/// Apply an optional per-request timeout.
+fn with_timeout(
+    request: reqwest::RequestBuilder,
+    timeout: Option<Duration>,
+) -> reqwest::RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_max_attempts(3)
+            .with_base_delay(Duration::from_millis(1))
+            .with_jitter(0.0)
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_on_503() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(fast_retry());
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert!(result.is_ok());
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_does_not_retry_on_4xx() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(400))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(fast_retry());
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert!(result.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_not_retried_unless_idempotent() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(fast_retry());
+
+        let result: IntegrationResult<serde_json::Value> =
+            client.post("/resource", &serde_json::json!({})).await;
+        assert!(result.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        let result: IntegrationResult<serde_json::Value> =
+            client.post_idempotent("/resource", &serde_json::json!({})).await;
+        assert!(result.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_put_sends_json_body() {
+        use wiremock::matchers::body_json;
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/resource/1"))
+            .and(header("content-type", "application/json"))
+            .and(body_json(serde_json::json!({ "name": "updated" })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "ok": true })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5));
+        let result: serde_json::Value = client
+            .put("/resource/1", &serde_json::json!({ "name": "updated" }))
+            .await
+            .unwrap();
+        assert_eq!(result["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_delete_retried_and_accepts_empty_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/resource/1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/resource/1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(fast_retry());
+        client.delete("/resource/1").await.unwrap();
+
+        let err = client.delete("/missing").await.unwrap_err();
+        assert!(matches!(err, IntegrationError::HttpStatus { code: 404, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_client_rate_limit_sheds_burst() {
+        use crate::integration::MockTransport;
+
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/resource",
+            serde_json::json!({ "ok": true }),
+        ));
+        let client = IntegrationClient::new("http://upstream".to_string(), Duration::from_secs(5))
+            .with_transport(transport.clone())
+            .with_client_rate_limit(RateLimitConfig {
+                enabled: true,
+                requests_per_second: 20,
+                burst_size: 2,
+            });
+
+        // Two calls fit the bucket, two more queue for tokens, the rest are shed.
+        let calls = (0..6).map(|_| client.get::<serde_json::Value>("/resource"));
+        let results = futures::future::join_all(calls).await;
+
+        assert!(results[..4].iter().all(|r| r.is_ok()));
+        assert!(results[4..]
+            .iter()
+            .all(|r| matches!(r, Err(IntegrationError::RateLimited))));
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_get_stream_yields_items_and_line_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/records"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "{\"id\":1}\n{\"id\":2}\n{not json}\n\n{\"id\":3}",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5));
+        let items: Vec<IntegrationResult<serde_json::Value>> =
+            client.get_stream("/records").collect().await;
+
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0], Ok(serde_json::json!({ "id": 1 })));
+        assert_eq!(items[1], Ok(serde_json::json!({ "id": 2 })));
+        assert!(matches!(
+            &items[2],
+            Err(IntegrationError::Deserialize(msg)) if msg.starts_with("line 3:")
+        ));
+        assert_eq!(items[3], Ok(serde_json::json!({ "id": 3 })));
+
+        let options = NdjsonOptions {
+            fail_fast: true,
+            ..NdjsonOptions::default()
+        };
+        let items: Vec<IntegrationResult<serde_json::Value>> =
+            client.get_stream_with("/records", options).collect().await;
+        assert_eq!(items.len(), 3);
+        assert!(items[2].is_err());
+
+        let items: Vec<IntegrationResult<serde_json::Value>> =
+            client.get_stream("/missing").collect().await;
+        assert!(matches!(items[..], [Err(IntegrationError::HttpStatus { code: 404, .. })]));
+    }
+
+    #[tokio::test]
+    async fn test_offline_client_sends_nothing() {
+        use crate::integration::MockTransport;
+
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/resource",
+            serde_json::json!({ "ok": true }),
+        ));
+        let client = IntegrationClient::new("http://upstream".to_string(), Duration::from_secs(5))
+            .with_transport(transport.clone())
+            .with_offline(true);
+
+        let err = client.get::<serde_json::Value>("/resource").await.unwrap_err();
+        assert_eq!(err, IntegrationError::Offline);
+        let err = client
+            .post::<serde_json::Value, _>("/resource", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(err, IntegrationError::Offline);
+        assert_eq!(client.delete("/resource/1").await.unwrap_err(), IntegrationError::Offline);
+
+        assert!(client.health_check().await);
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_http_status_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such resource"))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5));
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            IntegrationError::HttpStatus {
+                code: 404,
+                body: "no such resource".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5));
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert!(matches!(result, Err(IntegrationError::Deserialize(_))));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_millis(50))
+            .with_retry_policy(RetryPolicy::none());
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert_eq!(result.unwrap_err(), IntegrationError::Timeout);
+    }
+
+    #[tokio::test]
+    async fn test_per_request_timeout_override() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/fast"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(30))
+            .with_retry_policy(RetryPolicy::none());
+
+        let result: IntegrationResult<serde_json::Value> =
+            client.get_with_timeout("/slow", Duration::from_millis(50)).await;
+        assert_eq!(result.unwrap_err(), IntegrationError::Timeout);
+
+        let result: IntegrationResult<serde_json::Value> = client
+            .post_with_timeout("/fast", &serde_json::json!({}), Duration::from_millis(500))
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_override_bounded_by_max() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(30))
+            .with_retry_policy(RetryPolicy::none())
+            .with_max_timeout(Duration::from_millis(50));
+
+        let result: IntegrationResult<serde_json::Value> =
+            client.get_with_timeout("/slow", Duration::from_secs(30)).await;
+        assert_eq!(result.unwrap_err(), IntegrationError::Timeout);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_uses_short_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(30))
+            .with_health_check_timeout(Duration::from_millis(50));
+        assert!(!client.health_check().await);
+
+        let client = client.with_health_check_timeout(Duration::from_secs(5));
+        assert!(client.health_check().await);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_recorded_by_path_template() {
+        use crate::integration::PrometheusRecorder;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/items/a"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/items/b"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let registry = prometheus::Registry::new();
+        let recorder = Arc::new(PrometheusRecorder::new(&registry).unwrap());
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_metrics("test", &["/api/v1/items/{id}"], recorder.clone());
+
+        let ok: IntegrationResult<serde_json::Value> = client.get("/api/v1/items/a").await;
+        assert!(ok.is_ok());
+        let missing: IntegrationResult<serde_json::Value> = client.get("/api/v1/items/b").await;
+        assert!(missing.is_err());
+
+        assert_eq!(recorder.request_count("test", "/api/v1/items/{id}"), 2);
+        assert_eq!(recorder.error_count("test", "/api/v1/items/{id}", "http_status"), 1);
+        assert_eq!(recorder.request_count("test", "/api/v1/items/a"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_post_compresses_large_bodies() {
+        use std::io::Read;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client =
+            IntegrationClient::new(server.uri(), Duration::from_secs(5)).with_compression(1024);
+
+        let events: Vec<_> = (0..200)
+            .map(|i| serde_json::json!({"event_id": format!("evt-{}", i), "decision": "allow"}))
+            .collect();
+        let large = serde_json::json!({ "events": events });
+        let result: IntegrationResult<serde_json::Value> = client.post("/batch", &large).await;
+        assert!(result.is_ok());
+
+        let small = serde_json::json!({"events": []});
+        let result: IntegrationResult<serde_json::Value> = client.post("/batch", &small).await;
+        assert!(result.is_ok());
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+
+        let compressed = &requests[0];
+        assert_eq!(compressed.headers.get("content-encoding").unwrap(), "gzip");
+        assert!(compressed.body.len() < serde_json::to_vec(&large).unwrap().len());
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(compressed.body.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&decoded).unwrap();
+        assert_eq!(payload, large);
+
+        let uncompressed = &requests[1];
+        assert!(uncompressed.headers.get("content-encoding").is_none());
+        let payload: serde_json::Value = serde_json::from_slice(&uncompressed.body).unwrap();
+        assert_eq!(payload, small);
+    }
+
+    #[tokio::test]
+    async fn test_connection_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = IntegrationClient::new(uri, Duration::from_secs(5))
+            .with_retry_policy(RetryPolicy::none());
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert!(matches!(result, Err(IntegrationError::Connection(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_optional_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5));
+        let result: IntegrationResult<Option<serde_json::Value>> =
+            client.get_optional("/resource").await;
+
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .and(header("authorization", "Bearer token-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_auth(AuthConfig::bearer("token-123"));
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[derive(Clone, Default)]
+    struct LogCapture(Arc<parking_lot::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogCapture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for LogCapture {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_logging_redacts_secrets() {
+        use tracing::Instrument;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/config/db"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "key": "db.password",
+                "value": "hunter2",
+                "value_type": "secret"
+            })))
+            .mount(&server)
+            .await;
+
+        let logs = LogCapture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(logs.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_auth(AuthConfig::bearer("token-123"))
+            .with_request_logging(RequestLogging {
+                log_bodies: true,
+                redact_paths: vec!["$.credentials.password".to_string()],
+                path_templates: &["/api/v1/config/{key}"],
+            });
+        let body = serde_json::json!({"credentials": {"user": "svc", "password": "p4ss"}});
+        let value: serde_json::Value = client
+            .post("/api/v1/config/db", &body)
+            .instrument(tracing::info_span!("evaluate"))
+            .await
+            .unwrap();
+
+        // The caller still sees the unredacted response.
+        assert_eq!(value["value"], "hunter2");
+
+        let output = String::from_utf8(logs.0.lock().clone()).unwrap();
+        let span = "evaluate:integration_request{method=POST path=/api/v1/config/{key}}";
+        assert!(output.contains(span));
+        assert!(output.contains("status=200"));
+        assert!(output.contains(r#""user":"svc""#));
+        assert!(output.contains(r#""password":"***""#));
+        assert!(output.contains(r#""value":"***""#));
+        for secret in ["p4ss", "hunter2", "token-123"] {
+            assert!(!output.contains(secret), "{} leaked into logs:\n{}", secret, output);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_auth_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/resource"))
+            .and(header("x-api-key", "key-456"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_auth(AuthConfig::api_key("X-API-Key", "key-456"));
+        let result: IntegrationResult<serde_json::Value> =
+            client.post("/resource", &serde_json::json!({})).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_trace_headers_injected() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .and(header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ))
+            .and(header("tracestate", "vendor=abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut trace = TraceContext::new("4bf92f3577b34da6a3ce929d0e0e4736".to_string());
+        trace.trace_state = Some("vendor=abc".to_string());
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5));
+        let traced = client.traced(&trace, "00f067aa0ba902b7");
+        let result: IntegrationResult<serde_json::Value> = traced.get("/resource").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_lifecycle() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(RetryPolicy::none())
+            .with_circuit_breaker(
+                CircuitBreakerConfig::default()
+                    .with_failure_threshold(2)
+                    .with_cooldown(Duration::from_millis(100)),
+            );
+        let observer = client.clone();
+
+        // Closed -> Open
+        for _ in 0..2 {
+            let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+            assert!(result.is_err());
+        }
+        assert_eq!(observer.circuit_state(), CircuitState::Open);
+
+        // Open: short-circuited without reaching the server
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+        assert_eq!(result.unwrap_err(), IntegrationError::CircuitOpen);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        // Open -> HalfOpen
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(observer.circuit_state(), CircuitState::HalfOpen);
+
+        // HalfOpen -> Closed
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+        assert!(result.is_ok());
+        assert_eq!(observer.circuit_state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_on_429() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(fast_retry().with_base_delay(Duration::from_secs(30)));
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+
+        assert!(result.is_ok());
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_exhausted_by_failure_storm() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let exhausted = || {
+            retry_budget_exhausted_counter()
+                .with_label_values(&["unknown"])
+                .get()
+        };
+        let before = exhausted();
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(fast_retry())
+            .with_retry_budget(RetryBudget::new(0.1).with_capacity(2));
+
+        // The first call spends the saved-up retries.
+        let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+        assert!(result.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        // Later calls fail fast instead of retrying.
+        for call in 1..=5 {
+            let result: IntegrationResult<serde_json::Value> = client.get("/resource").await;
+            assert!(result.is_err());
+            assert_eq!(server.received_requests().await.unwrap().len(), 3 + call);
+        }
+        assert!(exhausted() >= before + 5);
+        assert_eq!(client.retry_budget().unwrap().available(), 0);
+    }
 }
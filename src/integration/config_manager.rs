@@ -11,9 +11,33 @@
 //! dependency pattern: Config Manager -> Policy Engine (consumes-from).
 
 use super::client::{IntegrationClient, IntegrationResult};
-use serde::{Deserialize, Serialize};
+use super::decryptor::SecretDecryptor;
+use super::error::IntegrationError;
+use super::metrics::MetricsRecorder;
+use super::query::with_query;
+use crate::config::Secret;
+use base64::Engine;
+use futures::Stream;
+use parking_lot::Mutex;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default maximum age of a cached value served during an outage.
+pub const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(15 * 60);
+
+/// Path templates for metrics labels.
+const PATH_TEMPLATES: &[&str] = &[
+    "/api/v1/config/{namespace}/enforcement",
+    "/api/v1/config/{namespace}/thresholds",
+    "/api/v1/config/{namespace}/policy-settings",
+    "/api/v1/config/{namespace}/features",
+    "/api/v1/config/{namespace}/version",
+    "/api/v1/config/{namespace}/{key}",
+];
 
 /// Client for consuming configuration from LLM Config Manager.
 ///
@@ -23,14 +47,44 @@ pub struct ConfigManagerAdapter {
     client: IntegrationClient,
     /// Namespace for policy engine configuration
     namespace: String,
+    /// Decryptor for secret values
+    decryptor: Option<Arc<dyn SecretDecryptor>>,
+    /// Last successfully fetched settings, served when Config Manager is down
+    fallback: Mutex<FallbackCache>,
+    /// Maximum age of a fallback value
+    max_staleness: Duration,
+}
+
+/// A value kept for use when a later fetch fails.
+#[derive(Debug, Clone)]
+struct CachedValue<T> {
+    value: T,
+    fetched_at: Instant,
+}
+
+/// Last successfully fetched enforcement settings.
+#[derive(Debug, Default)]
+struct FallbackCache {
+    enforcement_params: Option<CachedValue<EnforcementParams>>,
+    rule_thresholds: Option<CachedValue<RuleThresholds>>,
+    policy_settings: Option<CachedValue<PolicySettings>>,
+    feature_flags: Option<CachedValue<FeatureFlags>>,
 }
 
 impl ConfigManagerAdapter {
     /// Create a new Config Manager adapter.
     pub fn new(base_url: String, timeout: Duration) -> Self {
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Config Manager adapter from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
         Self {
-            client: IntegrationClient::new(base_url, timeout),
+            client,
             namespace: "policy-engine".to_string(),
+            decryptor: None,
+            fallback: Mutex::new(FallbackCache::default()),
+            max_staleness: DEFAULT_MAX_STALENESS,
         }
     }
 
@@ -39,13 +93,44 @@ impl ConfigManagerAdapter {
         Self {
             client: IntegrationClient::new(base_url, timeout),
             namespace,
+            decryptor: None,
+            fallback: Mutex::new(FallbackCache::default()),
+            max_staleness: DEFAULT_MAX_STALENESS,
         }
     }
 
+    /// Set the decryptor used for secret values.
+    ///
+    /// Values returned by this adapter decrypt themselves in
+    /// [`ConfigValue::as_secret_str`].
+    pub fn with_decryptor(mut self, decryptor: Arc<dyn SecretDecryptor>) -> Self {
+        self.decryptor = Some(decryptor);
+        self
+    }
+
+    /// Set how long a cached value may be served after a failed fetch.
+    pub fn with_max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("config_manager", PATH_TEMPLATES, recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
+    }
+
     /// Get a configuration value by key.
     pub async fn get_config(&self, key: &str) -> IntegrationResult<ConfigValue> {
         let path = format!("/api/v1/config/{}/{}", self.namespace, key);
-        self.client.get(&path).await
+        let mut value: ConfigValue = self.client.get(&path).await?;
+        value.decryptor = self.decryptor.clone();
+        Ok(value)
     }
 
     /// Get multiple configuration values.
@@ -54,44 +139,247 @@ impl ConfigManagerAdapter {
             namespace: self.namespace.clone(),
             keys: keys.iter().map(|s| s.to_string()).collect(),
         };
-        self.client
+        let mut values: HashMap<String, ConfigValue> = self
+            .client
             .post("/api/v1/config/batch", &request)
-            .await
+            .await?;
+        for value in values.values_mut() {
+            value.decryptor = self.decryptor.clone();
+        }
+        Ok(values)
+    }
+
+    /// Get multiple configuration values, reporting each requested key.
+    ///
+    /// Unlike [`get_configs`](Self::get_configs), which fails if any value
+    /// is malformed and leaves callers to notice absent keys, every
+    /// requested key ends up in exactly one of the result's `resolved`,
+    /// `missing` or `errored` sets. Keys Config Manager returns without a
+    /// value count as missing. Secret values are test-decrypted when the
+    /// adapter has a decryptor, so a secret that cannot be decrypted is
+    /// reported as errored rather than failing when it is first used.
+    pub async fn get_configs_detailed(&self, keys: &[&str]) -> IntegrationResult<ConfigBatch> {
+        let request = BatchConfigRequest {
+            namespace: self.namespace.clone(),
+            keys: keys.iter().map(|s| s.to_string()).collect(),
+        };
+        let mut values: HashMap<String, serde_json::Value> =
+            self.client.post("/api/v1/config/batch", &request).await?;
+
+        let mut batch = ConfigBatch::default();
+        for key in keys {
+            let key = key.to_string();
+            let value = match values.remove(&key) {
+                None | Some(serde_json::Value::Null) => {
+                    batch.missing.push(key);
+                    continue;
+                }
+                Some(value) => value,
+            };
+            let mut value: ConfigValue = match serde_json::from_value(value) {
+                Ok(value) => value,
+                Err(e) => {
+                    let error = IntegrationError::Deserialize(e.to_string());
+                    batch.errored.insert(key, error);
+                    continue;
+                }
+            };
+            value.decryptor = self.decryptor.clone();
+            if value.is_sensitive() && value.decryptor.is_some() {
+                if let Err(e) = value.as_secret_str() {
+                    batch.errored.insert(key, e);
+                    continue;
+                }
+            }
+            batch.resolved.insert(key, value);
+        }
+        Ok(batch)
     }
 
     /// Get all enforcement parameters for policy evaluation.
     pub async fn get_enforcement_params(&self) -> IntegrationResult<EnforcementParams> {
         let path = format!("/api/v1/config/{}/enforcement", self.namespace);
-        self.client.get(&path).await
+        let params = self.client.get(&path).await?;
+        self.remember(|cache| &mut cache.enforcement_params, &params);
+        Ok(params)
+    }
+
+    /// Get enforcement parameters, falling back to the last fetched value.
+    ///
+    /// Returns the value and whether it is stale. A stale value is only
+    /// returned if it is younger than the configured max staleness;
+    /// otherwise the fetch error is returned.
+    pub async fn get_enforcement_params_or_cached(
+        &self,
+    ) -> IntegrationResult<(EnforcementParams, bool)> {
+        let result = self.get_enforcement_params().await;
+        self.or_cached(result, "enforcement", |cache| &cache.enforcement_params)
     }
 
     /// Get rule threshold configuration.
     pub async fn get_rule_thresholds(&self) -> IntegrationResult<RuleThresholds> {
         let path = format!("/api/v1/config/{}/thresholds", self.namespace);
-        self.client.get(&path).await
+        let thresholds = self.client.get(&path).await?;
+        self.remember(|cache| &mut cache.rule_thresholds, &thresholds);
+        Ok(thresholds)
+    }
+
+    /// Get rule thresholds, falling back to the last fetched value.
+    ///
+    /// See [`get_enforcement_params_or_cached`](Self::get_enforcement_params_or_cached).
+    pub async fn get_rule_thresholds_or_cached(&self) -> IntegrationResult<(RuleThresholds, bool)> {
+        let result = self.get_rule_thresholds().await;
+        self.or_cached(result, "thresholds", |cache| &cache.rule_thresholds)
     }
 
     /// Get dynamic policy settings.
     pub async fn get_policy_settings(&self) -> IntegrationResult<PolicySettings> {
         let path = format!("/api/v1/config/{}/policy-settings", self.namespace);
-        self.client.get(&path).await
+        let settings = self.client.get(&path).await?;
+        self.remember(|cache| &mut cache.policy_settings, &settings);
+        Ok(settings)
+    }
+
+    /// Get policy settings, falling back to the last fetched value.
+    ///
+    /// See [`get_enforcement_params_or_cached`](Self::get_enforcement_params_or_cached).
+    pub async fn get_policy_settings_or_cached(&self) -> IntegrationResult<(PolicySettings, bool)> {
+        let result = self.get_policy_settings().await;
+        self.or_cached(result, "policy-settings", |cache| &cache.policy_settings)
     }
 
     /// Get feature flags for policy engine.
     pub async fn get_feature_flags(&self) -> IntegrationResult<FeatureFlags> {
         let path = format!("/api/v1/config/{}/features", self.namespace);
-        self.client.get(&path).await
+        let flags = self.client.get(&path).await?;
+        self.remember(|cache| &mut cache.feature_flags, &flags);
+        Ok(flags)
+    }
+
+    /// Get feature flags, falling back to the last fetched value.
+    ///
+    /// See [`get_enforcement_params_or_cached`](Self::get_enforcement_params_or_cached).
+    pub async fn get_feature_flags_or_cached(&self) -> IntegrationResult<(FeatureFlags, bool)> {
+        let result = self.get_feature_flags().await;
+        self.or_cached(result, "features", |cache| &cache.feature_flags)
+    }
+
+    /// Store a successfully fetched value for later fallback.
+    fn remember<T: Clone>(
+        &self,
+        slot: impl FnOnce(&mut FallbackCache) -> &mut Option<CachedValue<T>>,
+        value: &T,
+    ) {
+        let mut cache = self.fallback.lock();
+        *slot(&mut cache) = Some(CachedValue {
+            value: value.clone(),
+            fetched_at: Instant::now(),
+        });
+    }
+
+    /// Fall back to a cached value when a fetch failed.
+    fn or_cached<T: Clone>(
+        &self,
+        result: IntegrationResult<T>,
+        name: &str,
+        slot: impl FnOnce(&FallbackCache) -> &Option<CachedValue<T>>,
+    ) -> IntegrationResult<(T, bool)> {
+        let error = match result {
+            Ok(value) => return Ok((value, false)),
+            Err(e) => e,
+        };
+
+        let cache = self.fallback.lock();
+        match slot(&cache) {
+            Some(cached) if cached.fetched_at.elapsed() <= self.max_staleness => {
+                tracing::warn!(
+                    namespace = %self.namespace,
+                    config = name,
+                    error = %error,
+                    age_ms = cached.fetched_at.elapsed().as_millis() as u64,
+                    "Config Manager unavailable, using cached value"
+                );
+                Ok((cached.value.clone(), true))
+            }
+            _ => Err(error),
+        }
     }
 
-    /// Subscribe to configuration changes (returns current version).
+    /// Get the current configuration version.
     ///
-    /// In a full implementation, this would set up a watch/subscription.
-    /// For now, it returns the current configuration version for polling.
+    /// Use [`watch_config`](Self::watch_config) to be notified of changes.
     pub async fn get_config_version(&self) -> IntegrationResult<ConfigVersion> {
         let path = format!("/api/v1/config/{}/version", self.namespace);
         self.client.get(&path).await
     }
 
+    /// Watch for configuration changes.
+    ///
+    /// Returns a stream that yields whenever the configuration version
+    /// increments. The first observed version is treated as the baseline.
+    /// Errors are retried with exponential backoff; the last-seen version is
+    /// kept across failures. Dropping the stream stops the watch. The stream
+    /// ends immediately if the client is offline.
+    pub fn watch_config(&self) -> impl Stream<Item = ConfigVersion> + '_ {
+        self.watch_config_with(WatchOptions::default())
+    }
+
+    /// Watch for configuration changes with custom options.
+    pub fn watch_config_with(
+        &self,
+        options: WatchOptions,
+    ) -> impl Stream<Item = ConfigVersion> + '_ {
+        let state = WatchState {
+            last_version: options.since,
+            delay: None,
+            backoff: options.initial_backoff,
+        };
+
+        futures::stream::unfold(state, move |mut state| {
+            let options = options.clone();
+            async move {
+                loop {
+                    if let Some(delay) = state.delay.take() {
+                        tokio::time::sleep(delay).await;
+                    }
+
+                    let since = state.last_version.map(|version| version.to_string());
+                    let path = with_query(
+                        &format!("/api/v1/config/{}/version", self.namespace),
+                        &[("since", since.as_deref())],
+                    );
+
+                    match self.client.get::<ConfigVersion>(&path).await {
+                        Ok(current) => {
+                            state.backoff = options.initial_backoff;
+                            state.delay = Some(options.poll_interval);
+
+                            match state.last_version {
+                                Some(last) if current.version > last => {
+                                    state.last_version = Some(current.version);
+                                    return Some((current, state));
+                                }
+                                Some(_) => {}
+                                None => state.last_version = Some(current.version),
+                            }
+                        }
+                        Err(IntegrationError::Offline) => return None,
+                        Err(e) => {
+                            tracing::warn!(
+                                namespace = %self.namespace,
+                                error = %e,
+                                retry_in_ms = state.backoff.as_millis() as u64,
+                                "Config watch poll failed"
+                            );
+                            state.delay = Some(state.backoff);
+                            state.backoff = (state.backoff * 2).min(options.max_backoff);
+                        }
+                    }
+                }
+            }
+        })
+    }
+
     /// Validate configuration access (RBAC check).
     pub async fn validate_access(&self, request: &AccessValidationRequest) -> IntegrationResult<AccessValidationResult> {
         self.client
@@ -106,7 +394,10 @@ impl ConfigManagerAdapter {
 }
 
 /// A configuration value from Config Manager.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The `Debug` and serialized output redact values of type
+/// [`ConfigValueType::Secret`], like those of a [`Secret`].
+#[derive(Clone, Deserialize)]
 pub struct ConfigValue {
     /// Configuration key
     pub key: String,
@@ -117,6 +408,144 @@ pub struct ConfigValue {
     /// Configuration metadata
     #[serde(default)]
     pub metadata: ConfigMetadata,
+    /// Decryptor attached by the adapter for secret values
+    #[serde(skip)]
+    decryptor: Option<Arc<dyn SecretDecryptor>>,
+}
+
+impl ConfigValue {
+    /// Create a configuration value.
+    pub fn new(
+        key: impl Into<String>,
+        value: serde_json::Value,
+        value_type: ConfigValueType,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            value,
+            value_type,
+            metadata: ConfigMetadata::default(),
+            decryptor: None,
+        }
+    }
+
+    /// Check if the value is sensitive and must not be logged.
+    pub fn is_sensitive(&self) -> bool {
+        self.value_type == ConfigValueType::Secret
+    }
+
+    /// Get the value as a string.
+    ///
+    /// Also works for [`ConfigValueType::Secret`] values; check
+    /// [`is_sensitive`](Self::is_sensitive) before logging the result.
+    pub fn as_str(&self) -> IntegrationResult<&str> {
+        self.expect_type(&[ConfigValueType::String, ConfigValueType::Secret], "string")?;
+        self.value.as_str().ok_or_else(|| self.mismatch("string"))
+    }
+
+    /// Get the value as a secret string, decrypting it if needed.
+    ///
+    /// [`ConfigValueType::Secret`] values are base64-decoded and decrypted
+    /// when the adapter has a decryptor; otherwise they are returned as sent.
+    /// String values are returned unchanged.
+    pub fn as_secret_str(&self) -> IntegrationResult<Secret<String>> {
+        let raw = self.as_str()?;
+        let decryptor = match (&self.decryptor, self.value_type) {
+            (Some(decryptor), ConfigValueType::Secret) => decryptor,
+            _ => return Ok(Secret::new(raw.to_string())),
+        };
+
+        let ciphertext = base64::engine::general_purpose::STANDARD
+            .decode(raw)
+            .map_err(|e| IntegrationError::Decryption(format!("invalid base64: {}", e)))?;
+        let plaintext = decryptor.decrypt(&ciphertext)?;
+        String::from_utf8(plaintext)
+            .map(Secret::new)
+            .map_err(|_| IntegrationError::Decryption("plaintext is not UTF-8".to_string()))
+    }
+
+    /// Get the value as an integer.
+    pub fn as_i64(&self) -> IntegrationResult<i64> {
+        self.expect_type(&[ConfigValueType::Integer], "integer")?;
+        self.value.as_i64().ok_or_else(|| self.mismatch("integer"))
+    }
+
+    /// Get the value as a float. Integer values are widened.
+    pub fn as_f64(&self) -> IntegrationResult<f64> {
+        self.expect_type(&[ConfigValueType::Float, ConfigValueType::Integer], "float")?;
+        self.value.as_f64().ok_or_else(|| self.mismatch("float"))
+    }
+
+    /// Get the value as a boolean.
+    pub fn as_bool(&self) -> IntegrationResult<bool> {
+        self.expect_type(&[ConfigValueType::Boolean], "boolean")?;
+        self.value.as_bool().ok_or_else(|| self.mismatch("boolean"))
+    }
+
+    /// Get the value as a JSON object.
+    pub fn as_object(&self) -> IntegrationResult<&serde_json::Map<String, serde_json::Value>> {
+        self.expect_type(&[ConfigValueType::Object], "object")?;
+        self.value.as_object().ok_or_else(|| self.mismatch("object"))
+    }
+
+    fn expect_type(&self, allowed: &[ConfigValueType], expected: &str) -> IntegrationResult<()> {
+        if allowed.contains(&self.value_type) {
+            Ok(())
+        } else {
+            Err(IntegrationError::TypeMismatch {
+                key: self.key.clone(),
+                expected: expected.to_string(),
+                actual: format!("{:?}", self.value_type).to_lowercase(),
+            })
+        }
+    }
+
+    fn mismatch(&self, expected: &str) -> IntegrationError {
+        let actual = match &self.value {
+            serde_json::Value::Null => "null",
+            serde_json::Value::Bool(_) => "boolean",
+            serde_json::Value::Number(_) => "number",
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Array(_) => "array",
+            serde_json::Value::Object(_) => "object",
+        };
+        IntegrationError::TypeMismatch {
+            key: self.key.clone(),
+            expected: expected.to_string(),
+            actual: format!("JSON {}", actual),
+        }
+    }
+}
+
+impl fmt::Debug for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ConfigValue");
+        debug.field("key", &self.key);
+        if self.value_type == ConfigValueType::Secret {
+            debug.field("value", &Secret::new(&self.value));
+        } else {
+            debug.field("value", &self.value);
+        }
+        debug
+            .field("value_type", &self.value_type)
+            .field("metadata", &self.metadata)
+            .finish()
+    }
+}
+
+impl Serialize for ConfigValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut value = serializer.serialize_struct("ConfigValue", 4)?;
+        value.serialize_field("key", &self.key)?;
+        if self.value_type == ConfigValueType::Secret {
+            value.serialize_field("value", &Secret::new(&self.value))?;
+        } else {
+            value.serialize_field("value", &self.value)?;
+        }
+        value.serialize_field("value_type", &self.value_type)?;
+        value.serialize_field("metadata", &self.metadata)?;
+        value.end()
+    }
 }
 
 /// Configuration value types.
@@ -162,6 +591,29 @@ pub struct ConfigMetadata {
     pub source: Option<String>,
 }
 
+/// Outcome of a batch configuration lookup, by requested key.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBatch {
+    /// Values of the keys that were found
+    pub resolved: HashMap<String, ConfigValue>,
+    /// Requested keys Config Manager has no value for, in request order
+    pub missing: Vec<String>,
+    /// Requested keys whose value could not be read, with the error
+    pub errored: HashMap<String, IntegrationError>,
+}
+
+impl ConfigBatch {
+    /// Check whether every requested key was resolved.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.errored.is_empty()
+    }
+
+    /// Get the value of a resolved key.
+    pub fn get(&self, key: &str) -> Option<&ConfigValue> {
+        self.resolved.get(key)
+    }
+}
+
 /// Batch configuration request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BatchConfigRequest {
@@ -175,7 +627,7 @@ pub struct EnforcementParams {
     /// Whether strict mode is enabled
     #[serde(default)]
     pub strict_mode: bool,
-    /// Default decision when no rules match
+    /// Default decision when no rules match: `allow` or `deny`
     #[serde(default = "default_decision")]
     pub default_decision: String,
     /// Maximum evaluation time in milliseconds
@@ -190,12 +642,19 @@ pub struct EnforcementParams {
     /// Rate limiting configuration
     #[serde(default)]
     pub rate_limits: RateLimitConfig,
+    /// How outcomes of matching rules are combined, unless a policy sets its own
+    #[serde(default = "default_decision_combiner")]
+    pub decision_combiner: String,
 }
 
 fn default_decision() -> String {
     "deny".to_string()
 }
 
+fn default_decision_combiner() -> String {
+    "deny_overrides".to_string()
+}
+
 fn default_max_eval_time() -> u64 {
     100
 }
@@ -213,6 +672,7 @@ impl Default for EnforcementParams {
             fail_open: false,
             audit_level: default_audit_level(),
             rate_limits: RateLimitConfig::default(),
+            decision_combiner: default_decision_combiner(),
         }
     }
 }
@@ -240,12 +700,12 @@ fn default_burst() -> u32 {
 }
 
 /// Rule threshold configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RuleThresholds {
-    /// Cost threshold for budget enforcement
+    /// Percentage of the CostOps budget requests may spend up to
     #[serde(default)]
     pub cost_threshold: f64,
-    /// Token limit threshold
+    /// Maximum estimated tokens per request
     #[serde(default)]
     pub token_limit: u64,
     /// Request rate threshold
@@ -257,6 +717,21 @@ pub struct RuleThresholds {
     /// Error rate threshold (percentage)
     #[serde(default)]
     pub error_rate_threshold: f64,
+    /// Window in seconds over which anomalies are counted per subject
+    #[serde(default = "default_anomaly_window_secs")]
+    pub anomaly_window_secs: u64,
+    /// Denies per subject within the anomaly window that signal a spike
+    /// (0 disables)
+    #[serde(default = "default_deny_spike_threshold")]
+    pub deny_spike_threshold: u32,
+    /// Shield injection flags per subject within the anomaly window
+    /// (0 disables)
+    #[serde(default = "default_injection_flag_threshold")]
+    pub injection_flag_threshold: u32,
+    /// Authentication failures per subject within the anomaly window
+    /// (0 disables)
+    #[serde(default = "default_auth_failure_threshold")]
+    pub auth_failure_threshold: u32,
     /// Custom thresholds
     #[serde(default)]
     pub custom: HashMap<String, serde_json::Value>,
@@ -270,21 +745,44 @@ impl Default for RuleThresholds {
             request_rate_limit: 1000,
             latency_threshold_ms: 5000,
             error_rate_threshold: 5.0,
+            anomaly_window_secs: default_anomaly_window_secs(),
+            deny_spike_threshold: default_deny_spike_threshold(),
+            injection_flag_threshold: default_injection_flag_threshold(),
+            auth_failure_threshold: default_auth_failure_threshold(),
             custom: HashMap::new(),
         }
     }
 }
 
+fn default_anomaly_window_secs() -> u64 {
+    60
+}
+
+fn default_deny_spike_threshold() -> u32 {
+    20
+}
+
+fn default_injection_flag_threshold() -> u32 {
+    3
+}
+
+fn default_auth_failure_threshold() -> u32 {
+    5
+}
+
 /// Dynamic policy settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicySettings {
-    /// Enabled policy namespaces
+    /// Namespaces whose policies are evaluated; empty enables all
     #[serde(default)]
     pub enabled_namespaces: Vec<String>,
-    /// Disabled policy IDs (overrides)
+    /// Disabled policy IDs; a trailing `*` disables all IDs with that prefix
     #[serde(default)]
     pub disabled_policies: Vec<String>,
-    /// Policy priority overrides
+    /// Policy IDs evaluated in shadow mode: reported but not enforced
+    #[serde(default)]
+    pub shadow_policies: Vec<String>,
+    /// Rule priority overrides, by rule ID
     #[serde(default)]
     pub priority_overrides: HashMap<String, i32>,
     /// Environment-specific settings
@@ -311,6 +809,7 @@ impl Default for PolicySettings {
         Self {
             enabled_namespaces: vec!["default".to_string()],
             disabled_policies: Vec::new(),
+            shadow_policies: Vec::new(),
             priority_overrides: HashMap::new(),
             environment: "production".to_string(),
             cache_ttl_seconds: default_cache_ttl(),
@@ -337,6 +836,9 @@ pub struct FeatureFlags {
     /// Advanced telemetry enabled
     #[serde(default = "default_true")]
     pub advanced_telemetry: bool,
+    /// Evaluation inputs validated against the input schema, if configured
+    #[serde(default = "default_true")]
+    pub input_validation: bool,
     /// Custom feature flags
     #[serde(default)]
     pub custom: HashMap<String, bool>,
@@ -354,11 +856,43 @@ impl Default for FeatureFlags {
             wasm_enabled: false,
             distributed_cache: false,
             advanced_telemetry: true,
+            input_validation: true,
             custom: HashMap::new(),
         }
     }
 }
 
+/// Options for [`ConfigManagerAdapter::watch_config_with`].
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Delay between successful polls
+    pub poll_interval: Duration,
+    /// Delay after the first failed poll
+    pub initial_backoff: Duration,
+    /// Maximum delay between failed polls
+    pub max_backoff: Duration,
+    /// Version already seen by the caller; changes after it are yielded
+    pub since: Option<u64>,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(5),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+            since: None,
+        }
+    }
+}
+
+/// Internal state of a config watch stream.
+struct WatchState {
+    last_version: Option<u64>,
+    delay: Option<Duration>,
+    backoff: Duration,
+}
+
 /// Configuration version information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigVersion {
@@ -396,6 +930,9 @@ pub struct AccessValidationResult {
     /// Applicable policies
     #[serde(default)]
     pub policies: Vec<String>,
+    /// Identity the subject resolves to, e.g. the owner of an API key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_subject: Option<String>,
 }
 
 impl Default for AccessValidationResult {
@@ -404,6 +941,7 @@ impl Default for AccessValidationResult {
             allowed: false,
             reason: None,
             policies: Vec::new(),
+            resolved_subject: None,
         }
     }
 }
@@ -412,6 +950,304 @@ impl Default for AccessValidationResult {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_watch_config_yields_on_version_bump() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let version = |v: u64| {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "version": v,
+                "modified_at": "2025-01-01T00:00:00Z"
+            }))
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/version"))
+            .respond_with(version(1))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/version"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/version"))
+            .respond_with(version(2).set_delay(Duration::from_millis(20)))
+            .mount(&server)
+            .await;
+
+        let adapter = ConfigManagerAdapter::new(server.uri(), Duration::from_secs(5));
+        let options = WatchOptions {
+            poll_interval: Duration::from_millis(10),
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let stream = adapter.watch_config_with(options);
+        futures::pin_mut!(stream);
+
+        let changed = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("watch timed out")
+            .expect("stream ended");
+        assert_eq!(changed.version, 2);
+    }
+
+    fn config_value(value: serde_json::Value, value_type: ConfigValueType) -> ConfigValue {
+        ConfigValue::new("test-key", value, value_type)
+    }
+
+    #[test]
+    fn test_config_value_accessors() {
+        let string = config_value(serde_json::json!("hello"), ConfigValueType::String);
+        assert_eq!(string.as_str().unwrap(), "hello");
+        assert!(!string.is_sensitive());
+
+        let integer = config_value(serde_json::json!(42), ConfigValueType::Integer);
+        assert_eq!(integer.as_i64().unwrap(), 42);
+        assert_eq!(integer.as_f64().unwrap(), 42.0);
+
+        let float = config_value(serde_json::json!(1.5), ConfigValueType::Float);
+        assert_eq!(float.as_f64().unwrap(), 1.5);
+
+        let boolean = config_value(serde_json::json!(true), ConfigValueType::Boolean);
+        assert!(boolean.as_bool().unwrap());
+
+        let object = config_value(serde_json::json!({"a": 1}), ConfigValueType::Object);
+        assert_eq!(object.as_object().unwrap().len(), 1);
+
+        let secret = config_value(serde_json::json!("s3cr3t"), ConfigValueType::Secret);
+        assert_eq!(secret.as_str().unwrap(), "s3cr3t");
+        assert!(secret.is_sensitive());
+    }
+
+    #[test]
+    fn test_config_value_type_mismatch() {
+        let integer = config_value(serde_json::json!(42), ConfigValueType::Integer);
+        match integer.as_bool() {
+            Err(IntegrationError::TypeMismatch { key, expected, actual }) => {
+                assert_eq!(key, "test-key");
+                assert_eq!(expected, "boolean");
+                assert_eq!(actual, "integer");
+            }
+            other => panic!("expected TypeMismatch, got {:?}", other),
+        }
+        assert!(integer.as_str().is_err());
+        assert!(integer.as_object().is_err());
+
+        // Declared type matches but the stored value does not
+        let inconsistent = config_value(serde_json::json!("42"), ConfigValueType::Integer);
+        match inconsistent.as_i64() {
+            Err(IntegrationError::TypeMismatch { actual, .. }) => assert_eq!(actual, "JSON string"),
+            other => panic!("expected TypeMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_secret_config_value_redacted() {
+        let value = ConfigValue::new(
+            "api-token",
+            serde_json::json!("tok-abc123"),
+            ConfigValueType::Secret,
+        );
+        let debug = format!("{:?}", value);
+        assert!(!debug.contains("tok-abc123"));
+        assert!(debug.contains("REDACTED"));
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["value"], "***");
+        assert_eq!(json["value_type"], "secret");
+    }
+
+    #[tokio::test]
+    async fn test_secret_values_decrypted() {
+        use crate::integration::AesGcmDecryptor;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let key = [42u8; 32];
+        let encryptor = AesGcmDecryptor::new(&key).unwrap();
+        let ciphertext = encryptor.encrypt(b"db-password").unwrap();
+        let encoded = base64::engine::general_purpose::STANDARD.encode(ciphertext);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/db-password"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "key": "db-password",
+                "value": encoded,
+                "value_type": "secret"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/region"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "key": "region",
+                "value": "eu-west-1",
+                "value_type": "string"
+            })))
+            .mount(&server)
+            .await;
+
+        let adapter = ConfigManagerAdapter::new(server.uri(), Duration::from_secs(5))
+            .with_decryptor(Arc::new(AesGcmDecryptor::new(&key).unwrap()));
+        let secret = adapter.get_config("db-password").await.unwrap();
+        assert_eq!(secret.as_secret_str().unwrap().expose(), "db-password");
+
+        // Non-secret values bypass decryption
+        let region = adapter.get_config("region").await.unwrap();
+        assert_eq!(region.as_secret_str().unwrap().expose(), "eu-west-1");
+
+        let wrong_key = ConfigManagerAdapter::new(server.uri(), Duration::from_secs(5))
+            .with_decryptor(Arc::new(AesGcmDecryptor::new(&[0u8; 32]).unwrap()));
+        let secret = wrong_key.get_config("db-password").await.unwrap();
+        assert!(matches!(
+            secret.as_secret_str(),
+            Err(IntegrationError::Decryption(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_configs_detailed_reports_each_key() {
+        use crate::integration::{AesGcmDecryptor, MockTransport};
+
+        let key = [42u8; 32];
+        let encrypt = |key: &[u8; 32], plaintext: &[u8]| {
+            let ciphertext = AesGcmDecryptor::new(key)
+                .unwrap()
+                .encrypt(plaintext)
+                .unwrap();
+            base64::engine::general_purpose::STANDARD.encode(ciphertext)
+        };
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/config/batch",
+            serde_json::json!({
+                "region": { "key": "region", "value": "eu-west-1", "value_type": "string" },
+                "db-password": {
+                    "key": "db-password",
+                    "value": encrypt(&key, b"db-password"),
+                    "value_type": "secret"
+                },
+                "api-token": {
+                    "key": "api-token",
+                    "value": encrypt(&[0u8; 32], b"tok-abc123"),
+                    "value_type": "secret"
+                },
+                "max-tokens": { "key": "max-tokens", "value": 4096, "value_type": "blob" },
+                "retired": null
+            }),
+        ));
+        let client =
+            IntegrationClient::new("http://config-manager".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        let adapter = ConfigManagerAdapter::from_client(client)
+            .with_decryptor(Arc::new(AesGcmDecryptor::new(&key).unwrap()));
+
+        let keys = [
+            "region",
+            "db-password",
+            "api-token",
+            "max-tokens",
+            "retired",
+            "absent",
+        ];
+        let batch = adapter.get_configs_detailed(&keys).await.unwrap();
+        assert!(!batch.is_complete());
+        assert_eq!(batch.get("region").unwrap().as_str().unwrap(), "eu-west-1");
+        let secret = batch.get("db-password").unwrap().as_secret_str().unwrap();
+        assert_eq!(secret.expose(), "db-password");
+        assert_eq!(batch.resolved.len(), 2);
+        assert_eq!(batch.missing, vec!["retired", "absent"]);
+        assert!(matches!(
+            batch.errored["api-token"],
+            IntegrationError::Decryption(_)
+        ));
+        assert!(matches!(
+            batch.errored["max-tokens"],
+            IntegrationError::Deserialize(_)
+        ));
+        let request = transport.requests()[0].json().unwrap();
+        assert_eq!(request["keys"], serde_json::json!(keys));
+    }
+
+    #[tokio::test]
+    async fn test_enforcement_params_fall_back_to_cache() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/enforcement"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "strict_mode": true,
+                "default_decision": "allow",
+                "max_evaluation_time_ms": 50,
+                "fail_open": false
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/enforcement"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let adapter = ConfigManagerAdapter::new(server.uri(), Duration::from_secs(5));
+
+        let (params, stale) = adapter.get_enforcement_params_or_cached().await.unwrap();
+        assert!(params.strict_mode);
+        assert!(!stale);
+
+        let (params, stale) = adapter.get_enforcement_params_or_cached().await.unwrap();
+        assert!(params.strict_mode);
+        assert_eq!(params.default_decision, "allow");
+        assert!(stale);
+
+        // Nothing cached for other settings yet
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/thresholds"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        assert!(adapter.get_rule_thresholds_or_cached().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_value_expires_after_max_staleness() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/features"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/config/policy-engine/features"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let adapter = ConfigManagerAdapter::new(server.uri(), Duration::from_secs(5))
+            .with_max_staleness(Duration::from_millis(10));
+
+        assert!(adapter.get_feature_flags_or_cached().await.is_ok());
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let error = adapter.get_feature_flags_or_cached().await.unwrap_err();
+        assert!(error.is_not_found());
+    }
+
     #[test]
     fn test_enforcement_params_default() {
         let params = EnforcementParams::default();
@@ -444,12 +1280,8 @@ mod tests {
 
     #[test]
     fn test_config_value_serialization() {
-        let config = ConfigValue {
-            key: "test.key".to_string(),
-            value: serde_json::json!(42),
-            value_type: ConfigValueType::Integer,
-            metadata: ConfigMetadata::default(),
-        };
+        let config =
+            ConfigValue::new("test.key", serde_json::json!(42), ConfigValueType::Integer);
 
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("test.key"));
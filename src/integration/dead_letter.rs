@@ -0,0 +1,235 @@
+//! Dead-letter queue for Observatory events.
+//!
+//! Batches an [`EventSink`](super::EventSink) fails to deliver are kept here
+//! with the failure reason instead of being lost. The queue is bounded by
+//! event count, evicting the oldest batches first, and can be persisted to a
+//! JSON Lines file so dead letters survive a restart. Operators drain it to
+//! replay the events once Observatory recovers.
+
+use super::observatory::PolicyEvaluationEvent;
+
+use parking_lot::Mutex;
+use prometheus::IntCounter;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Default maximum number of dead-lettered events.
+pub const DEFAULT_DEAD_LETTER_CAPACITY: usize = 10_000;
+
+/// A batch of events that could not be delivered to Observatory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Events of the failed batch
+    pub events: Vec<PolicyEvaluationEvent>,
+    /// Why the batch failed
+    pub reason: String,
+    /// When the batch failed (RFC 3339)
+    pub failed_at: String,
+}
+
+/// Bounded queue of dead letters, optionally persisted to a file.
+#[derive(Debug)]
+pub(crate) struct DeadLetterQueue {
+    capacity: usize,
+    path: Option<PathBuf>,
+    letters: Mutex<VecDeque<DeadLetter>>,
+}
+
+impl DeadLetterQueue {
+    /// Open a queue holding at most `capacity` events, loading the dead
+    /// letters persisted at `path`. A capacity of 0 disables the queue.
+    pub(crate) fn open(capacity: usize, path: Option<PathBuf>) -> Self {
+        let letters = match &path {
+            Some(path) if capacity > 0 => load(path),
+            _ => VecDeque::new(),
+        };
+        let queue = Self {
+            capacity,
+            path,
+            letters: Mutex::new(letters),
+        };
+        let mut letters = queue.letters.lock();
+        if queue.evict(&mut letters) > 0 {
+            queue.persist(&letters);
+        }
+        drop(letters);
+        queue
+    }
+
+    /// Check if failed batches are kept.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Add a failed batch, evicting the oldest batches if the queue is full.
+    pub(crate) fn push(&self, events: Vec<PolicyEvaluationEvent>, reason: String) {
+        if !self.is_enabled() || events.is_empty() {
+            return;
+        }
+
+        dead_lettered_counter().inc_by(events.len() as u64);
+        let mut letters = self.letters.lock();
+        letters.push_back(DeadLetter {
+            events,
+            reason,
+            failed_at: chrono::Utc::now().to_rfc3339(),
+        });
+        let evicted = self.evict(&mut letters);
+        if evicted > 0 {
+            tracing::warn!(
+                evicted,
+                capacity = self.capacity,
+                "Observatory dead-letter queue full, discarding oldest events"
+            );
+        }
+        self.persist(&letters);
+    }
+
+    /// Take every dead letter, oldest first.
+    pub(crate) fn drain(&self) -> Vec<DeadLetter> {
+        let mut letters = self.letters.lock();
+        let drained = letters.drain(..).collect();
+        self.persist(&letters);
+        drained
+    }
+
+    /// Number of dead-lettered events.
+    pub(crate) fn event_count(&self) -> usize {
+        event_count(&self.letters.lock())
+    }
+
+    /// Discard the oldest letters until the queue fits its capacity,
+    /// returning the number of events discarded.
+    fn evict(&self, letters: &mut VecDeque<DeadLetter>) -> usize {
+        let mut total = event_count(letters);
+        let mut evicted = 0;
+        while total > self.capacity {
+            let Some(letter) = letters.pop_front() else {
+                break;
+            };
+            total -= letter.events.len();
+            evicted += letter.events.len();
+        }
+        evicted
+    }
+
+    /// Rewrite the queue file, if any, with the current letters.
+    fn persist(&self, letters: &VecDeque<DeadLetter>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = write(path, letters) {
+            tracing::warn!(
+                path = %path.display(),
+                error = %e,
+                "Failed to persist Observatory dead-letter queue"
+            );
+        }
+    }
+}
+
+fn event_count(letters: &VecDeque<DeadLetter>) -> usize {
+    letters.iter().map(|letter| letter.events.len()).sum()
+}
+
+/// Read persisted dead letters, skipping malformed lines.
+fn load(path: &Path) -> VecDeque<DeadLetter> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return VecDeque::new(),
+        Err(e) => {
+            tracing::warn!(
+                path = %path.display(),
+                error = %e,
+                "Failed to read Observatory dead-letter queue"
+            );
+            return VecDeque::new();
+        }
+    };
+
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(letter) => Some(letter),
+            Err(e) => {
+                tracing::warn!(error = %e, "Skipping malformed dead letter");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Write dead letters as JSON Lines, replacing the file atomically.
+fn write(path: &Path, letters: &VecDeque<DeadLetter>) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp)?);
+    for letter in letters {
+        serde_json::to_writer(&mut file, letter)?;
+        file.write_all(b"\n")?;
+    }
+    file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Counter of events moved to the dead-letter queue.
+fn dead_lettered_counter() -> &'static IntCounter {
+    static COUNTER: OnceLock<IntCounter> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounter::new(
+            "policy_engine_dead_lettered_events_total",
+            "Total number of Observatory events moved to the dead-letter queue",
+        )
+        .expect("Failed to create dead-lettered events counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register dead-lettered events counter");
+        counter
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::observatory::DecisionOutcome;
+    use std::collections::HashMap;
+
+    fn events(ids: std::ops::Range<usize>) -> Vec<PolicyEvaluationEvent> {
+        ids.map(|id| PolicyEvaluationEvent {
+            event_id: format!("evt-{}", id),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            trace_id: None,
+            span_id: None,
+            policy_id: "policy-1".to_string(),
+            rule_id: None,
+            decision: DecisionOutcome::Allow,
+            reason: None,
+            obligations: None,
+            shadow_decision: None,
+            duration_ms: 1.0,
+            cached: false,
+            context: HashMap::new(),
+            labels: HashMap::new(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_oldest_batches_evicted_when_full() {
+        let queue = DeadLetterQueue::open(5, None);
+        queue.push(events(0..2), "first".to_string());
+        queue.push(events(2..4), "second".to_string());
+        queue.push(events(4..7), "third".to_string());
+
+        assert_eq!(queue.event_count(), 5);
+        let reasons: Vec<_> = queue.drain().into_iter().map(|l| l.reason).collect();
+        assert_eq!(reasons, vec!["second", "third"]);
+        assert_eq!(queue.event_count(), 0);
+
+        let disabled = DeadLetterQueue::open(0, None);
+        disabled.push(events(0..2), "ignored".to_string());
+        assert_eq!(disabled.event_count(), 0);
+    }
+}
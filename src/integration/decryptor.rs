@@ -0,0 +1,115 @@
+//! Decryption of secret configuration values.
+//!
+//! Config Manager delivers [`Secret`](super::ConfigValueType::Secret) values
+//! as base64-encoded ciphertext. A [`SecretDecryptor`] attached to the
+//! adapter turns them back into plaintext on access.
+
+use super::client::IntegrationResult;
+use super::error::IntegrationError;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use std::fmt;
+
+/// Length of the AES-GCM nonce prefixed to each ciphertext.
+const NONCE_LEN: usize = 12;
+
+/// Decrypts secret configuration values.
+pub trait SecretDecryptor: Send + Sync {
+    /// Decrypt a ciphertext into plaintext bytes.
+    fn decrypt(&self, ciphertext: &[u8]) -> IntegrationResult<Vec<u8>>;
+}
+
+/// AES-256-GCM secret decryptor.
+///
+/// Ciphertexts are the 12-byte nonce followed by the encrypted data and
+/// authentication tag.
+#[derive(Clone)]
+pub struct AesGcmDecryptor {
+    cipher: Aes256Gcm,
+}
+
+impl AesGcmDecryptor {
+    /// Create a decryptor from a 32-byte key.
+    pub fn new(key: &[u8]) -> IntegrationResult<Self> {
+        if key.len() != 32 {
+            return Err(IntegrationError::Decryption(format!(
+                "AES-256-GCM key must be 32 bytes, got {}",
+                key.len()
+            )));
+        }
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        })
+    }
+
+    /// Encrypt plaintext with a random nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> IntegrationResult<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| IntegrationError::Decryption("encryption failed".to_string()))?;
+
+        let mut output = Vec::with_capacity(NONCE_LEN + encrypted.len());
+        output.extend_from_slice(&nonce);
+        output.extend_from_slice(&encrypted);
+        Ok(output)
+    }
+}
+
+impl SecretDecryptor for AesGcmDecryptor {
+    fn decrypt(&self, ciphertext: &[u8]) -> IntegrationResult<Vec<u8>> {
+        if ciphertext.len() < NONCE_LEN {
+            return Err(IntegrationError::Decryption(
+                "ciphertext is shorter than the nonce".to_string(),
+            ));
+        }
+
+        let (nonce, encrypted) = ciphertext.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| {
+                IntegrationError::Decryption("authentication failed (wrong key?)".to_string())
+            })
+    }
+}
+
+impl fmt::Debug for AesGcmDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesGcmDecryptor")
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let decryptor = AesGcmDecryptor::new(&[7u8; 32]).unwrap();
+        let ciphertext = decryptor.encrypt(b"hunter2").unwrap();
+
+        assert_ne!(&ciphertext[NONCE_LEN..], b"hunter2");
+        assert_eq!(decryptor.decrypt(&ciphertext).unwrap(), b"hunter2");
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let encryptor = AesGcmDecryptor::new(&[7u8; 32]).unwrap();
+        let decryptor = AesGcmDecryptor::new(&[8u8; 32]).unwrap();
+        let ciphertext = encryptor.encrypt(b"hunter2").unwrap();
+
+        assert!(matches!(
+            decryptor.decrypt(&ciphertext),
+            Err(IntegrationError::Decryption(_))
+        ));
+        assert!(decryptor.decrypt(&[0u8; 4]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_key_length() {
+        assert!(AesGcmDecryptor::new(&[0u8; 16]).is_err());
+    }
+}
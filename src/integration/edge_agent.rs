@@ -3,9 +3,19 @@
 //! Edge Agent handles policy distribution to edge locations.
 
 use super::client::{IntegrationClient, IntegrationResult};
+use super::metrics::MetricsRecorder;
+use crate::policy::Policy;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Path templates for metrics labels.
+const PATH_TEMPLATES: &[&str] = &[
+    "/api/v1/deployments/{deployment_id}",
+    "/api/v1/locations/{location_id}/resync",
+    "/api/v1/policies/{policy_id}",
+];
+
 /// Client for LLM Edge Agent service.
 pub struct EdgeAgentClient {
     client: IntegrationClient,
@@ -14,9 +24,23 @@ pub struct EdgeAgentClient {
 impl EdgeAgentClient {
     /// Create a new Edge Agent client.
     pub fn new(base_url: String, timeout: Duration) -> Self {
-        Self {
-            client: IntegrationClient::new(base_url, timeout),
-        }
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Edge Agent client from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
+        Self { client }
+    }
+
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("edge_agent", PATH_TEMPLATES, recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
     }
 
     /// Deploy a policy to edge locations.
@@ -42,6 +66,29 @@ impl EdgeAgentClient {
         self.client.get(&path).await
     }
 
+    /// Distribute changed policies to all edge locations.
+    pub async fn distribute_policies(
+        &self,
+        request: &PolicyDistributionRequest,
+    ) -> IntegrationResult<PolicyDistributionResponse> {
+        self.client.post("/api/v1/policies/distribute", request).await
+    }
+
+    /// Replace the policies of one edge location with a full policy set.
+    pub async fn resync_location(
+        &self,
+        location_id: &str,
+        request: &PolicyDistributionRequest,
+    ) -> IntegrationResult<PolicyDistributionResponse> {
+        let path = format!("/api/v1/locations/{}/resync", location_id);
+        self.client.post(&path, request).await
+    }
+
+    /// Get the policy version applied at each edge location.
+    pub async fn list_edge_versions(&self) -> IntegrationResult<EdgeVersionsResponse> {
+        self.client.get("/api/v1/locations/versions").await
+    }
+
     /// Get edge locations.
     pub async fn get_locations(&self) -> IntegrationResult<EdgeLocationsResponse> {
         self.client.get("/api/v1/locations").await
@@ -80,6 +127,54 @@ pub struct PolicyDeployRequest {
     pub force: bool,
 }
 
+/// Request to distribute policies, stamped with the policy set version.
+///
+/// Incremental requests carry changed and removed policies; full requests
+/// carry every policy and replace whatever the edge has.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyDistributionRequest {
+    /// Version of the policy set after this change
+    pub version: u64,
+    /// Added or updated policies, or every policy for a full request
+    #[serde(default)]
+    pub policies: Vec<Policy>,
+    /// IDs of removed policies
+    #[serde(default)]
+    pub removed: Vec<String>,
+    /// Whether the request carries the full policy set
+    #[serde(default)]
+    pub full: bool,
+}
+
+/// Response from policy distribution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyDistributionResponse {
+    /// Whether distribution was accepted
+    pub success: bool,
+    /// Locations the policies were sent to
+    #[serde(default)]
+    pub locations: Vec<String>,
+}
+
+/// Response with the policy version at each edge location.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeVersionsResponse {
+    /// Version per location
+    pub locations: Vec<EdgeVersion>,
+}
+
+/// Policy version applied at an edge location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeVersion {
+    /// Location ID
+    pub location_id: String,
+    /// Version of the policy set applied
+    pub version: u64,
+    /// Last synced at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<String>,
+}
+
 /// Response from policy deployment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyDeployResponse {
@@ -132,18 +227,25 @@ pub struct DeploymentStatus {
 }
 
 /// Deployment state.
+///
+/// Serialized in snake case, like the Edge Agent API. The Edge Agent
+/// reports completed deployments as `deployed`, and earlier releases of
+/// this crate wrote `inprogress` and `rolledback`; all are accepted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum DeploymentState {
     /// Deployment pending
     Pending,
     /// Deployment in progress
+    #[serde(alias = "inprogress")]
     InProgress,
     /// Deployment completed
+    #[serde(alias = "deployed")]
     Completed,
     /// Deployment failed
     Failed,
     /// Deployment rolled back
+    #[serde(alias = "rolledback")]
     RolledBack,
 }
 
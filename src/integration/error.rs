@@ -0,0 +1,166 @@
+//! Error types for integration calls.
+
+use thiserror::Error;
+
+/// Maximum number of response body bytes kept in an `HttpStatus` error.
+const MAX_ERROR_BODY_LEN: usize = 1024;
+
+/// Error returned by an integration call.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum IntegrationError {
+    /// Request timed out
+    #[error("Request timed out")]
+    Timeout,
+
+    /// Could not connect to the service
+    #[error("Connection failed: {0}")]
+    Connection(String),
+
+    /// Service responded with a non-success status
+    #[error("HTTP error {code}: {body}")]
+    HttpStatus {
+        /// HTTP status code
+        code: u16,
+        /// Response body (truncated)
+        body: String,
+    },
+
+    /// Response body could not be deserialized
+    #[error("Failed to parse response: {0}")]
+    Deserialize(String),
+
+    /// A returned value did not have the expected type
+    #[error("Type mismatch for '{key}': expected {expected}, found {actual}")]
+    TypeMismatch {
+        /// Key or field of the value
+        key: String,
+        /// Expected type
+        expected: String,
+        /// Actual type
+        actual: String,
+    },
+
+    /// A secret value could not be decrypted
+    #[error("Decryption failed: {0}")]
+    Decryption(String),
+
+    /// Circuit breaker is open; the call was not attempted
+    #[error("Circuit breaker open")]
+    CircuitOpen,
+
+    /// Client-side rate limit exceeded; the call was not attempted
+    #[error("Client-side rate limit exceeded")]
+    RateLimited,
+
+    /// Integrations are in offline mode; the call was not attempted
+    #[error("Integrations are offline")]
+    Offline,
+
+    /// Request could not be built or sent
+    #[error("Request failed: {0}")]
+    Request(String),
+}
+
+impl IntegrationError {
+    /// Create an HTTP status error, truncating the body.
+    pub fn http_status(code: u16, body: impl Into<String>) -> Self {
+        let mut body = body.into();
+        if body.len() > MAX_ERROR_BODY_LEN {
+            let mut end = MAX_ERROR_BODY_LEN;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+        }
+        IntegrationError::HttpStatus { code, body }
+    }
+
+    /// Short name of the error variant, for metrics and logs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            IntegrationError::Timeout => "timeout",
+            IntegrationError::Connection(_) => "connection",
+            IntegrationError::HttpStatus { .. } => "http_status",
+            IntegrationError::Deserialize(_) => "deserialize",
+            IntegrationError::TypeMismatch { .. } => "type_mismatch",
+            IntegrationError::Decryption(_) => "decryption",
+            IntegrationError::CircuitOpen => "circuit_open",
+            IntegrationError::RateLimited => "rate_limited",
+            IntegrationError::Offline => "offline",
+            IntegrationError::Request(_) => "request",
+        }
+    }
+
+    /// Check if the service was unreachable (timeout, connection failure or open circuit).
+    pub fn is_unavailable(&self) -> bool {
+        matches!(
+            self,
+            IntegrationError::Timeout | IntegrationError::Connection(_) | IntegrationError::CircuitOpen
+        )
+    }
+
+    /// Get the HTTP status code, if the service responded.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            IntegrationError::HttpStatus { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Check if the service responded with `404 Not Found`.
+    pub fn is_not_found(&self) -> bool {
+        self.status_code() == Some(404)
+    }
+}
+
+impl From<reqwest::Error> for IntegrationError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            IntegrationError::Timeout
+        } else if e.is_connect() {
+            IntegrationError::Connection(e.to_string())
+        } else if e.is_decode() {
+            IntegrationError::Deserialize(e.to_string())
+        } else {
+            IntegrationError::Request(e.to_string())
+        }
+    }
+}
+
+impl From<IntegrationError> for crate::Error {
+    fn from(e: IntegrationError) -> Self {
+        crate::Error::integration("upstream", e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_status_truncates_body() {
+        let error = IntegrationError::http_status(500, "x".repeat(4096));
+        match error {
+            IntegrationError::HttpStatus { code, body } => {
+                assert_eq!(code, 500);
+                assert_eq!(body.len(), MAX_ERROR_BODY_LEN);
+            }
+            _ => panic!("expected HttpStatus"),
+        }
+    }
+
+    #[test]
+    fn test_error_classification() {
+        assert!(IntegrationError::Timeout.is_unavailable());
+        assert!(IntegrationError::CircuitOpen.is_unavailable());
+        assert!(!IntegrationError::Deserialize("bad".to_string()).is_unavailable());
+        assert!(IntegrationError::http_status(404, "").is_not_found());
+        assert_eq!(IntegrationError::http_status(503, "").status_code(), Some(503));
+    }
+
+    #[test]
+    fn test_conversion_to_crate_error() {
+        let error: crate::Error = IntegrationError::http_status(502, "bad gateway").into();
+        assert_eq!(error.category(), "integration");
+    }
+}
@@ -0,0 +1,812 @@
+//! Buffered emission of policy evaluation events.
+//!
+//! An [`EventSink`] queues events in memory and a background task flushes
+//! them to Observatory in batches, either when the batch is full or when the
+//! flush interval elapses. Sending never blocks or fails the caller; events
+//! are dropped (and counted) when the queue is full.
+//!
+//! Batches that fail to send are moved to a bounded dead-letter queue, from
+//! which they can be drained and replayed once Observatory recovers. When
+//! Observatory accepts a batch but rejects some of its events, only those
+//! events are queued again, up to `max_rejection_retries` times, before
+//! they are dead-lettered too.
+//!
+//! A [`Shutdown`] drains the queue before the process exits, so the tail of
+//! the event stream is not lost during restarts.
+
+use super::dead_letter::{DeadLetter, DeadLetterQueue, DEFAULT_DEAD_LETTER_CAPACITY};
+use super::observatory::{BatchEventAck, ObservatoryAdapter, PolicyEvaluationEvent};
+use prometheus::{IntCounterVec, Opts};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// Default time allowed for draining queued events on shutdown.
+pub const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Default number of times an event rejected by Observatory is resent.
+pub const DEFAULT_MAX_REJECTION_RETRIES: u32 = 3;
+
+/// Configuration for batched event emission.
+#[derive(Debug, Clone)]
+pub struct BatchConfig {
+    /// Maximum number of events per batch
+    pub max_batch_size: usize,
+    /// Maximum time an event waits before being flushed
+    pub flush_interval: Duration,
+    /// Maximum number of queued events before new events are dropped
+    pub queue_capacity: usize,
+    /// Maximum number of events kept in the dead-letter queue; 0 disables it
+    pub dead_letter_capacity: usize,
+    /// JSON Lines file the dead-letter queue is persisted to, if any
+    pub dead_letter_path: Option<PathBuf>,
+    /// Times an event Observatory rejects is resent before it is
+    /// dead-lettered
+    pub max_rejection_retries: u32,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            queue_capacity: 10_000,
+            dead_letter_capacity: DEFAULT_DEAD_LETTER_CAPACITY,
+            dead_letter_path: None,
+            max_rejection_retries: DEFAULT_MAX_REJECTION_RETRIES,
+        }
+    }
+}
+
+/// Counters for an event sink.
+#[derive(Debug, Default)]
+struct SinkStats {
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    failed: AtomicU64,
+    /// Events queued or in flight, not yet sent or failed
+    pending: AtomicU64,
+    /// Set once shutdown begins; later events are dropped
+    closed: AtomicBool,
+}
+
+/// Message sent to the background flush task.
+enum SinkMessage {
+    Event(Box<PolicyEvaluationEvent>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Handle for sending events to a background batching task.
+///
+/// Clones share the same queue. The background task exits after flushing
+/// once every handle has been dropped.
+#[derive(Clone, Debug)]
+pub struct EventSink {
+    tx: mpsc::Sender<SinkMessage>,
+    stats: Arc<SinkStats>,
+    dead_letters: Arc<DeadLetterQueue>,
+}
+
+impl EventSink {
+    /// Queue an event for emission.
+    ///
+    /// Never blocks; if the queue is full or the sink is shutting down the
+    /// event is dropped and counted.
+    pub fn send(&self, event: PolicyEvaluationEvent) {
+        if self.is_closed() {
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        // Count the event before queueing so the task never sees it uncounted.
+        self.stats.pending.fetch_add(1, Ordering::Relaxed);
+        if self.tx.try_send(SinkMessage::Event(Box::new(event))).is_err() {
+            self.stats.pending.fetch_sub(1, Ordering::Relaxed);
+            let dropped = self.stats.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::debug!(dropped, "Observatory event queue full, dropping event");
+        }
+    }
+
+    /// Flush all queued events and wait for the batch requests to complete.
+    pub async fn flush(&self) {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        if self.tx.send(SinkMessage::Flush(ack_tx)).await.is_ok() {
+            let _ = ack_rx.await;
+        }
+    }
+
+    /// Number of events accepted by Observatory.
+    pub fn sent_count(&self) -> u64 {
+        self.stats.sent.load(Ordering::Relaxed)
+    }
+
+    /// Number of events dropped because the queue was full.
+    pub fn dropped_count(&self) -> u64 {
+        self.stats.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of events whose batch request failed or that Observatory
+    /// kept rejecting. They are kept in the dead-letter queue while it has
+    /// room.
+    pub fn failed_count(&self) -> u64 {
+        self.stats.failed.load(Ordering::Relaxed)
+    }
+
+    /// Number of events queued or being sent.
+    pub fn pending_count(&self) -> u64 {
+        self.stats.pending.load(Ordering::Relaxed)
+    }
+
+    /// Take every dead-lettered batch, oldest first, to replay its events
+    /// with [`send`](Self::send) once Observatory recovers.
+    ///
+    /// Drained batches are also removed from the persisted queue.
+    pub fn drain_dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.drain()
+    }
+
+    /// Number of events in the dead-letter queue.
+    pub fn dead_letter_count(&self) -> usize {
+        self.dead_letters.event_count()
+    }
+
+    /// Check if the sink has stopped accepting events.
+    pub fn is_closed(&self) -> bool {
+        self.stats.closed.load(Ordering::Relaxed)
+    }
+
+    /// Stop accepting events. Events already queued are still flushed.
+    fn close(&self) {
+        self.stats.closed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Outcome of draining an [`EventSink`] on shutdown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShutdownSummary {
+    /// Queued events accepted by Observatory during the drain
+    pub flushed: u64,
+    /// Queued events that failed to send or were still pending when the
+    /// grace period ran out
+    pub dropped: u64,
+}
+
+/// Coordinates draining an [`EventSink`] before the process exits.
+///
+/// On [`shutdown`](Self::shutdown) the sink stops accepting events and its
+/// queue is flushed, waiting at most the grace period.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    sink: EventSink,
+    grace_period: Duration,
+}
+
+impl Shutdown {
+    /// Create a coordinator for `sink` with the default grace period.
+    pub fn new(sink: EventSink) -> Self {
+        Self {
+            sink,
+            grace_period: DEFAULT_SHUTDOWN_GRACE_PERIOD,
+        }
+    }
+
+    /// Set the maximum time to wait for queued events to be flushed.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Stop accepting events and flush the queue.
+    ///
+    /// Events rejected after shutdown begins are counted by
+    /// [`EventSink::dropped_count`], not in the returned summary.
+    pub async fn shutdown(&self) -> ShutdownSummary {
+        let sent_before = self.sink.sent_count();
+        let failed_before = self.sink.failed_count();
+        self.sink.close();
+
+        if tokio::time::timeout(self.grace_period, self.sink.flush())
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                grace_period_ms = self.grace_period.as_millis() as u64,
+                pending = self.sink.pending_count(),
+                "Timed out flushing Observatory events on shutdown"
+            );
+        }
+
+        let summary = ShutdownSummary {
+            flushed: self.sink.sent_count() - sent_before,
+            dropped: self.sink.failed_count() - failed_before + self.sink.pending_count(),
+        };
+        tracing::info!(
+            flushed = summary.flushed,
+            dropped = summary.dropped,
+            "Drained Observatory events"
+        );
+        summary
+    }
+
+    /// Wait for SIGTERM or Ctrl-C, then [`shutdown`](Self::shutdown).
+    pub async fn shutdown_on_signal(&self) -> std::io::Result<ShutdownSummary> {
+        wait_for_signal().await?;
+        Ok(self.shutdown().await)
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    tokio::select! {
+        _ = terminate.recv() => Ok(()),
+        result = tokio::signal::ctrl_c() => result,
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
+
+/// Spawn the background batching task for an adapter.
+pub(crate) fn spawn(adapter: Arc<ObservatoryAdapter>, config: BatchConfig) -> EventSink {
+    let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+    let stats = Arc::new(SinkStats::default());
+    let dead_letters = Arc::new(DeadLetterQueue::open(
+        config.dead_letter_capacity,
+        config.dead_letter_path.clone(),
+    ));
+
+    tokio::spawn(run(
+        adapter,
+        config,
+        rx,
+        Arc::clone(&stats),
+        Arc::clone(&dead_letters),
+    ));
+
+    EventSink {
+        tx,
+        stats,
+        dead_letters,
+    }
+}
+
+async fn run(
+    adapter: Arc<ObservatoryAdapter>,
+    config: BatchConfig,
+    mut rx: mpsc::Receiver<SinkMessage>,
+    stats: Arc<SinkStats>,
+    dead_letters: Arc<DeadLetterQueue>,
+) {
+    let max_batch_size = config.max_batch_size.max(1);
+    let mut buffer: Vec<PolicyEvaluationEvent> = Vec::with_capacity(max_batch_size);
+    let mut sender = BatchSender {
+        adapter,
+        stats,
+        dead_letters,
+        max_rejection_retries: config.max_rejection_retries,
+        rejections: HashMap::new(),
+    };
+    let mut interval = tokio::time::interval_at(
+        tokio::time::Instant::now() + config.flush_interval,
+        config.flush_interval,
+    );
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            message = rx.recv() => match message {
+                Some(SinkMessage::Event(event)) => {
+                    buffer.push(*event);
+                    if buffer.len() >= max_batch_size {
+                        sender.flush(&mut buffer).await;
+                    }
+                }
+                Some(SinkMessage::Flush(ack)) => {
+                    sender.flush_all(&mut buffer).await;
+                    let _ = ack.send(());
+                }
+                None => {
+                    sender.flush_all(&mut buffer).await;
+                    break;
+                }
+            },
+            _ = interval.tick() => {
+                sender.flush(&mut buffer).await;
+            }
+        }
+    }
+}
+
+/// Sends batches for the background task, tracking rejected events.
+struct BatchSender {
+    adapter: Arc<ObservatoryAdapter>,
+    stats: Arc<SinkStats>,
+    dead_letters: Arc<DeadLetterQueue>,
+    max_rejection_retries: u32,
+    /// Times each re-queued event has been rejected, by event ID
+    rejections: HashMap<String, u32>,
+}
+
+impl BatchSender {
+    /// Send the buffered events as one batch. Events Observatory rejects
+    /// are put back into the buffer while they have retries left.
+    async fn flush(&mut self, buffer: &mut Vec<PolicyEvaluationEvent>) {
+        if buffer.is_empty() {
+            return;
+        }
+
+        let events = std::mem::take(buffer);
+        let result = self.adapter.emit_evaluation_events_batch(&events).await;
+        self.stats
+            .pending
+            .fetch_sub(events.len() as u64, Ordering::Relaxed);
+        match result {
+            Ok(ack) => {
+                self.stats
+                    .sent
+                    .fetch_add(ack.accepted_count, Ordering::Relaxed);
+                self.handle_rejections(&ack, events, buffer);
+            }
+            Err(e) => {
+                for event in &events {
+                    self.rejections.remove(&event.event_id);
+                }
+                self.stats
+                    .failed
+                    .fetch_add(events.len() as u64, Ordering::Relaxed);
+                tracing::warn!(
+                    integration = "observatory",
+                    error = %e,
+                    events = events.len(),
+                    dead_lettered = self.dead_letters.is_enabled(),
+                    "Failed to emit policy evaluation event batch; failing open"
+                );
+                self.dead_letters.push(events, e.to_string());
+            }
+        }
+    }
+
+    /// Send batches until the buffer is empty, including re-queued events.
+    async fn flush_all(&mut self, buffer: &mut Vec<PolicyEvaluationEvent>) {
+        while !buffer.is_empty() {
+            self.flush(buffer).await;
+        }
+    }
+
+    /// Re-queue the events of a sent batch that Observatory rejected, or
+    /// dead-letter them once they are out of retries.
+    fn handle_rejections(
+        &mut self,
+        ack: &BatchEventAck,
+        events: Vec<PolicyEvaluationEvent>,
+        buffer: &mut Vec<PolicyEvaluationEvent>,
+    ) {
+        let rejected: HashSet<&str> = ack.rejected_ids.iter().map(String::as_str).collect();
+        let mut identified = 0;
+        let mut exhausted: BTreeMap<&str, Vec<PolicyEvaluationEvent>> = BTreeMap::new();
+        for event in events {
+            let retries = self.rejections.remove(&event.event_id).unwrap_or(0);
+            if !rejected.contains(event.event_id.as_str()) {
+                continue;
+            }
+
+            identified += 1;
+            let reason = ack
+                .rejection_reasons
+                .get(&event.event_id)
+                .map_or("unknown", String::as_str);
+            rejected_events_counter().with_label_values(&[reason]).inc();
+            if retries < self.max_rejection_retries {
+                self.rejections.insert(event.event_id.clone(), retries + 1);
+                self.stats.pending.fetch_add(1, Ordering::Relaxed);
+                buffer.push(event);
+            } else {
+                exhausted.entry(reason).or_default().push(event);
+            }
+        }
+
+        if ack.rejected_count > identified {
+            tracing::warn!(
+                integration = "observatory",
+                rejected = ack.rejected_count - identified,
+                "Observatory rejected events without identifying them"
+            );
+        }
+        if !buffer.is_empty() {
+            tracing::debug!(
+                integration = "observatory",
+                requeued = buffer.len(),
+                "Re-queued policy evaluation events rejected by Observatory"
+            );
+        }
+        for (reason, events) in exhausted {
+            self.stats
+                .failed
+                .fetch_add(events.len() as u64, Ordering::Relaxed);
+            tracing::warn!(
+                integration = "observatory",
+                reason,
+                events = events.len(),
+                retries = self.max_rejection_retries,
+                "Observatory kept rejecting policy evaluation events"
+            );
+            self.dead_letters
+                .push(events, format!("Rejected by Observatory: {}", reason));
+        }
+    }
+}
+
+/// Counter of events rejected by Observatory, by the reason it gave.
+fn rejected_events_counter() -> &'static IntCounterVec {
+    static COUNTER: OnceLock<IntCounterVec> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounterVec::new(
+            Opts::new(
+                "policy_engine_observatory_rejected_events_total",
+                "Total number of policy evaluation events rejected by Observatory",
+            ),
+            &["reason"],
+        )
+        .expect("Failed to create rejected events counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register rejected events counter");
+        counter
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::observatory::DecisionOutcome;
+    use std::collections::HashMap;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn event(id: usize) -> PolicyEvaluationEvent {
+        PolicyEvaluationEvent {
+            event_id: format!("evt-{}", id),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            trace_id: None,
+            span_id: None,
+            policy_id: "policy-1".to_string(),
+            rule_id: None,
+            decision: DecisionOutcome::Allow,
+            reason: None,
+            obligations: None,
+            shadow_decision: None,
+            duration_ms: 1.0,
+            cached: false,
+            context: HashMap::new(),
+            labels: HashMap::new(),
+        }
+    }
+
+    async fn batch_server(accepted: u64) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "accepted_count": accepted,
+                "rejected_count": 0
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Respond to batches by rejecting the events with the given IDs, for
+    /// `reason`, and accepting the rest.
+    fn rejecting(
+        ids: &'static [&'static str],
+        reason: &'static str,
+    ) -> impl Fn(&wiremock::Request) -> ResponseTemplate {
+        move |request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let events = body["events"].as_array().unwrap();
+            let rejected: Vec<&str> = events
+                .iter()
+                .filter_map(|event| event["event_id"].as_str())
+                .filter(|id| ids.contains(id))
+                .collect();
+            let reasons: HashMap<&str, &str> = rejected.iter().map(|id| (*id, reason)).collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "accepted_count": events.len() - rejected.len(),
+                "rejected_count": rejected.len(),
+                "rejected_ids": rejected,
+                "rejection_reasons": reasons,
+            }))
+        }
+    }
+
+    fn batch_event_ids(requests: &[wiremock::Request]) -> Vec<Vec<String>> {
+        requests
+            .iter()
+            .map(|r| {
+                let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+                body["events"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|event| event["event_id"].as_str().unwrap().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn batch_sizes(requests: &[wiremock::Request]) -> Vec<usize> {
+        requests
+            .iter()
+            .map(|r| {
+                let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+                body["events"].as_array().unwrap().len()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_events_coalesced_into_batches() {
+        let server = batch_server(5).await;
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(BatchConfig {
+            max_batch_size: 5,
+            flush_interval: Duration::from_secs(60),
+            queue_capacity: 100,
+            ..BatchConfig::default()
+        });
+
+        for i in 0..10 {
+            sink.send(event(i));
+        }
+        sink.flush().await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(batch_sizes(&requests), vec![5, 5]);
+        assert_eq!(sink.sent_count(), 10);
+        assert_eq!(sink.dropped_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_flush_sends_partial_batch() {
+        let server = batch_server(3).await;
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(BatchConfig {
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(60),
+            queue_capacity: 100,
+            ..BatchConfig::default()
+        });
+
+        for i in 0..3 {
+            sink.send(event(i));
+        }
+        sink.flush().await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(batch_sizes(&requests), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_drops_and_counts() {
+        let server = batch_server(2).await;
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(BatchConfig {
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(60),
+            queue_capacity: 2,
+            ..BatchConfig::default()
+        });
+
+        // The single-threaded test runtime does not run the background task
+        // until we yield, so the queue fills deterministically.
+        for i in 0..5 {
+            sink.send(event(i));
+        }
+        assert_eq!(sink.dropped_count(), 3);
+
+        sink.flush().await;
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(batch_sizes(&requests), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_flushes_queued_events() {
+        let server = batch_server(7).await;
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(BatchConfig {
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(60),
+            queue_capacity: 100,
+            ..BatchConfig::default()
+        });
+
+        for i in 0..7 {
+            sink.send(event(i));
+        }
+        let shutdown = Shutdown::new(sink.clone()).with_grace_period(Duration::from_secs(2));
+        let summary = shutdown.shutdown().await;
+
+        assert_eq!(summary, ShutdownSummary { flushed: 7, dropped: 0 });
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(batch_sizes(&requests), vec![7]);
+
+        // Events sent after shutdown are refused.
+        sink.send(event(7));
+        assert!(sink.is_closed());
+        assert_eq!(sink.dropped_count(), 1);
+        assert_eq!(sink.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_gives_up_after_grace_period() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(30)));
+        let sink = adapter.spawn_batching(BatchConfig::default());
+
+        for i in 0..3 {
+            sink.send(event(i));
+        }
+        let summary = Shutdown::new(sink)
+            .with_grace_period(Duration::from_millis(100))
+            .shutdown()
+            .await;
+
+        assert_eq!(summary, ShutdownSummary { flushed: 0, dropped: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_failed_batches_dead_lettered_and_replayed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let dead_letter_path = std::env::temp_dir().join(format!(
+            "policy-engine-{}-{}-dead-letters.jsonl",
+            std::process::id(),
+            uuid::Uuid::new_v4()
+        ));
+        let config = BatchConfig {
+            max_batch_size: 2,
+            flush_interval: Duration::from_secs(60),
+            queue_capacity: 100,
+            dead_letter_capacity: 10,
+            dead_letter_path: Some(dead_letter_path.clone()),
+            ..BatchConfig::default()
+        };
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(config.clone());
+
+        for i in 0..4 {
+            sink.send(event(i));
+        }
+        sink.flush().await;
+        assert_eq!(sink.sent_count(), 0);
+        assert_eq!(sink.failed_count(), 4);
+        assert_eq!(sink.dead_letter_count(), 4);
+        drop(sink);
+
+        // Dead letters survive a restart.
+        let sink = adapter.spawn_batching(config);
+        assert_eq!(sink.dead_letter_count(), 4);
+
+        // Replay once Observatory recovers.
+        server.reset().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "accepted_count": 2,
+                "rejected_count": 0
+            })))
+            .mount(&server)
+            .await;
+        let letters = sink.drain_dead_letters();
+        assert_eq!(letters.len(), 2);
+        assert!(letters.iter().all(|letter| !letter.reason.is_empty()));
+        for event in letters.into_iter().flat_map(|letter| letter.events) {
+            sink.send(event);
+        }
+        sink.flush().await;
+
+        assert_eq!(sink.sent_count(), 4);
+        assert_eq!(sink.dead_letter_count(), 0);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(batch_sizes(&requests), vec![2, 2]);
+        let replayed: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(replayed["events"][0]["event_id"], "evt-0");
+        assert!(std::fs::read_to_string(&dead_letter_path)
+            .unwrap()
+            .is_empty());
+        std::fs::remove_file(dead_letter_path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_events_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(rejecting(&["evt-1"], "observatory_busy"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(rejecting(&[], "none"))
+            .mount(&server)
+            .await;
+        let rejected = rejected_events_counter().with_label_values(&["observatory_busy"]);
+        let rejected_before = rejected.get();
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(BatchConfig {
+            flush_interval: Duration::from_secs(60),
+            ..BatchConfig::default()
+        });
+
+        for i in 0..3 {
+            sink.send(event(i));
+        }
+        sink.flush().await;
+
+        // Only the rejected event is sent again.
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            batch_event_ids(&requests),
+            vec![vec!["evt-0", "evt-1", "evt-2"], vec!["evt-1"]]
+        );
+        assert_eq!(sink.sent_count(), 3);
+        assert_eq!(sink.failed_count(), 0);
+        assert_eq!(sink.pending_count(), 0);
+        assert_eq!(sink.dead_letter_count(), 0);
+        assert_eq!(rejected.get() - rejected_before, 1);
+    }
+
+    #[tokio::test]
+    async fn test_persistently_rejected_events_dead_lettered() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(rejecting(&["evt-1"], "invalid_context"))
+            .mount(&server)
+            .await;
+        let rejected = rejected_events_counter().with_label_values(&["invalid_context"]);
+        let rejected_before = rejected.get();
+        let adapter = Arc::new(ObservatoryAdapter::new(server.uri(), Duration::from_secs(5)));
+        let sink = adapter.spawn_batching(BatchConfig {
+            flush_interval: Duration::from_secs(60),
+            max_rejection_retries: 2,
+            ..BatchConfig::default()
+        });
+
+        sink.send(event(0));
+        sink.send(event(1));
+        sink.flush().await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            batch_event_ids(&requests),
+            vec![vec!["evt-0", "evt-1"], vec!["evt-1"], vec!["evt-1"]]
+        );
+        assert_eq!(sink.sent_count(), 1);
+        assert_eq!(sink.failed_count(), 1);
+        assert_eq!(sink.pending_count(), 0);
+        assert_eq!(rejected.get() - rejected_before, 3);
+
+        let letters = sink.drain_dead_letters();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].events[0].event_id, "evt-1");
+        assert_eq!(letters[0].reason, "Rejected by Observatory: invalid_context");
+    }
+}
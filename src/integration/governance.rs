@@ -3,7 +3,9 @@
 //! Governance provides compliance checking and audit logging for LLM operations.
 
 use super::client::{IntegrationClient, IntegrationResult};
+use super::metrics::MetricsRecorder;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Client for LLM Governance service.
@@ -14,9 +16,23 @@ pub struct GovernanceClient {
 impl GovernanceClient {
     /// Create a new Governance client.
     pub fn new(base_url: String, timeout: Duration) -> Self {
-        Self {
-            client: IntegrationClient::new(base_url, timeout),
-        }
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Governance client from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
+        Self { client }
+    }
+
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("governance", &[], recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
     }
 
     /// Check compliance for a request.
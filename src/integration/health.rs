@@ -0,0 +1,267 @@
+//! Aggregate health reporting for configured integrations.
+//!
+//! A [`HealthReport`] collects the health of every configured upstream
+//! service and rolls it up into a single readiness answer. Checks run
+//! concurrently up to a limit set by [`HealthCheckOptions`], and a report
+//! is returned by its deadline even if some checks have not finished.
+
+use super::observatory::HealthStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Default time allowed for each health check in a report.
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default time allowed for a whole health report.
+pub const DEFAULT_HEALTH_REPORT_DEADLINE: Duration = Duration::from_secs(5);
+
+/// Default maximum number of health checks run at once.
+pub const MAX_HEALTH_CHECK_CONCURRENCY: usize = 8;
+
+/// Options for running the health checks of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthCheckOptions {
+    /// Time allowed for each check; slower integrations are unhealthy
+    pub timeout: Duration,
+    /// Time allowed for the whole report; integrations whose checks have not
+    /// finished by then are reported as [`HealthStatus::Unknown`]
+    pub deadline: Duration,
+    /// Maximum number of checks run at once; defaults to the number of
+    /// configured integrations, up to [`MAX_HEALTH_CHECK_CONCURRENCY`]
+    pub concurrency: Option<usize>,
+}
+
+impl Default for HealthCheckOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_HEALTH_CHECK_TIMEOUT,
+            deadline: DEFAULT_HEALTH_REPORT_DEADLINE,
+            concurrency: None,
+        }
+    }
+}
+
+impl HealthCheckOptions {
+    /// Set the time allowed for each check.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the time allowed for the whole report.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Set the maximum number of checks run at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Number of checks to run at once out of `checks`.
+    pub(crate) fn concurrency_for(&self, checks: usize) -> usize {
+        self.concurrency
+            .unwrap_or_else(|| checks.min(MAX_HEALTH_CHECK_CONCURRENCY))
+            .max(1)
+    }
+}
+
+/// Health of all configured integrations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Health status by integration name
+    pub integrations: BTreeMap<String, HealthStatus>,
+    /// Whether all required integrations are healthy
+    pub healthy: bool,
+}
+
+impl HealthReport {
+    /// Build a report, rolling up the required integrations.
+    ///
+    /// With no required names, every configured integration must be healthy.
+    /// A required integration that is not configured is reported as
+    /// [`HealthStatus::Unknown`] and fails the rollup. Offline integrations
+    /// count as healthy.
+    pub(crate) fn new(mut integrations: BTreeMap<String, HealthStatus>, required: &[&str]) -> Self {
+        for name in required {
+            integrations
+                .entry((*name).to_string())
+                .or_insert(HealthStatus::Unknown);
+        }
+
+        let healthy = if required.is_empty() {
+            integrations.values().all(is_up)
+        } else {
+            required
+                .iter()
+                .all(|name| integrations.get(*name).is_some_and(is_up))
+        };
+
+        Self {
+            integrations,
+            healthy,
+        }
+    }
+
+    /// Get the status of an integration.
+    pub fn status(&self, name: &str) -> Option<HealthStatus> {
+        self.integrations.get(name).copied()
+    }
+
+    /// Names of integrations that are not healthy.
+    pub fn unhealthy(&self) -> Vec<&str> {
+        self.integrations
+            .iter()
+            .filter(|(_, status)| !is_up(status))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// Check if a status satisfies the readiness rollup.
+fn is_up(status: &HealthStatus) -> bool {
+    matches!(status, HealthStatus::Healthy | HealthStatus::Offline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::IntegrationsConfig;
+    use crate::integration::Integrations;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn health_server(status: u16, delay: Duration) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(status).set_delay(delay))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_health_report_mixed() {
+        let healthy = health_server(200, Duration::ZERO).await;
+        let unhealthy = health_server(503, Duration::ZERO).await;
+
+        let integrations = Integrations::from_config(&IntegrationsConfig {
+            shield_url: Some(healthy.uri()),
+            observatory_url: Some(unhealthy.uri()),
+            ..Default::default()
+        });
+
+        let report = integrations.health_report(&[]).await;
+        assert_eq!(report.integrations.len(), 2);
+        assert_eq!(report.status("shield"), Some(HealthStatus::Healthy));
+        assert_eq!(report.status("observatory"), Some(HealthStatus::Unhealthy));
+        assert_eq!(report.unhealthy(), vec!["observatory"]);
+        assert!(!report.healthy);
+
+        // Observatory is optional for readiness.
+        let report = integrations.health_report(&["shield"]).await;
+        assert!(report.healthy);
+
+        let report = integrations.health_report(&["shield", "observatory"]).await;
+        assert!(!report.healthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_report_slow_service_times_out() {
+        let healthy = health_server(200, Duration::ZERO).await;
+        let slow = health_server(200, Duration::from_secs(10)).await;
+
+        let integrations = Integrations::from_config(&IntegrationsConfig {
+            shield_url: Some(healthy.uri()),
+            sentinel_url: Some(slow.uri()),
+            timeout_ms: 30_000,
+            ..Default::default()
+        });
+
+        let started = std::time::Instant::now();
+        let report = integrations
+            .health_report_with_timeout(&["shield"], Duration::from_millis(200))
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(report.status("sentinel"), Some(HealthStatus::Unhealthy));
+        assert!(report.healthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_report_offline_skips_checks() {
+        let server = health_server(503, Duration::ZERO).await;
+
+        let integrations = Integrations::from_config(&IntegrationsConfig {
+            shield_url: Some(server.uri()),
+            config_manager_url: Some(server.uri()),
+            offline: true,
+            ..Default::default()
+        });
+        assert!(integrations.any_configured());
+
+        let report = integrations.health_report(&["shield"]).await;
+        assert_eq!(report.status("shield"), Some(HealthStatus::Offline));
+        assert_eq!(report.status("config_manager"), Some(HealthStatus::Offline));
+        assert!(report.unhealthy().is_empty());
+        assert!(report.healthy);
+
+        let shield = integrations.shield.as_ref().unwrap();
+        assert!(shield.health_check().await);
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_health_report_deadline_marks_hung_check_unknown() {
+        let healthy = health_server(200, Duration::ZERO).await;
+        let unhealthy = health_server(503, Duration::ZERO).await;
+        let hung = health_server(200, Duration::from_secs(30)).await;
+
+        let integrations = Integrations::from_config(&IntegrationsConfig {
+            shield_url: Some(healthy.uri()),
+            costops_url: Some(healthy.uri()),
+            governance_url: Some(unhealthy.uri()),
+            sentinel_url: Some(hung.uri()),
+            timeout_ms: 60_000,
+            ..Default::default()
+        });
+
+        let options = HealthCheckOptions::default()
+            .with_timeout(Duration::from_secs(30))
+            .with_deadline(Duration::from_millis(300))
+            .with_concurrency(2);
+        let started = std::time::Instant::now();
+        let report = integrations
+            .health_report_with_options(&["shield", "costops"], &options)
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(report.status("shield"), Some(HealthStatus::Healthy));
+        assert_eq!(report.status("costops"), Some(HealthStatus::Healthy));
+        assert_eq!(report.status("governance"), Some(HealthStatus::Unhealthy));
+        assert_eq!(report.status("sentinel"), Some(HealthStatus::Unknown));
+        assert!(report.healthy);
+    }
+
+    #[test]
+    fn test_default_concurrency_is_capped() {
+        let options = HealthCheckOptions::default();
+        assert_eq!(options.concurrency_for(3), 3);
+        assert_eq!(options.concurrency_for(20), MAX_HEALTH_CHECK_CONCURRENCY);
+        assert_eq!(options.with_concurrency(0).concurrency_for(3), 1);
+    }
+
+    #[test]
+    fn test_missing_required_integration_is_unhealthy() {
+        let mut statuses = BTreeMap::new();
+        statuses.insert("shield".to_string(), HealthStatus::Healthy);
+
+        let report = HealthReport::new(statuses, &["costops"]);
+        assert_eq!(report.status("costops"), Some(HealthStatus::Unknown));
+        assert!(!report.healthy);
+    }
+}
@@ -3,9 +3,16 @@
 //! Incident Manager handles policy violation alerting and incident creation.
 
 use super::client::{IntegrationClient, IntegrationResult};
+use super::metrics::MetricsRecorder;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Path templates for metrics labels.
+const PATH_TEMPLATES: &[&str] = &[
+    "/api/v1/incidents/{incident_id}",
+];
+
 /// Client for Incident Manager service.
 pub struct IncidentManagerClient {
     client: IntegrationClient,
@@ -14,9 +21,23 @@ pub struct IncidentManagerClient {
 impl IncidentManagerClient {
     /// Create a new Incident Manager client.
     pub fn new(base_url: String, timeout: Duration) -> Self {
-        Self {
-            client: IntegrationClient::new(base_url, timeout),
-        }
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Incident Manager client from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
+        Self { client }
+    }
+
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("incident_manager", PATH_TEMPLATES, recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
     }
 
     /// Create an incident from a policy violation.
@@ -0,0 +1,254 @@
+//! Request and response logging for integration calls.
+//!
+//! A client configured with [`RequestLogging`] logs each HTTP attempt as a
+//! `tracing` event: method, path template, status and duration at `DEBUG`,
+//! and, if enabled, the JSON request and response bodies at `TRACE`. Events
+//! are emitted inside an `integration_request` span, which nests under the
+//! caller's current span (e.g. the policy evaluation).
+//!
+//! Headers are never logged, so credentials sent in `Authorization` or API
+//! key headers cannot leak. Bodies are redacted before logging: fields
+//! matching a configured path are replaced with `***`, as are the values of
+//! Config Manager entries typed [`Secret`](super::ConfigValueType::Secret).
+//! Bodies that are not JSON are logged by size only.
+
+use super::client::IntegrationResult;
+use super::metrics::path_label;
+use super::transport::Transport;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use reqwest::ResponseBuilderExt;
+use serde_json::Value;
+use std::time::Instant;
+use tracing::{Instrument, Level};
+
+/// Replacement for redacted values.
+const REDACTED: &str = "***";
+
+/// Options for logging integration requests.
+#[derive(Debug, Clone, Default)]
+pub struct RequestLogging {
+    /// Log redacted JSON request and response bodies at `TRACE` level
+    pub log_bodies: bool,
+    /// Paths of body fields to redact, e.g. `$.credentials.token` or
+    /// `events[*].context.api_key`; `*` matches any key or array element
+    pub redact_paths: Vec<String>,
+    /// Path templates used to label requests, as for metrics
+    pub path_templates: &'static [&'static str],
+}
+
+impl RequestLogging {
+    /// Send a request through `transport`, logging the attempt.
+    pub(crate) async fn execute(
+        &self,
+        transport: &dyn Transport,
+        request: reqwest::Request,
+    ) -> IntegrationResult<reqwest::Response> {
+        let method = request.method().clone();
+        let path = path_label(request.url().path(), self.path_templates).to_string();
+        let span = tracing::debug_span!("integration_request", %method, path = %path);
+
+        async move {
+            if self.log_bodies && tracing::enabled!(Level::TRACE) {
+                let body = request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .map(|bytes| self.describe_body(bytes, is_gzip(request.headers())));
+                if let Some(body) = body {
+                    tracing::trace!(body = %body, "Integration request body");
+                }
+            }
+
+            let started = Instant::now();
+            let result = transport.execute(request).await;
+            let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::debug!(error = %e, duration_ms, "Integration request failed");
+                    return Err(e);
+                }
+            };
+            tracing::debug!(
+                status = response.status().as_u16(),
+                duration_ms,
+                "Integration request completed"
+            );
+
+            if self.log_bodies && tracing::enabled!(Level::TRACE) && is_json(&response) {
+                return self.log_response_body(response).await;
+            }
+            Ok(response)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Buffer a JSON response, log it, and rebuild an equivalent response.
+    async fn log_response_body(
+        &self,
+        response: reqwest::Response,
+    ) -> IntegrationResult<reqwest::Response> {
+        let mut builder = http::Response::builder()
+            .status(response.status())
+            .version(response.version())
+            .url(response.url().clone());
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(response.headers().clone());
+        }
+
+        let bytes = response.bytes().await?;
+        let gzip = builder.headers_ref().is_some_and(is_gzip);
+        tracing::trace!(body = %self.describe_body(&bytes, gzip), "Integration response body");
+
+        let response = builder
+            .body(bytes)
+            .expect("parts copied from a valid response");
+        Ok(reqwest::Response::from(response))
+    }
+
+    /// Render a body for logging, redacted if it is JSON.
+    fn describe_body(&self, bytes: &[u8], gzip: bool) -> String {
+        if gzip {
+            return format!("<{} bytes gzip>", bytes.len());
+        }
+        match serde_json::from_slice::<Value>(bytes) {
+            Ok(mut value) => {
+                self.redact(&mut value);
+                value.to_string()
+            }
+            Err(_) => format!("<{} bytes>", bytes.len()),
+        }
+    }
+
+    /// Redact configured paths and secret config values in place.
+    pub fn redact(&self, value: &mut Value) {
+        redact(value, &self.redact_paths);
+    }
+}
+
+/// Redact fields matching `paths` and secret config values in place.
+pub(crate) fn redact(value: &mut Value, paths: &[String]) {
+    for path in paths {
+        redact_path(value, &parse_path(path));
+    }
+    redact_secret_values(value);
+}
+
+/// Split a path like `$.a[*].b` into segments `["a", "*", "b"]`.
+fn parse_path(path: &str) -> Vec<&str> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    path.split(['.', '[', ']'])
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn redact_path(value: &mut Value, path: &[&str]) {
+    let Some((segment, rest)) = path.split_first() else {
+        *value = Value::String(REDACTED.to_string());
+        return;
+    };
+
+    match value {
+        Value::Object(map) if *segment == "*" => {
+            map.values_mut().for_each(|child| redact_path(child, rest));
+        }
+        Value::Object(map) => {
+            if let Some(child) = map.get_mut(*segment) {
+                redact_path(child, rest);
+            }
+        }
+        Value::Array(items) if *segment == "*" => {
+            items.iter_mut().for_each(|child| redact_path(child, rest));
+        }
+        Value::Array(items) => {
+            if let Some(child) = segment.parse().ok().and_then(|i: usize| items.get_mut(i)) {
+                redact_path(child, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Redact the `value` of every object typed `"value_type": "secret"`.
+fn redact_secret_values(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            if map.get("value_type").and_then(Value::as_str) == Some("secret") {
+                if let Some(secret) = map.get_mut("value") {
+                    *secret = Value::String(REDACTED.to_string());
+                }
+            }
+            map.values_mut().for_each(redact_secret_values);
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secret_values),
+        _ => {}
+    }
+}
+
+fn is_json(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+fn is_gzip(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
+        .get(CONTENT_ENCODING)
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"gzip"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_paths_and_wildcards() {
+        let logging = RequestLogging {
+            redact_paths: vec![
+                "$.credentials.token".to_string(),
+                "events[*].api_key".to_string(),
+            ],
+            ..RequestLogging::default()
+        };
+        let mut body = json!({
+            "credentials": {"user": "svc", "token": "t0k3n"},
+            "events": [{"api_key": "k1", "id": 1}, {"id": 2}]
+        });
+
+        logging.redact(&mut body);
+
+        assert_eq!(
+            body,
+            json!({
+                "credentials": {"user": "svc", "token": "***"},
+                "events": [{"api_key": "***", "id": 1}, {"id": 2}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_secret_config_values_always_redacted() {
+        let mut body = json!({
+            "values": [
+                {"key": "db.password", "value": "hunter2", "value_type": "secret"},
+                {"key": "max_tokens", "value": 100, "value_type": "integer"}
+            ]
+        });
+
+        RequestLogging::default().redact(&mut body);
+
+        assert_eq!(body["values"][0]["value"], "***");
+        assert_eq!(body["values"][1]["value"], 100);
+    }
+
+    #[test]
+    fn test_non_json_bodies_logged_by_size() {
+        let logging = RequestLogging::default();
+        assert_eq!(logging.describe_body(b"token=abc", false), "<9 bytes>");
+        assert_eq!(logging.describe_body(b"{}", true), "<2 bytes gzip>");
+    }
+}
@@ -0,0 +1,193 @@
+//! Metrics for integration calls.
+//!
+//! Each call made through an instrumented [`IntegrationClient`](super::IntegrationClient)
+//! is reported to a [`MetricsRecorder`], labeled by integration name and path
+//! template. Paths are matched against templates such as
+//! `/api/v1/schemas/{subject}/latest` so identifiers in URLs never become
+//! label values.
+
+use super::error::IntegrationError;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Records the outcome of integration calls.
+pub trait MetricsRecorder: Send + Sync {
+    /// Record a completed call.
+    ///
+    /// `error` is `None` when the call succeeded.
+    fn record_request(
+        &self,
+        integration: &str,
+        path: &str,
+        latency: Duration,
+        error: Option<&IntegrationError>,
+    );
+}
+
+/// Prometheus-backed metrics recorder.
+#[derive(Clone)]
+pub struct PrometheusRecorder {
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    latency: HistogramVec,
+}
+
+impl PrometheusRecorder {
+    /// Create a recorder and register its metrics with `registry`.
+    pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+        let requests = IntCounterVec::new(
+            Opts::new(
+                "integration_requests_total",
+                "Total number of integration requests",
+            ),
+            &["integration", "path"],
+        )?;
+        let errors = IntCounterVec::new(
+            Opts::new(
+                "integration_errors_total",
+                "Total number of failed integration requests",
+            ),
+            &["integration", "path", "error"],
+        )?;
+        let latency = HistogramVec::new(
+            HistogramOpts::new(
+                "integration_request_duration_seconds",
+                "Integration request latency in seconds",
+            ),
+            &["integration", "path"],
+        )?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(latency.clone()))?;
+
+        Ok(Self {
+            requests,
+            errors,
+            latency,
+        })
+    }
+
+    /// Get the recorder registered with the default Prometheus registry.
+    ///
+    /// These metrics are included in [`crate::telemetry::encode_metrics`].
+    pub fn global() -> Arc<Self> {
+        static GLOBAL: OnceLock<Arc<PrometheusRecorder>> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| {
+                Arc::new(
+                    Self::new(prometheus::default_registry())
+                        .expect("Failed to register integration metrics"),
+                )
+            })
+            .clone()
+    }
+
+    /// Number of requests recorded for an integration and path template.
+    pub fn request_count(&self, integration: &str, path: &str) -> u64 {
+        self.requests.with_label_values(&[integration, path]).get()
+    }
+
+    /// Number of errors of a kind recorded for an integration and path template.
+    pub fn error_count(&self, integration: &str, path: &str, error: &str) -> u64 {
+        self.errors
+            .with_label_values(&[integration, path, error])
+            .get()
+    }
+}
+
+impl MetricsRecorder for PrometheusRecorder {
+    fn record_request(
+        &self,
+        integration: &str,
+        path: &str,
+        latency: Duration,
+        error: Option<&IntegrationError>,
+    ) {
+        self.requests.with_label_values(&[integration, path]).inc();
+        self.latency
+            .with_label_values(&[integration, path])
+            .observe(latency.as_secs_f64());
+        if let Some(error) = error {
+            self.errors
+                .with_label_values(&[integration, path, error.kind()])
+                .inc();
+        }
+    }
+}
+
+/// Map a request path to its metrics label.
+///
+/// The query string is dropped and the path is matched segment-by-segment
+/// against `templates`, where `{name}` matches any single segment. Paths
+/// that match no template are used as-is, so adapters must register a
+/// template for every path with a dynamic segment.
+pub(crate) fn path_label<'a>(path: &'a str, templates: &[&'a str]) -> &'a str {
+    let path = path.split('?').next().unwrap_or(path);
+
+    templates
+        .iter()
+        .copied()
+        .find(|template| template_matches(template, path))
+        .unwrap_or(path)
+}
+
+fn template_matches(template: &str, path: &str) -> bool {
+    template.split('/').count() == path.split('/').count()
+        && template.split('/').zip(path.split('/')).all(|(expected, actual)| {
+            if expected.starts_with('{') && expected.ends_with('}') {
+                !actual.is_empty()
+            } else {
+                expected == actual
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_label_uses_templates() {
+        let templates = [
+            "/api/v1/schemas/{subject}/latest",
+            "/api/v1/schemas/{subject}/versions/{version}",
+        ];
+
+        assert_eq!(
+            path_label("/api/v1/schemas/policy-document/latest", &templates),
+            "/api/v1/schemas/{subject}/latest"
+        );
+        assert_eq!(
+            path_label("/api/v1/schemas/policy-rule/versions/3", &templates),
+            "/api/v1/schemas/{subject}/versions/{version}"
+        );
+        assert_eq!(
+            path_label("/api/v1/schemas?filter=policy", &templates),
+            "/api/v1/schemas"
+        );
+        assert_eq!(
+            path_label("/api/v1/schemas//latest", &templates),
+            "/api/v1/schemas//latest"
+        );
+    }
+
+    #[test]
+    fn test_prometheus_recorder_counts() {
+        let registry = Registry::new();
+        let recorder = PrometheusRecorder::new(&registry).unwrap();
+
+        recorder.record_request("shield", "/api/v1/scan", Duration::from_millis(5), None);
+        recorder.record_request(
+            "shield",
+            "/api/v1/scan",
+            Duration::from_millis(5),
+            Some(&IntegrationError::Timeout),
+        );
+
+        assert_eq!(recorder.request_count("shield", "/api/v1/scan"), 2);
+        assert_eq!(recorder.error_count("shield", "/api/v1/scan", "timeout"), 1);
+        assert_eq!(registry.gather().len(), 3);
+    }
+}
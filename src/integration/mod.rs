@@ -18,42 +18,110 @@
 //! - **Config Manager**: Dynamic configuration and enforcement parameters
 //! - **Observatory**: Telemetry signals and trace context propagation
 
+mod auth;
+mod avro;
+mod circuit_breaker;
 mod client;
 mod costops;
+mod dead_letter;
+mod decryptor;
 mod edge_agent;
+mod error;
+mod event_sink;
 mod governance;
+mod health;
 mod incident_manager;
+mod logging;
+mod metrics;
+mod ndjson;
+mod query;
+mod request_id;
+mod retry;
 mod sentinel;
 mod shield;
+mod throttle;
+mod transport;
 
 // Phase 2B: Upstream consumption adapters
 mod config_manager;
 mod observatory;
 mod schema_registry;
 
-pub use client::{IntegrationClient, IntegrationResult};
-pub use costops::CostOpsClient;
-pub use edge_agent::EdgeAgentClient;
-pub use governance::GovernanceClient;
-pub use incident_manager::IncidentManagerClient;
-pub use sentinel::SentinelClient;
-pub use shield::ShieldClient;
+pub use auth::AuthConfig;
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState};
+pub use client::{
+    ConditionalResponse, IntegrationClient, IntegrationResult, PoolConfig,
+    DEFAULT_MAX_REQUEST_TIMEOUT,
+};
+pub use costops::{BudgetCheckRequest, BudgetCheckResponse, BudgetStatus, CostOpsClient};
+pub use dead_letter::{DeadLetter, DEFAULT_DEAD_LETTER_CAPACITY};
+pub use decryptor::{AesGcmDecryptor, SecretDecryptor};
+pub use edge_agent::{
+    DeploymentState, EdgeAgentClient, EdgeVersion, EdgeVersionsResponse,
+    PolicyDistributionRequest, PolicyDistributionResponse,
+};
+pub use error::IntegrationError;
+pub use event_sink::{
+    BatchConfig, EventSink, Shutdown, ShutdownSummary, DEFAULT_MAX_REJECTION_RETRIES,
+    DEFAULT_SHUTDOWN_GRACE_PERIOD,
+};
+pub use governance::{
+    AuditEvent, AuditOutcome, ComplianceCheckRequest, ComplianceCheckResponse, ComplianceViolation,
+    GovernanceClient, ModelApprovalStatus, ViolationSeverity,
+};
+pub use health::{
+    HealthCheckOptions, HealthReport, DEFAULT_HEALTH_CHECK_TIMEOUT, DEFAULT_HEALTH_REPORT_DEADLINE,
+    MAX_HEALTH_CHECK_CONCURRENCY,
+};
+pub use incident_manager::{
+    AlertType, CreateIncidentRequest, IncidentManagerClient, IncidentSeverity, IncidentStatus,
+};
+pub use logging::RequestLogging;
+pub(crate) use logging::redact;
+pub use metrics::{MetricsRecorder, PrometheusRecorder};
+pub use ndjson::NdjsonOptions;
+pub use request_id::{
+    current_request_id, new_request_id, request_id_from_headers, with_request_id,
+    MAX_REQUEST_ID_LEN, REQUEST_ID_HEADER,
+};
+pub(crate) use request_id::{in_current_request, is_valid_request_id};
+pub use retry::{
+    RetryBudget, RetryPolicy, DEFAULT_RETRY_BUDGET_CAPACITY, DEFAULT_RETRY_BUDGET_RATIO,
+};
+pub(crate) use schema_registry::{compile_json_schema, validate_compiled};
+pub use sentinel::{
+    IndicatorType, RecommendedAction, SecurityEvent, SecurityEventType, SecuritySeverity,
+    SentinelClient,
+};
+pub use shield::{ShieldClient, ShieldScanRequest, ShieldScanResponse, ThreatDetail, ThreatType};
+pub use transport::{MockTransport, RecordedRequest, Transport};
 
 // Phase 2B: Re-export upstream adapters
 pub use config_manager::{
-    ConfigManagerAdapter, ConfigValue, ConfigValueType, EnforcementParams, FeatureFlags,
-    PolicySettings, RuleThresholds,
+    AccessValidationRequest, AccessValidationResult, ConfigBatch, ConfigManagerAdapter,
+    ConfigValue, ConfigValueType, ConfigVersion, EnforcementParams, FeatureFlags, PolicySettings,
+    RateLimitConfig, RuleThresholds, WatchOptions, DEFAULT_MAX_STALENESS,
 };
 pub use observatory::{
-    DecisionOutcome, ObservatoryAdapter, PolicyDecisionRecord, PolicyEvaluationEvent,
-    TelemetrySignals, TraceContext,
+    DecisionOutcome, HealthStatus, ObservatoryAdapter, OutcomeCounts, PolicyDecisionRecord,
+    PolicyEvaluationEvent, PolicyEvaluationEventBuilder, PolicySpan, PolicyStats, SignalType,
+    SpanKind, SpanRegistration, SpanResult, SpanStatus, SubscriptionAck, SubscriptionOptions,
+    SubscriptionStatus, TelemetrySignalRequest, TelemetrySignals, TelemetrySubscription,
+    TelemetryThreshold, ThresholdOperator, TraceContext, TraceParseError, UnknownOperator,
+    BAGGAGE_CONTEXT_PREFIX,
 };
 pub use schema_registry::{
-    SchemaDefinition, SchemaRegistryAdapter, SchemaType, ValidationResult,
+    ChangeKind, CompatibilityLevel, PolicyDocumentSchema, SchemaBatch, SchemaChange,
+    SchemaDefinition, SchemaDiff, SchemaRegistryAdapter, SchemaType, ValidationError,
+    ValidationResult, ValidationSource, ValidationWarning,
 };
 
 use crate::config::IntegrationsConfig;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::StreamExt;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Collection of all integration clients.
 pub struct Integrations {
@@ -77,51 +145,196 @@ pub struct Integrations {
     pub config_manager: Option<Arc<ConfigManagerAdapter>>,
     /// Observatory adapter for telemetry and tracing
     pub observatory: Option<Arc<ObservatoryAdapter>>,
+
+    /// Whether network calls are disabled for every client
+    pub offline: bool,
+
+    /// Configuration the clients were built from
+    config: IntegrationsConfig,
+    /// HTTP connection pool shared by every client
+    http: Arc<reqwest::Client>,
+}
+
+/// Integrations changed by [`Integrations::reconcile`], by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrationChanges {
+    /// Integrations given a URL
+    pub added: Vec<&'static str>,
+    /// Integrations whose URL was removed
+    pub removed: Vec<&'static str>,
+    /// Integrations whose client was rebuilt for a new URL or new client
+    /// settings
+    pub reconfigured: Vec<&'static str>,
+}
+
+impl IntegrationChanges {
+    /// Check if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.reconfigured.is_empty()
+    }
 }
 
 impl Integrations {
     /// Create integrations from configuration.
+    ///
+    /// All clients share one HTTP connection pool and report call metrics to
+    /// [`PrometheusRecorder::global`]. If `offline` is set, clients are still
+    /// created for every configured URL but never send a request.
     pub fn from_config(config: &IntegrationsConfig) -> Self {
-        Self {
-            shield: config
-                .shield_url
-                .as_ref()
-                .map(|url| Arc::new(ShieldClient::new(url.clone(), config.timeout()))),
-            costops: config
-                .costops_url
-                .as_ref()
-                .map(|url| Arc::new(CostOpsClient::new(url.clone(), config.timeout()))),
-            governance: config
-                .governance_url
-                .as_ref()
-                .map(|url| Arc::new(GovernanceClient::new(url.clone(), config.timeout()))),
-            edge_agent: config
-                .edge_agent_url
-                .as_ref()
-                .map(|url| Arc::new(EdgeAgentClient::new(url.clone(), config.timeout()))),
-            incident_manager: config
-                .incident_manager_url
-                .as_ref()
-                .map(|url| Arc::new(IncidentManagerClient::new(url.clone(), config.timeout()))),
-            sentinel: config
-                .sentinel_url
-                .as_ref()
-                .map(|url| Arc::new(SentinelClient::new(url.clone(), config.timeout()))),
-
-            // Phase 2B: Upstream consumption adapters
-            schema_registry: config
-                .schema_registry_url
-                .as_ref()
-                .map(|url| Arc::new(SchemaRegistryAdapter::new(url.clone(), config.timeout()))),
-            config_manager: config
-                .config_manager_url
-                .as_ref()
-                .map(|url| Arc::new(ConfigManagerAdapter::new(url.clone(), config.timeout()))),
-            observatory: config
-                .observatory_url
-                .as_ref()
-                .map(|url| Arc::new(ObservatoryAdapter::new(url.clone(), config.timeout()))),
+        let mut integrations = Self {
+            shield: None,
+            costops: None,
+            governance: None,
+            edge_agent: None,
+            incident_manager: None,
+            sentinel: None,
+            schema_registry: None,
+            config_manager: None,
+            observatory: None,
+            offline: config.offline,
+            config: IntegrationsConfig {
+                shield_url: None,
+                costops_url: None,
+                governance_url: None,
+                edge_agent_url: None,
+                incident_manager_url: None,
+                sentinel_url: None,
+                schema_registry_url: None,
+                config_manager_url: None,
+                observatory_url: None,
+                ..config.clone()
+            },
+            http: connection_pool(config),
+        };
+        integrations.reconcile(config);
+        integrations
+    }
+
+    /// Bring the clients in line with a new configuration without a
+    /// restart.
+    ///
+    /// Clients are created for integrations given a URL and dropped for
+    /// those whose URL was removed; a changed URL replaces the client.
+    /// Unchanged integrations keep their client, with its circuit breaker
+    /// and connections. Changing the timeout, offline mode or retry budget
+    /// ratio rebuilds every client on the same connection pool, with a fresh
+    /// retry budget; changing the pool settings also replaces the pool.
+    /// Calls in flight on a replaced or dropped client hold their own
+    /// reference to it and finish normally.
+    pub fn reconcile(&mut self, config: &IntegrationsConfig) -> IntegrationChanges {
+        let pool_changed = config.pool_max_idle_per_host != self.config.pool_max_idle_per_host
+            || config.pool_idle_timeout_ms != self.config.pool_idle_timeout_ms;
+        if pool_changed {
+            self.http = connection_pool(config);
+        }
+        let rebuild = pool_changed
+            || config.timeout_ms != self.config.timeout_ms
+            || config.offline != self.config.offline
+            || config.retry_budget_ratio != self.config.retry_budget_ratio;
+        let previous = std::mem::replace(&mut self.config, config.clone());
+        self.offline = config.offline;
+
+        let metrics: Arc<dyn MetricsRecorder> = PrometheusRecorder::global();
+        let http = self.http.clone();
+        let client = |url: &str| {
+            IntegrationClient::from_http_client(url.to_string(), config.timeout(), http.clone())
+                .with_offline(config.offline)
+                .with_retry_budget(RetryBudget::new(config.retry_budget_ratio))
+        };
+        let mut changes = IntegrationChanges::default();
+        // The new client of a changed integration, `Some(None)` if dropped.
+        let mut update = |name, old: &Option<String>, new: &Option<String>| {
+            let change = match (old, new) {
+                (Some(_), None) => &mut changes.removed,
+                (None, Some(_)) => &mut changes.added,
+                (Some(old), Some(new)) if rebuild || old != new => &mut changes.reconfigured,
+                _ => return None,
+            };
+            change.push(name);
+            Some(new.as_deref().map(&client))
+        };
+
+        if let Some(client) = update("shield", &previous.shield_url, &config.shield_url) {
+            self.shield = client.map(|client| {
+                Arc::new(ShieldClient::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+        if let Some(client) = update("costops", &previous.costops_url, &config.costops_url) {
+            self.costops = client.map(|client| {
+                Arc::new(CostOpsClient::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+        if let Some(client) = update(
+            "governance",
+            &previous.governance_url,
+            &config.governance_url,
+        ) {
+            self.governance = client.map(|client| {
+                Arc::new(GovernanceClient::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+        if let Some(client) = update(
+            "edge_agent",
+            &previous.edge_agent_url,
+            &config.edge_agent_url,
+        ) {
+            self.edge_agent = client.map(|client| {
+                Arc::new(EdgeAgentClient::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+        if let Some(client) = update(
+            "incident_manager",
+            &previous.incident_manager_url,
+            &config.incident_manager_url,
+        ) {
+            self.incident_manager = client.map(|client| {
+                Arc::new(IncidentManagerClient::from_client(client).with_metrics(metrics.clone()))
+            });
         }
+        if let Some(client) = update("sentinel", &previous.sentinel_url, &config.sentinel_url) {
+            self.sentinel = client.map(|client| {
+                Arc::new(SentinelClient::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+
+        // Phase 2B: Upstream consumption adapters
+        if let Some(client) = update(
+            "schema_registry",
+            &previous.schema_registry_url,
+            &config.schema_registry_url,
+        ) {
+            self.schema_registry = client.map(|client| {
+                Arc::new(SchemaRegistryAdapter::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+        if let Some(client) = update(
+            "config_manager",
+            &previous.config_manager_url,
+            &config.config_manager_url,
+        ) {
+            self.config_manager = client.map(|client| {
+                Arc::new(ConfigManagerAdapter::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+        if let Some(client) = update(
+            "observatory",
+            &previous.observatory_url,
+            &config.observatory_url,
+        ) {
+            self.observatory = client.map(|client| {
+                Arc::new(ObservatoryAdapter::from_client(client).with_metrics(metrics.clone()))
+            });
+        }
+
+        if !changes.is_empty() {
+            tracing::info!(
+                added = ?changes.added,
+                removed = ?changes.removed,
+                reconfigured = ?changes.reconfigured,
+                "Reconciled integration clients"
+            );
+        }
+        changes
     }
 
     /// Check if any integrations are configured.
@@ -137,6 +350,121 @@ impl Integrations {
             || self.observatory.is_some()
     }
 
+    /// Check the health of every configured integration.
+    ///
+    /// Checks run with the default [`HealthCheckOptions`]: concurrently, each
+    /// bounded by [`DEFAULT_HEALTH_CHECK_TIMEOUT`], and the report by
+    /// [`DEFAULT_HEALTH_REPORT_DEADLINE`]. Only integrations named in
+    /// `healthy_required` affect the overall result; if it is empty, all
+    /// configured integrations must be healthy. In offline mode no checks are
+    /// made and every configured integration is reported as
+    /// [`HealthStatus::Offline`].
+    pub async fn health_report(&self, healthy_required: &[&str]) -> HealthReport {
+        self.health_report_with_timeout(healthy_required, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await
+    }
+
+    /// Check the health of every configured integration with a custom timeout.
+    ///
+    /// A check that does not complete within `timeout` is reported as
+    /// unhealthy.
+    pub async fn health_report_with_timeout(
+        &self,
+        healthy_required: &[&str],
+        timeout: Duration,
+    ) -> HealthReport {
+        let options = HealthCheckOptions::default().with_timeout(timeout);
+        self.health_report_with_options(healthy_required, &options)
+            .await
+    }
+
+    /// Check the health of every configured integration with custom options.
+    ///
+    /// At most `options.concurrency` checks run at once, each independently
+    /// bounded by `options.timeout`. Once `options.deadline` passes, the
+    /// report is returned with the integrations still being checked, or not
+    /// yet checked, reported as [`HealthStatus::Unknown`].
+    pub async fn health_report_with_options(
+        &self,
+        healthy_required: &[&str],
+        options: &HealthCheckOptions,
+    ) -> HealthReport {
+        let mut checks: Vec<(&'static str, BoxFuture<'_, bool>)> = Vec::new();
+        if let Some(client) = &self.shield {
+            checks.push(("shield", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.costops {
+            checks.push(("costops", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.governance {
+            checks.push(("governance", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.edge_agent {
+            checks.push(("edge_agent", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.incident_manager {
+            checks.push(("incident_manager", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.sentinel {
+            checks.push(("sentinel", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.schema_registry {
+            checks.push(("schema_registry", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.config_manager {
+            checks.push(("config_manager", client.health_check().boxed()));
+        }
+        if let Some(client) = &self.observatory {
+            checks.push(("observatory", client.health_check().boxed()));
+        }
+
+        if self.offline {
+            let statuses = checks
+                .into_iter()
+                .map(|(name, _)| (name.to_string(), HealthStatus::Offline))
+                .collect();
+            return HealthReport::new(statuses, healthy_required);
+        }
+
+        let mut statuses: BTreeMap<String, HealthStatus> = checks
+            .iter()
+            .map(|(name, _)| (name.to_string(), HealthStatus::Unknown))
+            .collect();
+        let concurrency = options.concurrency_for(checks.len());
+        let timeout = options.timeout;
+        let mut results = futures::stream::iter(checks)
+            .map(|(name, check)| async move {
+                let status = match tokio::time::timeout(timeout, check).await {
+                    Ok(true) => HealthStatus::Healthy,
+                    Ok(false) | Err(_) => HealthStatus::Unhealthy,
+                };
+                (name, status)
+            })
+            .buffer_unordered(concurrency);
+
+        let deadline = tokio::time::sleep(options.deadline);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                result = results.next() => match result {
+                    Some((name, status)) => {
+                        statuses.insert(name.to_string(), status);
+                    }
+                    None => break,
+                },
+                _ = &mut deadline => {
+                    tracing::warn!(
+                        deadline_ms = options.deadline.as_millis() as u64,
+                        "Health report deadline passed; unfinished checks are unknown"
+                    );
+                    break;
+                }
+            }
+        }
+
+        HealthReport::new(statuses, healthy_required)
+    }
+
     /// Check if any Phase 2B upstream adapters are configured.
     pub fn any_upstream_configured(&self) -> bool {
         self.schema_registry.is_some()
@@ -144,3 +472,69 @@ impl Integrations {
             || self.observatory.is_some()
     }
 }
+
+/// Build the HTTP connection pool shared by integration clients.
+fn connection_pool(config: &IntegrationsConfig) -> Arc<reqwest::Client> {
+    PoolConfig::default()
+        .with_max_idle_per_host(config.pool_max_idle_per_host)
+        .with_idle_timeout(config.pool_idle_timeout())
+        .build_http_client()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integrations_share_connection_pool() {
+        let integrations = Integrations::from_config(&IntegrationsConfig {
+            shield_url: Some("http://shield:8080".to_string()),
+            observatory_url: Some("http://observatory:8080".to_string()),
+            ..Default::default()
+        });
+
+        let shield = integrations.shield.as_ref().unwrap().client();
+        let observatory = integrations.observatory.as_ref().unwrap().client();
+        assert!(shield.shares_connection_pool(observatory));
+
+        let standalone =
+            ShieldClient::new("http://shield:8080".to_string(), Duration::from_secs(1));
+        assert!(!standalone.client().shares_connection_pool(shield));
+
+        let repooled = shield.clone().with_pool_config(PoolConfig::default());
+        assert!(!repooled.shares_connection_pool(shield));
+    }
+
+    #[test]
+    fn test_reconcile_changes_only_changed_clients() {
+        let mut config = IntegrationsConfig {
+            shield_url: Some("http://shield:8080".to_string()),
+            costops_url: Some("http://costops:8080".to_string()),
+            ..Default::default()
+        };
+        let mut integrations = Integrations::from_config(&config);
+        let shield = integrations.shield.clone().unwrap();
+        let costops = integrations.costops.clone().unwrap();
+
+        config.costops_url = None;
+        config.observatory_url = Some("http://observatory:8080".to_string());
+        let changes = integrations.reconcile(&config);
+        assert_eq!(changes.added, vec!["observatory"]);
+        assert_eq!(changes.removed, vec!["costops"]);
+        assert!(changes.reconfigured.is_empty());
+        assert!(Arc::ptr_eq(integrations.shield.as_ref().unwrap(), &shield));
+        assert!(integrations.costops.is_none());
+        // Callers still holding the removed client can keep using it.
+        assert_eq!(costops.client().base_url(), "http://costops:8080");
+        let observatory = integrations.observatory.as_ref().unwrap().client();
+        assert!(observatory.shares_connection_pool(shield.client()));
+
+        config.shield_url = Some("http://shield-v2:8080".to_string());
+        let changes = integrations.reconcile(&config);
+        assert_eq!(changes.reconfigured, vec!["shield"]);
+        let repointed = integrations.shield.as_ref().unwrap();
+        assert_eq!(repointed.client().base_url(), "http://shield-v2:8080");
+        assert!(repointed.client().shares_connection_pool(shield.client()));
+        assert!(integrations.reconcile(&config).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+//! Incremental parsing of newline-delimited JSON (NDJSON) responses.
+//!
+//! Large result sets are streamed one record per line so callers can process
+//! them without buffering the whole response. [`NdjsonReader`] pulls body
+//! chunks as they arrive and yields each complete line as soon as it parses;
+//! memory use is bounded by the longest line rather than the response size.
+
+use super::client::IntegrationResult;
+use super::error::IntegrationError;
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+
+/// Options for [`IntegrationClient::get_stream_with`](super::IntegrationClient::get_stream_with).
+#[derive(Debug, Clone)]
+pub struct NdjsonOptions {
+    /// End the stream after the first error instead of skipping the bad line
+    pub fail_fast: bool,
+    /// Longest line accepted, in bytes; longer lines yield an error
+    pub max_line_bytes: usize,
+}
+
+impl Default for NdjsonOptions {
+    fn default() -> Self {
+        Self {
+            fail_fast: false,
+            max_line_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Splits chunks of an NDJSON body into lines and parses each one.
+#[derive(Debug)]
+struct NdjsonDecoder {
+    buffer: Vec<u8>,
+    /// 1-based number of the line being accumulated
+    line: usize,
+    max_line_bytes: usize,
+    /// Set while skipping the rest of an oversized line
+    discarding: bool,
+}
+
+impl NdjsonDecoder {
+    fn new(max_line_bytes: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            line: 1,
+            max_line_bytes,
+            discarding: false,
+        }
+    }
+
+    /// Feed a chunk, queueing an item for every line it completes.
+    fn push<T: DeserializeOwned>(
+        &mut self,
+        chunk: &[u8],
+        out: &mut VecDeque<IntegrationResult<T>>,
+    ) {
+        for segment in chunk.split_inclusive(|byte| *byte == b'\n') {
+            let complete = segment.last() == Some(&b'\n');
+            let data = if complete {
+                &segment[..segment.len() - 1]
+            } else {
+                segment
+            };
+
+            if !self.discarding {
+                if self.buffer.len() + data.len() > self.max_line_bytes {
+                    out.push_back(Err(IntegrationError::Deserialize(format!(
+                        "line {}: exceeds {} bytes",
+                        self.line, self.max_line_bytes
+                    ))));
+                    self.buffer.clear();
+                    self.discarding = true;
+                } else {
+                    self.buffer.extend_from_slice(data);
+                }
+            }
+
+            if complete {
+                if !self.discarding {
+                    self.parse_line(out);
+                }
+                self.buffer.clear();
+                self.discarding = false;
+                self.line += 1;
+            }
+        }
+    }
+
+    /// Parse a final line that was not terminated by a newline.
+    fn finish<T: DeserializeOwned>(&mut self, out: &mut VecDeque<IntegrationResult<T>>) {
+        if !self.discarding {
+            self.parse_line(out);
+        }
+        self.buffer.clear();
+    }
+
+    /// Parse the buffered line; blank lines are skipped.
+    fn parse_line<T: DeserializeOwned>(&self, out: &mut VecDeque<IntegrationResult<T>>) {
+        let line = self.buffer.trim_ascii();
+        if line.is_empty() {
+            return;
+        }
+        out.push_back(
+            serde_json::from_slice(line)
+                .map_err(|e| IntegrationError::Deserialize(format!("line {}: {}", self.line, e))),
+        );
+    }
+}
+
+/// Reads an NDJSON response body item by item.
+pub(crate) struct NdjsonReader<T> {
+    response: Option<reqwest::Response>,
+    decoder: NdjsonDecoder,
+    pending: VecDeque<IntegrationResult<T>>,
+    fail_fast: bool,
+}
+
+impl<T: DeserializeOwned> NdjsonReader<T> {
+    /// Read items from a successful response.
+    pub(crate) fn new(response: reqwest::Response, options: &NdjsonOptions) -> Self {
+        Self {
+            response: Some(response),
+            decoder: NdjsonDecoder::new(options.max_line_bytes),
+            pending: VecDeque::new(),
+            fail_fast: options.fail_fast,
+        }
+    }
+
+    /// Get the next item, reading more of the body as needed.
+    async fn next_item(&mut self) -> Option<IntegrationResult<T>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                if item.is_err() && self.fail_fast {
+                    self.pending.clear();
+                    self.response = None;
+                }
+                return Some(item);
+            }
+
+            let response = self.response.as_mut()?;
+            match response.chunk().await {
+                Ok(Some(chunk)) => self.decoder.push(&chunk, &mut self.pending),
+                Ok(None) => {
+                    self.response = None;
+                    self.decoder.finish(&mut self.pending);
+                }
+                Err(e) => {
+                    self.response = None;
+                    self.pending.push_back(Err(e.into()));
+                }
+            }
+        }
+    }
+
+    /// Convert into a stream of items.
+    pub(crate) fn into_stream(self) -> impl Stream<Item = IntegrationResult<T>> {
+        futures::stream::unfold(self, |mut reader| async move {
+            let item = reader.next_item().await?;
+            Some((item, reader))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(chunks: &[&[u8]], max_line_bytes: usize) -> Vec<IntegrationResult<u32>> {
+        let mut decoder = NdjsonDecoder::new(max_line_bytes);
+        let mut out = VecDeque::new();
+        for chunk in chunks {
+            decoder.push(chunk, &mut out);
+        }
+        decoder.finish(&mut out);
+        out.into_iter().collect()
+    }
+
+    #[test]
+    fn test_lines_split_across_chunks() {
+        let items = decode(&[b"1\n2", b"3\r\n\n", b"4"], 64);
+        let items: Vec<u32> = items.into_iter().map(Result::unwrap).collect();
+        assert_eq!(items, vec![1, 23, 4]);
+    }
+
+    #[test]
+    fn test_oversized_line_is_skipped() {
+        let items = decode(&[b"1\n123456", b"789\n3\n"], 4);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0], Ok(1));
+        let err = items[1].clone().unwrap_err().to_string();
+        assert!(err.contains("line 2: exceeds 4 bytes"), "{}", err);
+        assert_eq!(items[2], Ok(3));
+    }
+}
@@ -11,10 +11,34 @@
 //! dependency pattern: Observatory -> Policy Engine (consumes-from).
 
 use super::client::{IntegrationClient, IntegrationResult};
+use super::error::IntegrationError;
+use super::event_sink::{self, BatchConfig, EventSink};
+use super::metrics::MetricsRecorder;
+use super::query::{encode_component, with_query};
+use crate::policy::{DecisionReason, DecisionType, Obligations, ReasonCode};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Path templates for metrics labels.
+const PATH_TEMPLATES: &[&str] = &[
+    "/api/v1/traces/{trace_id}/context",
+    "/api/v1/spans/{span_id}/complete",
+    "/api/v1/analytics/policies/{policy_id}/stats",
+    "/api/v1/subscriptions/telemetry/{subscription_id}",
+];
+
+/// Prefix for baggage entries copied into [`PolicyEvaluationEvent::context`].
+pub const BAGGAGE_CONTEXT_PREFIX: &str = "baggage.";
+
+/// Maximum number of baggage entries propagated (W3C Baggage limit).
+const MAX_BAGGAGE_ENTRIES: usize = 180;
+
+/// Maximum total size of propagated baggage in bytes (W3C Baggage limit).
+const MAX_BAGGAGE_BYTES: usize = 8192;
+
 /// Client for integrating with LLM Observatory.
 ///
 /// This is a thin adapter that:
@@ -30,8 +54,13 @@ pub struct ObservatoryAdapter {
 impl ObservatoryAdapter {
     /// Create a new Observatory adapter.
     pub fn new(base_url: String, timeout: Duration) -> Self {
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Observatory adapter from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
         Self {
-            client: IntegrationClient::new(base_url, timeout),
+            client,
             service_name: "llm-policy-engine".to_string(),
         }
     }
@@ -44,15 +73,35 @@ impl ObservatoryAdapter {
         }
     }
 
+    /// Gzip-compress event payloads of at least `min_bytes`.
+    ///
+    /// Useful for large batches from [`emit_evaluation_events_batch`](Self::emit_evaluation_events_batch).
+    pub fn with_compression(mut self, min_bytes: usize) -> Self {
+        self.client = self.client.with_compression(min_bytes);
+        self
+    }
+
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("observatory", PATH_TEMPLATES, recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
+    }
+
     /// Emit a policy evaluation event.
     ///
     /// This sends evaluation metadata to Observatory for aggregation and analysis.
+    /// Events carry a unique ID, so the request is retried on transient failures.
     pub async fn emit_evaluation_event(
         &self,
         event: &PolicyEvaluationEvent,
     ) -> IntegrationResult<EventAck> {
         self.client
-            .post("/api/v1/events/policy-evaluation", event)
+            .post_idempotent("/api/v1/events/policy-evaluation", event)
             .await
     }
 
@@ -66,16 +115,26 @@ impl ObservatoryAdapter {
             events: events.to_vec(),
         };
         self.client
-            .post("/api/v1/events/batch", &request)
+            .post_idempotent("/api/v1/events/batch", &request)
             .await
     }
 
+    /// Start a background task that emits events in batches.
+    ///
+    /// Events sent to the returned [`EventSink`] are flushed with
+    /// [`emit_evaluation_events_batch`](Self::emit_evaluation_events_batch)
+    /// when a batch fills up or the flush interval elapses. Must be called
+    /// from within a Tokio runtime.
+    pub fn spawn_batching(self: &Arc<Self>, config: BatchConfig) -> EventSink {
+        event_sink::spawn(Arc::clone(self), config)
+    }
+
     /// Get trace context for a request.
     ///
     /// This retrieves distributed trace context from Observatory for
     /// trace correlation across services.
     pub async fn get_trace_context(&self, trace_id: &str) -> IntegrationResult<TraceContext> {
-        let path = format!("/api/v1/traces/{}/context", trace_id);
+        let path = format!("/api/v1/traces/{}/context", encode_component(trace_id));
         self.client.get(&path).await
     }
 
@@ -88,7 +147,7 @@ impl ObservatoryAdapter {
 
     /// Complete a trace span with results.
     pub async fn complete_span(&self, span_id: &str, result: &SpanResult) -> IntegrationResult<()> {
-        let path = format!("/api/v1/spans/{}/complete", span_id);
+        let path = format!("/api/v1/spans/{}/complete", encode_component(span_id));
         self.client.post::<(), _>(&path, result).await.map(|_| ())
     }
 
@@ -101,7 +160,7 @@ impl ObservatoryAdapter {
         request: &TelemetrySignalRequest,
     ) -> IntegrationResult<TelemetrySignals> {
         self.client
-            .post("/api/v1/signals/query", request)
+            .post_idempotent("/api/v1/signals/query", request)
             .await
     }
 
@@ -111,10 +170,32 @@ impl ObservatoryAdapter {
         service: &str,
         model: Option<&str>,
     ) -> IntegrationResult<CurrentMetrics> {
-        let path = match model {
-            Some(m) => format!("/api/v1/metrics/current?service={}&model={}", service, m),
-            None => format!("/api/v1/metrics/current?service={}", service),
-        };
+        self.get_current_metrics_filtered(service, model, None, None)
+            .await
+    }
+
+    /// Get current metrics for a service, optionally narrowed to a model and
+    /// provider and aggregated over a time window, as in
+    /// [`TelemetrySignalRequest`].
+    ///
+    /// Without a window, Observatory uses its default (the last minute).
+    pub async fn get_current_metrics_filtered(
+        &self,
+        service: &str,
+        model: Option<&str>,
+        provider: Option<&str>,
+        window: Option<Duration>,
+    ) -> IntegrationResult<CurrentMetrics> {
+        let window_seconds = window.map(|window| window.as_secs().to_string());
+        let path = with_query(
+            "/api/v1/metrics/current",
+            &[
+                ("service", Some(service)),
+                ("model", model),
+                ("provider", provider),
+                ("window_seconds", window_seconds.as_deref()),
+            ],
+        );
         self.client.get(&path).await
     }
 
@@ -128,6 +209,124 @@ impl ObservatoryAdapter {
             .await
     }
 
+    /// Cancel a telemetry subscription created by
+    /// [`subscribe_telemetry`](Self::subscribe_telemetry).
+    pub async fn unsubscribe_telemetry(&self, subscription_id: &str) -> IntegrationResult<()> {
+        let path = format!(
+            "/api/v1/subscriptions/telemetry/{}",
+            encode_component(subscription_id)
+        );
+        self.client.delete(&path).await
+    }
+
+    /// Get the state of a telemetry subscription.
+    pub async fn get_subscription(
+        &self,
+        subscription_id: &str,
+    ) -> IntegrationResult<SubscriptionAck> {
+        let path = format!(
+            "/api/v1/subscriptions/telemetry/{}",
+            encode_component(subscription_id)
+        );
+        self.client.get(&path).await
+    }
+
+    /// Subscribe to telemetry and keep the subscription alive, yielding its
+    /// status as it changes.
+    ///
+    /// The subscription is checked every `check_interval` and renewed before
+    /// it expires. If Observatory no longer knows it (after a restart, or
+    /// once expired), it is re-established with exponential backoff. Each
+    /// re-subscription requests the last subscription ID, so Observatory can
+    /// keep the ID stable across reconnects. The stream ends if integrations
+    /// go offline; dropping it leaves the subscription in place, to be
+    /// cancelled with [`unsubscribe_telemetry`](Self::unsubscribe_telemetry).
+    pub fn watch_subscription(
+        &self,
+        request: TelemetrySubscription,
+        options: SubscriptionOptions,
+    ) -> impl Stream<Item = SubscriptionStatus> + '_ {
+        let state = SubscriptionState {
+            subscription_id: request.subscription_id.clone(),
+            active: false,
+            expires_at: None,
+            delay: None,
+            backoff: options.initial_backoff,
+        };
+
+        futures::stream::unfold(state, move |mut state| {
+            let request = request.clone();
+            let options = options.clone();
+            async move {
+                loop {
+                    if let Some(delay) = state.delay.take() {
+                        tokio::time::sleep(delay).await;
+                    }
+
+                    if state.active && !state.expiring(&options) {
+                        let subscription_id = state.subscription_id.clone().unwrap_or_default();
+                        let reason = match self.get_subscription(&subscription_id).await {
+                            Ok(ack) if ack.active => {
+                                state.backoff = options.initial_backoff;
+                                state.expires_at = parse_expiry(&ack);
+                                state.delay = Some(state.next_check(&options));
+                                continue;
+                            }
+                            Ok(_) => "subscription is no longer active".to_string(),
+                            Err(IntegrationError::HttpStatus { code: 404, .. }) => {
+                                "subscription not found".to_string()
+                            }
+                            Err(IntegrationError::Offline) => return None,
+                            Err(e) => return Some((state.retry(e.to_string(), &options), state)),
+                        };
+
+                        tracing::warn!(
+                            subscription_id = %subscription_id,
+                            reason = %reason,
+                            "Observatory telemetry subscription dropped, resubscribing"
+                        );
+                        state.active = false;
+                        state.expires_at = None;
+                        let status = SubscriptionStatus::Lost {
+                            subscription_id,
+                            reason,
+                        };
+                        return Some((status, state));
+                    }
+
+                    let request = TelemetrySubscription {
+                        subscription_id: state.subscription_id.clone(),
+                        ..request.clone()
+                    };
+                    match self.subscribe_telemetry(&request).await {
+                        Ok(ack) if ack.active => {
+                            let previous =
+                                state.subscription_id.replace(ack.subscription_id.clone());
+                            let changed = !state.active || previous != state.subscription_id;
+                            state.active = true;
+                            state.backoff = options.initial_backoff;
+                            state.expires_at = parse_expiry(&ack);
+                            state.delay = Some(state.next_check(&options));
+                            if changed {
+                                let status = SubscriptionStatus::Active {
+                                    subscription_id: ack.subscription_id,
+                                };
+                                return Some((status, state));
+                            }
+                        }
+                        Ok(ack) => {
+                            let error =
+                                format!("subscription {} was not activated", ack.subscription_id);
+                            return Some((state.retry(error, &options), state));
+                        }
+                        Err(IntegrationError::Offline) => return None,
+                        Err(e) => return Some((state.retry(e.to_string(), &options), state)),
+                    }
+                }
+            }
+        })
+    }
+
     /// Record a policy decision for analytics.
     pub async fn record_decision(
         &self,
@@ -138,6 +337,27 @@ impl ObservatoryAdapter {
             .await
     }
 
+    /// Get aggregated decision statistics for a policy over a time window.
+    ///
+    /// Returns empty statistics when Observatory has no data for the policy
+    /// in the requested window.
+    pub async fn get_policy_stats(
+        &self,
+        policy_id: &str,
+        window: Duration,
+    ) -> IntegrationResult<PolicyStats> {
+        let window_seconds = window.as_secs();
+        let path = with_query(
+            &format!(
+                "/api/v1/analytics/policies/{}/stats",
+                encode_component(policy_id)
+            ),
+            &[("window_seconds", Some(window_seconds.to_string().as_str()))],
+        );
+        let stats = self.client.get_optional::<PolicyStats>(&path).await?;
+        Ok(stats.unwrap_or_else(|| PolicyStats::empty(policy_id, window_seconds)))
+    }
+
     /// Check if Observatory service is healthy.
     pub async fn health_check(&self) -> bool {
         self.client.health_check().await
@@ -164,6 +384,15 @@ pub struct PolicyEvaluationEvent {
     pub rule_id: Option<String>,
     /// Decision result
     pub decision: DecisionOutcome,
+    /// Machine-readable reason for the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<DecisionReason>,
+    /// Obligations of a modify decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obligations: Option<Obligations>,
+    /// Decision of shadow policies, reported separately and never enforced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_decision: Option<DecisionOutcome>,
     /// Evaluation duration in milliseconds
     pub duration_ms: f64,
     /// Whether result was cached
@@ -177,7 +406,181 @@ pub struct PolicyEvaluationEvent {
     pub labels: HashMap<String, String>,
 }
 
+impl PolicyEvaluationEvent {
+    /// Create an event builder for a policy's decision.
+    pub fn builder(
+        policy_id: impl Into<String>,
+        decision: DecisionOutcome,
+    ) -> PolicyEvaluationEventBuilder {
+        PolicyEvaluationEventBuilder::new(policy_id, decision)
+    }
+
+    /// Fill the trace ID, span ID and baggage from a trace context.
+    ///
+    /// The span ID is the context's parent span. Baggage entries are copied
+    /// into `context` under [`BAGGAGE_CONTEXT_PREFIX`] in key order; keys
+    /// outside the W3C token character set are skipped, and entries beyond
+    /// the W3C size limits are dropped.
+    pub fn with_trace_context(mut self, ctx: &TraceContext) -> Self {
+        self.trace_id = Some(ctx.trace_id.clone());
+        self.span_id = ctx.parent_span_id.clone();
+
+        let mut entries: Vec<_> = ctx
+            .baggage
+            .iter()
+            .filter(|(key, _)| is_valid_baggage_key(key))
+            .collect();
+        entries.sort();
+
+        let total = entries.len();
+        let mut bytes = 0;
+        for (count, (key, value)) in entries.into_iter().enumerate() {
+            // Each entry costs `key=value` plus a separating comma.
+            let size = key.len() + value.len() + 2;
+            if count == MAX_BAGGAGE_ENTRIES || bytes + size > MAX_BAGGAGE_BYTES {
+                tracing::debug!(
+                    trace_id = %ctx.trace_id,
+                    dropped = total - count,
+                    "Truncating oversized baggage"
+                );
+                break;
+            }
+            bytes += size;
+            self.context
+                .insert(format!("{}{}", BAGGAGE_CONTEXT_PREFIX, key), value.clone());
+        }
+
+        self
+    }
+}
+
+/// Builder for [`PolicyEvaluationEvent`]s.
+///
+/// The event ID defaults to a new UUID and the timestamp to the current
+/// time. [`build`](Self::build) rejects a span ID without a trace ID and a
+/// negative or non-finite duration.
+#[derive(Debug, Clone)]
+pub struct PolicyEvaluationEventBuilder {
+    event: PolicyEvaluationEvent,
+}
+
+impl PolicyEvaluationEventBuilder {
+    /// Create a builder for a policy's decision.
+    pub fn new(policy_id: impl Into<String>, decision: DecisionOutcome) -> Self {
+        Self {
+            event: PolicyEvaluationEvent {
+                event_id: uuid::Uuid::new_v4().to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                trace_id: None,
+                span_id: None,
+                policy_id: policy_id.into(),
+                rule_id: None,
+                decision,
+                reason: None,
+                obligations: None,
+                shadow_decision: None,
+                duration_ms: 0.0,
+                cached: false,
+                context: HashMap::new(),
+                labels: HashMap::new(),
+            },
+        }
+    }
+
+    /// Set the event ID.
+    pub fn event_id(mut self, event_id: impl Into<String>) -> Self {
+        self.event.event_id = event_id.into();
+        self
+    }
+
+    /// Set the timestamp (RFC 3339).
+    pub fn timestamp(mut self, timestamp: impl Into<String>) -> Self {
+        self.event.timestamp = timestamp.into();
+        self
+    }
+
+    /// Set the trace ID.
+    pub fn trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.event.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Set the span ID; requires a trace ID.
+    pub fn span_id(mut self, span_id: impl Into<String>) -> Self {
+        self.event.span_id = Some(span_id.into());
+        self
+    }
+
+    /// Set the matched rule.
+    pub fn rule_id(mut self, rule_id: impl Into<String>) -> Self {
+        self.event.rule_id = Some(rule_id.into());
+        self
+    }
+
+    /// Set the machine-readable reason for the decision.
+    pub fn reason(mut self, reason: DecisionReason) -> Self {
+        self.event.reason = Some(reason);
+        self
+    }
+
+    /// Set the obligations of a modify decision.
+    pub fn obligations(mut self, obligations: Obligations) -> Self {
+        self.event.obligations = Some(obligations);
+        self
+    }
+
+    /// Set the decision of shadow policies.
+    pub fn shadow_decision(mut self, decision: DecisionOutcome) -> Self {
+        self.event.shadow_decision = Some(decision);
+        self
+    }
+
+    /// Set the evaluation duration in milliseconds.
+    pub fn duration_ms(mut self, duration_ms: f64) -> Self {
+        self.event.duration_ms = duration_ms;
+        self
+    }
+
+    /// Set whether the result was cached.
+    pub fn cached(mut self, cached: bool) -> Self {
+        self.event.cached = cached;
+        self
+    }
+
+    /// Set the additional context.
+    pub fn context(mut self, context: HashMap<String, String>) -> Self {
+        self.event.context = context;
+        self
+    }
+
+    /// Add a label.
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.event.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Build the event.
+    pub fn build(self) -> crate::Result<PolicyEvaluationEvent> {
+        let event = self.event;
+        if event.span_id.is_some() && event.trace_id.is_none() {
+            return Err(crate::Error::validation_field(
+                "Span ID requires a trace ID",
+                "span_id",
+            ));
+        }
+        if !(event.duration_ms.is_finite() && event.duration_ms >= 0.0) {
+            return Err(crate::Error::validation_field(
+                format!("Duration must be non-negative, got {}", event.duration_ms),
+                "duration_ms",
+            ));
+        }
+        Ok(event)
+    }
+}
+
 /// Decision outcome for telemetry.
+///
+/// Serialized in snake case, as the Observatory API names outcomes, e.g. `allow`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DecisionOutcome {
@@ -193,6 +596,30 @@ pub enum DecisionOutcome {
     Error,
 }
 
+impl DecisionOutcome {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecisionOutcome::Allow => "allow",
+            DecisionOutcome::Deny => "deny",
+            DecisionOutcome::Warn => "warn",
+            DecisionOutcome::Modify => "modify",
+            DecisionOutcome::Error => "error",
+        }
+    }
+}
+
+impl From<DecisionType> for DecisionOutcome {
+    fn from(decision: DecisionType) -> Self {
+        match decision {
+            DecisionType::Allow => DecisionOutcome::Allow,
+            DecisionType::Deny => DecisionOutcome::Deny,
+            DecisionType::Warn => DecisionOutcome::Warn,
+            DecisionType::Modify => DecisionOutcome::Modify,
+        }
+    }
+}
+
 /// Batch event request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BatchEventRequest {
@@ -220,6 +647,9 @@ pub struct BatchEventAck {
     /// IDs of rejected events
     #[serde(default)]
     pub rejected_ids: Vec<String>,
+    /// Why events were rejected, by event ID, if the server says
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rejection_reasons: HashMap<String, String>,
 }
 
 /// Trace context for distributed tracing.
@@ -257,6 +687,152 @@ impl TraceContext {
     pub fn is_sampled(&self) -> bool {
         self.trace_flags & 0x01 != 0
     }
+
+    /// Format a W3C `traceparent` header for a span in this trace.
+    pub fn to_traceparent(&self, span_id: &str) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            self.trace_id.to_ascii_lowercase(),
+            span_id.to_ascii_lowercase(),
+            self.trace_flags
+        )
+    }
+
+    /// Parse a W3C `traceparent` header.
+    ///
+    /// The parent span ID from the header is stored in `parent_span_id`.
+    pub fn from_traceparent(header: &str) -> Result<Self, TraceParseError> {
+        let parts: Vec<&str> = header.trim().split('-').collect();
+        if parts.len() < 4 {
+            return Err(TraceParseError::InvalidFormat);
+        }
+
+        let version = parts[0];
+        if version.len() != 2 || !is_lower_hex(version) {
+            return Err(TraceParseError::InvalidFormat);
+        }
+        if version == "ff" || (version == "00" && parts.len() != 4) {
+            return Err(TraceParseError::UnsupportedVersion(version.to_string()));
+        }
+
+        let trace_id = parts[1];
+        if trace_id.len() != 32 || !is_lower_hex(trace_id) || is_all_zeros(trace_id) {
+            return Err(TraceParseError::InvalidTraceId);
+        }
+
+        let span_id = parts[2];
+        if span_id.len() != 16 || !is_lower_hex(span_id) || is_all_zeros(span_id) {
+            return Err(TraceParseError::InvalidSpanId);
+        }
+
+        let flags = parts[3];
+        if flags.len() != 2 || !is_lower_hex(flags) {
+            return Err(TraceParseError::InvalidFlags);
+        }
+        let trace_flags =
+            u8::from_str_radix(flags, 16).map_err(|_| TraceParseError::InvalidFlags)?;
+
+        Ok(Self {
+            trace_id: trace_id.to_string(),
+            parent_span_id: Some(span_id.to_string()),
+            trace_flags,
+            trace_state: None,
+            baggage: HashMap::new(),
+        })
+    }
+
+    /// Parse W3C `traceparent` and optional `tracestate` headers.
+    pub fn from_headers(
+        traceparent: &str,
+        tracestate: Option<&str>,
+    ) -> Result<Self, TraceParseError> {
+        let mut context = Self::from_traceparent(traceparent)?;
+        context.trace_state = tracestate
+            .map(str::trim)
+            .filter(|state| !state.is_empty())
+            .map(str::to_string);
+        Ok(context)
+    }
+
+    /// Add entries from a W3C `baggage` header.
+    ///
+    /// Values are percent-decoded and member properties are ignored. Members
+    /// with invalid keys or undecodable values are skipped.
+    pub fn with_baggage_header(mut self, header: &str) -> Self {
+        for member in header.split(',') {
+            let entry = member.split(';').next().unwrap_or_default();
+            let Some((key, value)) = entry.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            if !is_valid_baggage_key(key) {
+                continue;
+            }
+            if let Some(value) = percent_decode(value.trim()) {
+                self.baggage.insert(key.to_string(), value);
+            }
+        }
+        self
+    }
+}
+
+/// Error parsing a W3C trace context header.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TraceParseError {
+    /// Header is not in `version-traceid-spanid-flags` form
+    #[error("Malformed traceparent header")]
+    InvalidFormat,
+    /// Version is not supported
+    #[error("Unsupported traceparent version: {0}")]
+    UnsupportedVersion(String),
+    /// Trace ID is not 32 lowercase hex characters or is all zeros
+    #[error("Invalid trace ID in traceparent header")]
+    InvalidTraceId,
+    /// Span ID is not 16 lowercase hex characters or is all zeros
+    #[error("Invalid span ID in traceparent header")]
+    InvalidSpanId,
+    /// Trace flags are not 2 hex characters
+    #[error("Invalid trace flags in traceparent header")]
+    InvalidFlags,
+}
+
+fn is_lower_hex(value: &str) -> bool {
+    value.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_all_zeros(value: &str) -> bool {
+    value.bytes().all(|b| b == b'0')
+}
+
+/// Check a baggage key against the RFC 7230 token characters required by W3C Baggage.
+fn is_valid_baggage_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.bytes().all(|b| {
+            b.is_ascii_alphanumeric()
+                || matches!(
+                    b,
+                    b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^'
+                        | b'_' | b'`' | b'|' | b'~'
+                )
+        })
+}
+
+/// Decode a percent-encoded UTF-8 string.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
 }
 
 /// A policy evaluation span.
@@ -280,6 +856,9 @@ pub struct PolicySpan {
 }
 
 /// Span kind enumeration.
+///
+/// Serialized in screaming snake case, as OpenTelemetry names span kinds,
+/// e.g. `SERVER`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SpanKind {
@@ -326,6 +905,9 @@ pub struct SpanResult {
 }
 
 /// Span status codes.
+///
+/// Serialized in screaming snake case, as OpenTelemetry names status codes,
+/// e.g. `OK`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SpanStatus {
@@ -411,6 +993,31 @@ pub struct TelemetrySignals {
     pub availability: Option<f64>,
 }
 
+impl TelemetrySignals {
+    /// Get the value a threshold on `signal_type` is compared against.
+    ///
+    /// Latency uses the p99 percentile and token usage the total token count.
+    /// Returns `None` if the signal was not reported.
+    pub fn value(&self, signal_type: SignalType) -> Option<f64> {
+        match signal_type {
+            SignalType::ErrorRate => self.error_rate,
+            SignalType::Latency => self.latency_percentiles.p99,
+            SignalType::RequestRate => self.request_rate,
+            SignalType::TokenUsage => self.token_usage.as_ref().map(|u| u.total_tokens as f64),
+            SignalType::Cost => self.cost,
+            SignalType::Availability => self.availability,
+        }
+    }
+
+    /// Check if the signal named by `threshold` crosses it.
+    ///
+    /// A signal that was not reported never crosses a threshold.
+    pub fn exceeds_threshold(&self, threshold: &TelemetryThreshold) -> bool {
+        self.value(threshold.signal_type)
+            .is_some_and(|value| threshold.operator.compare(value, threshold.value))
+    }
+}
+
 /// Latency percentile values.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LatencyPercentiles {
@@ -471,6 +1078,8 @@ pub enum HealthStatus {
     Unhealthy,
     /// Unknown
     Unknown,
+    /// Not checked because integrations are offline
+    Offline,
 }
 
 impl Default for HealthStatus {
@@ -479,6 +1088,87 @@ impl Default for HealthStatus {
     }
 }
 
+/// Aggregated decision statistics for a single policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyStats {
+    /// Policy ID
+    pub policy_id: String,
+    /// Time window covered
+    pub window_seconds: u64,
+    /// Total number of decisions in the window
+    #[serde(default)]
+    pub total_decisions: u64,
+    /// Decision counts by outcome
+    #[serde(default)]
+    pub outcome_counts: OutcomeCounts,
+    /// Evaluation latency percentiles
+    #[serde(default)]
+    pub latency_percentiles: LatencyPercentiles,
+    /// Fraction of decisions served from cache (0.0 - 1.0)
+    #[serde(default)]
+    pub cache_hit_rate: f64,
+}
+
+impl PolicyStats {
+    /// Create empty statistics for a policy with no recorded decisions.
+    pub fn empty(policy_id: impl Into<String>, window_seconds: u64) -> Self {
+        Self {
+            policy_id: policy_id.into(),
+            window_seconds,
+            total_decisions: 0,
+            outcome_counts: OutcomeCounts::default(),
+            latency_percentiles: LatencyPercentiles::default(),
+            cache_hit_rate: 0.0,
+        }
+    }
+
+    /// Check if no decisions were recorded in the window.
+    pub fn is_empty(&self) -> bool {
+        self.total_decisions == 0
+    }
+
+    /// Get the fraction of decisions (0.0 - 1.0) with the given outcome.
+    pub fn rate(&self, outcome: DecisionOutcome) -> f64 {
+        if self.total_decisions == 0 {
+            return 0.0;
+        }
+        self.outcome_counts.get(outcome) as f64 / self.total_decisions as f64
+    }
+}
+
+/// Decision counts broken down by outcome.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OutcomeCounts {
+    /// Allowed decisions
+    #[serde(default)]
+    pub allow: u64,
+    /// Denied decisions
+    #[serde(default)]
+    pub deny: u64,
+    /// Warnings
+    #[serde(default)]
+    pub warn: u64,
+    /// Modifications
+    #[serde(default)]
+    pub modify: u64,
+    /// Evaluation errors
+    #[serde(default)]
+    pub error: u64,
+}
+
+impl OutcomeCounts {
+    /// Get the count for an outcome.
+    pub fn get(&self, outcome: DecisionOutcome) -> u64 {
+        match outcome {
+            DecisionOutcome::Allow => self.allow,
+            DecisionOutcome::Deny => self.deny,
+            DecisionOutcome::Warn => self.warn,
+            DecisionOutcome::Modify => self.modify,
+            DecisionOutcome::Error => self.error,
+        }
+    }
+}
+
 /// Telemetry subscription request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetrySubscription {
@@ -496,6 +1186,10 @@ pub struct TelemetrySubscription {
     /// Threshold for notifications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub threshold: Option<TelemetryThreshold>,
+    /// ID of an earlier subscription to re-establish, kept by Observatory
+    /// when it is free
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription_id: Option<String>,
 }
 
 /// Threshold for telemetry notifications.
@@ -503,12 +1197,88 @@ pub struct TelemetrySubscription {
 pub struct TelemetryThreshold {
     /// Signal type
     pub signal_type: SignalType,
-    /// Operator (gt, lt, eq, gte, lte)
-    pub operator: String,
+    /// Comparison applied as `signal <operator> value`
+    pub operator: ThresholdOperator,
     /// Threshold value
     pub value: f64,
 }
 
+/// Comparison operator for a [`TelemetryThreshold`].
+///
+/// Serialized as `gt`, `gte`, `lt`, `lte` or `eq`. Deserializing any other
+/// string fails with an [`UnknownOperator`] message naming the bad value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThresholdOperator {
+    /// Greater than
+    Gt,
+    /// Greater than or equal
+    Gte,
+    /// Less than
+    Lt,
+    /// Less than or equal
+    Lte,
+    /// Equal
+    Eq,
+}
+
+impl ThresholdOperator {
+    /// Apply the operator as `value <op> threshold`.
+    pub fn compare(self, value: f64, threshold: f64) -> bool {
+        match self {
+            ThresholdOperator::Gt => value > threshold,
+            ThresholdOperator::Gte => value >= threshold,
+            ThresholdOperator::Lt => value < threshold,
+            ThresholdOperator::Lte => value <= threshold,
+            ThresholdOperator::Eq => (value - threshold).abs() < f64::EPSILON,
+        }
+    }
+
+    /// Get the operator name as used on the wire.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThresholdOperator::Gt => "gt",
+            ThresholdOperator::Gte => "gte",
+            ThresholdOperator::Lt => "lt",
+            ThresholdOperator::Lte => "lte",
+            ThresholdOperator::Eq => "eq",
+        }
+    }
+}
+
+impl std::fmt::Display for ThresholdOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ThresholdOperator {
+    type Err = UnknownOperator;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gt" => Ok(ThresholdOperator::Gt),
+            "gte" => Ok(ThresholdOperator::Gte),
+            "lt" => Ok(ThresholdOperator::Lt),
+            "lte" => Ok(ThresholdOperator::Lte),
+            "eq" => Ok(ThresholdOperator::Eq),
+            _ => Err(UnknownOperator(s.to_string())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ThresholdOperator {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error parsing a [`ThresholdOperator`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown threshold operator '{0}', expected one of gt, gte, lt, lte, eq")]
+pub struct UnknownOperator(pub String);
+
 /// Subscription acknowledgment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubscriptionAck {
@@ -516,6 +1286,112 @@ pub struct SubscriptionAck {
     pub subscription_id: String,
     /// Whether subscription is active
     pub active: bool,
+    /// When the subscription expires unless renewed (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+/// Options for [`ObservatoryAdapter::watch_subscription`].
+#[derive(Debug, Clone)]
+pub struct SubscriptionOptions {
+    /// Delay between checks of an active subscription
+    pub check_interval: Duration,
+    /// How long before expiry a subscription is renewed
+    pub renew_before: Duration,
+    /// Delay after the first failed attempt
+    pub initial_backoff: Duration,
+    /// Maximum delay between failed attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(30),
+            renew_before: Duration::from_secs(10),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Status of a watched telemetry subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionStatus {
+    /// The subscription was established, or re-established under a new ID
+    Active {
+        /// Subscription ID
+        subscription_id: String,
+    },
+    /// Observatory dropped the subscription; it is being re-established
+    Lost {
+        /// ID of the dropped subscription
+        subscription_id: String,
+        /// Why the subscription is considered dropped
+        reason: String,
+    },
+    /// Observatory could not be reached; the attempt is retried after
+    /// `retry_in`
+    Retrying {
+        /// Error of the failed attempt
+        error: String,
+        /// Delay before the next attempt
+        retry_in: Duration,
+    },
+}
+
+/// Internal state of a subscription watch stream.
+struct SubscriptionState {
+    subscription_id: Option<String>,
+    active: bool,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    delay: Option<Duration>,
+    backoff: Duration,
+}
+
+impl SubscriptionState {
+    /// Check if the subscription is due for renewal.
+    fn expiring(&self, options: &SubscriptionOptions) -> bool {
+        self.until_renewal(options)
+            .is_some_and(|until| until.is_zero())
+    }
+
+    /// Delay before the subscription should be checked or renewed.
+    fn next_check(&self, options: &SubscriptionOptions) -> Duration {
+        match self.until_renewal(options) {
+            Some(until) => until.min(options.check_interval),
+            None => options.check_interval,
+        }
+    }
+
+    fn until_renewal(&self, options: &SubscriptionOptions) -> Option<Duration> {
+        let renew_at = self.expires_at? - chrono::Duration::from_std(options.renew_before).ok()?;
+        let until = renew_at - chrono::Utc::now();
+        Some(until.to_std().unwrap_or(Duration::ZERO))
+    }
+
+    /// Schedule a retry after a failed attempt.
+    fn retry(&mut self, error: String, options: &SubscriptionOptions) -> SubscriptionStatus {
+        tracing::warn!(
+            subscription_id = ?self.subscription_id,
+            error = %error,
+            retry_in_ms = self.backoff.as_millis() as u64,
+            "Observatory telemetry subscription attempt failed"
+        );
+        let retry_in = self.backoff;
+        self.delay = Some(retry_in);
+        self.backoff = (self.backoff * 2).min(options.max_backoff);
+        SubscriptionStatus::Retrying { error, retry_in }
+    }
+}
+
+/// Parse the expiry of an acknowledged subscription, ignoring malformed
+/// timestamps.
+fn parse_expiry(ack: &SubscriptionAck) -> Option<chrono::DateTime<chrono::Utc>> {
+    let expires_at = ack.expires_at.as_deref()?;
+    chrono::DateTime::parse_from_rfc3339(expires_at)
+        .ok()
+        .map(|expires_at| expires_at.with_timezone(&chrono::Utc))
 }
 
 /// Policy decision record for analytics.
@@ -543,6 +1419,9 @@ pub struct PolicyDecisionRecord {
     /// Reason for decision
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
+    /// Machine-readable code for the reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
     /// Additional metadata
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
@@ -561,6 +1440,7 @@ pub struct RecordAck {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::integration::MockTransport;
 
     #[test]
     fn test_decision_outcome_serialization() {
@@ -576,6 +1456,63 @@ mod tests {
         assert!(ctx.is_sampled());
     }
 
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const SPAN_ID: &str = "00f067aa0ba902b7";
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let header = format!("00-{}-{}-01", TRACE_ID, SPAN_ID);
+        let ctx = TraceContext::from_headers(&header, Some("vendor=abc,other=xyz")).unwrap();
+
+        assert_eq!(ctx.trace_id, TRACE_ID);
+        assert_eq!(ctx.parent_span_id.as_deref(), Some(SPAN_ID));
+        assert!(ctx.is_sampled());
+        assert_eq!(ctx.trace_state.as_deref(), Some("vendor=abc,other=xyz"));
+        assert_eq!(ctx.to_traceparent(SPAN_ID), header);
+
+        let header = format!("00-{}-{}-00", TRACE_ID, SPAN_ID);
+        let unsampled = TraceContext::from_traceparent(&header).unwrap();
+        assert!(!unsampled.is_sampled());
+        assert!(unsampled.to_traceparent(SPAN_ID).ends_with("-00"));
+    }
+
+    #[test]
+    fn test_traceparent_rejects_malformed() {
+        let cases = [
+            ("", TraceParseError::InvalidFormat),
+            ("00-abc", TraceParseError::InvalidFormat),
+            (
+                "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                TraceParseError::UnsupportedVersion("ff".to_string()),
+            ),
+            (
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+                TraceParseError::UnsupportedVersion("00".to_string()),
+            ),
+            (
+                "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+                TraceParseError::InvalidTraceId,
+            ),
+            (
+                "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+                TraceParseError::InvalidTraceId,
+            ),
+            (
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+                TraceParseError::InvalidSpanId,
+            ),
+            (
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-zz",
+                TraceParseError::InvalidFlags,
+            ),
+        ];
+
+        for (header, expected) in cases {
+            let error = TraceContext::from_traceparent(header).unwrap_err();
+            assert_eq!(error, expected, "{}", header);
+        }
+    }
+
     #[test]
     fn test_span_kind_default() {
         assert_eq!(SpanKind::default(), SpanKind::Internal);
@@ -586,6 +1523,204 @@ mod tests {
         assert_eq!(HealthStatus::default(), HealthStatus::Unknown);
     }
 
+    #[test]
+    fn test_policy_stats_empty() {
+        let stats = PolicyStats::empty("policy-1", 3600);
+        assert!(stats.is_empty());
+        assert_eq!(stats.window_seconds, 3600);
+        assert_eq!(stats.rate(DecisionOutcome::Allow), 0.0);
+    }
+
+    #[test]
+    fn test_policy_stats_rates() {
+        let json = r#"{
+            "policy_id": "policy-1",
+            "window_seconds": 3600,
+            "total_decisions": 100,
+            "outcome_counts": {"allow": 94, "deny": 5, "error": 1},
+            "latency_percentiles": {"p50": 1.2, "p99": 8.5},
+            "cache_hit_rate": 0.75
+        }"#;
+        let stats: PolicyStats = serde_json::from_str(json).unwrap();
+        assert!(!stats.is_empty());
+        assert!((stats.rate(DecisionOutcome::Allow) - 0.94).abs() < f64::EPSILON);
+        assert!((stats.rate(DecisionOutcome::Deny) - 0.05).abs() < f64::EPSILON);
+        assert_eq!(stats.outcome_counts.warn, 0);
+        assert_eq!(stats.latency_percentiles.p99, Some(8.5));
+    }
+
+    fn evaluation_event() -> PolicyEvaluationEvent {
+        PolicyEvaluationEvent {
+            event_id: "evt-123".to_string(),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            trace_id: None,
+            span_id: None,
+            policy_id: "policy-789".to_string(),
+            rule_id: None,
+            decision: DecisionOutcome::Allow,
+            reason: None,
+            obligations: None,
+            shadow_decision: None,
+            duration_ms: 1.0,
+            cached: false,
+            context: HashMap::new(),
+            labels: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_baggage_propagates_into_emitted_event() {
+        let header = format!("00-{}-{}-01", TRACE_ID, SPAN_ID);
+        let ctx = TraceContext::from_headers(&header, Some("vendor=abc"))
+            .unwrap()
+            .with_baggage_header("userId=alice, tenant=acme%20corp;ttl=60, bad key=x, broken=%zz");
+
+        assert_eq!(ctx.baggage.len(), 2);
+        assert_eq!(ctx.baggage["tenant"], "acme corp");
+
+        let event = evaluation_event().with_trace_context(&ctx);
+        assert_eq!(event.trace_id.as_deref(), Some(TRACE_ID));
+        assert_eq!(event.span_id.as_deref(), Some(SPAN_ID));
+
+        let (adapter, transport) = mock_adapter(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/events/policy-evaluation",
+            serde_json::json!({ "event_id": "evt-123", "accepted": true }),
+        ));
+        assert!(adapter.emit_evaluation_event(&event).await.unwrap().accepted);
+
+        let body = transport.requests()[0].json().unwrap();
+        assert_eq!(body["trace_id"], TRACE_ID);
+        assert_eq!(body["context"]["baggage.userId"], "alice");
+        assert_eq!(body["context"]["baggage.tenant"], "acme corp");
+    }
+
+    #[test]
+    fn test_oversized_baggage_is_truncated() {
+        let mut ctx = TraceContext::new(TRACE_ID.to_string());
+        for i in 0..20 {
+            ctx.baggage.insert(format!("key{:02}", i), "v".repeat(1000));
+        }
+        ctx.baggage.insert("not valid".to_string(), "x".to_string());
+
+        let event = evaluation_event().with_trace_context(&ctx);
+
+        // 1000-byte values fit eight to the 8192-byte budget, in key order.
+        assert_eq!(event.context.len(), 8);
+        assert!(event.context.contains_key("baggage.key00"));
+        assert!(event.context.contains_key("baggage.key07"));
+        assert!(!event.context.contains_key("baggage.key08"));
+    }
+
+    fn signals() -> TelemetrySignals {
+        serde_json::from_value(serde_json::json!({
+            "timestamp": "2025-01-01T00:00:00Z",
+            "time_window_seconds": 300,
+            "error_rate": 2.5,
+            "latency_percentiles": {"p50": 120.0, "p99": 800.0},
+            "request_rate": 40.0,
+            "token_usage": {"input_tokens": 600, "output_tokens": 400, "total_tokens": 1000},
+            "cost": 12.0,
+            "availability": 99.5
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exceeds_threshold_for_each_signal_and_operator() {
+        use ThresholdOperator::*;
+
+        let signals = signals();
+        let cases = [
+            (SignalType::ErrorRate, 2.5),
+            (SignalType::Latency, 800.0),
+            (SignalType::RequestRate, 40.0),
+            (SignalType::TokenUsage, 1000.0),
+            (SignalType::Cost, 12.0),
+            (SignalType::Availability, 99.5),
+        ];
+        // (operator, crosses below the value, crosses at it, crosses above it)
+        let operators = [
+            (Gt, true, false, false),
+            (Gte, true, true, false),
+            (Lt, false, false, true),
+            (Lte, false, true, true),
+            (Eq, false, true, false),
+        ];
+
+        for (signal_type, actual) in cases {
+            for (operator, below, at, above) in operators {
+                let threshold = |value| TelemetryThreshold {
+                    signal_type,
+                    operator,
+                    value,
+                };
+                let label = format!("{:?} {}", signal_type, operator);
+                assert_eq!(signals.exceeds_threshold(&threshold(actual - 1.0)), below, "{}", label);
+                assert_eq!(signals.exceeds_threshold(&threshold(actual)), at, "{}", label);
+                assert_eq!(signals.exceeds_threshold(&threshold(actual + 1.0)), above, "{}", label);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unreported_signal_never_exceeds_threshold() {
+        let mut signals = signals();
+        signals.latency_percentiles.p99 = None;
+
+        let threshold = TelemetryThreshold {
+            signal_type: SignalType::Latency,
+            operator: ThresholdOperator::Gte,
+            value: 0.0,
+        };
+        assert!(!signals.exceeds_threshold(&threshold));
+    }
+
+    #[test]
+    fn test_threshold_operator_parsing() {
+        assert_eq!("GTE".parse::<ThresholdOperator>().unwrap(), ThresholdOperator::Gte);
+        let err = "between".parse::<ThresholdOperator>().unwrap_err();
+        assert_eq!(err, UnknownOperator("between".to_string()));
+
+        let threshold: TelemetryThreshold = serde_json::from_value(serde_json::json!({
+            "signal_type": "latency", "operator": "lt", "value": 500.0
+        }))
+        .unwrap();
+        assert_eq!(threshold.operator, ThresholdOperator::Lt);
+
+        let err = serde_json::from_value::<TelemetryThreshold>(serde_json::json!({
+            "signal_type": "latency", "operator": "approx", "value": 500.0
+        }))
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Unknown threshold operator 'approx'"));
+    }
+
+    #[test]
+    fn test_threshold_operator_round_trip() {
+        for (operator, wire) in [
+            (ThresholdOperator::Gt, "gt"),
+            (ThresholdOperator::Gte, "gte"),
+            (ThresholdOperator::Lt, "lt"),
+            (ThresholdOperator::Lte, "lte"),
+            (ThresholdOperator::Eq, "eq"),
+        ] {
+            let json = serde_json::to_value(operator).unwrap();
+            assert_eq!(json, serde_json::json!(wire));
+            assert_eq!(serde_json::from_value::<ThresholdOperator>(json).unwrap(), operator);
+            assert_eq!(operator.to_string(), wire);
+        }
+
+        let threshold = TelemetryThreshold {
+            signal_type: SignalType::ErrorRate,
+            operator: ThresholdOperator::Gte,
+            value: 5.0,
+        };
+        let json = serde_json::to_string(&threshold).unwrap();
+        assert_eq!(json, r#"{"signal_type":"error_rate","operator":"gte","value":5.0}"#);
+        let parsed: TelemetryThreshold = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.operator, ThresholdOperator::Gte);
+    }
+
     #[test]
     fn test_policy_evaluation_event_serialization() {
         let event = PolicyEvaluationEvent {
@@ -596,6 +1731,9 @@ mod tests {
             policy_id: "policy-789".to_string(),
             rule_id: Some("rule-1".to_string()),
             decision: DecisionOutcome::Allow,
+            reason: None,
+            obligations: None,
+            shadow_decision: None,
             duration_ms: 5.5,
             cached: false,
             context: HashMap::new(),
@@ -606,4 +1744,294 @@ mod tests {
         assert!(json.contains("policy-789"));
         assert!(json.contains("allow"));
     }
+
+    fn mock_adapter(transport: MockTransport) -> (ObservatoryAdapter, Arc<MockTransport>) {
+        let transport = Arc::new(transport);
+        let client =
+            IntegrationClient::new("http://observatory".to_string(), Duration::from_secs(1))
+                .with_transport(transport.clone());
+        (ObservatoryAdapter::from_client(client), transport)
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_telemetry_sends_delete() {
+        let (adapter, transport) = mock_adapter(MockTransport::new().with_status(
+            reqwest::Method::DELETE,
+            "/api/v1/subscriptions/telemetry/sub-1",
+            reqwest::StatusCode::NO_CONTENT,
+        ));
+
+        adapter.unsubscribe_telemetry("sub-1").await.unwrap();
+        assert_eq!(
+            transport.request_lines(),
+            vec!["DELETE /api/v1/subscriptions/telemetry/sub-1"]
+        );
+    }
+
+    async fn subscription_server() -> (wiremock::MockServer, ObservatoryAdapter) {
+        let server = wiremock::MockServer::start().await;
+        let client = IntegrationClient::new(server.uri(), Duration::from_secs(5))
+            .with_retry_policy(crate::integration::RetryPolicy::none());
+        (server, ObservatoryAdapter::from_client(client))
+    }
+
+    fn test_subscription() -> TelemetrySubscription {
+        TelemetrySubscription {
+            name: "policy-engine".to_string(),
+            services: vec!["gateway".to_string()],
+            signal_types: vec![SignalType::ErrorRate],
+            callback_url: Some("http://policy-engine/telemetry".to_string()),
+            threshold: None,
+            subscription_id: None,
+        }
+    }
+
+    async fn subscribe_requests(server: &wiremock::MockServer) -> Vec<serde_json::Value> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|request| request.method.to_string() == "POST")
+            .map(|request| serde_json::from_slice(&request.body).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscription_reestablished_after_backoff() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let (server, adapter) = subscription_server().await;
+        let ack = serde_json::json!({ "subscription_id": "sub-1", "active": true });
+        Mock::given(method("POST"))
+            .and(path("/api/v1/subscriptions/telemetry"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&ack))
+            .mount(&server)
+            .await;
+        // Observatory restarts: unreachable, then without the subscription.
+        let status = "/api/v1/subscriptions/telemetry/sub-1";
+        Mock::given(method("GET"))
+            .and(path(status))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(status))
+            .respond_with(ResponseTemplate::new(404))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(status))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&ack))
+            .mount(&server)
+            .await;
+
+        let options = SubscriptionOptions {
+            check_interval: Duration::from_millis(10),
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let stream = adapter.watch_subscription(test_subscription(), options);
+        futures::pin_mut!(stream);
+        let mut statuses = Vec::new();
+        for _ in 0..4 {
+            let status = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .expect("watch timed out")
+                .expect("stream ended");
+            statuses.push(status);
+        }
+
+        let active = SubscriptionStatus::Active {
+            subscription_id: "sub-1".to_string(),
+        };
+        assert_eq!(statuses[0], active);
+        assert!(
+            matches!(
+                &statuses[1],
+                SubscriptionStatus::Retrying { retry_in, .. }
+                    if *retry_in == Duration::from_millis(10)
+            ),
+            "{:?}",
+            statuses[1]
+        );
+        assert_eq!(
+            statuses[2],
+            SubscriptionStatus::Lost {
+                subscription_id: "sub-1".to_string(),
+                reason: "subscription not found".to_string(),
+            }
+        );
+        assert_eq!(statuses[3], active);
+
+        // The reconnect asks Observatory to keep the subscription ID.
+        let requests = subscribe_requests(&server).await;
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].get("subscription_id").is_none());
+        assert_eq!(requests[1]["subscription_id"], "sub-1");
+    }
+
+    #[tokio::test]
+    async fn test_expiring_subscription_renewed() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let (server, adapter) = subscription_server().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/subscriptions/telemetry"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "subscription_id": "sub-1",
+                "active": true,
+                "expires_at": "2020-01-01T00:00:00Z",
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/subscriptions/telemetry"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "subscription_id": "sub-2",
+                "active": true,
+            })))
+            .mount(&server)
+            .await;
+
+        let stream = adapter.watch_subscription(test_subscription(), Default::default());
+        futures::pin_mut!(stream);
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let status = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .expect("watch timed out")
+                .expect("stream ended");
+            match status {
+                SubscriptionStatus::Active { subscription_id } => ids.push(subscription_id),
+                other => panic!("expected Active, got {:?}", other),
+            }
+        }
+        assert_eq!(ids, vec!["sub-1", "sub-2"]);
+
+        // The expired subscription is renewed without being checked first.
+        let requests = subscribe_requests(&server).await;
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1]["subscription_id"], "sub-1");
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_policy_stats_missing_is_empty() {
+        // No route registered, so the mock answers 404.
+        let (adapter, transport) = mock_adapter(MockTransport::new());
+
+        let stats = adapter
+            .get_policy_stats("policy-1", Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert!(stats.is_empty());
+        assert_eq!(
+            transport.request_lines(),
+            vec!["GET /api/v1/analytics/policies/policy-1/stats?window_seconds=3600"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_current_metrics_query_is_encoded() {
+        let metrics = serde_json::json!({
+            "timestamp": "2025-01-01T00:00:00Z",
+            "service": "gateway",
+            "model": "meta/llama+3",
+            "active_requests": 4,
+            "error_count": 1,
+            "avg_latency_ms": 120.5,
+            "health_status": "healthy"
+        });
+        let (adapter, transport) = mock_adapter(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/api/v1/metrics/current",
+            metrics,
+        ));
+
+        let current = adapter
+            .get_current_metrics_filtered(
+                "gateway",
+                Some("meta/llama+3"),
+                Some("open ai"),
+                Some(Duration::from_secs(600)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(current.model.as_deref(), Some("meta/llama+3"));
+        assert_eq!(current.health_status, HealthStatus::Healthy);
+
+        adapter.get_current_metrics("gateway", None).await.unwrap();
+        assert_eq!(
+            transport.request_lines(),
+            vec![
+                "GET /api/v1/metrics/current?service=gateway&model=meta%2Fllama%2B3\
+                 &provider=open%20ai&window_seconds=600",
+                "GET /api/v1/metrics/current?service=gateway",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_special_characters_cannot_inject_parameters() {
+        let (adapter, transport) = mock_adapter(MockTransport::new());
+
+        let _ = adapter
+            .get_current_metrics("chat service", Some("gpt-4&evil=1"))
+            .await;
+        let _ = adapter.get_trace_context("../spans?x=1").await;
+        assert_eq!(
+            transport.request_lines(),
+            vec![
+                "GET /api/v1/metrics/current?service=chat%20service&model=gpt-4%26evil%3D1",
+                "GET /api/v1/traces/..%2Fspans%3Fx%3D1/context",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_builder_fills_id_and_timestamp() {
+        let event = PolicyEvaluationEvent::builder("policy-1", DecisionOutcome::Deny)
+            .rule_id("rule-1")
+            .duration_ms(2.5)
+            .label("shadow", "true")
+            .build()
+            .unwrap();
+
+        assert!(uuid::Uuid::parse_str(&event.event_id).is_ok());
+        assert!(chrono::DateTime::parse_from_rfc3339(&event.timestamp).is_ok());
+        assert_eq!(event.policy_id, "policy-1");
+        assert_eq!(event.rule_id.as_deref(), Some("rule-1"));
+        assert_eq!(event.labels["shadow"], "true");
+
+        let other = PolicyEvaluationEvent::builder("policy-1", DecisionOutcome::Deny)
+            .build()
+            .unwrap();
+        assert_ne!(event.event_id, other.event_id);
+    }
+
+    #[test]
+    fn test_event_builder_rejects_invalid_events() {
+        let builder = || PolicyEvaluationEvent::builder("policy-1", DecisionOutcome::Allow);
+
+        let error = builder().span_id("b7ad6b7169203331").build().unwrap_err();
+        assert!(error.to_string().contains("trace ID"), "{}", error);
+        let traced = builder()
+            .trace_id("4bf92f3577b34da6a3ce929d0e0e4736")
+            .span_id("b7ad6b7169203331")
+            .build();
+        assert!(traced.is_ok());
+
+        for duration_ms in [-1.0, f64::NAN] {
+            assert!(builder().duration_ms(duration_ms).build().is_err());
+        }
+    }
 }
@@ -0,0 +1,68 @@
+//! Request path and query string encoding.
+//!
+//! Integration clients take request paths as strings, so values interpolated
+//! into them must be percent-encoded first: a raw `&`, `=` or space in a
+//! model name would otherwise split or truncate the query and silently
+//! change what is requested. Build query strings with [`with_query`] and
+//! encode path segments with [`encode_component`].
+
+/// Percent-encode a path segment or query component, keeping only RFC 3986
+/// unreserved characters.
+pub(crate) fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    encoded
+}
+
+/// Append query parameters to a path, in order. Parameters without a value
+/// are omitted, and the path is returned unchanged if none remain.
+pub(crate) fn with_query(path: &str, params: &[(&str, Option<&str>)]) -> String {
+    let query: Vec<_> = params
+        .iter()
+        .filter_map(|(name, value)| {
+            value.map(|value| format!("{}={}", encode_component(name), encode_component(value)))
+        })
+        .collect();
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, query.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_component() {
+        assert_eq!(encode_component("gpt-4_turbo.v1~x"), "gpt-4_turbo.v1~x");
+        assert_eq!(encode_component("gpt-4&evil=1"), "gpt-4%26evil%3D1");
+        assert_eq!(encode_component("a/b+c d"), "a%2Fb%2Bc%20d");
+        assert_eq!(encode_component("modèle"), "mod%C3%A8le");
+    }
+
+    #[test]
+    fn test_with_query() {
+        assert_eq!(
+            with_query(
+                "/api/v1/metrics/current",
+                &[
+                    ("service", Some("gateway")),
+                    ("model", Some("gpt-4&evil=1"))
+                ]
+            ),
+            "/api/v1/metrics/current?service=gateway&model=gpt-4%26evil%3D1"
+        );
+        assert_eq!(
+            with_query("/api/v1/metrics/current", &[("model", None)]),
+            "/api/v1/metrics/current"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Request IDs correlating integration calls.
+//!
+//! Every evaluation runs under a request ID, taken from the incoming request
+//! or generated. Integration requests made on its behalf carry the ID in an
+//! `X-Request-Id` header and emitted events record it, so one ID can be
+//! followed through the logs of Shield, CostOps, Config Manager,
+//! Observatory and the engine itself.
+//!
+//! The ID is scoped to a task with [`with_request_id`];
+//! [`IntegrationClient`](super::IntegrationClient) attaches
+//! [`current_request_id`] to every request it sends.
+
+use reqwest::header::HeaderMap;
+use std::future::Future;
+
+/// Header carrying the request ID.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest request ID accepted from a caller.
+pub const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Run a future with a request ID attached to its integration requests.
+pub async fn with_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// Get the request ID of the current task, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Generate a new request ID.
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Read the request ID of an incoming request.
+///
+/// Returns `None` if the header is missing or its value is not a valid
+/// request ID: 1 to [`MAX_REQUEST_ID_LEN`] visible ASCII characters.
+pub fn request_id_from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+}
+
+/// Whether a request ID can be passed on in a header.
+pub(crate) fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Carry the current request ID, if any, into a future spawned as a
+/// separate task.
+pub(crate) fn in_current_request<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let request_id = current_request_id();
+    async move {
+        match request_id {
+            Some(request_id) => with_request_id(request_id, future).await,
+            None => future.await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[tokio::test]
+    async fn test_request_id_scoped_to_task() {
+        assert_eq!(current_request_id(), None);
+        let request_id = with_request_id("req-1".to_string(), async {
+            let spawned = tokio::spawn(in_current_request(async { current_request_id() }));
+            assert_eq!(spawned.await.unwrap().as_deref(), Some("req-1"));
+            current_request_id()
+        })
+        .await;
+        assert_eq!(request_id.as_deref(), Some("req-1"));
+        assert_eq!(current_request_id(), None);
+    }
+
+    #[test]
+    fn test_request_id_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_id_from_headers(&headers), None);
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("abc-123"));
+        assert_eq!(
+            request_id_from_headers(&headers).as_deref(),
+            Some("abc-123")
+        );
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("has space"));
+        assert_eq!(request_id_from_headers(&headers), None);
+        let long = "x".repeat(MAX_REQUEST_ID_LEN + 1);
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_str(&long).unwrap());
+        assert_eq!(request_id_from_headers(&headers), None);
+        assert!(is_valid_request_id(&new_request_id()));
+    }
+}
@@ -0,0 +1,295 @@
+//! Retry policy for integration calls.
+//!
+//! Transient upstream failures (connection errors, timeouts, 5xx and 429
+//! responses) are retried with exponential backoff and jitter. Client errors
+//! other than 429 are never retried.
+//!
+//! A [`RetryBudget`] keeps retries from amplifying load during an outage:
+//! every call earns a fraction of a retry, and a retry is only made while
+//! one has been earned, so retries stay a bounded share of requests and
+//! stop altogether once the budget is spent.
+
+use parking_lot::Mutex;
+use prometheus::{IntCounterVec, Opts};
+use rand::Rng;
+use reqwest::StatusCode;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Default share of requests that may be retries.
+pub const DEFAULT_RETRY_BUDGET_RATIO: f64 = 0.1;
+
+/// Default number of retries a budget can save up.
+pub const DEFAULT_RETRY_BUDGET_CAPACITY: u32 = 10;
+
+/// Retry policy with exponential backoff.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Multiplier applied to the delay after each attempt
+    pub multiplier: f64,
+    /// Random jitter as a fraction of the delay (0.0 - 1.0)
+    pub jitter: f64,
+    /// Upper bound for a single delay, including `Retry-After`
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of attempts.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the base delay.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the backoff multiplier.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Set the jitter fraction.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the maximum delay between attempts.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Check if an HTTP status should be retried.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// Compute the delay before the next attempt.
+    ///
+    /// `attempt` is the number of attempts made so far (starting at 1).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(32) as i32;
+        let base = self.base_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        let capped = base.min(self.max_delay.as_secs_f64());
+
+        let delay = if self.jitter > 0.0 {
+            let factor = rand::thread_rng().gen_range(-self.jitter..=self.jitter);
+            capped * (1.0 + factor)
+        } else {
+            capped
+        };
+
+        Duration::from_secs_f64(delay.max(0.0))
+    }
+
+    /// Clamp a server-provided `Retry-After` delay to the policy maximum.
+    pub fn clamp_delay(&self, delay: Duration) -> Duration {
+        delay.min(self.max_delay)
+    }
+}
+
+/// Token bucket limiting retries to a share of requests.
+///
+/// Each call deposits `ratio` tokens and each retry withdraws one, so over
+/// time at most `ratio` retries are made per call. The bucket starts full
+/// and holds at most `capacity` tokens, allowing short bursts of retries.
+/// Clones share the bucket.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    ratio: f64,
+    capacity: f64,
+    tokens: Arc<Mutex<f64>>,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETRY_BUDGET_RATIO)
+    }
+}
+
+impl RetryBudget {
+    /// Create a budget allowing `ratio` (0.0 - 1.0) retries per call, with
+    /// the default capacity.
+    pub fn new(ratio: f64) -> Self {
+        let capacity = f64::from(DEFAULT_RETRY_BUDGET_CAPACITY);
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            capacity,
+            tokens: Arc::new(Mutex::new(capacity)),
+        }
+    }
+
+    /// Set how many retries the budget can save up, refilling it.
+    pub fn with_capacity(mut self, capacity: u32) -> Self {
+        self.capacity = f64::from(capacity);
+        self.tokens = Arc::new(Mutex::new(self.capacity));
+        self
+    }
+
+    /// Get the share of requests that may be retries.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Get the number of retries currently available.
+    pub fn available(&self) -> u32 {
+        *self.tokens.lock() as u32
+    }
+
+    /// Earn a call's share of a retry.
+    pub(crate) fn deposit(&self) {
+        let mut tokens = self.tokens.lock();
+        *tokens = (*tokens + self.ratio).min(self.capacity);
+    }
+
+    /// Spend a retry, returning `false` if none is available.
+    pub(crate) fn try_withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock();
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+}
+
+/// Counter of retries skipped because the retry budget was exhausted, by
+/// integration.
+pub(crate) fn retry_budget_exhausted_counter() -> &'static IntCounterVec {
+    static COUNTER: OnceLock<IntCounterVec> = OnceLock::new();
+    COUNTER.get_or_init(|| {
+        let counter = IntCounterVec::new(
+            Opts::new(
+                "integration_retry_budget_exhausted_total",
+                "Retries skipped because the retry budget was exhausted",
+            ),
+            &["integration"],
+        )
+        .expect("Failed to create retry budget exhausted counter");
+        prometheus::register(Box::new(counter.clone()))
+            .expect("Failed to register retry budget exhausted counter");
+        counter
+    })
+}
+
+/// Parse a `Retry-After` header value (delay seconds or HTTP date).
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delta.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_without_jitter() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(100))
+            .with_multiplier(2.0)
+            .with_jitter(0.0);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_backoff_capped() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_secs(1))
+            .with_max_delay(Duration::from_secs(2))
+            .with_jitter(0.0);
+
+        assert_eq!(policy.backoff(5), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_backoff_jitter_bounds() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(100))
+            .with_jitter(0.5);
+
+        for _ in 0..20 {
+            let delay = policy.backoff(1);
+            assert!(delay >= Duration::from_millis(50));
+            assert!(delay <= Duration::from_millis(150));
+        }
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(RetryPolicy::is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(RetryPolicy::is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_retry_budget_limits_retries_to_ratio() {
+        let budget = RetryBudget::new(0.5).with_capacity(2);
+        assert_eq!(budget.available(), 2);
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+
+        // Two calls earn one retry, and clones share the bucket.
+        let clone = budget.clone();
+        clone.deposit();
+        assert!(!budget.try_withdraw());
+        clone.deposit();
+        assert!(budget.try_withdraw());
+
+        // Deposits never exceed the capacity.
+        for _ in 0..10 {
+            budget.deposit();
+        }
+        assert_eq!(budget.available(), 2);
+        assert_eq!(RetryBudget::new(3.0).ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("5"), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
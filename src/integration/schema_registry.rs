@@ -10,9 +10,27 @@
 //! that could create circular dependencies. It follows the unidirectional
 //! dependency pattern: Schema Registry -> Policy Engine (consumes-from).
 
-use super::client::{IntegrationClient, IntegrationResult};
+use super::avro;
+use super::client::{ConditionalResponse, IntegrationClient, IntegrationResult};
+use super::metrics::MetricsRecorder;
+use super::query::encode_component;
+use crate::{Error, Result};
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Path templates for metrics labels.
+const PATH_TEMPLATES: &[&str] = &[
+    "/api/v1/schemas/{subject}/latest",
+    "/api/v1/schemas/{subject}/versions/{version}",
+    "/api/v1/schemas/{subject}/versions/{version}/validate",
+];
+
+/// Default time-to-live for cached schema definitions.
+const DEFAULT_SCHEMA_CACHE_TTL: Duration = Duration::from_secs(300);
 
 /// Client for consuming schema definitions from LLM Schema Registry.
 ///
@@ -20,22 +38,149 @@ use std::time::Duration;
 /// validating policy documents and rule structures at runtime.
 pub struct SchemaRegistryAdapter {
     client: IntegrationClient,
+    /// Schema definitions keyed by subject and version
+    schema_cache: RwLock<SchemaCache>,
+    /// Time-to-live for cached schemas
+    cache_ttl: Duration,
 }
 
 impl SchemaRegistryAdapter {
     /// Create a new Schema Registry adapter.
     pub fn new(base_url: String, timeout: Duration) -> Self {
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Schema Registry adapter from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
         Self {
-            client: IntegrationClient::new(base_url, timeout),
+            client,
+            schema_cache: RwLock::new(SchemaCache::default()),
+            cache_ttl: DEFAULT_SCHEMA_CACHE_TTL,
         }
     }
 
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("schema_registry", PATH_TEMPLATES, recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
+    }
+
+    /// Set the time-to-live for cached schema definitions.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
     /// Fetch a schema definition by its subject name.
     ///
     /// Returns the schema that can be used to validate policy documents.
+    /// Results are cached for the configured TTL; once expired, the registry
+    /// is asked with `If-None-Match` so an unchanged schema costs a 304.
     pub async fn get_schema(&self, subject: &str) -> IntegrationResult<SchemaDefinition> {
+        let (fresh, etag) = {
+            let cache = self.schema_cache.read();
+            (cache.fresh_latest(subject), cache.latest_etag(subject))
+        };
+        if let Some(schema) = fresh {
+            return Ok(schema);
+        }
+
         let path = format!("/api/v1/schemas/{}/latest", subject);
-        self.client.get(&path).await
+        match self
+            .client
+            .get_conditional::<SchemaDefinition>(&path, etag.as_deref())
+            .await?
+        {
+            ConditionalResponse::Modified { value, etag } => {
+                let expires_at = Instant::now() + self.cache_ttl;
+                self.schema_cache
+                    .write()
+                    .insert_latest(subject, value.clone(), etag, expires_at);
+                Ok(value)
+            }
+            ConditionalResponse::NotModified => {
+                let expires_at = Instant::now() + self.cache_ttl;
+                let refreshed = self.schema_cache.write().refresh_latest(subject, expires_at);
+                match refreshed {
+                    Some(schema) => Ok(schema),
+                    // Entry evicted concurrently; fetch unconditionally.
+                    None => {
+                        let schema: SchemaDefinition = self.client.get(&path).await?;
+                        self.schema_cache
+                            .write()
+                            .insert_latest(subject, schema.clone(), None, expires_at);
+                        Ok(schema)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fetch the latest schemas for several subjects in one request.
+    ///
+    /// Subjects with a fresh cached schema are served locally; the rest are
+    /// requested from the batch endpoint and cached. Subjects the registry
+    /// does not know are listed in [`SchemaBatch::missing`].
+    pub async fn get_schemas(&self, subjects: &[&str]) -> IntegrationResult<SchemaBatch> {
+        let mut batch = SchemaBatch::default();
+        let mut to_fetch = Vec::new();
+        {
+            let cache = self.schema_cache.read();
+            for subject in subjects.iter().copied().collect::<BTreeSet<_>>() {
+                match cache.fresh_latest(subject) {
+                    Some(schema) => {
+                        batch.schemas.insert(subject.to_string(), schema);
+                    }
+                    None => to_fetch.push(subject.to_string()),
+                }
+            }
+        }
+        if to_fetch.is_empty() {
+            return Ok(batch);
+        }
+
+        let request = BatchSchemaRequest {
+            subjects: to_fetch.clone(),
+        };
+        let fetched: HashMap<String, SchemaDefinition> = self
+            .client
+            .post_idempotent("/api/v1/schemas/batch", &request)
+            .await?;
+
+        let expires_at = Instant::now() + self.cache_ttl;
+        let mut cache = self.schema_cache.write();
+        for subject in to_fetch {
+            match fetched.get(&subject) {
+                Some(schema) => {
+                    cache.insert_latest(&subject, schema.clone(), None, expires_at);
+                    batch.schemas.insert(subject, schema.clone());
+                }
+                None => batch.missing.push(subject),
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Get the cached latest schema for a subject, if one was fetched.
+    ///
+    /// Expired entries are still returned so validation can work offline.
+    pub fn cached_schema(&self, subject: &str) -> Option<SchemaDefinition> {
+        self.schema_cache.read().latest(subject)
+    }
+
+    /// Clear all cached schema definitions.
+    pub fn clear_schema_cache(&self) {
+        self.schema_cache.write().clear();
+    }
+
+    /// List the subjects that currently have cached schemas.
+    pub fn cached_subjects(&self) -> Vec<String> {
+        self.schema_cache.read().subjects()
     }
 
     /// Fetch a specific version of a schema.
@@ -44,8 +189,18 @@ impl SchemaRegistryAdapter {
         subject: &str,
         version: u32,
     ) -> IntegrationResult<SchemaDefinition> {
+        if let Some(schema) = self.schema_cache.read().fresh_version(subject, version) {
+            return Ok(schema);
+        }
+
         let path = format!("/api/v1/schemas/{}/versions/{}", subject, version);
-        self.client.get(&path).await
+        let schema: SchemaDefinition = self.client.get(&path).await?;
+        self.schema_cache.write().insert_version(
+            subject,
+            schema.clone(),
+            Instant::now() + self.cache_ttl,
+        );
+        Ok(schema)
     }
 
     /// Validate a policy document against the policy schema.
@@ -61,6 +216,92 @@ impl SchemaRegistryAdapter {
             .await
     }
 
+    /// Validate a policy document in-process against a JSON Schema or Avro
+    /// schema definition.
+    ///
+    /// Error paths are JSON Pointer locations within the document. Fails if
+    /// the schema type has no local validator or the schema is invalid.
+    pub fn validate_policy_document_local(
+        &self,
+        document: &PolicyDocumentSchema,
+        schema: &SchemaDefinition,
+    ) -> Result<ValidationResult> {
+        let instance = serde_json::to_value(document)?;
+        validate_local(&instance, schema)
+    }
+
+    /// Validate a JSON value against a schema definition.
+    ///
+    /// Schema types with a local validator (see
+    /// [`SchemaType::has_local_validator`]) are validated in-process. For
+    /// other types, such as Protobuf, the value is sent to the registry to
+    /// be validated against the schema's subject and version, and the result
+    /// is marked [`ValidationSource::Remote`].
+    pub async fn validate(
+        &self,
+        instance: &serde_json::Value,
+        schema: &SchemaDefinition,
+    ) -> Result<ValidationResult> {
+        if schema.schema_type.has_local_validator() {
+            return validate_local(instance, schema);
+        }
+
+        let path = format!(
+            "/api/v1/schemas/{}/versions/{}/validate",
+            encode_component(&schema.subject),
+            schema.version
+        );
+        let mut result: ValidationResult = self
+            .client
+            .post_idempotent(&path, instance)
+            .await
+            .map_err(|e| Error::integration("schema_registry", e.to_string()))?;
+        result.source = ValidationSource::Remote;
+        Ok(result)
+    }
+
+    /// Validate a policy document against the cached latest schema for a subject.
+    ///
+    /// Works without contacting the registry once the schema has been fetched
+    /// with [`get_schema`](Self::get_schema).
+    pub fn validate_policy_document_cached(
+        &self,
+        subject: &str,
+        document: &PolicyDocumentSchema,
+    ) -> Result<ValidationResult> {
+        let schema = self.cached_schema(subject).ok_or_else(|| {
+            Error::validation(format!("No cached schema for subject '{}'", subject))
+        })?;
+        self.validate_policy_document_local(document, &schema)
+    }
+
+    /// Compute the field-level differences between two schema versions.
+    ///
+    /// Runs locally, so it works offline once both schemas have been fetched.
+    /// Only JSON Schemas are compared field by field; any other change in
+    /// schema type or content is reported as a single breaking change at the
+    /// root.
+    pub fn diff_schemas(&self, old: &SchemaDefinition, new: &SchemaDefinition) -> SchemaDiff {
+        let mut changes = Vec::new();
+
+        if old.schema_type == SchemaType::JsonSchema && new.schema_type == SchemaType::JsonSchema {
+            diff_json_schema(&old.schema, &new.schema, "", &mut changes);
+        } else if old.schema_type != new.schema_type || old.schema != new.schema {
+            changes.push(SchemaChange {
+                path: String::new(),
+                kind: ChangeKind::Modified,
+                backward_breaking: true,
+                forward_breaking: true,
+                description: format!(
+                    "{:?} schemas cannot be compared field by field",
+                    new.schema_type
+                ),
+            });
+        }
+
+        SchemaDiff { changes }
+    }
+
     /// Validate a policy rule structure against the rule schema.
     pub async fn validate_rule_structure(
         &self,
@@ -97,6 +338,275 @@ impl SchemaRegistryAdapter {
     }
 }
 
+/// A cached schema definition.
+#[derive(Debug, Clone)]
+struct CachedSchema {
+    schema: SchemaDefinition,
+    expires_at: Instant,
+}
+
+/// The resolved "latest" version of a subject.
+#[derive(Debug, Clone)]
+struct LatestVersion {
+    version: u32,
+    etag: Option<String>,
+    expires_at: Instant,
+}
+
+/// In-memory schema cache keyed by `(subject, version)`.
+#[derive(Debug, Default)]
+struct SchemaCache {
+    entries: HashMap<(String, u32), CachedSchema>,
+    latest: HashMap<String, LatestVersion>,
+}
+
+impl SchemaCache {
+    fn latest(&self, subject: &str) -> Option<SchemaDefinition> {
+        let latest = self.latest.get(subject)?;
+        self.entries
+            .get(&(subject.to_string(), latest.version))
+            .map(|entry| entry.schema.clone())
+    }
+
+    fn fresh_latest(&self, subject: &str) -> Option<SchemaDefinition> {
+        let latest = self.latest.get(subject)?;
+        if latest.expires_at <= Instant::now() {
+            return None;
+        }
+        self.latest(subject)
+    }
+
+    fn latest_etag(&self, subject: &str) -> Option<String> {
+        let latest = self.latest.get(subject)?;
+        // Only send a validator if there is a body to fall back on.
+        self.entries
+            .get(&(subject.to_string(), latest.version))
+            .and(latest.etag.clone())
+    }
+
+    fn fresh_version(&self, subject: &str, version: u32) -> Option<SchemaDefinition> {
+        self.entries
+            .get(&(subject.to_string(), version))
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| entry.schema.clone())
+    }
+
+    fn insert_version(&mut self, subject: &str, schema: SchemaDefinition, expires_at: Instant) {
+        self.entries.insert(
+            (subject.to_string(), schema.version),
+            CachedSchema { schema, expires_at },
+        );
+    }
+
+    fn insert_latest(
+        &mut self,
+        subject: &str,
+        schema: SchemaDefinition,
+        etag: Option<String>,
+        expires_at: Instant,
+    ) {
+        self.latest.insert(
+            subject.to_string(),
+            LatestVersion {
+                version: schema.version,
+                etag,
+                expires_at,
+            },
+        );
+        self.insert_version(subject, schema, expires_at);
+    }
+
+    fn refresh_latest(&mut self, subject: &str, expires_at: Instant) -> Option<SchemaDefinition> {
+        let latest = self.latest.get_mut(subject)?;
+        latest.expires_at = expires_at;
+        let entry = self.entries.get_mut(&(subject.to_string(), latest.version))?;
+        entry.expires_at = expires_at;
+        Some(entry.schema.clone())
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.latest.clear();
+    }
+
+    fn subjects(&self) -> Vec<String> {
+        self.entries
+            .keys()
+            .map(|(subject, _)| subject.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Validate a JSON value in-process against a schema definition.
+///
+/// Fails if the schema type has no local validator or the schema is invalid.
+fn validate_local(
+    instance: &serde_json::Value,
+    schema: &SchemaDefinition,
+) -> Result<ValidationResult> {
+    match schema.schema_type {
+        SchemaType::JsonSchema => Ok(validate_compiled(&compile_json_schema(schema)?, instance)),
+        SchemaType::Avro => avro::validate(&schema.schema, instance).map_err(|e| {
+            Error::validation(format!("Invalid Avro schema '{}': {}", schema.subject, e))
+        }),
+        SchemaType::Protobuf | SchemaType::OpenApi => Err(Error::validation(format!(
+            "Schema '{}' has type {:?}, which cannot be validated locally",
+            schema.subject, schema.schema_type
+        ))),
+    }
+}
+
+/// Compile a JSON Schema definition for validation.
+///
+/// Fails if the schema is not a JSON Schema or cannot be compiled.
+pub(crate) fn compile_json_schema(schema: &SchemaDefinition) -> Result<jsonschema::JSONSchema> {
+    if schema.schema_type != SchemaType::JsonSchema {
+        return Err(Error::validation(format!(
+            "Schema '{}' has type {:?}; local validation requires a JSON Schema",
+            schema.subject, schema.schema_type
+        )));
+    }
+
+    jsonschema::JSONSchema::compile(&schema.schema).map_err(|e| {
+        Error::validation(format!("Invalid JSON Schema '{}': {}", schema.subject, e))
+    })
+}
+
+/// Validate a JSON value against a compiled JSON Schema.
+///
+/// Error paths are JSON Pointer locations within the value.
+pub(crate) fn validate_compiled(
+    compiled: &jsonschema::JSONSchema,
+    instance: &serde_json::Value,
+) -> ValidationResult {
+    let errors: Vec<ValidationError> = match compiled.validate(instance) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|e| {
+                let schema_path = e.schema_path.to_string();
+                ValidationError {
+                    path: e.instance_path.to_string(),
+                    message: e.to_string(),
+                    code: schema_path.rsplit('/').next().map(str::to_string),
+                }
+            })
+            .collect(),
+    };
+
+    ValidationResult {
+        valid: errors.is_empty(),
+        errors,
+        warnings: Vec::new(),
+        source: ValidationSource::Local,
+    }
+}
+
+/// Append the differences between two JSON Schema objects to `changes`.
+///
+/// Walks `properties` of object schemas and `items` of array schemas,
+/// building dotted field paths (`policies[].id`).
+fn diff_json_schema(
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    path: &str,
+    changes: &mut Vec<SchemaChange>,
+) {
+    if old.get("type") != new.get("type") {
+        changes.push(SchemaChange {
+            path: path.to_string(),
+            kind: ChangeKind::Modified,
+            backward_breaking: true,
+            forward_breaking: true,
+            description: format!(
+                "type changed from {} to {}",
+                type_label(old.get("type")),
+                type_label(new.get("type"))
+            ),
+        });
+        return;
+    }
+
+    if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+        diff_json_schema(old_items, new_items, &format!("{}[]", path), changes);
+    }
+
+    let empty = serde_json::Map::new();
+    let old_props = old.get("properties").and_then(|p| p.as_object()).unwrap_or(&empty);
+    let new_props = new.get("properties").and_then(|p| p.as_object()).unwrap_or(&empty);
+    let old_required = required_fields(old);
+    let new_required = required_fields(new);
+    let closed = new.get("additionalProperties") == Some(&serde_json::Value::Bool(false));
+
+    let names: BTreeSet<&String> = old_props.keys().chain(new_props.keys()).collect();
+    for name in names {
+        let field_path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", path, name)
+        };
+        let required = new_required.contains(name.as_str());
+        let was_required = old_required.contains(name.as_str());
+
+        match (old_props.get(name), new_props.get(name)) {
+            (None, Some(_)) => changes.push(SchemaChange {
+                path: field_path,
+                kind: ChangeKind::Added,
+                backward_breaking: false,
+                forward_breaking: required,
+                description: if required {
+                    "required field added".to_string()
+                } else {
+                    "optional field added".to_string()
+                },
+            }),
+            (Some(_), None) => changes.push(SchemaChange {
+                path: field_path,
+                kind: ChangeKind::Removed,
+                backward_breaking: true,
+                forward_breaking: closed,
+                description: "field removed".to_string(),
+            }),
+            (Some(old_field), Some(new_field)) => {
+                if required && !was_required {
+                    changes.push(SchemaChange {
+                        path: field_path.clone(),
+                        kind: ChangeKind::Modified,
+                        backward_breaking: false,
+                        forward_breaking: true,
+                        description: "field became required".to_string(),
+                    });
+                } else if was_required && !required {
+                    changes.push(SchemaChange {
+                        path: field_path.clone(),
+                        kind: ChangeKind::Modified,
+                        backward_breaking: true,
+                        forward_breaking: false,
+                        description: "field became optional".to_string(),
+                    });
+                }
+                diff_json_schema(old_field, new_field, &field_path, changes);
+            }
+            (None, None) => unreachable!("field names come from either schema"),
+        }
+    }
+}
+
+/// Names listed in a JSON Schema's `required` array.
+fn required_fields(schema: &serde_json::Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Render a JSON Schema `type` keyword for a diff description.
+fn type_label(value: Option<&serde_json::Value>) -> String {
+    value.map_or_else(|| "any".to_string(), |v| v.to_string())
+}
+
 /// A schema definition from the Schema Registry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaDefinition {
@@ -115,7 +625,32 @@ pub struct SchemaDefinition {
     pub metadata: SchemaMetadata,
 }
 
+/// Result of a batch schema fetch.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaBatch {
+    /// Latest schema for each subject found, keyed by subject
+    pub schemas: HashMap<String, SchemaDefinition>,
+    /// Requested subjects the registry does not know, in sorted order
+    pub missing: Vec<String>,
+}
+
+impl SchemaBatch {
+    /// Whether every requested subject was found.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Batch schema request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BatchSchemaRequest {
+    subjects: Vec<String>,
+}
+
 /// Schema type enumeration.
+///
+/// Serialized in kebab case, as the Schema Registry names schema types:
+/// `json-schema`, `avro`, `protobuf` and `openapi`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum SchemaType {
@@ -126,6 +661,7 @@ pub enum SchemaType {
     /// Protocol Buffers
     Protobuf,
     /// OpenAPI/Swagger
+    #[serde(rename = "openapi", alias = "open-api")]
     OpenApi,
 }
 
@@ -135,6 +671,14 @@ impl Default for SchemaType {
     }
 }
 
+impl SchemaType {
+    /// Check if values can be validated against schemas of this type
+    /// in-process, without asking the registry.
+    pub fn has_local_validator(&self) -> bool {
+        matches!(self, Self::JsonSchema | Self::Avro)
+    }
+}
+
 /// Schema metadata.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SchemaMetadata {
@@ -193,6 +737,9 @@ pub struct ValidationResult {
     /// Validation warnings if any
     #[serde(default)]
     pub warnings: Vec<ValidationWarning>,
+    /// Where the validation ran
+    #[serde(default)]
+    pub source: ValidationSource,
 }
 
 impl Default for ValidationResult {
@@ -201,10 +748,22 @@ impl Default for ValidationResult {
             valid: true,
             errors: Vec::new(),
             warnings: Vec::new(),
+            source: ValidationSource::default(),
         }
     }
 }
 
+/// Where a validation ran.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationSource {
+    /// In-process
+    Local,
+    /// By the Schema Registry service
+    #[default]
+    Remote,
+}
+
 /// A validation error.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationError {
@@ -239,6 +798,9 @@ pub struct CompatibilityCheckRequest {
 }
 
 /// Schema compatibility levels.
+///
+/// Serialized in screaming snake case, as the Schema Registry names them,
+/// e.g. `BACKWARD_TRANSITIVE`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CompatibilityLevel {
@@ -295,6 +857,98 @@ pub struct CompatibilityIssue {
     pub path: Option<String>,
 }
 
+/// Field-level differences between two schema versions.
+///
+/// Produced by [`SchemaRegistryAdapter::diff_schemas`]. The
+/// [`Display`](fmt::Display) output is a one-line-per-change report suitable
+/// for CI logs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    /// Changes ordered by field path
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Whether the schemas are identical field by field.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether no change breaks existing consumers.
+    pub fn is_backward_compatible(&self) -> bool {
+        self.changes.iter().all(|c| !c.backward_breaking)
+    }
+
+    /// Whether no change breaks existing documents.
+    pub fn is_forward_compatible(&self) -> bool {
+        self.changes.iter().all(|c| !c.forward_breaking)
+    }
+
+    /// Changes that are breaking in either direction.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|c| c.is_breaking())
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single field-level schema change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    /// Dotted field path (empty for the schema root)
+    pub path: String,
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// Consumers of the old schema may no longer find the data they read
+    pub backward_breaking: bool,
+    /// Documents valid under the old schema may fail the new one
+    pub forward_breaking: bool,
+    /// Human-readable description
+    pub description: String,
+}
+
+impl SchemaChange {
+    /// Whether the change is breaking in either direction.
+    pub fn is_breaking(&self) -> bool {
+        self.backward_breaking || self.forward_breaking
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "<root>" } else { &self.path };
+        let breaking = match (self.backward_breaking, self.forward_breaking) {
+            (true, true) => " [breaking: backward, forward]",
+            (true, false) => " [breaking: backward]",
+            (false, true) => " [breaking: forward]",
+            (false, false) => "",
+        };
+        write!(f, "{:?} {}: {}{}", self.kind, path, self.description, breaking)
+    }
+}
+
+/// Kind of schema change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Field present only in the new schema
+    Added,
+    /// Field present only in the old schema
+    Removed,
+    /// Field present in both with a different definition
+    Modified,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,6 +970,444 @@ mod tests {
         assert_eq!(CompatibilityLevel::default(), CompatibilityLevel::Backward);
     }
 
+    fn policy_schema() -> SchemaDefinition {
+        SchemaDefinition {
+            id: "schema-1".to_string(),
+            subject: "policy-document".to_string(),
+            version: 1,
+            schema_type: SchemaType::JsonSchema,
+            schema: serde_json::json!({
+                "type": "object",
+                "required": ["api_version", "kind", "policies"],
+                "properties": {
+                    "kind": { "const": "PolicyDocument" },
+                    "policies": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["id"],
+                            "properties": {
+                                "id": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }),
+            metadata: SchemaMetadata::default(),
+        }
+    }
+
+    fn offline_adapter() -> SchemaRegistryAdapter {
+        SchemaRegistryAdapter::new("http://localhost:0".to_string(), Duration::from_secs(1))
+    }
+
+    fn schema_body(version: u32) -> serde_json::Value {
+        serde_json::json!({
+            "id": format!("schema-{}", version),
+            "subject": "policy-document",
+            "version": version,
+            "schema_type": "json-schema",
+            "schema": { "type": "object" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_schema_cached_within_ttl() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/policy-document/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schema_body(3)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let adapter = SchemaRegistryAdapter::new(server.uri(), Duration::from_secs(5));
+        let first = adapter.get_schema("policy-document").await.unwrap();
+        let second = adapter.get_schema("policy-document").await.unwrap();
+
+        assert_eq!(first.version, 3);
+        assert_eq!(second.id, first.id);
+        assert_eq!(adapter.cached_subjects(), vec!["policy-document".to_string()]);
+
+        // Versioned lookups are served from the same cache.
+        let versioned = adapter.get_schema_version("policy-document", 3).await.unwrap();
+        assert_eq!(versioned.id, "schema-3");
+
+        adapter.clear_schema_cache();
+        assert!(adapter.cached_subjects().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_schemas_reports_missing() {
+        use crate::integration::MockTransport;
+
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/schemas/batch",
+            serde_json::json!({
+                "policy-document": schema_body(3),
+                "policy-rule": schema_body(1),
+            }),
+        ));
+        let client = IntegrationClient::new("http://registry".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        let adapter = SchemaRegistryAdapter::from_client(client);
+
+        let batch = adapter
+            .get_schemas(&["policy-rule", "policy-document", "unknown", "policy-rule"])
+            .await
+            .unwrap();
+
+        assert_eq!(batch.schemas.len(), 2);
+        assert_eq!(batch.schemas["policy-document"].version, 3);
+        assert_eq!(batch.missing, vec!["unknown".to_string()]);
+        assert!(!batch.is_complete());
+        assert_eq!(
+            transport.requests()[0].json(),
+            Some(serde_json::json!({
+                "subjects": ["policy-document", "policy-rule", "unknown"]
+            }))
+        );
+
+        // Found schemas are cached; only the missing subject is requested again.
+        assert!(adapter.cached_schema("policy-rule").is_some());
+        let batch = adapter
+            .get_schemas(&["policy-document", "unknown"])
+            .await
+            .unwrap();
+        assert_eq!(batch.missing, vec!["unknown".to_string()]);
+        assert_eq!(
+            transport.requests()[1].json(),
+            Some(serde_json::json!({ "subjects": ["unknown"] }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_schema_not_modified() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/policy-document/latest"))
+            .and(header("if-none-match", "\"v3\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/policy-document/latest"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v3\"")
+                    .set_body_json(schema_body(3)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let adapter = SchemaRegistryAdapter::new(server.uri(), Duration::from_secs(5))
+            .with_cache_ttl(Duration::ZERO);
+        let first = adapter.get_schema("policy-document").await.unwrap();
+        let second = adapter.get_schema("policy-document").await.unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_schema_with_mock_transport() {
+        use crate::integration::{IntegrationError, MockTransport};
+
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::GET,
+            "/api/v1/schemas/foo/latest",
+            schema_body(2),
+        ));
+        let client = IntegrationClient::new("http://registry".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        let adapter = SchemaRegistryAdapter::from_client(client);
+
+        let schema = adapter.get_schema("foo").await.unwrap();
+        assert_eq!(schema.version, 2);
+
+        let err = adapter.get_schema_version("foo", 1).await.unwrap_err();
+        assert!(matches!(err, IntegrationError::HttpStatus { code: 404, .. }));
+
+        assert_eq!(
+            transport.request_lines(),
+            vec!["GET /api/v1/schemas/foo/latest", "GET /api/v1/schemas/foo/versions/1"]
+        );
+    }
+
+    #[test]
+    fn test_validate_policy_document_local() {
+        let adapter = offline_adapter();
+        let doc = PolicyDocumentSchema {
+            api_version: "policy.llm-dev-ops.io/v1".to_string(),
+            kind: "PolicyDocument".to_string(),
+            policies: vec![serde_json::json!({ "id": "ok" })],
+        };
+
+        let result = adapter.validate_policy_document_local(&doc, &policy_schema()).unwrap();
+        assert!(result.valid);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_validate_policy_document_local_errors() {
+        let adapter = offline_adapter();
+        let doc = PolicyDocumentSchema {
+            api_version: "policy.llm-dev-ops.io/v1".to_string(),
+            kind: "Wrong".to_string(),
+            policies: vec![
+                serde_json::json!({ "name": "missing id" }),
+                serde_json::json!({ "id": 42 }),
+            ],
+        };
+
+        let result = adapter.validate_policy_document_local(&doc, &policy_schema()).unwrap();
+        assert!(!result.valid);
+
+        let paths: Vec<&str> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"/kind"));
+        assert!(paths.contains(&"/policies/0"));
+        assert!(paths.contains(&"/policies/1/id"));
+
+        let missing = result.errors.iter().find(|e| e.path == "/policies/0").unwrap();
+        assert_eq!(missing.code.as_deref(), Some("required"));
+    }
+
+    #[test]
+    fn test_validate_local_rejects_schema_without_local_validator() {
+        let adapter = offline_adapter();
+        let mut schema = policy_schema();
+        schema.schema_type = SchemaType::Protobuf;
+        let doc = PolicyDocumentSchema {
+            api_version: "v1".to_string(),
+            kind: "PolicyDocument".to_string(),
+            policies: vec![],
+        };
+
+        assert!(adapter.validate_policy_document_local(&doc, &schema).is_err());
+        assert!(adapter.validate_policy_document_cached("policy-document", &doc).is_err());
+    }
+
+    fn avro_schema() -> SchemaDefinition {
+        SchemaDefinition {
+            schema_type: SchemaType::Avro,
+            schema: serde_json::json!({
+                "type": "record",
+                "name": "PolicyDocument",
+                "namespace": "io.llm_dev_ops.policy",
+                "fields": [
+                    { "name": "api_version", "type": "string" },
+                    {
+                        "name": "kind",
+                        "type": { "type": "enum", "name": "Kind", "symbols": ["PolicyDocument"] }
+                    },
+                    {
+                        "name": "policies",
+                        "type": {
+                            "type": "array",
+                            "items": {
+                                "type": "record",
+                                "name": "Policy",
+                                "fields": [
+                                    { "name": "id", "type": "string" },
+                                    { "name": "priority", "type": "int", "default": 0 },
+                                    {
+                                        "name": "owner",
+                                        "type": ["null", "string"],
+                                        "default": null
+                                    },
+                                    {
+                                        "name": "labels",
+                                        "type": { "type": "map", "values": "string" },
+                                        "default": {}
+                                    }
+                                ]
+                            }
+                        }
+                    }
+                ]
+            }),
+            ..policy_schema()
+        }
+    }
+
+    #[test]
+    fn test_validate_avro_conforming_record() {
+        let adapter = offline_adapter();
+        let doc = PolicyDocumentSchema {
+            api_version: "policy.llm-dev-ops.io/v1".to_string(),
+            kind: "PolicyDocument".to_string(),
+            policies: vec![
+                serde_json::json!({ "id": "minimal" }),
+                serde_json::json!({
+                    "id": "full",
+                    "priority": 10,
+                    "owner": "security",
+                    "labels": { "team": "platform" },
+                    "ignored": true
+                }),
+            ],
+        };
+
+        let result = adapter.validate_policy_document_local(&doc, &avro_schema()).unwrap();
+        assert!(result.valid, "{:?}", result.errors);
+        assert_eq!(result.source, ValidationSource::Local);
+    }
+
+    #[test]
+    fn test_validate_avro_non_conforming_record() {
+        let adapter = offline_adapter();
+        let doc = PolicyDocumentSchema {
+            api_version: "policy.llm-dev-ops.io/v1".to_string(),
+            kind: "Wrong".to_string(),
+            policies: vec![
+                serde_json::json!({ "priority": 1 }),
+                serde_json::json!({ "id": 42, "priority": 5_000_000_000i64, "owner": 7 }),
+                serde_json::json!({ "id": "ok", "labels": { "team": 1 } }),
+            ],
+        };
+
+        let result = adapter.validate_policy_document_local(&doc, &avro_schema()).unwrap();
+        assert!(!result.valid);
+        let errors: Vec<_> = result
+            .errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.code.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("/kind", "enum"),
+                ("/policies/0/id", "required"),
+                ("/policies/1/id", "type"),
+                ("/policies/1/priority", "type"),
+                ("/policies/1/owner", "union"),
+                ("/policies/2/labels/team", "type"),
+            ]
+        );
+
+        // A reference to an undefined type is a schema error.
+        let mut invalid = avro_schema();
+        invalid.schema["fields"][0]["type"] = serde_json::json!("Missing");
+        assert!(adapter.validate_policy_document_local(&doc, &invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_without_local_validator_uses_registry() {
+        use crate::integration::MockTransport;
+
+        let transport = Arc::new(MockTransport::new().with_json(
+            reqwest::Method::POST,
+            "/api/v1/schemas/policy%20document/versions/1/validate",
+            serde_json::json!({ "valid": false, "errors": [{ "path": "/id", "message": "bad" }] }),
+        ));
+        let client = IntegrationClient::new("http://registry".to_string(), Duration::from_secs(1))
+            .with_transport(transport.clone());
+        let adapter = SchemaRegistryAdapter::from_client(client);
+        let schema = SchemaDefinition {
+            subject: "policy document".to_string(),
+            version: 1,
+            schema_type: SchemaType::Protobuf,
+            ..policy_schema()
+        };
+
+        let result = adapter
+            .validate(&serde_json::json!({ "id": 1 }), &schema)
+            .await
+            .unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.source, ValidationSource::Remote);
+        assert_eq!(transport.requests()[0].json().unwrap(), serde_json::json!({ "id": 1 }));
+
+        // Avro is validated locally, without a request.
+        let result = adapter
+            .validate(&serde_json::json!({ "policies": [] }), &avro_schema())
+            .await
+            .unwrap();
+        assert_eq!(result.source, ValidationSource::Local);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    fn json_schema(schema: serde_json::Value) -> SchemaDefinition {
+        SchemaDefinition {
+            schema,
+            ..policy_schema()
+        }
+    }
+
+    #[test]
+    fn test_diff_added_required_field_is_forward_breaking() {
+        let adapter = offline_adapter();
+        let old = policy_schema();
+        let mut new = policy_schema();
+        new.schema["properties"]["owner"] = serde_json::json!({ "type": "string" });
+        new.schema["required"] = serde_json::json!(["api_version", "kind", "policies", "owner"]);
+
+        let diff = adapter.diff_schemas(&old, &new);
+
+        assert_eq!(diff.changes.len(), 1);
+        let change = &diff.changes[0];
+        assert_eq!(change.path, "owner");
+        assert_eq!(change.kind, ChangeKind::Added);
+        assert!(change.forward_breaking);
+        assert!(!change.backward_breaking);
+        assert!(diff.is_backward_compatible());
+        assert!(!diff.is_forward_compatible());
+        assert_eq!(
+            diff.to_string(),
+            "Added owner: required field added [breaking: forward]\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_removed_field_is_backward_breaking() {
+        let adapter = offline_adapter();
+        let old = policy_schema();
+        let mut new = policy_schema();
+        new.schema["properties"]["policies"]["items"]["properties"]
+            .as_object_mut()
+            .unwrap()
+            .remove("id");
+
+        let diff = adapter.diff_schemas(&old, &new);
+
+        assert_eq!(diff.changes.len(), 1);
+        let change = &diff.changes[0];
+        assert_eq!(change.path, "policies[].id");
+        assert_eq!(change.kind, ChangeKind::Removed);
+        assert!(change.backward_breaking);
+        assert!(!change.forward_breaking);
+        assert_eq!(diff.breaking_changes().count(), 1);
+    }
+
+    #[test]
+    fn test_diff_type_change_and_identical_schemas() {
+        let adapter = offline_adapter();
+        let old = json_schema(serde_json::json!({
+            "type": "object",
+            "properties": { "priority": { "type": "integer" } }
+        }));
+        let new = json_schema(serde_json::json!({
+            "type": "object",
+            "properties": { "priority": { "type": "string" } }
+        }));
+
+        assert!(adapter.diff_schemas(&old, &old).is_empty());
+
+        let diff = adapter.diff_schemas(&old, &new);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].kind, ChangeKind::Modified);
+        assert!(diff.changes[0].backward_breaking && diff.changes[0].forward_breaking);
+    }
+
     #[test]
     fn test_policy_document_schema_serialization() {
         let doc = PolicyDocumentSchema {
@@ -3,9 +3,16 @@
 //! Sentinel provides security monitoring and anomaly detection.
 
 use super::client::{IntegrationClient, IntegrationResult};
+use super::metrics::MetricsRecorder;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Path templates for metrics labels.
+const PATH_TEMPLATES: &[&str] = &[
+    "/api/v1/intel/{indicator}",
+];
+
 /// Client for Sentinel service.
 pub struct SentinelClient {
     client: IntegrationClient,
@@ -14,9 +21,23 @@ pub struct SentinelClient {
 impl SentinelClient {
     /// Create a new Sentinel client.
     pub fn new(base_url: String, timeout: Duration) -> Self {
-        Self {
-            client: IntegrationClient::new(base_url, timeout),
-        }
+        Self::from_client(IntegrationClient::new(base_url, timeout))
+    }
+
+    /// Create a new Sentinel client from a configured integration client.
+    pub fn from_client(client: IntegrationClient) -> Self {
+        Self { client }
+    }
+
+    /// Report calls to a metrics recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.client = self.client.with_metrics("sentinel", PATH_TEMPLATES, recorder);
+        self
+    }
+
+    /// Get the underlying integration client.
+    pub fn client(&self) -> &IntegrationClient {
+        &self.client
     }
 
     /// Report a security event.
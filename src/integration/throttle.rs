@@ -0,0 +1,195 @@
+//! Client-side throttling for integration calls.
+//!
+//! A token bucket limits how fast a client sends requests to its upstream.
+//! Requests beyond the bucket wait in a bounded queue; once the queue is full
+//! they fail fast with [`IntegrationError::RateLimited`] instead of piling up.
+//! When the upstream answers `429 Too Many Requests` with a `Retry-After`
+//! header, new requests are held until that window has passed.
+
+use super::client::IntegrationResult;
+use super::config_manager::RateLimitConfig;
+use super::error::IntegrationError;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct ThrottleState {
+    /// Available tokens; negative when requests are queued for future tokens
+    tokens: f64,
+    last_refill: Instant,
+    queued: usize,
+    paused_until: Option<Instant>,
+}
+
+/// Token bucket throttle shared across clones of a client.
+#[derive(Debug, Clone)]
+pub(crate) struct Throttle {
+    /// Tokens added per second, or `None` if only `Retry-After` pauses apply
+    rate: Option<f64>,
+    capacity: f64,
+    max_queue: usize,
+    inner: Arc<Mutex<ThrottleState>>,
+}
+
+impl Throttle {
+    /// Create a throttle from a rate limit configuration.
+    ///
+    /// The bucket holds `burst_size` tokens and refills at
+    /// `requests_per_second`; up to `burst_size` further requests may queue.
+    /// A disabled configuration only honors `Retry-After` pauses.
+    pub(crate) fn new(config: &RateLimitConfig) -> Self {
+        let capacity = f64::from(config.burst_size.max(1));
+        let rate = (config.enabled && config.requests_per_second > 0)
+            .then(|| f64::from(config.requests_per_second));
+
+        Self {
+            rate,
+            capacity,
+            max_queue: config.burst_size.max(1) as usize,
+            inner: Arc::new(Mutex::new(ThrottleState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+                queued: 0,
+                paused_until: None,
+            })),
+        }
+    }
+
+    /// Wait for permission to send a request.
+    ///
+    /// Fails immediately with [`IntegrationError::RateLimited`] if the
+    /// request would have to queue and the queue is full.
+    pub(crate) async fn acquire(&self) -> IntegrationResult<()> {
+        let (wait, _slot) = self.reserve()?;
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// Reserve a token, returning how long to wait before using it.
+    ///
+    /// A request that has to wait holds a queue slot until the returned
+    /// guard is dropped.
+    fn reserve(&self) -> IntegrationResult<(Duration, Option<QueueSlot>)> {
+        let mut state = self.inner.lock();
+        let now = Instant::now();
+
+        let pause = state
+            .paused_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or_default();
+
+        let mut wait = pause;
+        if let Some(rate) = self.rate {
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * rate).min(self.capacity);
+            state.last_refill = now;
+
+            if state.tokens < 1.0 {
+                wait = wait.max(Duration::from_secs_f64((1.0 - state.tokens) / rate));
+            }
+        }
+
+        if wait.is_zero() {
+            if self.rate.is_some() {
+                state.tokens -= 1.0;
+            }
+            return Ok((Duration::ZERO, None));
+        }
+
+        if state.queued >= self.max_queue {
+            return Err(IntegrationError::RateLimited);
+        }
+        if self.rate.is_some() {
+            state.tokens -= 1.0;
+        }
+        state.queued += 1;
+
+        Ok((
+            wait,
+            Some(QueueSlot {
+                inner: self.inner.clone(),
+            }),
+        ))
+    }
+
+    /// Hold new requests until `delay` has passed.
+    pub(crate) fn pause_for(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut state = self.inner.lock();
+        state.paused_until = state.paused_until.max(Some(until));
+    }
+}
+
+/// Releases a queue slot when the waiting request proceeds or is dropped.
+#[derive(Debug)]
+struct QueueSlot {
+    inner: Arc<Mutex<ThrottleState>>,
+}
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        state.queued = state.queued.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(requests_per_second: u32, burst_size: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            enabled: true,
+            requests_per_second,
+            burst_size,
+        }
+    }
+
+    #[test]
+    fn test_burst_beyond_limit_is_shed() {
+        let throttle = Throttle::new(&limit(1, 2));
+
+        // The bucket admits a full burst immediately...
+        let (first, _) = throttle.reserve().unwrap();
+        let (second, _) = throttle.reserve().unwrap();
+        assert!(first.is_zero() && second.is_zero());
+
+        // ...queues up to the burst size behind it...
+        let (third, third_slot) = throttle.reserve().unwrap();
+        let (fourth, _fourth_slot) = throttle.reserve().unwrap();
+        assert!(third > Duration::from_millis(900));
+        assert!(fourth > third);
+
+        // ...and sheds the rest.
+        assert_eq!(throttle.reserve().unwrap_err(), IntegrationError::RateLimited);
+        assert_eq!(throttle.reserve().unwrap_err(), IntegrationError::RateLimited);
+
+        // A request leaving the queue frees its slot.
+        drop(third_slot);
+        assert!(throttle.reserve().is_ok());
+    }
+
+    #[test]
+    fn test_pause_holds_requests() {
+        // Without a token bucket only Retry-After pauses apply.
+        let throttle = Throttle::new(&RateLimitConfig {
+            enabled: false,
+            ..limit(1, 4)
+        });
+
+        let (wait, _) = throttle.reserve().unwrap();
+        assert!(wait.is_zero());
+
+        throttle.pause_for(Duration::from_secs(5));
+        let (wait, _first) = throttle.reserve().unwrap();
+        assert!(wait > Duration::from_secs(4));
+
+        // A shorter Retry-After does not cut an existing pause short.
+        throttle.pause_for(Duration::from_secs(1));
+        let (wait, _second) = throttle.reserve().unwrap();
+        assert!(wait > Duration::from_secs(4));
+    }
+}
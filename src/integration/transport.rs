@@ -0,0 +1,218 @@
+//! Pluggable HTTP transport for integration clients.
+//!
+//! [`IntegrationClient`](super::IntegrationClient) builds requests with
+//! reqwest and hands them to a [`Transport`] to send. Production clients use
+//! the reqwest client itself; tests can swap in a [`MockTransport`] to check
+//! request construction and response handling without opening a socket.
+
+use super::client::IntegrationResult;
+use futures::future::{BoxFuture, FutureExt};
+use parking_lot::Mutex;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+
+/// Sends prepared HTTP requests.
+pub trait Transport: Send + Sync {
+    /// Send a request and return the response, whatever its status.
+    ///
+    /// Failures to get a response at all should be reported as
+    /// [`IntegrationError::Timeout`](super::IntegrationError::Timeout) or
+    /// [`IntegrationError::Connection`](super::IntegrationError::Connection)
+    /// so idempotent requests are retried.
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, IntegrationResult<reqwest::Response>>;
+}
+
+impl Transport for reqwest::Client {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, IntegrationResult<reqwest::Response>> {
+        reqwest::Client::execute(self, request)
+            .map(|result| result.map_err(Into::into))
+            .boxed()
+    }
+}
+
+/// A request captured by [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Request path, including the query string if any
+    pub path: String,
+    /// Request headers
+    pub headers: HeaderMap,
+    /// Request body, if any
+    pub body: Option<Vec<u8>>,
+}
+
+impl RecordedRequest {
+    /// Parse the request body as JSON.
+    pub fn json(&self) -> Option<serde_json::Value> {
+        self.body
+            .as_deref()
+            .and_then(|body| serde_json::from_slice(body).ok())
+    }
+}
+
+/// A canned response registered on a [`MockTransport`].
+#[derive(Debug, Clone)]
+struct MockRoute {
+    method: Method,
+    path: String,
+    status: StatusCode,
+    body: Vec<u8>,
+    json: bool,
+}
+
+impl MockRoute {
+    /// Routes without a query string match any query.
+    fn matches(&self, method: &Method, url: &reqwest::Url) -> bool {
+        if self.method != *method {
+            return false;
+        }
+        match (self.path.contains('?'), url.query()) {
+            (true, Some(query)) => self.path == format!("{}?{}", url.path(), query),
+            (true, None) => false,
+            (false, _) => self.path == url.path(),
+        }
+    }
+}
+
+/// In-memory transport returning canned responses by method and path.
+///
+/// Routes are matched in registration order; a route without a query string
+/// matches the path with any query. Unmatched requests get `404 Not Found`.
+/// Every request is recorded so tests can assert on exact paths and bodies.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Vec<MockRoute>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl MockTransport {
+    /// Create a transport with no routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Respond to `method` `path` with `200 OK` and a JSON body.
+    pub fn with_json(
+        self,
+        method: Method,
+        path: impl Into<String>,
+        body: serde_json::Value,
+    ) -> Self {
+        self.with_json_status(method, path, StatusCode::OK, body)
+    }
+
+    /// Respond to `method` `path` with the given status and a JSON body.
+    pub fn with_json_status(
+        mut self,
+        method: Method,
+        path: impl Into<String>,
+        status: StatusCode,
+        body: serde_json::Value,
+    ) -> Self {
+        self.routes.push(MockRoute {
+            method,
+            path: path.into(),
+            status,
+            body: body.to_string().into_bytes(),
+            json: true,
+        });
+        self
+    }
+
+    /// Respond to `method` `path` with the given status and an empty body.
+    pub fn with_status(
+        mut self,
+        method: Method,
+        path: impl Into<String>,
+        status: StatusCode,
+    ) -> Self {
+        self.routes.push(MockRoute {
+            method,
+            path: path.into(),
+            status,
+            body: Vec::new(),
+            json: false,
+        });
+        self
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().clone()
+    }
+
+    /// Paths of the requests received so far, prefixed with their method
+    /// (e.g. `GET /api/v1/schemas/foo/latest`).
+    pub fn request_lines(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.path))
+            .collect()
+    }
+
+    fn respond(&self, request: &reqwest::Request) -> reqwest::Response {
+        let mut builder = http::Response::builder();
+        let (status, body) = match self
+            .routes
+            .iter()
+            .find(|route| route.matches(request.method(), request.url()))
+        {
+            Some(route) => {
+                if route.json {
+                    builder =
+                        builder.header(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                }
+                (route.status, route.body.clone())
+            }
+            None => (
+                StatusCode::NOT_FOUND,
+                format!(
+                    "no mock route for {} {}",
+                    request.method(),
+                    request.url().path()
+                )
+                .into_bytes(),
+            ),
+        };
+
+        let response = builder
+            .status(status)
+            .body(body)
+            .expect("mock response parts are valid");
+        reqwest::Response::from(response)
+    }
+}
+
+impl Transport for MockTransport {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, IntegrationResult<reqwest::Response>> {
+        let url = request.url();
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        self.requests.lock().push(RecordedRequest {
+            method: request.method().clone(),
+            path,
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(<[u8]>::to_vec),
+        });
+
+        let response = self.respond(&request);
+        futures::future::ready(Ok(response)).boxed()
+    }
+}
@@ -67,15 +67,19 @@ pub mod integration;
 pub mod policy;
 pub mod telemetry;
 
+#[cfg(test)]
+mod wire_format;
+
 // Re-export main types for convenience
 pub use api::{
-    EvaluationContext, EvaluationContextBuilder, PolicyDecision, PolicyEngine, PolicyEngineBuilder,
+    Decision, EngineConfig, EvaluationContext, EvaluationContextBuilder, EvaluationInput,
+    PolicyDecision, PolicyEngine, PolicyEngineBuilder,
 };
-pub use config::Config;
+pub use config::{Config, ConfigBuilder, ConfigError, ConfigSource, ConfigWarning};
 pub use error::{Error, Result};
 pub use policy::{
-    Action, ActionType, Condition, ConditionOperator, DecisionType, Policy, PolicyDocument,
-    PolicyMetadata, PolicyRule,
+    Action, ActionType, Condition, ConditionOperator, DecisionReason, DecisionType, Obligations,
+    Policy, PolicyDocument, PolicyMetadata, PolicyRule, ReasonCode,
 };
 
 /// Library version
@@ -2,7 +2,7 @@
 
 use super::DecisionType;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// An action to take when a rule matches.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +19,9 @@ pub struct Action {
     /// Modifications to apply (for modify actions)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub modifications: Vec<Modification>,
+    /// Obligations the caller must fulfil (for modify actions)
+    #[serde(default, skip_serializing_if = "Obligations::is_empty")]
+    pub obligations: Obligations,
     /// Additional metadata to include in the decision
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
@@ -32,6 +35,7 @@ impl Action {
             decision: DecisionType::Allow,
             reason: None,
             modifications: Vec::new(),
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
         }
     }
@@ -43,6 +47,7 @@ impl Action {
             decision: DecisionType::Deny,
             reason: Some(reason.into()),
             modifications: Vec::new(),
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
         }
     }
@@ -54,6 +59,7 @@ impl Action {
             decision: DecisionType::Warn,
             reason: Some(reason.into()),
             modifications: Vec::new(),
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
         }
     }
@@ -65,6 +71,7 @@ impl Action {
             decision: DecisionType::Modify,
             reason: None,
             modifications,
+            obligations: Obligations::default(),
             metadata: HashMap::new(),
         }
     }
@@ -86,9 +93,18 @@ impl Action {
         self.modifications.push(modification);
         self
     }
+
+    /// Set the obligations of the action.
+    pub fn with_obligations(mut self, obligations: Obligations) -> Self {
+        self.obligations = obligations;
+        self
+    }
 }
 
 /// The type of action to take.
+///
+/// Serialized in lowercase, except `rate_limit`, which policy documents
+/// spell in snake case. The older `ratelimit` is still accepted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ActionType {
@@ -103,6 +119,7 @@ pub enum ActionType {
     /// Log the request (no decision change)
     Log,
     /// Rate limit the request
+    #[serde(rename = "rate_limit", alias = "ratelimit")]
     RateLimit,
 }
 
@@ -179,6 +196,92 @@ pub enum ModificationType {
     Truncate,
 }
 
+/// How the caller must transform a request allowed by a modify decision.
+///
+/// Obligations from every matching modify rule are merged in evaluation
+/// order (policies by priority, then rules in order) by [`merge`]:
+///
+/// - `redact_fields` are combined, keeping each field once in the order
+///   first seen;
+/// - `set_headers` and `inject_system_prompt` keep the value seen first,
+///   so higher-priority policies and earlier rules win;
+/// - `max_tokens_override` keeps the lowest limit.
+///
+/// [`merge`]: Obligations::merge
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Obligations {
+    /// Request fields to redact before forwarding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact_fields: Vec<String>,
+    /// Headers to set on the upstream request
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set_headers: BTreeMap<String, String>,
+    /// Maximum number of tokens to request, replacing the caller's value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens_override: Option<u32>,
+    /// System prompt to inject ahead of the conversation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inject_system_prompt: Option<String>,
+}
+
+impl Obligations {
+    /// Create empty obligations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to redact.
+    pub fn redact(mut self, field: impl Into<String>) -> Self {
+        self.redact_fields.push(field.into());
+        self
+    }
+
+    /// Add a header to set.
+    pub fn set_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Cap the number of tokens requested.
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens_override = Some(max_tokens);
+        self
+    }
+
+    /// Inject a system prompt.
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.inject_system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Check whether there are no obligations.
+    pub fn is_empty(&self) -> bool {
+        self.redact_fields.is_empty()
+            && self.set_headers.is_empty()
+            && self.max_tokens_override.is_none()
+            && self.inject_system_prompt.is_none()
+    }
+
+    /// Merge obligations from a decision later in evaluation order.
+    pub fn merge(&mut self, other: Obligations) {
+        for field in other.redact_fields {
+            if !self.redact_fields.contains(&field) {
+                self.redact_fields.push(field);
+            }
+        }
+        for (name, value) in other.set_headers {
+            self.set_headers.entry(name).or_insert(value);
+        }
+        self.max_tokens_override = match (self.max_tokens_override, other.max_tokens_override) {
+            (Some(current), Some(other)) => Some(current.min(other)),
+            (current, other) => current.or(other),
+        };
+        if self.inject_system_prompt.is_none() {
+            self.inject_system_prompt = other.inject_system_prompt;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +318,42 @@ mod tests {
         let parsed: Action = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.reason, action.reason);
     }
+
+    #[test]
+    fn test_obligations_merge_conflicts() {
+        let mut obligations = Obligations::new()
+            .redact("user.email")
+            .set_header("x-policy", "strict")
+            .max_tokens(2000);
+        obligations.merge(
+            Obligations::new()
+                .redact("llm.prompt")
+                .redact("user.email")
+                .set_header("x-policy", "lenient")
+                .set_header("x-team", "ml")
+                .max_tokens(500)
+                .system_prompt("Be concise."),
+        );
+        obligations.merge(Obligations::new().max_tokens(1000).system_prompt("Be verbose."));
+
+        assert_eq!(obligations.redact_fields, vec!["user.email", "llm.prompt"]);
+        assert_eq!(obligations.set_headers["x-policy"], "strict");
+        assert_eq!(obligations.set_headers["x-team"], "ml");
+        assert_eq!(obligations.max_tokens_override, Some(500));
+        assert_eq!(obligations.inject_system_prompt.as_deref(), Some("Be concise."));
+    }
+
+    #[test]
+    fn test_obligations_serialization() {
+        let action = Action::modify(Vec::new())
+            .with_obligations(Obligations::new().redact("user.email").max_tokens(256));
+        let json = serde_json::to_value(&action).unwrap();
+        assert_eq!(json["obligations"]["redact_fields"][0], "user.email");
+        assert_eq!(json["obligations"]["max_tokens_override"], 256);
+        assert!(json["obligations"].get("set_headers").is_none());
+        assert!(serde_json::to_value(Action::allow()).unwrap().get("obligations").is_none());
+
+        let parsed: Action = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.obligations, action.obligations);
+    }
 }
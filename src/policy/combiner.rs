@@ -0,0 +1,166 @@
+//! Decision combining algorithms.
+//!
+//! When several rules of a policy match, their outcomes are combined into a
+//! single outcome by a [`DecisionCombiner`]. `Warn` and `Modify` are both
+//! permits: they allow the request, with a warning or with modifications.
+//! Among permits the strongest wins, `Modify` over `Warn` over `Allow`, so a
+//! warning and a modification together yield `Modify` (the evaluator keeps
+//! the warning as the decision's reason). A `Deny` never composes with a
+//! permit; whichever side the strategy favors wins outright and the other
+//! outcomes are discarded.
+
+use crate::integration::DecisionOutcome;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Strategy for combining the outcomes of several matching rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionCombiner {
+    /// Any deny wins; otherwise any error; otherwise the strongest permit
+    #[default]
+    DenyOverrides,
+    /// Any permit wins; otherwise any error; otherwise deny
+    PermitOverrides,
+    /// The first outcome wins, whatever it is
+    FirstApplicable,
+    /// Deny unless some outcome permits; never returns an error
+    UnlessPermit,
+}
+
+impl DecisionCombiner {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecisionCombiner::DenyOverrides => "deny_overrides",
+            DecisionCombiner::PermitOverrides => "permit_overrides",
+            DecisionCombiner::FirstApplicable => "first_applicable",
+            DecisionCombiner::UnlessPermit => "unless_permit",
+        }
+    }
+}
+
+impl fmt::Display for DecisionCombiner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for DecisionCombiner {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "deny_overrides" => Ok(DecisionCombiner::DenyOverrides),
+            "permit_overrides" => Ok(DecisionCombiner::PermitOverrides),
+            "first_applicable" => Ok(DecisionCombiner::FirstApplicable),
+            "unless_permit" => Ok(DecisionCombiner::UnlessPermit),
+            _ => Err(crate::Error::parse(format!("Unknown decision combiner: {}", s))),
+        }
+    }
+}
+
+/// Combine rule outcomes into one outcome using `strategy`.
+///
+/// An empty set means no rule applied: it combines to `Allow`, leaving the
+/// engine's default decision to apply, except under
+/// [`DecisionCombiner::UnlessPermit`], where it combines to `Deny`.
+pub fn combine(outcomes: &[DecisionOutcome], strategy: DecisionCombiner) -> DecisionOutcome {
+    let has = |outcome: DecisionOutcome| outcomes.contains(&outcome);
+    let permit = strongest_permit(outcomes);
+
+    match strategy {
+        DecisionCombiner::DenyOverrides if has(DecisionOutcome::Deny) => DecisionOutcome::Deny,
+        DecisionCombiner::DenyOverrides if has(DecisionOutcome::Error) => DecisionOutcome::Error,
+        DecisionCombiner::DenyOverrides => permit.unwrap_or(DecisionOutcome::Allow),
+        DecisionCombiner::PermitOverrides => match permit {
+            Some(permit) => permit,
+            None if has(DecisionOutcome::Error) => DecisionOutcome::Error,
+            None if has(DecisionOutcome::Deny) => DecisionOutcome::Deny,
+            None => DecisionOutcome::Allow,
+        },
+        DecisionCombiner::FirstApplicable => {
+            outcomes.first().copied().unwrap_or(DecisionOutcome::Allow)
+        }
+        DecisionCombiner::UnlessPermit => permit.unwrap_or(DecisionOutcome::Deny),
+    }
+}
+
+/// The strongest permitting outcome, if any: `Modify`, then `Warn`, then `Allow`.
+fn strongest_permit(outcomes: &[DecisionOutcome]) -> Option<DecisionOutcome> {
+    [DecisionOutcome::Modify, DecisionOutcome::Warn, DecisionOutcome::Allow]
+        .into_iter()
+        .find(|permit| outcomes.contains(permit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DecisionOutcome::{Allow, Deny, Error, Modify, Warn};
+
+    /// Representative outcome sets shared by the per-strategy tests.
+    const SETS: &[&[DecisionOutcome]] = &[
+        &[],
+        &[Allow],
+        &[Deny],
+        &[Error],
+        &[Allow, Warn],
+        &[Warn, Modify],
+        &[Allow, Deny],
+        &[Deny, Allow],
+        &[Modify, Deny],
+        &[Error, Allow],
+        &[Deny, Error],
+        &[Error, Deny, Warn, Modify],
+    ];
+
+    fn combine_all(strategy: DecisionCombiner) -> Vec<DecisionOutcome> {
+        SETS.iter().map(|set| combine(set, strategy)).collect()
+    }
+
+    #[test]
+    fn test_deny_overrides() {
+        assert_eq!(
+            combine_all(DecisionCombiner::DenyOverrides),
+            vec![Allow, Allow, Deny, Error, Warn, Modify, Deny, Deny, Deny, Error, Deny, Deny]
+        );
+    }
+
+    #[test]
+    fn test_permit_overrides() {
+        assert_eq!(
+            combine_all(DecisionCombiner::PermitOverrides),
+            vec![Allow, Allow, Deny, Error, Warn, Modify, Allow, Allow, Modify, Allow, Error, Modify]
+        );
+    }
+
+    #[test]
+    fn test_first_applicable() {
+        assert_eq!(
+            combine_all(DecisionCombiner::FirstApplicable),
+            vec![Allow, Allow, Deny, Error, Allow, Warn, Allow, Deny, Modify, Error, Deny, Error]
+        );
+    }
+
+    #[test]
+    fn test_unless_permit() {
+        assert_eq!(
+            combine_all(DecisionCombiner::UnlessPermit),
+            vec![Deny, Allow, Deny, Deny, Warn, Modify, Allow, Allow, Modify, Allow, Deny, Modify]
+        );
+    }
+
+    #[test]
+    fn test_combiner_from_str() {
+        assert_eq!(
+            "deny_overrides".parse::<DecisionCombiner>().unwrap(),
+            DecisionCombiner::DenyOverrides
+        );
+        assert_eq!(
+            "Permit-Overrides".parse::<DecisionCombiner>().unwrap(),
+            DecisionCombiner::PermitOverrides
+        );
+        assert!("majority".parse::<DecisionCombiner>().is_err());
+        assert_eq!(DecisionCombiner::default(), DecisionCombiner::DenyOverrides);
+    }
+}
@@ -99,6 +99,16 @@ impl Condition {
         }
     }
 
+    /// Create a condition from a CEL expression over the context.
+    pub fn expression(source: impl Into<String>) -> Self {
+        Self {
+            operator: ConditionOperator::Expression,
+            field: None,
+            value: Some(ConditionValue::String(source.into())),
+            conditions: Vec::new(),
+        }
+    }
+
     /// Create an AND condition combining multiple conditions.
     pub fn and(conditions: Vec<Condition>) -> Self {
         Self {
@@ -151,6 +161,13 @@ impl Condition {
                 }
                 self.conditions[0].validate()?;
             }
+            ConditionOperator::Expression => {
+                if !matches!(self.value, Some(ConditionValue::String(_))) {
+                    return Err(crate::Error::validation(
+                        "EXPRESSION operator requires an expression string as its value",
+                    ));
+                }
+            }
             ConditionOperator::Exists => {
                 if self.field.is_none() {
                     return Err(crate::Error::validation(
@@ -175,6 +192,19 @@ impl Condition {
         }
         Ok(())
     }
+
+    /// Compile the condition's regular expressions, failing on the first
+    /// that is invalid.
+    pub fn compile(&self) -> crate::Result<()> {
+        if let (ConditionOperator::Matches, Some(ConditionValue::String(pattern))) =
+            (self.operator, &self.value)
+        {
+            regex::Regex::new(pattern).map_err(|e| {
+                crate::Error::expression_with_expr(format!("Invalid regex: {}", e), pattern.clone())
+            })?;
+        }
+        self.conditions.iter().try_for_each(Condition::compile)
+    }
 }
 
 /// Operators for condition evaluation.
@@ -215,6 +245,8 @@ pub enum ConditionOperator {
     Or,
     /// Logical NOT
     Not,
+    /// CEL expression over the context, given as the value
+    Expression,
 }
 
 /// A value that can be used in conditions.
@@ -1,6 +1,7 @@
 //! Decision types for policy evaluation.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 /// The type of decision returned by policy evaluation.
@@ -65,6 +66,113 @@ impl std::str::FromStr for DecisionType {
     }
 }
 
+/// Machine-readable code for why a decision was made.
+///
+/// Codes are stable across releases, unlike reason messages, so downstream
+/// systems should key off them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    /// A policy rule matched the request
+    RuleMatched,
+    /// No policy matched, so the default decision applied
+    NoMatchDefault,
+    /// Shield detected prompt injection or a jailbreak
+    InjectionDetected,
+    /// Shield flagged the prompt as unsafe for another reason
+    UnsafePrompt,
+    /// The request's projected cost exceeds its budget
+    BudgetExceeded,
+    /// The request's estimated tokens exceed the token limit
+    TokenLimitExceeded,
+    /// The request is not compliant with a compliance policy
+    ComplianceViolation,
+    /// An integration the request depends on failed closed
+    IntegrationUnavailable,
+    /// The request exceeded its rate limit
+    RateLimited,
+    /// Evaluation failed and the engine failed open
+    FailedOpen,
+    /// Evaluation failed
+    EvaluationError,
+    /// Evaluation exceeded the maximum evaluation time
+    Timeout,
+}
+
+impl ReasonCode {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasonCode::RuleMatched => "rule_matched",
+            ReasonCode::NoMatchDefault => "no_match_default",
+            ReasonCode::InjectionDetected => "injection_detected",
+            ReasonCode::UnsafePrompt => "unsafe_prompt",
+            ReasonCode::BudgetExceeded => "budget_exceeded",
+            ReasonCode::TokenLimitExceeded => "token_limit_exceeded",
+            ReasonCode::ComplianceViolation => "compliance_violation",
+            ReasonCode::IntegrationUnavailable => "integration_unavailable",
+            ReasonCode::RateLimited => "rate_limited",
+            ReasonCode::FailedOpen => "failed_open",
+            ReasonCode::EvaluationError => "evaluation_error",
+            ReasonCode::Timeout => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Structured reason for a decision: a stable code, a human-readable
+/// message and code-specific details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionReason {
+    /// Machine-readable reason code
+    pub code: ReasonCode,
+    /// Human-readable message
+    pub message: String,
+    /// Code-specific details, such as the matched rule or the exceeded limit
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub details: HashMap<String, serde_json::Value>,
+}
+
+impl DecisionReason {
+    /// Create a reason without details.
+    pub fn new(code: ReasonCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: HashMap::new(),
+        }
+    }
+
+    /// Add a detail.
+    pub fn with_detail(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.details.insert(key.into(), value);
+        self
+    }
+
+    /// The reason for an evaluation that failed with `error`.
+    pub fn from_error(error: &crate::Error) -> Self {
+        let code = match error {
+            crate::Error::RateLimited { .. } => ReasonCode::RateLimited,
+            crate::Error::Timeout { .. } => ReasonCode::Timeout,
+            _ => ReasonCode::EvaluationError,
+        };
+        let mut reason =
+            Self::new(code, error.to_string()).with_detail("error", error.category().into());
+        if let Some(retry_after) = error.retry_after() {
+            reason = reason.with_detail("retry_after_ms", (retry_after.as_millis() as u64).into());
+        }
+        if let Some(policy_id) = error.policy_id() {
+            reason = reason.with_detail("policy_id", policy_id.into());
+        }
+        reason
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,4 +207,32 @@ mod tests {
         let parsed: DecisionType = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, DecisionType::Allow);
     }
+
+    #[test]
+    fn test_reason_serialization() {
+        let reason = DecisionReason::new(ReasonCode::BudgetExceeded, "Over budget")
+            .with_detail("period", serde_json::json!("daily"));
+        let json = serde_json::to_value(&reason).unwrap();
+        assert_eq!(json["code"], "budget_exceeded");
+        assert_eq!(json["details"]["period"], "daily");
+        assert_eq!(ReasonCode::BudgetExceeded.to_string(), "budget_exceeded");
+
+        let parsed: DecisionReason = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, reason);
+    }
+
+    #[test]
+    fn test_reason_from_error() {
+        let limited =
+            crate::Error::rate_limited("too many requests", std::time::Duration::from_millis(250));
+        let reason = DecisionReason::from_error(&limited);
+        assert_eq!(reason.code, ReasonCode::RateLimited);
+        assert_eq!(reason.details["retry_after_ms"], 250);
+
+        let failed = crate::Error::evaluation("bad regex");
+        assert_eq!(DecisionReason::from_error(&failed).code, ReasonCode::EvaluationError);
+
+        let timed_out = crate::Error::timeout("too slow", 100);
+        assert_eq!(DecisionReason::from_error(&timed_out).code, ReasonCode::Timeout);
+    }
 }
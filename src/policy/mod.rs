@@ -4,15 +4,17 @@
 //! conditions, and actions.
 
 mod action;
+mod combiner;
 mod condition;
 mod decision;
 mod document;
 mod metadata;
 mod rule;
 
-pub use action::{Action, ActionType, Modification};
+pub use action::{Action, ActionType, Modification, ModificationType, Obligations};
+pub use combiner::{combine, DecisionCombiner};
 pub use condition::{Condition, ConditionOperator, ConditionValue};
-pub use decision::DecisionType;
+pub use decision::{DecisionReason, DecisionType, ReasonCode};
 pub use document::PolicyDocument;
 pub use metadata::PolicyMetadata;
 pub use rule::PolicyRule;
@@ -34,12 +36,41 @@ pub struct Policy {
     /// Policy priority (higher = evaluated first)
     #[serde(default)]
     pub priority: i32,
+    /// How outcomes of matching rules are combined (engine default if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub combiner: Option<DecisionCombiner>,
+    /// Raise an incident when this policy denies a request or fails
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub alert_on_violation: bool,
+    /// Cache this policy's denials and evaluation errors for the negative
+    /// cache TTL; otherwise they are never cached
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cache_negative: bool,
+    /// How Governance compliance checks apply to requests (none if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<ComplianceMode>,
+    /// Custom feature flag the policy is gated on: it is evaluated only
+    /// while the flag is on, letting rules be dark-launched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_flag: Option<String>,
 }
 
 fn default_enabled() -> bool {
     true
 }
 
+/// How a policy's Governance compliance check affects decisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceMode {
+    /// Wait for the check and deny non-compliant requests
+    Enforce,
+    /// Wait for the check and downgrade non-compliant requests to warn
+    Warn,
+    /// Check in the background; the result is only audited
+    Audit,
+}
+
 impl Policy {
     /// Create a new policy with the given ID and name.
     pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
@@ -49,6 +80,11 @@ impl Policy {
             rules: Vec::new(),
             enabled: true,
             priority: 0,
+            combiner: None,
+            alert_on_violation: false,
+            cache_negative: false,
+            compliance: None,
+            feature_flag: None,
         }
     }
 
@@ -88,6 +124,17 @@ impl Policy {
 
         Ok(())
     }
+
+    /// Compile the expressions in the policy's rules, so invalid ones are
+    /// found before the policy is evaluated.
+    pub fn compile(&self) -> crate::Result<()> {
+        for rule in &self.rules {
+            rule.compile().map_err(|e| {
+                crate::Error::validation(format!("Rule {} failed to compile: {}", rule.id, e))
+            })?;
+        }
+        Ok(())
+    }
 }
 
 /// Builder for creating policies.
@@ -102,6 +149,11 @@ pub struct PolicyBuilder {
     rules: Vec<PolicyRule>,
     enabled: bool,
     priority: i32,
+    combiner: Option<DecisionCombiner>,
+    alert_on_violation: bool,
+    cache_negative: bool,
+    compliance: Option<ComplianceMode>,
+    feature_flag: Option<String>,
 }
 
 impl PolicyBuilder {
@@ -168,6 +220,36 @@ impl PolicyBuilder {
         self
     }
 
+    /// Set how the outcomes of matching rules are combined.
+    pub fn combiner(mut self, combiner: DecisionCombiner) -> Self {
+        self.combiner = Some(combiner);
+        self
+    }
+
+    /// Set whether violations of the policy raise incidents.
+    pub fn alert_on_violation(mut self, alert: bool) -> Self {
+        self.alert_on_violation = alert;
+        self
+    }
+
+    /// Set whether the policy's denials and errors may be cached briefly.
+    pub fn cache_negative(mut self, cache: bool) -> Self {
+        self.cache_negative = cache;
+        self
+    }
+
+    /// Check requests for compliance with Governance.
+    pub fn compliance(mut self, mode: ComplianceMode) -> Self {
+        self.compliance = Some(mode);
+        self
+    }
+
+    /// Evaluate the policy only while a custom feature flag is on.
+    pub fn feature_flag(mut self, flag: impl Into<String>) -> Self {
+        self.feature_flag = Some(flag.into());
+        self
+    }
+
     /// Build the policy.
     pub fn build(self) -> Policy {
         let name = self.name.unwrap_or_else(|| self.id.clone());
@@ -190,6 +272,11 @@ impl PolicyBuilder {
             rules: self.rules,
             enabled: self.enabled,
             priority: self.priority,
+            combiner: self.combiner,
+            alert_on_violation: self.alert_on_violation,
+            cache_negative: self.cache_negative,
+            compliance: self.compliance,
+            feature_flag: self.feature_flag,
         }
     }
 }
@@ -238,6 +325,10 @@ mod tests {
             rules: vec![],
             enabled: true,
             priority: 0,
+            combiner: None,
+            alert_on_violation: false,
+            cache_negative: false,
+            compliance: None,
         };
         assert!(invalid_policy.validate().is_err());
     }
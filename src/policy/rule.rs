@@ -85,6 +85,11 @@ impl PolicyRule {
 
         Ok(())
     }
+
+    /// Compile the rule's condition.
+    pub fn compile(&self) -> crate::Result<()> {
+        self.condition.compile()
+    }
 }
 
 /// Builder for creating policy rules.
@@ -4,6 +4,14 @@
 //! and Prometheus metrics collection, aligned with the LLM Dev Ops platform
 //! unified telemetry stack (OpenTelemetry v0.27).
 
+mod policy_metrics;
+mod spans;
+
+pub use policy_metrics::PolicyMetrics;
+pub use spans::{otlp_layer, EVALUATION_SPAN_NAME, STAGE_SPAN_NAME};
+
+pub(crate) use spans::{in_stage, set_remote_parent, Stage};
+
 use crate::config::TelemetryConfig;
 use crate::policy::DecisionType;
 use crate::Result;
@@ -191,6 +199,21 @@ pub fn span(name: impl Into<String>) -> Span {
     Span::new(name)
 }
 
+/// Encode metrics from the default Prometheus registry in text format.
+///
+/// This is the payload served at `TelemetryConfig::metrics_path`.
+pub fn encode_metrics() -> Result<String> {
+    use prometheus::Encoder;
+
+    let encoder = prometheus::TextEncoder::new();
+    let mut buffer = Vec::new();
+    encoder
+        .encode(&prometheus::default_registry().gather(), &mut buffer)
+        .map_err(|e| crate::Error::internal(format!("Failed to encode metrics: {}", e)))?;
+    String::from_utf8(buffer)
+        .map_err(|e| crate::Error::internal(format!("Invalid metrics encoding: {}", e)))
+}
+
 /// Record a metric value.
 pub fn record_metric(_name: &str, _value: f64, _labels: &[(&str, &str)]) {
     // In a full implementation, this would record to Prometheus
@@ -229,6 +252,22 @@ mod tests {
         assert_eq!(metrics.cache_misses, 2);
     }
 
+    #[test]
+    fn test_encode_metrics_includes_integration_metrics() {
+        use crate::integration::{MetricsRecorder, PrometheusRecorder};
+
+        PrometheusRecorder::global().record_request(
+            "telemetry-test",
+            "/health",
+            std::time::Duration::from_millis(1),
+            None,
+        );
+
+        let output = encode_metrics().unwrap();
+        assert!(output.contains("integration_requests_total"));
+        assert!(output.contains("telemetry-test"));
+    }
+
     #[test]
     fn test_span() {
         let mut span = Span::new("test_operation");
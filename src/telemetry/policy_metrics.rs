@@ -0,0 +1,160 @@
+//! Per-policy evaluation metrics.
+//!
+//! Each policy evaluated is counted, timed and broken down by outcome, and
+//! cache hits and misses are counted for the policies that decided a
+//! request. Metrics are labeled by policy ID only, never by request
+//! content, so their cardinality is bounded by the number of policies
+//! loaded.
+
+use crate::integration::DecisionOutcome;
+
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Prometheus metrics for policy evaluations, by policy ID.
+#[derive(Clone)]
+pub struct PolicyMetrics {
+    evaluations: IntCounterVec,
+    decisions: IntCounterVec,
+    latency: HistogramVec,
+    cache: IntCounterVec,
+}
+
+impl PolicyMetrics {
+    /// Create policy metrics and register them with `registry`.
+    pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+        let evaluations = IntCounterVec::new(
+            Opts::new(
+                "policy_evaluations_total",
+                "Total number of evaluations of each policy",
+            ),
+            &["policy"],
+        )?;
+        let decisions = IntCounterVec::new(
+            Opts::new(
+                "policy_decisions_total",
+                "Total number of policy evaluations by outcome",
+            ),
+            &["policy", "decision"],
+        )?;
+        let latency = HistogramVec::new(
+            HistogramOpts::new(
+                "policy_evaluation_duration_seconds",
+                "Policy evaluation latency in seconds",
+            ),
+            &["policy"],
+        )?;
+        let cache = IntCounterVec::new(
+            Opts::new(
+                "policy_cache_requests_total",
+                "Total number of cache lookups for decisions made by each policy",
+            ),
+            &["policy", "result"],
+        )?;
+
+        registry.register(Box::new(evaluations.clone()))?;
+        registry.register(Box::new(decisions.clone()))?;
+        registry.register(Box::new(latency.clone()))?;
+        registry.register(Box::new(cache.clone()))?;
+
+        Ok(Self {
+            evaluations,
+            decisions,
+            latency,
+            cache,
+        })
+    }
+
+    /// Get the metrics registered with the default Prometheus registry.
+    ///
+    /// These metrics are included in [`super::encode_metrics`].
+    pub fn global() -> Arc<Self> {
+        static GLOBAL: OnceLock<Arc<PolicyMetrics>> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| {
+                Arc::new(
+                    Self::new(prometheus::default_registry())
+                        .expect("Failed to register policy metrics"),
+                )
+            })
+            .clone()
+    }
+
+    /// Record an evaluation of a policy.
+    pub fn record_evaluation(&self, policy_id: &str, outcome: DecisionOutcome, latency: Duration) {
+        self.evaluations.with_label_values(&[policy_id]).inc();
+        self.decisions
+            .with_label_values(&[policy_id, outcome.as_str()])
+            .inc();
+        self.latency
+            .with_label_values(&[policy_id])
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Record a cache lookup for a decision made by a policy.
+    pub fn record_cache(&self, policy_id: &str, hit: bool) {
+        self.cache
+            .with_label_values(&[policy_id, cache_result(hit)])
+            .inc();
+    }
+
+    /// Number of evaluations recorded for a policy.
+    pub fn evaluation_count(&self, policy_id: &str) -> u64 {
+        self.evaluations.with_label_values(&[policy_id]).get()
+    }
+
+    /// Number of evaluations of a policy with an outcome.
+    pub fn decision_count(&self, policy_id: &str, outcome: DecisionOutcome) -> u64 {
+        self.decisions
+            .with_label_values(&[policy_id, outcome.as_str()])
+            .get()
+    }
+
+    /// Number of cache hits, or misses, recorded for a policy.
+    pub fn cache_count(&self, policy_id: &str, hit: bool) -> u64 {
+        self.cache
+            .with_label_values(&[policy_id, cache_result(hit)])
+            .get()
+    }
+}
+
+impl fmt::Debug for PolicyMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyMetrics").finish_non_exhaustive()
+    }
+}
+
+/// Label value of a cache lookup result.
+fn cache_result(hit: bool) -> &'static str {
+    if hit {
+        "hit"
+    } else {
+        "miss"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_metrics_counts() {
+        let registry = Registry::new();
+        let metrics = PolicyMetrics::new(&registry).unwrap();
+
+        let latency = Duration::from_micros(200);
+        metrics.record_evaluation("guests", DecisionOutcome::Deny, latency);
+        metrics.record_evaluation("guests", DecisionOutcome::Allow, latency);
+        metrics.record_evaluation("models", DecisionOutcome::Error, latency);
+        metrics.record_cache("guests", true);
+
+        assert_eq!(metrics.evaluation_count("guests"), 2);
+        assert_eq!(metrics.decision_count("guests", DecisionOutcome::Deny), 1);
+        assert_eq!(metrics.decision_count("models", DecisionOutcome::Error), 1);
+        assert_eq!(metrics.cache_count("guests", true), 1);
+        assert_eq!(metrics.cache_count("guests", false), 0);
+        assert_eq!(registry.gather().len(), 4);
+    }
+}
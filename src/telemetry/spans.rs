@@ -0,0 +1,139 @@
+//! Tracing spans for evaluation stages.
+//!
+//! Each evaluation runs in an `evaluate` span, exported to OpenTelemetry as
+//! `policy.evaluate`, with a child span per pipeline stage: input
+//! validation, Shield check, budget check, cache lookup, CEL evaluation and
+//! the decision combiner. Stage spans carry the `stage`, its `duration_ms`
+//! and its `outcome`. The evaluation span joins the trace of the incoming
+//! request, so with [`otlp_layer`] installed the stages show up under the
+//! caller's span. This is the in-process counterpart to the
+//! [`PolicySpan`](crate::integration::PolicySpan) registered with
+//! Observatory.
+
+use crate::config::TelemetryConfig;
+use crate::integration::TraceContext;
+use crate::Result;
+
+use opentelemetry::trace::{
+    SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{Sampler, Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use std::future::Future;
+use std::time::Instant;
+use tracing::{field, Instrument, Span, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// OpenTelemetry name of the span of each evaluation.
+pub const EVALUATION_SPAN_NAME: &str = "policy.evaluate";
+
+/// Name of the span of each evaluation stage.
+pub const STAGE_SPAN_NAME: &str = "policy.stage";
+
+/// A pipeline stage span, timed from its start.
+pub(crate) struct Stage {
+    span: Span,
+    start: Instant,
+}
+
+impl Stage {
+    /// Start a stage span as a child of the current span.
+    pub(crate) fn start(stage: &'static str) -> Self {
+        let span = tracing::info_span!(
+            "policy.stage",
+            otel.name = %format_args!("policy.{}", stage),
+            stage,
+            duration_ms = field::Empty,
+            outcome = field::Empty,
+        );
+        Self {
+            span,
+            start: Instant::now(),
+        }
+    }
+
+    /// Record the stage's duration and outcome.
+    pub(crate) fn finish(self, outcome: &str) {
+        let duration_ms = self.start.elapsed().as_secs_f64() * 1000.0;
+        self.span.record("duration_ms", duration_ms);
+        self.span.record("outcome", outcome);
+    }
+}
+
+/// Run a stage of the pipeline in its own span, recording the outcome of a
+/// result with `outcome`, or `error`.
+pub(crate) async fn in_stage<T, F>(
+    stage: &'static str,
+    future: F,
+    outcome: impl FnOnce(&T) -> &'static str,
+) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let stage = Stage::start(stage);
+    let result = future.instrument(stage.span.clone()).await;
+    stage.finish(match &result {
+        Ok(value) => outcome(value),
+        Err(_) => "error",
+    });
+    result
+}
+
+/// Make a span a child of the caller's span from a request's trace
+/// context, so exported spans join the caller's trace.
+///
+/// Malformed trace or span IDs are ignored.
+pub(crate) fn set_remote_parent(span: &Span, trace: &TraceContext) {
+    let Ok(trace_id) = TraceId::from_hex(&trace.trace_id) else {
+        return;
+    };
+    let Some(span_id) = trace
+        .parent_span_id
+        .as_deref()
+        .and_then(|span_id| SpanId::from_hex(span_id).ok())
+    else {
+        return;
+    };
+    let parent = SpanContext::new(
+        trace_id,
+        span_id,
+        TraceFlags::new(trace.trace_flags),
+        true,
+        TraceState::default(),
+    );
+    span.set_parent(opentelemetry::Context::new().with_remote_span_context(parent));
+}
+
+/// Build a tracing layer exporting spans to `telemetry.otlp_endpoint`.
+///
+/// Returns `None` if telemetry is disabled or no endpoint is configured.
+/// New traces are sampled with probability `telemetry.trace_sampling_ratio`;
+/// traces joined from incoming requests keep the caller's sampling decision.
+pub fn otlp_layer<S>(config: &TelemetryConfig) -> Result<Option<OpenTelemetryLayer<S, Tracer>>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let Some(endpoint) = config.otlp_endpoint.as_deref().filter(|_| config.enabled) else {
+        return Ok(None);
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| crate::Error::internal(format!("Failed to create OTLP exporter: {}", e)))?;
+    let sampler = Sampler::TraceIdRatioBased(config.trace_sampling_ratio.clamp(0.0, 1.0));
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_sampler(Sampler::ParentBased(Box::new(sampler)))
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            config.service_name.clone(),
+        )]))
+        .build();
+    let tracer = provider.tracer("llm-policy-engine");
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
@@ -0,0 +1,376 @@
+//! Round-trip tests of the wire format of public enums.
+//!
+//! Enums are sent to and received from upstream services, stored in policy
+//! documents and reported in events, so their serialized names are part of
+//! the API. Each enum is listed here with the name of every variant on the
+//! wire; the listing matches exhaustively, so adding a variant fails to
+//! compile until its wire format is recorded, and renaming one fails the
+//! tests.
+
+use crate::api::{AuditLevel, PolicyAction, RateLimitMode, TraceStepType};
+use crate::cache::NumberNormalization;
+use crate::config::{ConfigSource, FailurePolicy};
+use crate::integration::{
+    AlertType, AuditOutcome, ChangeKind, CircuitState, CompatibilityLevel, ConfigValueType,
+    DecisionOutcome, DeploymentState, HealthStatus, IncidentSeverity, IncidentStatus,
+    IndicatorType, ModelApprovalStatus, RecommendedAction, SchemaType, SecurityEventType,
+    SecuritySeverity, SignalType, SpanKind, SpanStatus, ThreatType, ThresholdOperator,
+    ValidationSource, ViolationSeverity,
+};
+use crate::policy::{
+    ActionType, ComplianceMode, ConditionOperator, ConditionValue, DecisionCombiner, DecisionType,
+    ModificationType, ReasonCode,
+};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use std::fmt::Debug;
+
+/// Assert that every variant of each enum serializes to its wire name and
+/// deserializes back.
+macro_rules! assert_wire_format {
+    ($($ty:ident { $($variant:ident => $wire:literal),+ $(,)? }),+ $(,)?) => {$({
+        let wire = |value: &$ty| match value {
+            $($ty::$variant => $wire,)+
+        };
+        for value in [$($ty::$variant),+] {
+            assert_round_trip(&value, wire(&value));
+        }
+    })+};
+}
+
+fn assert_round_trip<T>(value: &T, wire: &str)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let json = serde_json::to_value(value).unwrap();
+    assert_eq!(json, wire, "wire format of {:?}", value);
+    let parsed: T = serde_json::from_value(json).unwrap();
+    assert_eq!(&parsed, value);
+}
+
+#[test]
+fn test_policy_enums_wire_format() {
+    assert_wire_format! {
+        ActionType {
+            Allow => "allow",
+            Deny => "deny",
+            Warn => "warn",
+            Modify => "modify",
+            Log => "log",
+            RateLimit => "rate_limit",
+        },
+        ModificationType {
+            Set => "set",
+            Remove => "remove",
+            Append => "append",
+            Mask => "mask",
+            Truncate => "truncate",
+        },
+        DecisionCombiner {
+            DenyOverrides => "deny_overrides",
+            PermitOverrides => "permit_overrides",
+            FirstApplicable => "first_applicable",
+            UnlessPermit => "unless_permit",
+        },
+        ConditionOperator {
+            Equals => "equals",
+            NotEquals => "not_equals",
+            GreaterThan => "greater_than",
+            GreaterThanOrEquals => "greater_than_or_equals",
+            LessThan => "less_than",
+            LessThanOrEquals => "less_than_or_equals",
+            In => "in",
+            NotIn => "not_in",
+            Contains => "contains",
+            StartsWith => "starts_with",
+            EndsWith => "ends_with",
+            Matches => "matches",
+            Exists => "exists",
+            NotExists => "not_exists",
+            And => "and",
+            Or => "or",
+            Not => "not",
+            Expression => "expression",
+        },
+        DecisionType {
+            Allow => "allow",
+            Deny => "deny",
+            Warn => "warn",
+            Modify => "modify",
+        },
+        ReasonCode {
+            RuleMatched => "rule_matched",
+            NoMatchDefault => "no_match_default",
+            InjectionDetected => "injection_detected",
+            UnsafePrompt => "unsafe_prompt",
+            BudgetExceeded => "budget_exceeded",
+            TokenLimitExceeded => "token_limit_exceeded",
+            ComplianceViolation => "compliance_violation",
+            IntegrationUnavailable => "integration_unavailable",
+            RateLimited => "rate_limited",
+            FailedOpen => "failed_open",
+            EvaluationError => "evaluation_error",
+            Timeout => "timeout",
+        },
+        ComplianceMode {
+            Enforce => "enforce",
+            Warn => "warn",
+            Audit => "audit",
+        },
+    }
+}
+
+#[test]
+fn test_condition_values_wire_format() {
+    let values = [
+        (ConditionValue::String("gpt-4".to_string()), json!("gpt-4")),
+        (ConditionValue::Integer(3), json!(3)),
+        (ConditionValue::Float(0.5), json!(0.5)),
+        (ConditionValue::Boolean(true), json!(true)),
+        (
+            ConditionValue::Array(vec![ConditionValue::Integer(1), ConditionValue::Null]),
+            json!([1, null]),
+        ),
+        (ConditionValue::Null, json!(null)),
+    ];
+    for (value, wire) in values {
+        assert_eq!(serde_json::to_value(&value).unwrap(), wire);
+        let parsed: ConditionValue = serde_json::from_value(wire).unwrap();
+        assert_eq!(parsed, value);
+    }
+}
+
+#[test]
+fn test_engine_enums_wire_format() {
+    assert_wire_format! {
+        AuditLevel {
+            Off => "off",
+            Minimal => "minimal",
+            Standard => "standard",
+            Verbose => "verbose",
+        },
+        TraceStepType {
+            PolicyEvaluated => "policy_evaluated",
+            RuleEvaluated => "rule_evaluated",
+            ConditionEvaluated => "condition_evaluated",
+            CacheCheck => "cache_check",
+            IntegrationCall => "integration_call",
+        },
+        RateLimitMode {
+            Global => "global",
+            PerSubject => "per_subject",
+            PerApiKey => "per_api_key",
+        },
+        PolicyAction {
+            Create => "create",
+            Update => "update",
+            Delete => "delete",
+        },
+        NumberNormalization {
+            Unified => "unified",
+            Exact => "exact",
+        },
+        FailurePolicy {
+            FailOpen => "fail_open",
+            FailClosed => "fail_closed",
+        },
+    }
+
+    // Config sources are reported, never read back.
+    let wire = |source: ConfigSource| match source {
+        ConfigSource::Default => "default",
+        ConfigSource::File => "file",
+        ConfigSource::Env => "env",
+    };
+    for source in [ConfigSource::Default, ConfigSource::File, ConfigSource::Env] {
+        assert_eq!(serde_json::to_value(source).unwrap(), wire(source));
+    }
+}
+
+#[test]
+fn test_integration_enums_wire_format() {
+    assert_wire_format! {
+        CircuitState {
+            Closed => "closed",
+            Open => "open",
+            HalfOpen => "half_open",
+        },
+        ConfigValueType {
+            String => "string",
+            Integer => "integer",
+            Float => "float",
+            Boolean => "boolean",
+            Object => "object",
+            Array => "array",
+            Secret => "secret",
+        },
+        DeploymentState {
+            Pending => "pending",
+            InProgress => "in_progress",
+            Completed => "completed",
+            Failed => "failed",
+            RolledBack => "rolled_back",
+        },
+        ViolationSeverity {
+            Low => "low",
+            Medium => "medium",
+            High => "high",
+            Critical => "critical",
+        },
+        AuditOutcome {
+            Success => "success",
+            Failure => "failure",
+            Denied => "denied",
+        },
+        ModelApprovalStatus {
+            Approved => "approved",
+            Restricted => "restricted",
+            Review => "review",
+            Denied => "denied",
+        },
+        IncidentSeverity {
+            Info => "info",
+            Low => "low",
+            Medium => "medium",
+            High => "high",
+            Critical => "critical",
+        },
+        IncidentStatus {
+            New => "new",
+            Acknowledged => "acknowledged",
+            InProgress => "in_progress",
+            Resolved => "resolved",
+            Closed => "closed",
+        },
+        AlertType {
+            PolicyViolation => "policy_violation",
+            ThreatDetected => "threat_detected",
+            BudgetExceeded => "budget_exceeded",
+            RateLimitHit => "rate_limit_hit",
+            SystemError => "system_error",
+            Custom => "custom",
+        },
+        DecisionOutcome {
+            Allow => "allow",
+            Deny => "deny",
+            Warn => "warn",
+            Modify => "modify",
+            Error => "error",
+        },
+        SpanKind {
+            Internal => "INTERNAL",
+            Server => "SERVER",
+            Client => "CLIENT",
+            Producer => "PRODUCER",
+            Consumer => "CONSUMER",
+        },
+        SpanStatus {
+            Unset => "UNSET",
+            Ok => "OK",
+            Error => "ERROR",
+        },
+        SignalType {
+            ErrorRate => "error_rate",
+            Latency => "latency",
+            RequestRate => "request_rate",
+            TokenUsage => "token_usage",
+            Cost => "cost",
+            Availability => "availability",
+        },
+        HealthStatus {
+            Healthy => "healthy",
+            Degraded => "degraded",
+            Unhealthy => "unhealthy",
+            Unknown => "unknown",
+            Offline => "offline",
+        },
+        ThresholdOperator {
+            Gt => "gt",
+            Gte => "gte",
+            Lt => "lt",
+            Lte => "lte",
+            Eq => "eq",
+        },
+        SchemaType {
+            JsonSchema => "json-schema",
+            Avro => "avro",
+            Protobuf => "protobuf",
+            OpenApi => "openapi",
+        },
+        ValidationSource {
+            Local => "local",
+            Remote => "remote",
+        },
+        CompatibilityLevel {
+            None => "NONE",
+            Backward => "BACKWARD",
+            Forward => "FORWARD",
+            Full => "FULL",
+            BackwardTransitive => "BACKWARD_TRANSITIVE",
+            ForwardTransitive => "FORWARD_TRANSITIVE",
+            FullTransitive => "FULL_TRANSITIVE",
+        },
+        ChangeKind {
+            Added => "added",
+            Removed => "removed",
+            Modified => "modified",
+        },
+        SecurityEventType {
+            AuthFailure => "auth_failure",
+            AuthzFailure => "authz_failure",
+            PolicyViolation => "policy_violation",
+            RateLimitExceeded => "rate_limit_exceeded",
+            SuspiciousActivity => "suspicious_activity",
+            DataAccess => "data_access",
+            ConfigChange => "config_change",
+            AnomalyDetected => "anomaly_detected",
+        },
+        SecuritySeverity {
+            Info => "info",
+            Low => "low",
+            Medium => "medium",
+            High => "high",
+            Critical => "critical",
+        },
+        RecommendedAction {
+            None => "none",
+            Monitor => "monitor",
+            RequireAuth => "require_auth",
+            Block => "block",
+            Alert => "alert",
+        },
+        IndicatorType {
+            IpAddress => "ip_address",
+            Domain => "domain",
+            Hash => "hash",
+            Email => "email",
+            UserAgent => "user_agent",
+        },
+        ThreatType {
+            PromptInjection => "prompt_injection",
+            Jailbreak => "jailbreak",
+            DataExfiltration => "data_exfiltration",
+            PiiLeakage => "pii_leakage",
+            ToxicContent => "toxic_content",
+            Unknown => "unknown",
+        },
+    }
+}
+
+#[test]
+fn test_earlier_spellings_still_accepted() {
+    let action: ActionType = serde_json::from_value(json!("ratelimit")).unwrap();
+    assert_eq!(action, ActionType::RateLimit);
+    let schema_type: SchemaType = serde_json::from_value(json!("open-api")).unwrap();
+    assert_eq!(schema_type, SchemaType::OpenApi);
+    for (wire, state) in [
+        ("inprogress", DeploymentState::InProgress),
+        ("rolledback", DeploymentState::RolledBack),
+        ("deployed", DeploymentState::Completed),
+    ] {
+        let parsed: DeploymentState = serde_json::from_value(json!(wire)).unwrap();
+        assert_eq!(parsed, state);
+    }
+}